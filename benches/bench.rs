@@ -1,6 +1,6 @@
-use rust_i18n::t;
+use rust_i18n::{t, Backend};
 
-rust_i18n::i18n!("./tests/locales");
+rust_i18n::i18n!("./tests/locales", static_table = true);
 
 use criterion::{criterion_group, criterion_main, Criterion};
 
@@ -47,6 +47,32 @@ fn bench_t(c: &mut Criterion) {
         b.iter(|| crate::_RUST_I18N_BACKEND.translate("en", "hello"))
     });
 
+    c.bench_function("_rust_i18n_backend().translate (dyn)", |b| {
+        b.iter(|| {
+            for _ in 0..100 {
+                criterion::black_box(
+                    crate::_rust_i18n_backend().translate("en", criterion::black_box("hello")),
+                );
+            }
+        })
+    });
+
+    c.bench_function("_rust_i18n_compiled_backend().translate", |b| {
+        b.iter(|| {
+            for _ in 0..100 {
+                criterion::black_box(
+                    crate::_rust_i18n_compiled_backend()
+                        .translate("en", criterion::black_box("hello")),
+                );
+            }
+        })
+    });
+
+    // 18.976 ns
+    c.bench_function("t_static", |b| {
+        b.iter(|| rust_i18n::t_static!("en", criterion::black_box("hello")))
+    });
+
     // 46.721
     c.bench_function("static_hashmap_get_to_string", |b| {
         b.iter(|| DICT.get("hello").unwrap().to_string())
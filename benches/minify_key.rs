@@ -12,6 +12,19 @@ pub fn bench_t(c: &mut Criterion) {
             Mauris suscipit bibendum mattis. Vestibulum eu augue diam. Morbi dapibus tempus viverra. Sed aliquam turpis eget justo ornare maximus vitae et tortor. Donec semper neque sit amet sapien congue scelerisque. Maecenas bibendum imperdiet dolor interdum facilisis. Integer non diam tempus, pharetra ex at, euismod diam. Ut enim turpis, sagittis in iaculis ut, finibus et sem. Suspendisse a felis euismod neque euismod placerat. Praesent ipsum libero, porta vel egestas quis, aliquet vitae lorem. Nullam vel pharetra erat, sit amet sodales leo."#
         )));
 
+    // The key of a literal message is minified at compile time, no hashing at runtime.
+    c.bench_function("t_minified (literal)", |b| {
+        b.iter(|| {
+            t!("Use YAML for mapping localized text, and support mutiple YAML files merging. Use `cargo i18n` to extract untranslated texts from source code.")
+        })
+    });
+
+    // The key of a dynamic message is hashed on every call.
+    c.bench_function("t_minified (dynamic)", |b| {
+        let msg = "Use YAML for mapping localized text, and support mutiple YAML files merging. Use `cargo i18n` to extract untranslated texts from source code.".to_string();
+        b.iter(|| t!(&msg))
+    });
+
     c.bench_function("t_with_locale", |b| b.iter(|| t!("hello", locale = "en")));
 
     c.bench_function("t_with_threads", |b| {
@@ -101,7 +114,7 @@ pub fn bench_t(c: &mut Criterion) {
                 city = "Litteltown",
                 zip = 8408,
                 website = "https://snoopy-napkin.name"
-            );
+            )
         })
     });
 }
@@ -1,9 +1,23 @@
 use anyhow::Error;
-use clap::{Args, Parser};
+use clap::{Args, Parser, Subcommand};
 use rust_i18n_extract::extractor::Message;
-use rust_i18n_extract::{extractor, generator, iter};
-use rust_i18n_support::{I18nConfig, MinifyKey};
-use std::{collections::HashMap, path::Path};
+use rust_i18n_extract::generator::{CsvOptions, QuoteStyle, SortBy};
+use rust_i18n_extract::merge::{MergeStrategy, Resolution};
+use rust_i18n_extract::{extractor, generator, git, iter, merge, prune};
+use rust_i18n_support::{
+    count_translation_status, deprecated_key_message, expand_load_path, find_deprecated_key,
+    find_empty_translations, find_fallback_translations, find_low_coverage_translations,
+    find_needs_review_translations, find_placeholder_mismatches, find_too_long_translations,
+    find_too_many_bytes_translations, load_deprecated_keys_with_separator,
+    load_manual_keys_with_separator, load_max_bytes_keys_with_separator,
+    load_max_len_keys_with_separator, load_status_keys_with_separator, set_allow_unknown_version,
+    try_load_locales_from_paths_with_separator, Encoding, I18nConfig, I18nError, MinifyKey,
+    SchemaVersion, TranslationStatus,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
 
 #[derive(Parser)]
 #[command(name = "cargo")]
@@ -18,11 +32,13 @@ enum CargoCli {
 /// Rust I18n command to help you extract all untranslated texts from source code.
 ///
 /// It will iterate all Rust files in the source directory and extract all untranslated texts
-/// that used `t!` macro.
+/// that used `t!` macro, and the templates configured by `template-extensions`.
 /// Then it will generate a YAML file and merge with the existing translations.
 ///
 /// https://github.com/longbridgeapp/rust-i18n
 struct I18nArgs {
+    #[command(subcommand)]
+    command: Option<I18nCommands>,
     /// Manually add a translation to the localization file.
     ///
     /// This is useful for non-literal values in the `t!` macro.
@@ -31,14 +47,248 @@ struct I18nArgs {
     /// you can add a translation for it using `-t "Hello, world!"`,
     /// or provide a translated message using `-t "Hello, world! => Hola, world!"`.
     ///
+    /// Or add the translations from a file with `-t @translations.txt`, one `key => value` per line,
+    /// the lines starting with `#` are comments, and the blank lines are ignored.
+    ///
     /// NOTE: The whitespace before and after the key and value will be trimmed.
-    #[arg(short, long, default_value = None, name = "TEXT", num_args(1..), value_parser = translate_value_parser, verbatim_doc_comment)]
-    translate: Option<Vec<(String, String)>>,
+    #[arg(short, long, default_value = None, name = "TEXT", num_args(1..), verbatim_doc_comment)]
+    translate: Option<Vec<String>>,
+    /// The encoding of the `-t @file` translations file: `utf-8` (the BOM is stripped) or `latin1`.
+    #[arg(long, default_value = "utf-8")]
+    encoding: Encoding,
+    /// Replace the invalid UTF-8 sequences of the `-t @file` translations file with U+FFFD,
+    /// instead of skipping their lines. The location of each invalid sequence is reported either way.
+    #[arg(long)]
+    lossy: bool,
+    /// Load the locale files with an unknown `_version` as the latest version instead of failing,
+    /// e.g. the files written by a newer version of rust-i18n.
+    #[arg(long, global = true)]
+    allow_unknown_version: bool,
+    /// List the `t!` calls with a non-literal key instead of extracting, as `file:line: expression`.
+    ///
+    /// These keys can't be extracted, add them manually with `-t`.
+    #[arg(long)]
+    report_dynamic: bool,
+    /// Only append the keys that are missing from the default locale to the end of its `TODO` file,
+    /// the other locales are not checked, and the existing texts are not rewritten or reordered.
+    #[arg(long)]
+    only_missing: bool,
+    /// Only extract the keys from these files instead of walking the source directory, e.g. the staged files
+    /// of a pre-commit hook. The files with other extensions are skipped.
+    #[arg(long, num_args(1..))]
+    files: Vec<PathBuf>,
     /// Extract all untranslated I18n texts from source code
     #[arg(default_value = "./", last = true)]
     source: Option<String>,
 }
 
+#[derive(Subcommand)]
+enum I18nCommands {
+    /// Create a new locale file with all keys of the default locale.
+    New(I18nNewArgs),
+    /// Sort the keys of all locale files, they are written in the canonical format as well.
+    Sort(I18nSortArgs),
+    /// Rewrite all locale files in the canonical format without reordering the keys,
+    /// so the files with the same translations are byte-identical.
+    Canonicalize(I18nCanonicalizeArgs),
+    /// Report the orphaned keys of the default locale that are not used in the source code.
+    ///
+    /// The keys marked with `_meta: manual` are not reported.
+    /// The empty translations are also reported if `forbid-empty` is enabled, and the locales below the `min-coverage`.
+    Lint(I18nLintArgs),
+    /// Export the translations of all locales into one file for the translators.
+    Export(I18nExportArgs),
+    /// Merge the locale files of another directory into the last load path.
+    Merge(I18nMergeArgs),
+    /// Remove the orphaned keys of the default locale from all locale files, the keys reported by `lint`.
+    ///
+    /// The keys marked with `_meta: manual` are kept, mark the dynamic keys with it before pruning.
+    Prune(I18nPruneArgs),
+    /// Show the translated keys of the default locale in each locale.
+    ///
+    /// With `--by-status`, the keys in each review status marked by `_meta: { status: ... }`.
+    Stats(I18nStatsArgs),
+    /// Report the translations whose placeholders differ from the default locale,
+    /// e.g. `%{nom}` in `fr` for `%{name}` in `en`, exits with 1 if any is found.
+    ValidatePlaceholders(I18nValidatePlaceholdersArgs),
+}
+
+#[derive(Args)]
+struct I18nNewArgs {
+    /// The locale to create, e.g. `zh-CN`.
+    locale: String,
+    /// Copy the values of the default locale instead of leaving them empty.
+    #[arg(long)]
+    copy_default: bool,
+    /// Overwrite the locale if it already exists.
+    #[arg(long)]
+    force: bool,
+    /// The source directory of the project.
+    #[arg(default_value = "./", last = true)]
+    source: Option<String>,
+}
+
+#[derive(Args)]
+struct I18nSortArgs {
+    /// Check the locale files are sorted without writing them, exits with 1 if any file is not sorted.
+    #[arg(long)]
+    check: bool,
+    /// The order of the keys: `key` (lexicographic), `natural` (`item2` before `item10`)
+    /// or `namespace` (grouped by the namespace before the first `key-separator`).
+    #[arg(long, default_value = "key")]
+    sort_by: SortBy,
+    /// The source directory of the project.
+    #[arg(default_value = "./", last = true)]
+    source: Option<String>,
+}
+
+#[derive(Args)]
+struct I18nCanonicalizeArgs {
+    /// Check the locale files are canonical without writing them, exits with 1 if any file is not canonical.
+    #[arg(long)]
+    check: bool,
+    /// The source directory of the project.
+    #[arg(default_value = "./", last = true)]
+    source: Option<String>,
+}
+
+#[derive(Args)]
+struct I18nLintArgs {
+    /// Also report the keys that a configured locale only gets through the fallback chain, from a locale of another language.
+    #[arg(long)]
+    locale_fallback_check: bool,
+    /// Only check the keys used in these files instead of walking the source directory, e.g. the staged files
+    /// of a pre-commit hook. The keys missing in the default locale are reported instead of the orphaned keys.
+    #[arg(long, num_args(1..))]
+    files: Vec<PathBuf>,
+
+    /// The source directory of the project.
+    #[arg(default_value = "./", last = true)]
+    source: Option<String>,
+}
+
+#[derive(Args)]
+struct I18nExportArgs {
+    /// The output file, the format is inferred from the extension: `yml`, `yaml`, `json`, `json5`, `toml`, `csv` or `tsv`.
+    /// Use `-` to write to the stdout with `--format`.
+    ///
+    /// Defaults to `i18n-export.yml`, or the `i18n-export` directory with `--split`.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Only export the keys whose default locale value was added or modified since the git revision, e.g. `v1.0`.
+    ///
+    /// All keys are exported with a warning if the locales are not in a git repository.
+    #[arg(long)]
+    since: Option<String>,
+    /// The encoding of the output file: `utf-8` or `latin1`.
+    #[arg(long, default_value = "utf-8")]
+    encoding: Encoding,
+    /// The `_version` of the output: `2` (all locales in one file) or `1` (one file per locale,
+    /// named with the locale before the extension, e.g. `i18n-export.en.yml`).
+    #[arg(long, default_value = "2")]
+    schema_version: SchemaVersion,
+    /// The delimiter of the `csv` output, a single ASCII char or `tab`, e.g. `;` for Excel in the non-US locales.
+    ///
+    /// Defaults to `,` for `csv` and the tab for `tsv`.
+    #[arg(long, value_parser = generator::parse_delimiter)]
+    delimiter: Option<u8>,
+    /// When to quote the fields of the `csv` output: `necessary`, `always`, `non-numeric` or `never`.
+    #[arg(long, default_value = "necessary")]
+    quote_style: QuoteStyle,
+    /// The header of the key column of the `csv` output, e.g. `id` for the tools that expect it.
+    ///
+    /// Defaults to `key`.
+    #[arg(long)]
+    key_column: Option<String>,
+    /// Write one file per locale into the output directory, e.g. `en.json`, `fr.json`,
+    /// each file is a flat map of the key to the text, for the i18n libraries of a frontend.
+    #[arg(long)]
+    split: bool,
+    /// The format of the output, instead of the extension of `--output`: `yml`, `yaml`, `json`, `json5`, `toml`, `csv` or `tsv`.
+    ///
+    /// The format of the `--split` files is `json`, `json5`, `yml`, `yaml` or `toml`, defaults to `json`.
+    #[arg(long)]
+    format: Option<String>,
+    /// Only write the files of these locales with `--split`, e.g. `--locale en fr`, default: all locales.
+    #[arg(long, num_args(1..), requires = "split")]
+    locale: Vec<String>,
+    /// Write a JSON list of the keys into this file instead of the translations, e.g. `keys.json`,
+    /// for the code generators of a frontend. Use `-` to write to the stdout.
+    #[arg(long, conflicts_with_all = ["split", "output"])]
+    manifest: Option<PathBuf>,
+    /// Write the manifest as a map of each key to the names of its placeholders, e.g. `{"greeting": ["name"]}`.
+    #[arg(long, requires = "manifest")]
+    placeholders: bool,
+    /// The source directory of the project.
+    #[arg(default_value = "./", last = true)]
+    source: Option<String>,
+}
+
+#[derive(Args)]
+struct I18nMergeArgs {
+    /// The locales directory to merge from, e.g. `../sub-team/locales`.
+    other: PathBuf,
+    /// How to resolve the keys that have different values: `ours`, `theirs`
+    /// or `newer` (the value of the locale file that is modified more recently).
+    #[arg(long, default_value = "ours")]
+    strategy: MergeStrategy,
+    /// The source directory of the project.
+    #[arg(default_value = "./", last = true)]
+    source: Option<String>,
+}
+
+#[derive(Args)]
+struct I18nPruneArgs {
+    /// List the keys to remove without writing the locale files.
+    #[arg(long)]
+    dry_run: bool,
+    /// Remove the keys without the confirmation prompt.
+    #[arg(short, long)]
+    yes: bool,
+    /// The source directory of the project.
+    #[arg(default_value = "./", last = true)]
+    source: Option<String>,
+}
+
+#[derive(Args)]
+struct I18nStatsArgs {
+    /// Show the number of keys in each status: `untranslated`, `translated`, `needs-review` and `approved`.
+    #[arg(long)]
+    by_status: bool,
+    /// The source directory of the project.
+    #[arg(default_value = "./", last = true)]
+    source: Option<String>,
+}
+
+#[derive(Args)]
+struct I18nValidatePlaceholdersArgs {
+    /// The format of the report: `text` or `json`, a list of `{"locale", "key", "missing", "extra"}`.
+    #[arg(long, default_value = "text")]
+    format: ReportFormat,
+    /// The source directory of the project.
+    #[arg(default_value = "./", last = true)]
+    source: Option<String>,
+}
+
+/// The format of a report printed to the stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReportFormat {
+    Text,
+    Json,
+}
+
+impl std::str::FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(ReportFormat::Text),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(format!("Invalid format `{}`, expected `text` or `json`", s)),
+        }
+    }
+}
+
 /// Remove quotes from a string at the start and end.
 fn remove_quotes(s: &str) -> &str {
     let mut start = 0;
@@ -53,14 +303,51 @@ fn remove_quotes(s: &str) -> &str {
 }
 
 /// Parse a string of the form "key => value" into a tuple.
-fn translate_value_parser(s: &str) -> Result<(String, String), std::io::Error> {
+fn parse_translate_value(s: &str) -> (String, String) {
     if let Some((key, msg)) = s.split_once("=>") {
         let key = remove_quotes(key.trim());
         let msg = remove_quotes(msg.trim());
-        Ok((key.to_owned(), msg.to_owned()))
+        (key.to_owned(), msg.to_owned())
     } else {
-        Ok((s.to_owned(), s.to_owned()))
+        (s.to_owned(), s.to_owned())
+    }
+}
+
+/// Parse a "key => value" string, or read the "key => value" lines from a file if it starts with `@`.
+///
+/// The file is decoded with the `encoding`, the lines with the invalid UTF-8 sequences are skipped,
+/// or the sequences are replaced with U+FFFD if `lossy` is true, so one bad line doesn't fail the whole file.
+fn translate_value_parser(
+    s: &str,
+    encoding: Encoding,
+    lossy: bool,
+) -> Result<Vec<(String, String)>, Error> {
+    let Some(path) = s.strip_prefix('@') else {
+        return Ok(vec![parse_translate_value(s)]);
+    };
+
+    let (content, invalid) = encoding.decode_lossy(&std::fs::read(path)?);
+    for (line, column) in &invalid {
+        eprintln!(
+            "Warning: invalid UTF-8 at {}:{}:{}, {}",
+            path,
+            line,
+            column,
+            if lossy {
+                "replaced with U+FFFD"
+            } else {
+                "the line is skipped, use `--lossy` to keep it"
+            }
+        );
     }
+    Ok(content
+        .lines()
+        .enumerate()
+        .filter(|(i, _)| lossy || !invalid.iter().any(|(line, _)| *line == i + 1))
+        .map(|(_, line)| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_translate_value)
+        .collect())
 }
 
 /// Add translations to the localize file for t!
@@ -87,17 +374,769 @@ fn add_translations(
         } else {
             item.0.clone()
         };
-        results.entry(key).or_insert(Message {
-            key: item.1.clone(),
+        results.entry(key.clone()).or_insert(Message {
+            key,
+            source: item.0.clone(),
+            value: Some(item.1.clone()),
             index,
             minify_key: *minify_key,
             locations: vec![],
+            placeholders: vec![],
         });
     }
 }
 
+/// Get the open and close delimiters of the `interpolation`, e.g. `%{` and `}`.
+fn interpolation_delimiters(cfg: &I18nConfig) -> Result<(&str, &str), Error> {
+    let mut delimiters = cfg.interpolation.split_whitespace();
+    match (delimiters.next(), delimiters.next(), delimiters.next()) {
+        (Some(open), Some(close), None) => Ok((open, close)),
+        _ => Err(Error::msg(format!(
+            "Invalid interpolation `{}`, expected the open and close delimiters separated by a space",
+            cfg.interpolation
+        ))),
+    }
+}
+
+/// Get the load paths of the locales in the source directory, the glob patterns are expanded to the matched directories.
+fn load_paths(source_path: &str, cfg: &I18nConfig) -> Vec<PathBuf> {
+    cfg.load_path
+        .iter()
+        .flat_map(|path| expand_load_path(&Path::new(source_path).join(path).display().to_string()))
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Extract the keys from the Rust sources and the templates configured by `template-extensions`.
+///
+/// Only the `files` are extracted if any, instead of the files in the source directory.
+fn extract_sources(
+    results: &mut extractor::Results,
+    source_path: &str,
+    files: &[PathBuf],
+    cfg: &I18nConfig,
+) -> Result<(), Error> {
+    let mut extensions = vec!["rs".to_string()];
+    extensions.extend(cfg.template_extensions.iter().cloned());
+    let callback = |path: &PathBuf, source: &str| {
+        if path.extension() == Some("rs".as_ref()) {
+            extractor::extract(results, path, source, cfg.clone())
+        } else {
+            extractor::extract_template(results, path, source, cfg.clone())
+        }
+    };
+    if files.is_empty() {
+        iter::iter_files(source_path, &extensions, callback)
+    } else {
+        iter::iter_paths(files, &extensions, callback)
+    }
+}
+
+/// Load the marked keys of each load path by `load` and merge them, the later paths override the earlier on key conflict.
+fn load_marked_keys<P, T, C>(
+    paths: &[P],
+    load: impl Fn(&P) -> Result<T, I18nError>,
+) -> Result<C, Error>
+where
+    T: IntoIterator,
+    C: Default + Extend<T::Item>,
+{
+    let mut keys = C::default();
+    for path in paths {
+        keys.extend(load(path)?);
+    }
+    Ok(keys)
+}
+
+/// Warn the deprecated keys that are still used in the source code, marked by `_meta: { deprecated: "..." }`.
+fn warn_deprecated_keys(
+    results: &extractor::Results,
+    source_path: &str,
+    cfg: &I18nConfig,
+) -> Result<(), Error> {
+    let deprecated_keys = load_marked_keys(&load_paths(source_path, cfg), |path| {
+        load_deprecated_keys_with_separator(
+            &path.display().to_string(),
+            |_| false,
+            &cfg.key_separator,
+        )
+    })?;
+
+    let mut messages = results.iter().collect::<Vec<_>>();
+    messages.sort_by_key(|(_, m)| m.index);
+    for (key, message) in messages {
+        let Some((deprecated, reason)) =
+            find_deprecated_key(&deprecated_keys, key, &cfg.key_separator)
+        else {
+            continue;
+        };
+        let warning = deprecated_key_message(key, deprecated, reason);
+        for location in &message.locations {
+            eprintln!(
+                "Warning: {}, at {}:{}",
+                warning,
+                location.file.display(),
+                location.line
+            );
+        }
+    }
+    Ok(())
+}
+
+/// List the `t!` calls with a non-literal key for `cargo i18n --report-dynamic`
+fn report_dynamic_keys(
+    source_path: &str,
+    files: &[PathBuf],
+    cfg: &I18nConfig,
+) -> Result<(), Error> {
+    let mut dynamic_keys = vec![];
+    let callback = |path: &PathBuf, source: &str| {
+        dynamic_keys.extend(extractor::extract_dynamic_keys(path, source, cfg.clone())?);
+        Ok(())
+    };
+    if files.is_empty() {
+        iter::iter_files(source_path, &["rs"], callback)?;
+    } else {
+        iter::iter_paths(files, &["rs"], callback)?;
+    }
+
+    for key in &dynamic_keys {
+        println!(
+            "{}:{}: {}",
+            key.location.file.display(),
+            key.location.line,
+            key.expr
+        );
+    }
+    eprintln!(
+        "Found {} t! calls with a non-literal key, add their keys manually with `cargo i18n -t`.",
+        dynamic_keys.len()
+    );
+    Ok(())
+}
+
+/// Create a new locale file for `cargo i18n new`
+fn new_locale(args: I18nNewArgs) -> Result<(), Error> {
+    let source_path = args.source.expect("Missing source path");
+
+    let cfg = I18nConfig::load(std::path::Path::new(&source_path))?;
+
+    generator::generate_locale(
+        &load_paths(&source_path, &cfg),
+        &cfg,
+        &args.locale,
+        args.copy_default,
+        args.force,
+    )?;
+
+    Ok(())
+}
+
+/// Sort the locale files for `cargo i18n sort`
+fn sort_locales(args: I18nSortArgs) -> Result<(), Error> {
+    let source_path = args.source.expect("Missing source path");
+
+    let cfg = I18nConfig::load(std::path::Path::new(&source_path))?;
+
+    let mut unsorted = vec![];
+    let mut uncanonical = HashSet::new();
+    for load_path in load_paths(&source_path, &cfg) {
+        unsorted.extend(generator::sort_locales(
+            &load_path,
+            args.check,
+            args.sort_by,
+            &cfg.key_separator,
+        )?);
+        if args.check {
+            uncanonical.extend(generator::canonicalize_files(&load_path, true)?);
+        }
+    }
+    if args.check && !unsorted.is_empty() {
+        eprintln!("Found {} locale files are not sorted:", unsorted.len());
+        for path in unsorted {
+            // The sorted files may still be rewritten in the canonical format
+            if uncanonical.contains(&path) {
+                eprintln!("  {} (not canonical)", path.display());
+            } else {
+                eprintln!("  {}", path.display());
+            }
+        }
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Rewrite the locale files in the canonical format for `cargo i18n canonicalize`
+fn canonicalize_files(args: I18nCanonicalizeArgs) -> Result<(), Error> {
+    let source_path = args.source.expect("Missing source path");
+
+    let cfg = I18nConfig::load(std::path::Path::new(&source_path))?;
+
+    let mut uncanonical = vec![];
+    for load_path in load_paths(&source_path, &cfg) {
+        uncanonical.extend(generator::canonicalize_files(load_path, args.check)?);
+    }
+    if args.check && !uncanonical.is_empty() {
+        eprintln!(
+            "Found {} locale files are not canonical:",
+            uncanonical.len()
+        );
+        for path in uncanonical {
+            eprintln!("  {}", path.display());
+        }
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Export the translations for `cargo i18n export`
+fn export(args: I18nExportArgs) -> Result<(), Error> {
+    let source_path = args.source.expect("Missing source path");
+
+    let cfg = I18nConfig::load(std::path::Path::new(&source_path))?;
+    let load_paths = load_paths(&source_path, &cfg);
+
+    let mut keys = None;
+    if let Some(since) = &args.since {
+        match git::load_translations_at(&load_paths, since, &cfg.key_separator)? {
+            Some(previous) => {
+                let paths = load_paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>();
+                let current = try_load_locales_from_paths_with_separator(
+                    &paths,
+                    |_| false,
+                    &cfg.key_separator,
+                )
+                .map_err(|errors| {
+                    let errors = errors.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+                    Error::msg(errors.join("\n"))
+                })?;
+                keys = Some(generator::changed_keys(
+                    &previous,
+                    &current,
+                    &cfg.default_locale,
+                ));
+            }
+            None => {
+                eprintln!("Warning: the locales are not in a git repository, exporting all keys.");
+            }
+        }
+    }
+
+    if let Some(manifest) = &args.manifest {
+        let count = generator::export_manifest(
+            &load_paths,
+            manifest,
+            keys.as_ref(),
+            &cfg.key_separator,
+            interpolation_delimiters(&cfg)?,
+            args.placeholders,
+        )?;
+        if manifest != Path::new("-") {
+            eprintln!("Exported {} keys to {}", count, manifest.display());
+        }
+        return Ok(());
+    }
+
+    if args.split {
+        let output = args.output.unwrap_or_else(|| "i18n-export".into());
+        let count = generator::export_split(
+            &load_paths,
+            &output,
+            keys.as_ref(),
+            &args.locale,
+            &cfg.key_separator,
+            args.encoding,
+            args.format.as_deref().unwrap_or("json"),
+            cfg.canonical_locales,
+        )?;
+        eprintln!("Exported {} locale files to {}", count, output.display());
+        return Ok(());
+    }
+
+    let output = args.output.unwrap_or_else(|| "i18n-export.yml".into());
+    let count = generator::export(
+        &load_paths,
+        &output,
+        args.format.as_deref(),
+        keys.as_ref(),
+        &cfg.key_separator,
+        args.encoding,
+        args.schema_version,
+        CsvOptions {
+            delimiter: args.delimiter,
+            quote_style: args.quote_style,
+            key_column: args.key_column,
+        },
+        cfg.canonical_locales,
+    )?;
+    if output != Path::new("-") {
+        eprintln!("Exported {} keys to {}", count, output.display());
+    }
+
+    Ok(())
+}
+
+/// Merge the locale files for `cargo i18n merge`
+fn merge_locales(args: I18nMergeArgs) -> Result<(), Error> {
+    let source_path = args.source.expect("Missing source path");
+
+    let cfg = I18nConfig::load(std::path::Path::new(&source_path))?;
+    let load_paths = load_paths(&source_path, &cfg);
+    let Some(ours) = load_paths.last() else {
+        return Err(Error::msg("No load path is configured"));
+    };
+
+    let report = merge::merge(ours, &args.other, args.strategy, &cfg.key_separator)?;
+
+    if !report.added.is_empty() {
+        eprintln!(
+            "Added {} new translations into {}",
+            report.added.len(),
+            ours.join(merge::MERGED_FILENAME).display()
+        );
+    }
+    if !report.conflicts.is_empty() {
+        eprintln!("Found {} conflicts:", report.conflicts.len());
+    }
+    let mut manual = 0;
+    for conflict in &report.conflicts {
+        let resolution = match conflict.resolution {
+            Resolution::Ours => "kept ours",
+            Resolution::Theirs => "took theirs",
+            Resolution::Manual => {
+                manual += 1;
+                "took theirs, update it manually"
+            }
+        };
+        eprintln!(
+            "  [{}] {}: {:?} (ours) vs {:?} (theirs), {}",
+            conflict.locale, conflict.key, conflict.ours, conflict.theirs, resolution
+        );
+    }
+    if report.added.is_empty() && report.conflicts.is_empty() {
+        println!("All thing done.\n");
+    }
+    if manual > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Report the orphaned keys for `cargo i18n lint`
+fn lint(args: I18nLintArgs) -> Result<(), Error> {
+    let mut results = HashMap::new();
+
+    let source_path = args.source.expect("Missing source path");
+
+    let cfg = I18nConfig::load(std::path::Path::new(&source_path))?;
+
+    extract_sources(&mut results, &source_path, &args.files, &cfg)?;
+    warn_deprecated_keys(&results, &source_path, &cfg)?;
+
+    let load_paths = load_paths(&source_path, &cfg)
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>();
+    let data = match try_load_locales_from_paths_with_separator(
+        &load_paths,
+        |_| false,
+        &cfg.key_separator,
+    ) {
+        Ok(data) => data,
+        Err(errors) => {
+            for err in errors {
+                eprintln!("{}", err);
+            }
+            std::process::exit(1);
+        }
+    };
+    let manual_keys = load_marked_keys(&load_paths, |path| {
+        load_manual_keys_with_separator(path, |_| false, &cfg.key_separator)
+    })?;
+
+    // The orphaned keys are unknown with a part of the files, check the used keys instead
+    let empty_trs = HashMap::new();
+    let default_trs = data.get(&cfg.default_locale).unwrap_or(&empty_trs);
+    let (orphans, missing) = if args.files.is_empty() {
+        let orphans =
+            prune::find_orphan_keys(default_trs, &results, &manual_keys, &cfg.key_separator);
+        (orphans, vec![])
+    } else {
+        let missing = prune::find_missing_keys(default_trs, &results, &cfg.key_separator);
+        (vec![], missing)
+    };
+
+    let empty = if cfg.forbid_empty {
+        find_empty_translations(&data, &cfg.default_locale)
+    } else {
+        vec![]
+    };
+
+    let fallbacks = if args.locale_fallback_check {
+        find_fallback_translations(
+            &data,
+            &cfg.default_locale,
+            &cfg.available_locales,
+            &cfg.fallback,
+            &cfg.no_fallback_locales,
+        )
+    } else {
+        vec![]
+    };
+
+    let max_len_keys = load_marked_keys(&load_paths, |path| {
+        load_max_len_keys_with_separator(path, |_| false, &cfg.key_separator)
+    })?;
+    let too_long = find_too_long_translations(&data, &max_len_keys, &cfg.key_separator);
+    let max_bytes_keys = load_marked_keys(&load_paths, |path| {
+        load_max_bytes_keys_with_separator(path, |_| false, &cfg.key_separator)
+    })?;
+    let too_many_bytes =
+        find_too_many_bytes_translations(&data, &max_bytes_keys, &cfg.key_separator);
+
+    let low_coverage =
+        find_low_coverage_translations(&data, &cfg.default_locale, &cfg.min_coverage);
+
+    let needs_review = if cfg.require_approved.is_empty() {
+        vec![]
+    } else {
+        let status_keys = load_marked_keys(&load_paths, |path| {
+            load_status_keys_with_separator(path, |_| false, &cfg.key_separator)
+        })?;
+        find_needs_review_translations(&status_keys, &cfg.require_approved)
+    };
+
+    if orphans.is_empty()
+        && missing.is_empty()
+        && low_coverage.is_empty()
+        && empty.is_empty()
+        && fallbacks.is_empty()
+        && too_long.is_empty()
+        && too_many_bytes.is_empty()
+        && needs_review.is_empty()
+    {
+        println!("All thing done.\n");
+        return Ok(());
+    }
+
+    if !orphans.is_empty() {
+        eprintln!(
+            "Found {} keys of [{}] are not used in the source code:",
+            orphans.len(),
+            cfg.default_locale
+        );
+        for key in orphans {
+            eprintln!("  {}", key);
+        }
+        eprintln!("\nMark the dynamic keys with `_meta: manual` to skip them.");
+    }
+
+    if !missing.is_empty() {
+        eprintln!(
+            "Found {} keys are missing in [{}]:",
+            missing.len(),
+            cfg.default_locale
+        );
+        for key in missing {
+            let message = &results[&key];
+            match message.locations.first() {
+                Some(location) => {
+                    eprintln!(
+                        "  {}, at {}:{}",
+                        message.source,
+                        location.file.display(),
+                        location.line
+                    )
+                }
+                None => eprintln!("  {}", message.source),
+            }
+        }
+    }
+
+    if !empty.is_empty() {
+        eprintln!("Found {} empty translations:", empty.len());
+        for (locale, key) in empty {
+            eprintln!("  [{}] {}", locale, key);
+        }
+    }
+
+    if !fallbacks.is_empty() {
+        eprintln!(
+            "Found {} translations are only resolved through the fallback chain:",
+            fallbacks.len()
+        );
+        for (locale, key, resolved) in fallbacks {
+            eprintln!("  [{}] {} (falls back to {})", locale, key, resolved);
+        }
+    }
+
+    if !too_long.is_empty() {
+        eprintln!(
+            "Found {} translations are longer than the `max_len` of the keys:",
+            too_long.len()
+        );
+        for (locale, key, len, max_len) in too_long {
+            eprintln!("  [{}] {} ({} > {})", locale, key, len, max_len);
+        }
+    }
+
+    if !too_many_bytes.is_empty() {
+        eprintln!(
+            "Found {} translations are longer than the `max_bytes` of the keys:",
+            too_many_bytes.len()
+        );
+        for (locale, key, channel, bytes, max_bytes) in too_many_bytes {
+            match channel.as_str() {
+                "" => eprintln!("  [{}] {} ({} > {} bytes)", locale, key, bytes, max_bytes),
+                _ => eprintln!(
+                    "  [{}] {} ({}: {} > {} bytes)",
+                    locale, key, channel, bytes, max_bytes
+                ),
+            }
+        }
+    }
+
+    if !low_coverage.is_empty() {
+        eprintln!(
+            "Found {} locales are below the `min-coverage`:",
+            low_coverage.len()
+        );
+        for (locale, translated, total, min) in low_coverage {
+            eprintln!(
+                "  [{}] {:.1}% ({}/{}) < {}%",
+                locale,
+                translated as f64 * 100.0 / total as f64,
+                translated,
+                total,
+                min
+            );
+        }
+    }
+
+    if !needs_review.is_empty() {
+        eprintln!(
+            "Found {} translations need review, but they must be approved by `require-approved`:",
+            needs_review.len()
+        );
+        for (locale, key) in needs_review {
+            eprintln!("  [{}] {}", locale, key);
+        }
+    }
+    std::process::exit(1);
+}
+
+/// Show the translated keys of each locale for `cargo i18n stats`
+fn stats(args: I18nStatsArgs) -> Result<(), Error> {
+    let source_path = args.source.expect("Missing source path");
+
+    let cfg = I18nConfig::load(std::path::Path::new(&source_path))?;
+
+    let load_paths = load_paths(&source_path, &cfg)
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>();
+    let data =
+        try_load_locales_from_paths_with_separator(&load_paths, |_| false, &cfg.key_separator)
+            .map_err(|errors| {
+                let errors = errors.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+                Error::msg(errors.join("\n"))
+            })?;
+    let status_keys = load_marked_keys(&load_paths, |path| {
+        load_status_keys_with_separator(path, |_| false, &cfg.key_separator)
+    })?;
+
+    let counts =
+        count_translation_status(&data, &cfg.default_locale, &status_keys, &cfg.key_separator);
+    for (locale, counts) in counts {
+        let total = counts.values().sum::<usize>();
+        if args.by_status {
+            let counts = TranslationStatus::ALL
+                .iter()
+                .map(|status| format!("{}: {}", status, counts[status]))
+                .collect::<Vec<_>>();
+            println!("[{}] {}", locale, counts.join(", "));
+        } else {
+            let translated = total - counts[&TranslationStatus::Untranslated];
+            println!(
+                "[{}] {:.1}% ({}/{})",
+                locale,
+                translated as f64 * 100.0 / total.max(1) as f64,
+                translated,
+                total
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Report the mismatched placeholders for `cargo i18n validate-placeholders`
+fn validate_placeholders(args: I18nValidatePlaceholdersArgs) -> Result<(), Error> {
+    let source_path = args.source.expect("Missing source path");
+
+    let cfg = I18nConfig::load(std::path::Path::new(&source_path))?;
+    let (open, close) = interpolation_delimiters(&cfg)?;
+
+    let load_paths = load_paths(&source_path, &cfg)
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>();
+    let data =
+        try_load_locales_from_paths_with_separator(&load_paths, |_| false, &cfg.key_separator)
+            .map_err(|errors| {
+                let errors = errors.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+                Error::msg(errors.join("\n"))
+            })?;
+
+    let mismatches = find_placeholder_mismatches(&data, &cfg.default_locale, open, close);
+    let placeholders = |names: &[String]| {
+        names
+            .iter()
+            .map(|name| format!("{}{}{}", open, name, close))
+            .collect::<Vec<_>>()
+    };
+    match args.format {
+        ReportFormat::Json => {
+            let report = mismatches
+                .iter()
+                .map(|(locale, key, missing, extra)| {
+                    serde_json::json!({
+                        "locale": locale,
+                        "key": key,
+                        "missing": placeholders(missing),
+                        "extra": placeholders(extra),
+                    })
+                })
+                .collect::<Vec<_>>();
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        ReportFormat::Text if mismatches.is_empty() => println!("All thing done.\n"),
+        ReportFormat::Text => {
+            eprintln!(
+                "Found {} translations have the placeholders different from [{}]:",
+                mismatches.len(),
+                cfg.default_locale
+            );
+            for (locale, key, missing, extra) in &mismatches {
+                let mut diffs = vec![];
+                if !missing.is_empty() {
+                    diffs.push(format!("missing {}", placeholders(missing).join(", ")));
+                }
+                if !extra.is_empty() {
+                    diffs.push(format!("extra {}", placeholders(extra).join(", ")));
+                }
+                eprintln!("  [{}] {}: {}", locale, key, diffs.join("; "));
+            }
+        }
+    }
+    if !mismatches.is_empty() {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Remove the orphaned keys for `cargo i18n prune`
+fn prune_keys(args: I18nPruneArgs) -> Result<(), Error> {
+    let mut results = HashMap::new();
+
+    let source_path = args.source.expect("Missing source path");
+
+    let cfg = I18nConfig::load(std::path::Path::new(&source_path))?;
+
+    extract_sources(&mut results, &source_path, &[], &cfg)?;
+
+    let load_paths = load_paths(&source_path, &cfg);
+    let display_paths = load_paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>();
+    let data = match try_load_locales_from_paths_with_separator(
+        &display_paths,
+        |_| false,
+        &cfg.key_separator,
+    ) {
+        Ok(data) => data,
+        Err(errors) => {
+            for err in errors {
+                eprintln!("{}", err);
+            }
+            std::process::exit(1);
+        }
+    };
+    let manual_keys = load_marked_keys(&display_paths, |path| {
+        load_manual_keys_with_separator(path, |_| false, &cfg.key_separator)
+    })?;
+
+    let orphans = data
+        .get(&cfg.default_locale)
+        .map(|trs| prune::find_orphan_keys(trs, &results, &manual_keys, &cfg.key_separator))
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    let pruned = prune::prune(&load_paths, &orphans, &cfg.key_separator, true)?;
+    if pruned.is_empty() {
+        println!("All thing done.\n");
+        return Ok(());
+    }
+
+    eprintln!(
+        "Found {} keys of [{}] are not used in the source code:",
+        orphans.len(),
+        cfg.default_locale
+    );
+    for file in &pruned {
+        eprintln!("  {}:", file.path.display());
+        for key in &file.keys {
+            eprintln!("    {}", key);
+        }
+    }
+    if args.dry_run {
+        return Ok(());
+    }
+
+    if !args.yes {
+        eprint!("\nRemove them from the locale files? [y/N] ");
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            eprintln!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let pruned = prune::prune(&load_paths, &orphans, &cfg.key_separator, false)?;
+    eprintln!("Removed the keys from {} locale files.", pruned.len());
+
+    Ok(())
+}
+
 fn main() -> Result<(), Error> {
     let CargoCli::I18n(args) = CargoCli::parse();
+    set_allow_unknown_version(args.allow_unknown_version);
+
+    match args.command {
+        Some(I18nCommands::New(new_args)) => return new_locale(new_args),
+        Some(I18nCommands::Sort(sort_args)) => return sort_locales(sort_args),
+        Some(I18nCommands::Canonicalize(canonicalize_args)) => {
+            return canonicalize_files(canonicalize_args)
+        }
+        Some(I18nCommands::Lint(lint_args)) => return lint(lint_args),
+        Some(I18nCommands::Export(export_args)) => return export(export_args),
+        Some(I18nCommands::Merge(merge_args)) => return merge_locales(merge_args),
+        Some(I18nCommands::Prune(prune_args)) => return prune_keys(prune_args),
+        Some(I18nCommands::Stats(stats_args)) => return stats(stats_args),
+        Some(I18nCommands::ValidatePlaceholders(validate_args)) => {
+            return validate_placeholders(validate_args)
+        }
+        None => {}
+    }
 
     let mut results = HashMap::new();
 
@@ -105,23 +1144,55 @@ fn main() -> Result<(), Error> {
 
     let cfg = I18nConfig::load(std::path::Path::new(&source_path))?;
 
-    iter::iter_crate(&source_path, |path, source| {
-        extractor::extract(&mut results, path, source, cfg.clone())
-    })?;
+    if args.report_dynamic {
+        return report_dynamic_keys(&source_path, &args.files, &cfg);
+    }
+
+    extract_sources(&mut results, &source_path, &args.files, &cfg)?;
+    warn_deprecated_keys(&results, &source_path, &cfg)?;
 
     if let Some(list) = args.translate {
-        add_translations(&list, &mut results, &cfg);
+        let list = list
+            .iter()
+            .map(|s| translate_value_parser(s, args.encoding, args.lossy))
+            .collect::<Result<Vec<_>, _>>()?;
+        add_translations(&list.concat(), &mut results, &cfg);
     }
 
     let mut messages: Vec<_> = results.iter().collect();
     messages.sort_by_key(|(_k, m)| m.index);
 
-    let mut has_error = false;
+    if args.only_missing {
+        let count = generator::generate_missing(
+            &load_paths(&source_path, &cfg),
+            &cfg.default_locale,
+            messages,
+            &cfg.key_separator,
+            &cfg.locale_formats,
+        )?;
+        // Fail the CI like the full extraction if there are untranslated texts
+        if count > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
-    let output_path = Path::new(&source_path).join(&cfg.load_path);
+    let mut has_error = false;
 
-    let result = generator::generate(output_path, &cfg.available_locales, messages.clone());
-    if result.is_err() {
+    let result = generator::generate(
+        &load_paths(&source_path, &cfg),
+        &cfg.available_locales,
+        messages.clone(),
+        &cfg.key_separator,
+        &cfg.locale_formats,
+        cfg.split_by_namespace,
+        cfg.canonical_locales,
+    );
+    if let Err(err) = result {
+        // The error is empty if there are untranslated texts
+        if !err.to_string().is_empty() {
+            eprintln!("{}", err);
+        }
         has_error = true;
     }
 
@@ -131,3 +1202,67 @@ fn main() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate_value_parser() {
+        assert_eq!(
+            translate_value_parser(
+                r#""Hello, world!" => "Hola, world!""#,
+                Encoding::Utf8,
+                false
+            )
+            .unwrap(),
+            vec![("Hello, world!".to_owned(), "Hola, world!".to_owned())]
+        );
+        assert_eq!(
+            translate_value_parser(" Hello ", Encoding::Utf8, false).unwrap(),
+            vec![(" Hello ".to_owned(), " Hello ".to_owned())]
+        );
+
+        let path = std::env::temp_dir().join("rust-i18n-test-translations.txt");
+        std::fs::write(
+            &path,
+            "# The dynamic texts\n\nHello, world! => Hola, world!\n  status.ok  \n",
+        )
+        .unwrap();
+        assert_eq!(
+            translate_value_parser(&format!("@{}", path.display()), Encoding::Utf8, false).unwrap(),
+            vec![
+                ("Hello, world!".to_owned(), "Hola, world!".to_owned()),
+                ("status.ok".to_owned(), "status.ok".to_owned())
+            ]
+        );
+
+        std::fs::write(&path, b"\xef\xbb\xbfCaf\xc3\xa9 => Caf\xc3\xa9").unwrap();
+        assert_eq!(
+            translate_value_parser(&format!("@{}", path.display()), Encoding::Utf8, false).unwrap(),
+            vec![("Café".to_owned(), "Café".to_owned())]
+        );
+        std::fs::write(&path, b"Caf\xe9 => Caf\xe9\nok => OK").unwrap();
+        // The invalid line is skipped, or kept with U+FFFD
+        assert_eq!(
+            translate_value_parser(&format!("@{}", path.display()), Encoding::Utf8, false).unwrap(),
+            vec![("ok".to_owned(), "OK".to_owned())]
+        );
+        assert_eq!(
+            translate_value_parser(&format!("@{}", path.display()), Encoding::Utf8, true).unwrap(),
+            vec![
+                ("Caf\u{fffd}".to_owned(), "Caf\u{fffd}".to_owned()),
+                ("ok".to_owned(), "OK".to_owned())
+            ]
+        );
+        std::fs::write(&path, b"Caf\xe9 => Caf\xe9").unwrap();
+        assert_eq!(
+            translate_value_parser(&format!("@{}", path.display()), Encoding::Latin1, false)
+                .unwrap(),
+            vec![("Café".to_owned(), "Café".to_owned())]
+        );
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(translate_value_parser("@not-exists.txt", Encoding::Utf8, false).is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use anyhow::Error;
-use proc_macro2::{TokenStream, TokenTree};
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
 use quote::ToTokens;
 use rust_i18n_support::I18nConfig;
 use std::collections::HashMap;
@@ -15,25 +15,46 @@ pub struct Location {
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Message {
+    /// The key to look up the translations, the same as the key of [`Results`], e.g. the hashed key of `minify-key`
     pub key: String,
+    /// The text as written in the source code, e.g. `Hello, world!` of the hashed key
+    pub source: String,
+    /// The translated value given by `cargo i18n -t` or the inline `default` of `t!`, instead of the untranslated text
+    pub value: Option<String>,
     pub index: usize,
     pub minify_key: bool,
     pub locations: Vec<Location>,
+    /// The names of the variables passed to `t!`, e.g. `name` of `t!("greeting", name = "Jason")`
+    pub placeholders: Vec<String>,
 }
 
 impl Message {
-    fn new(key: &str, index: usize, minify_key: bool) -> Self {
+    fn new(key: &str, source: &str, index: usize, minify_key: bool) -> Self {
         Self {
             key: key.to_owned(),
+            source: source.to_owned(),
+            value: None,
             index,
             minify_key,
             locations: vec![],
+            placeholders: vec![],
         }
     }
 }
 
+/// A `t!` call whose key is not a literal, it can't be extracted and must be added manually by `cargo i18n -t`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicKey {
+    pub location: Location,
+    /// The source text of the key expression, e.g. `format!("errors.{}", code)`
+    pub expr: String,
+}
+
 static METHOD_NAMES: &[&str] = &["t", "tr"];
 
+/// The arguments of `t!` that are not variables.
+static RESERVED_ARGUMENTS: &[&str] = &["locale", "index", "random", "default"];
+
 #[allow(clippy::ptr_arg)]
 pub fn extract(
     results: &mut Results,
@@ -41,26 +62,89 @@ pub fn extract(
     source: &str,
     cfg: I18nConfig,
 ) -> Result<(), Error> {
-    let mut ex = Extractor { results, path, cfg };
+    let mut ex = Extractor::new(results, path, cfg);
 
     let file = syn::parse_file(source)
         .unwrap_or_else(|_| panic!("Failed to parse file, file: {}", path.display()));
     let stream = file.into_token_stream();
+    ex.macros.extend(find_wrapper_macros(stream.clone()));
     ex.invoke(stream)
 }
 
+/// Find the `t!` calls whose key is not a literal in the source, which are skipped by [`extract`].
+#[allow(clippy::ptr_arg)]
+pub fn extract_dynamic_keys(
+    path: &PathBuf,
+    source: &str,
+    cfg: I18nConfig,
+) -> Result<Vec<DynamicKey>, Error> {
+    let mut results = Results::new();
+    let mut ex = Extractor::new(&mut results, path, cfg);
+
+    let file = syn::parse_file(source)
+        .unwrap_or_else(|_| panic!("Failed to parse file, file: {}", path.display()));
+    let stream = file.into_token_stream();
+    ex.macros.extend(find_wrapper_macros(stream.clone()));
+    ex.invoke(stream)?;
+    Ok(ex.dynamic_keys)
+}
+
+/// Extract the literal keys of the translation function calls in a template, e.g. `{{ t(key="hello") }}` of Tera
+/// or `{{ t("hello") }}` of Askama, the function name is configured by `template-function`.
+#[allow(clippy::ptr_arg)]
+pub fn extract_template(
+    results: &mut Results,
+    path: &PathBuf,
+    source: &str,
+    cfg: I18nConfig,
+) -> Result<(), Error> {
+    let re = regex::Regex::new(&format!(
+        r#"\b{}\s*\(\s*(?:key\s*=\s*)?("(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*')"#,
+        regex::escape(&cfg.template_function)
+    ))?;
+    let mut ex = Extractor::new(results, path, cfg);
+
+    for caps in re.captures_iter(source) {
+        let literal = caps.get(1).unwrap();
+        let line = source[..literal.start()].matches('\n').count() + 1;
+        let quoted = literal.as_str();
+        let key = unescape_template_str(&quoted[1..quoted.len() - 1]);
+        ex.add_message(key, line, &[], None);
+    }
+    Ok(())
+}
+
 #[allow(dead_code)]
 struct Extractor<'a> {
     results: &'a mut Results,
     path: &'a PathBuf,
     cfg: I18nConfig,
+    dynamic_keys: Vec<DynamicKey>,
+    /// The prefix of the enclosing `with_key_prefix!`, joined by the key separator
+    key_prefix: Option<String>,
+    /// The macros wrapping `t!`, of `extract-macros` and the `macro_rules!` found by [`find_wrapper_macros`]
+    macros: Vec<String>,
 }
 
 impl<'a> Extractor<'a> {
+    fn new(results: &'a mut Results, path: &'a PathBuf, cfg: I18nConfig) -> Self {
+        let macros = cfg.extract_macros.clone();
+        Self {
+            results,
+            path,
+            cfg,
+            dynamic_keys: vec![],
+            key_prefix: None,
+            macros,
+        }
+    }
+
     fn invoke(&mut self, stream: TokenStream) -> Result<(), Error> {
         let mut token_iter = stream.into_iter().peekable();
+        let mut after_dot = false;
 
         while let Some(token) = token_iter.next() {
+            let is_dot = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '.');
             match token {
                 TokenTree::Group(group) => self.invoke(group.stream())?,
                 TokenTree::Ident(ident) => {
@@ -75,66 +159,329 @@ impl<'a> Extractor<'a> {
                     let ident_str = ident.to_string();
                     if METHOD_NAMES.contains(&ident_str.as_str()) && is_macro {
                         if let Some(TokenTree::Group(group)) = token_iter.peek() {
-                            self.take_message(group.stream());
+                            let prefix = self.key_prefix.clone();
+                            if !self.take_message(group.stream(), prefix.as_deref())
+                                && !is_metavariable(group.stream())
+                            {
+                                self.add_dynamic_key(group.stream());
+                            }
+                        }
+                    }
+
+                    // The calls of the macros wrapping `t!`, e.g. `tr_label!("Submit")`
+                    if self.macros.contains(&ident_str) && is_macro {
+                        if let Some(TokenTree::Group(group)) = token_iter.peek() {
+                            let prefix = self.key_prefix.clone();
+                            self.take_message(group.stream(), prefix.as_deref());
+                        }
+                    }
+
+                    // The namespace of `t_ns!` is not joined to the prefix of `with_key_prefix!`
+                    if ident_str == "t_ns" && is_macro {
+                        if let Some(TokenTree::Group(group)) = token_iter.peek() {
+                            self.take_namespace_message(group.stream());
+                        }
+                    }
+
+                    if ident_str == "with_key_prefix" && is_macro {
+                        if let Some(TokenTree::Group(group)) = token_iter.next() {
+                            self.take_key_prefix(group.stream())?;
+                        }
+                    }
+
+                    // The builder calls, e.g. `.label("Hello")`
+                    if after_dot && !is_macro && self.cfg.extract_methods.contains(&ident_str) {
+                        if let Some(TokenTree::Group(group)) = token_iter.peek() {
+                            if group.delimiter() == Delimiter::Parenthesis {
+                                self.take_message(group.stream(), None);
+                            }
+                        }
+                    }
+                }
+                // The attributes, e.g. `#[error("Hello")]`
+                TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                    if let Some(TokenTree::Group(group)) = token_iter.peek() {
+                        if group.delimiter() == Delimiter::Bracket {
+                            self.take_attribute(group.stream());
                         }
                     }
                 }
                 _ => {}
             }
+            after_dot = is_dot;
         }
 
         Ok(())
     }
 
-    fn take_message(&mut self, stream: TokenStream) {
+    /// Take the message of the attribute if its path is configured in `extract_attributes`.
+    fn take_attribute(&mut self, stream: TokenStream) {
+        let mut path = String::new();
+        for token in stream {
+            match token {
+                TokenTree::Ident(ident) => path.push_str(&ident.to_string()),
+                TokenTree::Punct(punct) if punct.as_char() == ':' => path.push(':'),
+                TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                    if self.cfg.extract_attributes.contains(&path) {
+                        self.take_message(group.stream(), None);
+                    }
+                    return;
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Extract the body of `with_key_prefix!("prefix", { ... })` with the prefix joined to the outer one.
+    fn take_key_prefix(&mut self, stream: TokenStream) -> Result<(), Error> {
+        let mut token_iter = stream.into_iter();
+        let Some(prefix) = token_iter.next().and_then(|token| match token {
+            TokenTree::Literal(literal) => literal_to_string(&literal),
+            _ => None,
+        }) else {
+            return Ok(());
+        };
+
+        let outer = self.key_prefix.take();
+        self.key_prefix = Some(match &outer {
+            Some(outer) => format!("{}{}{}", outer, self.cfg.key_separator, prefix),
+            None => prefix,
+        });
+        let result = self.invoke(token_iter.collect());
+        self.key_prefix = outer;
+        result
+    }
+
+    /// Take the message of `t_ns!(namespace, "key")`, the namespace is an identifier or a string literal.
+    fn take_namespace_message(&mut self, stream: TokenStream) {
+        let mut token_iter = stream.into_iter();
+        let namespace = match token_iter.next() {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            Some(TokenTree::Literal(literal)) => match literal_to_string(&literal) {
+                Some(namespace) => namespace,
+                None => return,
+            },
+            _ => return,
+        };
+        if !matches!(token_iter.next(), Some(TokenTree::Punct(punct)) if punct.as_char() == ',') {
+            return;
+        }
+        let stream = token_iter.collect::<TokenStream>();
+        if !self.take_message(stream.clone(), Some(&namespace)) && !is_metavariable(stream.clone())
+        {
+            self.add_dynamic_key(stream);
+        }
+    }
+
+    /// Take the message if the key is a string literal, returns `false` if it's not.
+    ///
+    /// The key is joined to the `prefix` of `with_key_prefix!` if any.
+    fn take_message(&mut self, stream: TokenStream, prefix: Option<&str>) -> bool {
         let mut token_iter = stream.into_iter().peekable();
 
         let literal = if let Some(TokenTree::Literal(literal)) = token_iter.next() {
             literal
         } else {
+            return false;
+        };
+
+        if let Some(key) = literal_to_string(&literal) {
+            let key = match prefix {
+                Some(prefix) => format!("{}{}{}", prefix, self.cfg.key_separator, key),
+                None => key,
+            };
+            let (placeholders, default) = take_arguments(token_iter);
+            self.add_message(key, literal.span().start().line, &placeholders, default);
+            return true;
+        }
+        false
+    }
+
+    /// Record the key expression of a `t!` call, the tokens before the first `,`.
+    fn add_dynamic_key(&mut self, stream: TokenStream) {
+        let tokens = stream
+            .into_iter()
+            .take_while(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == ','))
+            .collect::<Vec<_>>();
+        let (Some(first), Some(last)) = (tokens.first(), tokens.last()) else {
             return;
         };
+        // Prefer the source text as written, the tokens are printed with the extra spaces
+        let expr = first
+            .span()
+            .join(last.span())
+            .and_then(|span| span.source_text())
+            .unwrap_or_else(|| tokens.iter().cloned().collect::<TokenStream>().to_string());
+        self.dynamic_keys.push(DynamicKey {
+            location: Location {
+                file: self.path.clone(),
+                line: first.span().start().line,
+            },
+            expr,
+        });
+    }
 
+    /// Add the message of the literal key at the line, the line is `0` if unknown.
+    ///
+    /// The first inline `default` of the key is the value of the message, e.g. `Hello` of `t!("greeting", default = "Hello")`.
+    fn add_message(
+        &mut self,
+        key: String,
+        line: usize,
+        placeholders: &[String],
+        default: Option<String>,
+    ) {
         let I18nConfig {
             minify_key,
             minify_key_len,
             minify_key_prefix,
-            minify_key_thresh,
+            max_key_len,
             ..
         } = &self.cfg;
-        let key: Option<proc_macro2::Literal> = Some(literal);
-
-        if let Some(lit) = key {
-            if let Some(key) = literal_to_string(&lit) {
-                let (message_key, message_content) = if *minify_key {
-                    let hashed_key = rust_i18n_support::MinifyKey::minify_key(
-                        &key,
-                        *minify_key_len,
-                        minify_key_prefix,
-                        *minify_key_thresh,
-                    );
-                    (hashed_key.to_string(), key.clone())
-                } else {
-                    let message_key = format_message_key(&key);
-                    (message_key.clone(), message_key)
-                };
-                let index = self.results.len();
-                let message = self
-                    .results
-                    .entry(message_key)
-                    .or_insert_with(|| Message::new(&message_content, index, *minify_key));
-
-                let span = lit.span();
-                let line = span.start().line;
-                if line > 0 {
-                    message.locations.push(Location {
-                        file: self.path.clone(),
-                        line,
-                    });
+        let minify_key_thresh = self.cfg.effective_minify_key_thresh();
+
+        // The long keys are hashed with `minify-key`, otherwise suggest to shorten them
+        if *max_key_len > 0 && key.len() > *max_key_len && !*minify_key {
+            eprintln!(
+                "Warning: the key is longer than `max-key-len` ({} > {}), use `minify-key` or a short key, at {}:{}",
+                key.len(),
+                max_key_len,
+                self.path.display(),
+                line
+            );
+        }
+        let (message_key, source) = if *minify_key {
+            let hashed_key = rust_i18n_support::MinifyKey::minify_key(
+                &key,
+                *minify_key_len,
+                minify_key_prefix,
+                minify_key_thresh,
+            );
+            (hashed_key.to_string(), key.clone())
+        } else if self.cfg.verbatim_keys {
+            (key.clone(), key.clone())
+        } else {
+            let message_key = format_message_key(&key);
+            (message_key.clone(), message_key)
+        };
+        let index = self.results.len();
+        let message = self
+            .results
+            .entry(message_key.clone())
+            .or_insert_with(|| Message::new(&message_key, &source, index, *minify_key));
+
+        if line > 0 {
+            message.locations.push(Location {
+                file: self.path.clone(),
+                line,
+            });
+        }
+        for name in placeholders {
+            if !message.placeholders.contains(name) {
+                message.placeholders.push(name.clone());
+            }
+        }
+        if message.value.is_none() {
+            message.value = default;
+        }
+    }
+}
+
+/// Find the `macro_rules!` that pass a metavariable as the key of `t!`, e.g. `tr_label` of
+/// `macro_rules! tr_label { ($key:expr) => { t!($key, label = true) } }`.
+///
+/// It's a heuristic token scan, the key of a call of the wrapper is its first argument if it's a string literal,
+/// the wrappers defined in other files must be configured by `extract-macros`, and the wrappers of the wrappers are not followed.
+fn find_wrapper_macros(stream: TokenStream) -> Vec<String> {
+    let mut macros = vec![];
+    let mut token_iter = stream.into_iter().peekable();
+    while let Some(token) = token_iter.next() {
+        match token {
+            TokenTree::Ident(ident) if ident == "macro_rules" => {
+                let tokens = token_iter.by_ref().take(3).collect::<Vec<_>>();
+                if let [TokenTree::Punct(bang), TokenTree::Ident(name), TokenTree::Group(body)] =
+                    tokens.as_slice()
+                {
+                    if bang.as_char() == '!' && calls_t_with_metavariable(body.stream()) {
+                        macros.push(name.to_string());
+                    }
                 }
             }
+            TokenTree::Group(group) => macros.extend(find_wrapper_macros(group.stream())),
+            _ => {}
         }
     }
+    macros
+}
+
+/// Check if the tokens call `t!` with a metavariable as the key, e.g. `t!($key)`.
+fn calls_t_with_metavariable(stream: TokenStream) -> bool {
+    let tokens = stream.into_iter().collect::<Vec<_>>();
+    tokens.iter().enumerate().any(|(i, token)| match token {
+        TokenTree::Ident(ident) if METHOD_NAMES.contains(&ident.to_string().as_str()) => {
+            matches!(
+                (tokens.get(i + 1), tokens.get(i + 2)),
+                (Some(TokenTree::Punct(bang)), Some(TokenTree::Group(group)))
+                    if bang.as_char() == '!' && is_metavariable(group.stream())
+            )
+        }
+        TokenTree::Group(group) => calls_t_with_metavariable(group.stream()),
+        _ => false,
+    })
+}
+
+/// Check if the key is a metavariable of `macro_rules!`, e.g. `$key` of `t!($key)`.
+fn is_metavariable(stream: TokenStream) -> bool {
+    matches!(stream.into_iter().next(), Some(TokenTree::Punct(punct)) if punct.as_char() == '$')
+}
+
+/// Take the names of the variables after the key, e.g. `name` and `count` of `"key", name = "Jason", count => 2`,
+/// and the inline default if it's a string literal, e.g. `Hello` of `"greeting", default = "Hello"`.
+fn take_arguments(tokens: impl Iterator<Item = TokenTree>) -> (Vec<String>, Option<String>) {
+    let mut placeholders = vec![];
+    let mut default = None;
+    let mut tokens = tokens.peekable();
+    while let Some(token) = tokens.peek() {
+        if matches!(token, TokenTree::Punct(punct) if punct.as_char() == ',') {
+            tokens.next();
+            continue;
+        }
+        // The argument is `name = value` or `name => value`, the name may be a string literal
+        let name = match tokens.next() {
+            Some(TokenTree::Ident(ident)) => Some(ident.to_string()),
+            Some(TokenTree::Literal(literal)) => literal_to_string(&literal),
+            _ => None,
+        };
+        let is_assign =
+            matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '=');
+        // Take the tokens to the next argument
+        let value = tokens
+            .by_ref()
+            .take_while(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == ','))
+            .collect::<Vec<_>>();
+        let Some(name) = name.filter(|_| is_assign) else {
+            continue;
+        };
+        if name == "default" {
+            // Only a string literal is known at the extraction, e.g. not `default = &text`
+            let literal = match value.as_slice() {
+                [_, TokenTree::Literal(literal)] => Some(literal),
+                [_, TokenTree::Punct(arrow), TokenTree::Literal(literal)]
+                    if arrow.as_char() == '>' =>
+                {
+                    Some(literal)
+                }
+                _ => None,
+            };
+            default = literal.and_then(literal_to_string);
+            continue;
+        }
+        let is_reserved = RESERVED_ARGUMENTS.contains(&name.as_str()) || name.starts_with('_');
+        if !is_reserved && !placeholders.contains(&name) {
+            placeholders.push(name);
+        }
+    }
+    (placeholders, default)
 }
 
 fn literal_to_string(lit: &proc_macro2::Literal) -> Option<String> {
@@ -144,6 +491,25 @@ fn literal_to_string(lit: &proc_macro2::Literal) -> Option<String> {
     }
 }
 
+/// Unescape the backslash escapes of a quoted template string, e.g. `\"` => `"`.
+fn unescape_template_str(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => output.push('\n'),
+            Some('t') => output.push('\t'),
+            Some(escaped) => output.push(escaped),
+            None => output.push(c),
+        }
+    }
+    output
+}
+
 fn format_message_key(key: &str) -> String {
     let re = regex::Regex::new(r"\s+").unwrap();
     let key = re.replace_all(key, " ").into_owned();
@@ -161,6 +527,8 @@ mod tests {
             $(
                 let message = Message {
                     key: $key.into(),
+                    source: $key.into(),
+                    value: None,
                     locations: vec![
                         $(
                             Location {
@@ -171,6 +539,7 @@ mod tests {
                     ],
                     index: 0,
                     minify_key: false,
+                    placeholders: vec![],
                 };
                 results.push(message);
             )+
@@ -213,6 +582,440 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_attributes_and_methods() {
+        let source = r#"
+        #[derive(thiserror::Error)]
+        enum MyError {
+            #[error("Not found")]
+            NotFound,
+            #[thiserror::error("Invalid value {0}")]
+            Invalid(String),
+            #[error(transparent)]
+            Other(Error),
+            #[doc("Ignored")]
+            Ignored,
+        }
+
+        fn main() {
+            let button = Button::new().label("Submit").tooltip("Ignored");
+            label("Ignored");
+            t!("hello");
+        }
+        "#;
+        let stream = proc_macro2::TokenStream::from_str(source).unwrap();
+
+        let mut results = HashMap::new();
+        let mut ex = Extractor {
+            dynamic_keys: vec![],
+            key_prefix: None,
+            macros: vec![],
+            results: &mut results,
+            path: &"hello.rs".to_owned().into(),
+            cfg: I18nConfig {
+                extract_attributes: vec!["error".into(), "thiserror::error".into()],
+                extract_methods: vec!["label".into()],
+                ..Default::default()
+            },
+        };
+        ex.invoke(stream).unwrap();
+
+        let mut keys: Vec<_> = ex.results.keys().map(|k| k.as_str()).collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec!["Invalid value {0}", "Not found", "Submit", "hello"]
+        );
+
+        // Opt-in only
+        let stream = proc_macro2::TokenStream::from_str(source).unwrap();
+        let mut results = HashMap::new();
+        let mut ex = Extractor {
+            dynamic_keys: vec![],
+            key_prefix: None,
+            macros: vec![],
+            results: &mut results,
+            path: &"hello.rs".to_owned().into(),
+            cfg: I18nConfig::default(),
+        };
+        ex.invoke(stream).unwrap();
+        assert_eq!(ex.results.keys().collect::<Vec<_>>(), vec!["hello"]);
+    }
+
+    #[test]
+    fn test_extract_placeholders() {
+        let source = r#"
+        fn main() {
+            t!("hello", name = user.name, locale = "en", "count" => items.len());
+            t!("hello", name = "Jason", title = format!("{}, {}", a, b), _minify_key = true);
+            t!("hello", index = 1, random = true);
+            t!("greeting", name = if x == y { a } else { b }, amount = 2);
+            t!("plain");
+        }
+        "#;
+        let stream = proc_macro2::TokenStream::from_str(source).unwrap();
+
+        let mut results = HashMap::new();
+        let mut ex = Extractor {
+            dynamic_keys: vec![],
+            key_prefix: None,
+            macros: vec![],
+            results: &mut results,
+            path: &"hello.rs".to_owned().into(),
+            cfg: I18nConfig::default(),
+        };
+        ex.invoke(stream).unwrap();
+
+        assert_eq!(
+            results["hello"].placeholders,
+            vec!["name", "count", "title"]
+        );
+        assert_eq!(results["greeting"].placeholders, vec!["name", "amount"]);
+        assert!(results["plain"].placeholders.is_empty());
+    }
+
+    #[test]
+    fn test_extract_default() {
+        let source = r#"
+        fn main() {
+            t!("greeting", default = "Hello, %{name}!", name = "Jason");
+            t!("greeting", default = "Hi!");
+            t!("title", "default" => "Welcome",);
+            t!("label", default = &text);
+            t!("plain");
+        }
+        "#;
+        let stream = proc_macro2::TokenStream::from_str(source).unwrap();
+
+        let mut results = HashMap::new();
+        let mut ex = Extractor {
+            dynamic_keys: vec![],
+            key_prefix: None,
+            macros: vec![],
+            results: &mut results,
+            path: &"hello.rs".to_owned().into(),
+            cfg: I18nConfig::default(),
+        };
+        ex.invoke(stream).unwrap();
+
+        // The first default of the key is kept
+        assert_eq!(
+            results["greeting"].value.as_deref(),
+            Some("Hello, %{name}!")
+        );
+        assert_eq!(results["greeting"].placeholders, vec!["name"]);
+        assert_eq!(results["title"].value.as_deref(), Some("Welcome"));
+        assert_eq!(results["label"].value, None);
+        assert_eq!(results["plain"].value, None);
+    }
+
+    #[test]
+    fn test_extract_format_fragments() {
+        let source = r#"
+        fn main() {
+            t!("Total: %{amount} (min %{min}) {:>10} {}", amount = 1, min = 2);
+            t!(r"Price: {:.2} \n {{x}}");
+        }
+        "#;
+        let stream = proc_macro2::TokenStream::from_str(source).unwrap();
+
+        let mut results = HashMap::new();
+        let mut ex = Extractor {
+            dynamic_keys: vec![],
+            key_prefix: None,
+            macros: vec![],
+            results: &mut results,
+            path: &"hello.rs".to_owned().into(),
+            cfg: I18nConfig::default(),
+        };
+        ex.invoke(stream).unwrap();
+
+        // The literals are captured verbatim
+        let message = &results["Total: %{amount} (min %{min}) {:>10} {}"];
+        assert_eq!(message.placeholders, vec!["amount", "min"]);
+        assert!(results.contains_key(r"Price: {:.2} \n {{x}}"));
+    }
+
+    #[test]
+    fn test_extract_verbatim_keys() {
+        let source = r##"
+        fn main() {
+            t!(r#"Say "hi" \o/"#);
+            t!("Tab\there,\n  \"quoted\" \\ backslash");
+            t!("Line 1
+    Line 2");
+            t!("Joined \
+                line");
+        }
+        "##;
+        let keys = [
+            r#"Say "hi" \o/"#,
+            "Tab\there,\n  \"quoted\" \\ backslash",
+            "Line 1\n    Line 2",
+            "Joined line",
+        ];
+        let extract = |verbatim_keys: bool| {
+            let stream = proc_macro2::TokenStream::from_str(source).unwrap();
+            let mut results = HashMap::new();
+            let mut ex = Extractor {
+                dynamic_keys: vec![],
+                key_prefix: None,
+                macros: vec![],
+                results: &mut results,
+                path: &"hello.rs".to_owned().into(),
+                cfg: I18nConfig {
+                    verbatim_keys,
+                    ..Default::default()
+                },
+            };
+            ex.invoke(stream).unwrap();
+            results
+        };
+
+        // The whitespace of the multi-line keys is collapsed by default
+        let results = extract(false);
+        assert!(results.contains_key(r#"Say "hi" \o/"#));
+        assert!(results.contains_key("Tab here, \"quoted\" \\ backslash"));
+        assert!(results.contains_key("Line 1 Line 2"));
+
+        let results = extract(true);
+        let mut extracted = results.keys().cloned().collect::<Vec<_>>();
+        extracted.sort();
+        let mut expected = keys.map(String::from).to_vec();
+        expected.sort();
+        assert_eq!(extracted, expected);
+
+        // The keys are written back with the quotes and escapes of each format, and loaded as the same keys
+        for format in ["yml", "json", "toml"] {
+            let output_path = std::env::temp_dir()
+                .join(format!("rust-i18n-test-extract-verbatim-keys-{}", format));
+            let _ = std::fs::remove_dir_all(&output_path);
+            std::fs::create_dir_all(&output_path).unwrap();
+            let locale_formats = [("en".to_string(), format.to_string())].into();
+            crate::generator::generate_missing(
+                &[&output_path],
+                "en",
+                results.iter(),
+                ".",
+                &locale_formats,
+            )
+            .unwrap();
+
+            let data =
+                rust_i18n_support::load_locales(&output_path.display().to_string(), |_| false);
+            for key in keys {
+                assert_eq!(
+                    data["en"].get(key).map(String::as_str),
+                    Some(key),
+                    "{}",
+                    format
+                );
+            }
+            std::fs::remove_dir_all(&output_path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_extract_key_prefix() {
+        let source = r#"
+        fn main() {
+            t!("hello");
+            rust_i18n::with_key_prefix!("billing.invoice", {
+                t!("title");
+                with_key_prefix!("items", { t!("name") });
+                t!("total");
+                t_ns!(auth, "login.title");
+            });
+            rust_i18n::t_ns!("billing.invoice", "due", date = today);
+        }
+        "#;
+
+        let mut results = HashMap::new();
+        extract(
+            &mut results,
+            &"hello.rs".into(),
+            source,
+            I18nConfig::default(),
+        )
+        .unwrap();
+
+        let mut keys = results.keys().map(String::as_str).collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                "auth.login.title",
+                "billing.invoice.due",
+                "billing.invoice.items.name",
+                "billing.invoice.title",
+                "billing.invoice.total",
+                "hello"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_dynamic_keys() {
+        let source = r#"
+        fn main() {
+            t!("hello");
+            t!(format!("errors.{}", code), name = "Jason");
+            t!(key);
+            t!(&self.key);
+        }
+        "#;
+
+        let keys = extract_dynamic_keys(&"hello.rs".into(), source, I18nConfig::default()).unwrap();
+        let keys = keys
+            .iter()
+            .map(|key| (key.location.line, key.expr.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            vec![
+                (4, r#"format!("errors.{}", code)"#),
+                (5, "key"),
+                (6, "&self.key")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_wrapper_macros() {
+        let source = r#"
+        macro_rules! tr_label {
+            ($key:expr) => { t!($key, suffix = ":") };
+            ($key:expr, $($args:tt)*) => { t!($key, $($args)*) };
+        }
+
+        macro_rules! greet {
+            () => { t!("greeting.default") };
+        }
+
+        fn main() {
+            tr_label!("labels.name");
+            tr_label!("labels.email", required = true);
+            tr_label!(dynamic);
+            tr_button!("buttons.submit");
+            greet!();
+            other!("ignored");
+        }
+        "#;
+
+        let mut results = HashMap::new();
+        extract(
+            &mut results,
+            &"hello.rs".into(),
+            source,
+            I18nConfig {
+                extract_macros: vec!["tr_button".into()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut keys = results.keys().map(String::as_str).collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                "buttons.submit",
+                "greeting.default",
+                "labels.email",
+                "labels.name"
+            ]
+        );
+        assert_eq!(results["labels.name"].locations[0].line, 12);
+        assert_eq!(results["labels.email"].placeholders, vec!["required"]);
+
+        // The metavariables of the wrappers are not the dynamic keys
+        let keys = extract_dynamic_keys(&"hello.rs".into(), source, I18nConfig::default()).unwrap();
+        assert!(keys.is_empty(), "{:?}", keys);
+    }
+
+    #[test]
+    fn test_extract_template() {
+        let source = r#"<h1>{{ t(key="views.title") }}</h1>
+<p>{{ t(key = 'Say \'hi\'', name=user.name) }}</p>
+<p>{{ format("ignored") }} {{ t(dynamic_key) }}</p>
+<p>{{ t("hello") }}</p>"#;
+
+        let mut results = HashMap::new();
+        extract_template(
+            &mut results,
+            &"index.html".into(),
+            source,
+            I18nConfig::default(),
+        )
+        .unwrap();
+
+        let mut messages: Vec<_> = results.values().collect();
+        messages.sort_by_key(|m| m.index);
+        let messages: Vec<_> = messages
+            .iter()
+            .map(|m| (m.key.as_str(), m.locations[0].line))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![("views.title", 1), ("Say 'hi'", 2), ("hello", 4)]
+        );
+
+        // The custom function name
+        let mut results = HashMap::new();
+        extract_template(
+            &mut results,
+            &"index.html".into(),
+            r#"{{ tr("hello") }} {{ t("ignored") }}"#,
+            I18nConfig {
+                template_function: "tr".into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(results.keys().collect::<Vec<_>>(), vec!["hello"]);
+    }
+
+    #[test]
+    fn test_extract_with_max_key_len() {
+        let source = r#"
+        fn main() {
+            t!("Hello");
+            t!("Hello, this is a long message");
+        }
+        "#;
+        let stream = proc_macro2::TokenStream::from_str(source).unwrap();
+        let mut results = HashMap::new();
+        let mut ex = Extractor {
+            dynamic_keys: vec![],
+            key_prefix: None,
+            macros: vec![],
+            results: &mut results,
+            path: &"hello.rs".to_owned().into(),
+            cfg: I18nConfig {
+                minify_key: true,
+                max_key_len: 10,
+                ..Default::default()
+            },
+        };
+        ex.invoke(stream).unwrap();
+
+        // The long key is hashed even if it's under `minify-key-thresh`
+        let long_key = rust_i18n_support::MinifyKey::minify_key(
+            "Hello, this is a long message",
+            rust_i18n_support::DEFAULT_MINIFY_KEY_LEN,
+            rust_i18n_support::DEFAULT_MINIFY_KEY_PREFIX,
+            0,
+        );
+        let mut keys: Vec<_> = ex.results.keys().map(|k| k.as_str()).collect();
+        keys.sort();
+        assert_eq!(keys, vec![long_key.as_ref(), "Hello"]);
+        assert_eq!(
+            ex.results[long_key.as_ref()].source,
+            "Hello, this is a long message"
+        );
+        assert_eq!(ex.results[long_key.as_ref()].key, long_key);
+    }
+
     #[test]
     fn test_extract() {
         let source = include_str!("example.test.rs");
@@ -237,6 +1040,9 @@ mod tests {
         let mut results = HashMap::new();
 
         let mut ex = Extractor {
+            dynamic_keys: vec![],
+            key_prefix: None,
+            macros: vec![],
             results: &mut results,
             path: &"hello.rs".to_owned().into(),
             cfg: I18nConfig::default(),
@@ -251,6 +1057,8 @@ mod tests {
         for (expected_message, actually_message) in expected.iter().zip(messages) {
             let mut actually_message = actually_message.clone();
             actually_message.index = 0;
+            // The placeholders are checked by `test_extract_placeholders`
+            actually_message.placeholders.clear();
 
             assert_eq!(*expected_message, actually_message);
         }
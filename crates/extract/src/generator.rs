@@ -1,21 +1,50 @@
 use crate::extractor::Message;
-use rust_i18n_support::load_locales;
-use std::collections::HashMap;
+use rust_i18n_support::{
+    canonicalize_locale, canonicalize_locales, is_locale_extension, is_plural_map,
+    iter_locale_files, load_source_texts_with_separator, load_status_keys_with_separator,
+    parse_value, placeholder_names, strip_bom, try_load_locales_from_paths_with_separator,
+    Encoding, I18nConfig, PluralCategory, SchemaVersion, TranslationStatus, DEFAULT_KEY_SEPARATOR,
+    LOCALE_EXTENSIONS, OVERLAYS_DIR,
+};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::io::prelude::*;
 use std::io::Result;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
-type Translations = HashMap<String, HashMap<String, String>>;
+/// The metadata key of a translation in the V2 format, e.g. `_meta: { source: ... }`.
+const META_KEY: &str = "_meta";
 
+pub(crate) type Translations = HashMap<String, HashMap<String, String>>;
+
+/// Generate the untranslated texts into `TODO.yml` of the last load path.
+///
+/// The translations of all load paths are merged to check the untranslated texts.
+/// The source texts of the minified keys are kept in `_meta: { source: ... }`, so the translators can read them.
+/// The locales of `locale_formats` are written into their own files in the format instead, e.g. `TODO.fr.json` for `{ fr = "json" }`.
+/// With `split_by_namespace`, the keys are written into the directory of their namespace, e.g. `auth/TODO.yml` for `auth.login.title`.
+/// With `canonical_locales`, the locales are written by [`canonicalize_locale`], e.g. `en-US` for `en-us`.
 pub fn generate<'a, P: AsRef<Path>>(
-    output_path: P,
+    load_paths: &[P],
     all_locales: &Vec<String>,
     messages: impl IntoIterator<Item = (&'a String, &'a Message)> + Clone,
+    key_separator: &str,
+    locale_formats: &BTreeMap<String, String>,
+    split_by_namespace: bool,
+    canonical_locales: bool,
 ) -> Result<()> {
-    let filename = "TODO.yml";
-    let format = "yaml";
+    let output_path = last_load_path(load_paths)?;
+    for locale in all_locales {
+        locale_format(locale_formats, locale)?;
+    }
 
-    let trs = generate_result(&output_path, filename, all_locales, messages);
+    let trs = generate_result(
+        load_paths,
+        all_locales,
+        messages.clone(),
+        key_separator,
+        canonical_locales,
+    )?;
 
     if trs.is_empty() {
         println!("All thing done.\n");
@@ -25,56 +54,1079 @@ pub fn generate<'a, P: AsRef<Path>>(
 
     eprintln!("Found {} new texts need to translate.", trs.len());
     eprintln!("----------------------------------------");
-    eprintln!("Writing to {}\n", filename);
 
-    let text = convert_text(&trs, format);
-    write_file(&output_path, filename, &text)?;
+    let mut namespace_trs = BTreeMap::<String, Translations>::new();
+    for (key, texts) in trs {
+        let dir = match split_by_namespace {
+            true => namespace_dir(&key, key_separator).unwrap_or_default(),
+            false => "",
+        };
+        namespace_trs
+            .entry(dir.to_string())
+            .or_default()
+            .insert(key, texts);
+    }
+    for (dir, trs) in namespace_trs {
+        write_todo_files(output_path, &dir, trs, messages.clone(), locale_formats)?;
+    }
 
     // Finally, return error for let CI fail
-    let err = std::io::Error::new(std::io::ErrorKind::Other, "");
+    let err = std::io::Error::other("");
     Err(err)
 }
 
-fn convert_text(trs: &Translations, format: &str) -> String {
-    let mut value = serde_json::Value::Object(serde_json::Map::new());
-    value["_version"] = serde_json::Value::Number(serde_json::Number::from(2));
+/// Get the directory of the key's namespace for `split-by-namespace`, the first segment of the key, e.g. `auth` of `auth.login.title`.
+///
+/// The keys without a namespace (e.g. the minified keys) are kept in the load path, so are the namespaces that are not
+/// a plain directory name, or that would be skipped as the overlays.
+fn namespace_dir<'a>(key: &'a str, key_separator: &str) -> Option<&'a str> {
+    let (namespace, _) = key.split_once(key_separator)?;
+    let mut components = Path::new(namespace).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None)
+            if namespace != OVERLAYS_DIR && !namespace.contains(['/', '\\']) =>
+        {
+            Some(namespace)
+        }
+        _ => None,
+    }
+}
+
+/// Write the untranslated texts into `TODO.yml` of the `dir` in the output path,
+/// and the texts of the locales with their own format into their own files, e.g. `TODO.fr.json`.
+fn write_todo_files<'a>(
+    output_path: &Path,
+    dir: &str,
+    mut trs: Translations,
+    messages: impl IntoIterator<Item = (&'a String, &'a Message)> + Clone,
+    locale_formats: &BTreeMap<String, String>,
+) -> Result<()> {
+    let filename = "TODO.yml";
+    let format = "yaml";
+    let output_path = output_path.join(dir);
+
+    // Move the texts of the locales with their own format out of `TODO.yml`
+    let mut locale_trs = BTreeMap::<&str, serde_json::Map<String, serde_json::Value>>::new();
+    for (key, texts) in trs.iter_mut() {
+        texts.retain(|locale, text| {
+            let Some((locale, _)) = locale_formats.get_key_value(locale) else {
+                return true;
+            };
+            locale_trs
+                .entry(locale.as_str())
+                .or_default()
+                .insert(key.clone(), serde_json::Value::String(text.clone()));
+            false
+        });
+    }
+    trs.retain(|_, texts| !texts.is_empty());
+
+    for (locale, mut texts) in locale_trs {
+        let ext = locale_format(locale_formats, locale)?;
+        let filename = format!("TODO.{}.{}", locale, ext);
+        eprintln!("Writing to {}", Path::new(dir).join(&filename).display());
+        texts.sort_keys();
+        let text = convert_value(&serde_json::Value::Object(texts), ext);
+        write_file(&output_path, &filename, text.trim_end())?;
+    }
+
+    if !trs.is_empty() {
+        eprintln!("Writing to {}\n", Path::new(dir).join(filename).display());
+        let sources = messages
+            .clone()
+            .into_iter()
+            .filter(|(key, m)| m.minify_key && trs.contains_key(*key))
+            .map(|(key, m)| (key.clone(), m.source.clone()))
+            .collect();
+        let text = convert_text(&trs, &sources, &HashMap::new(), format);
+        let text = annotate_placeholders(&text, messages);
+        write_file(&output_path, filename, &text)?;
+    }
+
+    Ok(())
+}
+
+/// Append the texts missing from the default locale to the end of its `TODO` file in the last load path, e.g. `TODO.yml`.
+///
+/// Only the keys that no file of the default locale has (including the `TODO` file itself) are added, in the order of `messages`,
+/// the other locales are not checked, and the existing content of the file is kept as is.
+/// Returns the number of the added keys.
+pub fn generate_missing<'a, P: AsRef<Path>>(
+    load_paths: &[P],
+    default_locale: &str,
+    messages: impl IntoIterator<Item = (&'a String, &'a Message)> + Clone,
+    key_separator: &str,
+    locale_formats: &BTreeMap<String, String>,
+) -> Result<usize> {
+    let output_path = last_load_path(load_paths)?;
+    let format = locale_format(locale_formats, default_locale)?;
+
+    println!(
+        "Checking [{}] and generating missing texts...",
+        default_locale
+    );
+    let data = load_translations(&display_paths(load_paths), |_| false, key_separator)?;
+    let default_trs = data.get(default_locale);
+    let missing = messages
+        .into_iter()
+        .filter(|(key, _)| default_trs.is_none_or(|trs| !trs.contains_key(*key)))
+        .collect::<Vec<_>>();
+
+    if missing.is_empty() {
+        println!("All thing done.\n");
+
+        return Ok(0);
+    }
+
+    eprintln!("Found {} new texts need to translate.", missing.len());
+    eprintln!("----------------------------------------");
+
+    // The locale with its own format has a `TODO.{locale}.{ext}` file of its texts
+    if locale_formats.contains_key(default_locale) {
+        let filename = format!("TODO.{}.{}", default_locale, format);
+        let path = output_path.join(&filename);
+        let mut value = match path.exists() {
+            true => {
+                let content = std::fs::read_to_string(&path)?;
+                parse_value(strip_bom(&content), format).map_err(|err| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("{}: {}", path.display(), err),
+                    )
+                })?
+            }
+            false => serde_json::Value::Object(serde_json::Map::new()),
+        };
+        let Some(texts) = value.as_object_mut() else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{}: expected a map of the texts", path.display()),
+            ));
+        };
+        for (key, m) in &missing {
+            texts.insert(
+                key.to_string(),
+                serde_json::Value::String(untranslated_text(m, key_separator)),
+            );
+        }
+
+        eprintln!("Writing to {}\n", filename);
+        write_file(
+            &output_path,
+            &filename,
+            convert_value(&value, format).trim_end(),
+        )?;
+        return Ok(missing.len());
+    }
+
+    let filename = "TODO.yml";
+    let path = output_path.join(filename);
+    let mut text = match path.exists() {
+        true => std::fs::read_to_string(&path)?.trim_end().to_string(),
+        false => format!("_version: {}", SchemaVersion::V2.as_u64()),
+    };
+    for &(key, m) in &missing {
+        let mut texts = serde_json::Map::new();
+        if m.minify_key {
+            texts.insert(META_KEY.into(), serde_json::json!({ "source": m.source }));
+        }
+        texts.insert(
+            default_locale.into(),
+            untranslated_text(m, key_separator).into(),
+        );
+        let value = serde_json::json!({ key: texts });
+        let entry = convert_value(&value, "yaml");
+        text.push('\n');
+        text.push_str(annotate_placeholders(&entry, [(key, m)]).trim_end());
+    }
+
+    eprintln!("Writing to {}\n", filename);
+    write_file(&output_path, filename, &text)?;
+    Ok(missing.len())
+}
+
+/// Add a comment of the placeholders above the YAML keys, so the translators keep them,
+/// e.g. `# Placeholders: name, count` above `greeting:`.
+fn annotate_placeholders<'a>(
+    text: &str,
+    messages: impl IntoIterator<Item = (&'a String, &'a Message)>,
+) -> String {
+    // The serialized key line of each key, e.g. `"a: b":` for the key `a: b`
+    let comments = messages
+        .into_iter()
+        .filter(|(_, m)| !m.placeholders.is_empty())
+        .filter_map(|(key, m)| {
+            let line = convert_value(&serde_json::json!({ key: null }), "yaml");
+            let line = line.trim_end().strip_suffix(" null")?.to_string();
+            Some((line, m.placeholders.join(", ")))
+        })
+        .collect::<HashMap<_, _>>();
+    if comments.is_empty() {
+        return text.to_string();
+    }
+
+    let mut output = String::with_capacity(text.len());
+    for line in text.lines() {
+        if let Some(placeholders) = comments.get(line) {
+            output.push_str(&format!("# Placeholders: {}\n", placeholders));
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+    output
+}
+
+/// Generate a new locale file `{locale}.yml` in the last load path that contains all keys of the default locale.
+///
+/// The file is written in the format of the locale in `locale-formats` if any, e.g. `fr.json` for `{ fr = "json" }`.
+/// The values are left empty, or copied from the default locale if `copy_default` is true.
+/// The keys of `namespace-default-locales` are taken from the default locale of their namespace instead.
+/// An existing locale will not be overwritten unless `force` is true.
+/// The locale is named by [`canonicalize_locale`] with `canonical-locales`, e.g. `pt-BR.yml` for `pt_br`.
+pub fn generate_locale<P: AsRef<Path>>(
+    load_paths: &[P],
+    cfg: &I18nConfig,
+    locale: &str,
+    copy_default: bool,
+    force: bool,
+) -> Result<()> {
+    let default_locale = cfg.default_locale.as_str();
+    let key_separator = cfg.key_separator.as_str();
+    let format = locale_format(&cfg.locale_formats, locale)?;
+    let locale = match cfg.canonical_locales {
+        true => canonicalize_locale(locale),
+        false => locale.to_string(),
+    };
+    let locale = locale.as_str();
+    let filename = format!("{}.{}", locale, format);
+    let output_path = last_load_path(load_paths)?;
+
+    let mut data = load_translations(&display_paths(load_paths), |_| false, key_separator)?;
+    if cfg.canonical_locales {
+        data = canonicalize_locales(data);
+    }
+
+    let exists = data.contains_key(locale) || output_path.join(&filename).exists();
+    if exists && !force {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!(
+                "Locale `{}` already exists, use `--force` to overwrite it",
+                locale
+            ),
+        ));
+    }
+
+    if !data.contains_key(default_locale) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!(
+                "No translations found for default locale `{}`",
+                default_locale
+            ),
+        ));
+    }
+
+    let mut source_locales = vec![default_locale];
+    for namespace_locale in cfg.namespace_default_locales.values() {
+        if !source_locales.contains(&namespace_locale.as_str()) {
+            source_locales.push(namespace_locale);
+        }
+    }
+
+    let mut value = serde_json::Map::new();
+    for source_locale in source_locales {
+        for (key, text) in data.get(source_locale).into_iter().flatten() {
+            if cfg.default_locale_of(key) != source_locale {
+                continue;
+            }
+            let text = if copy_default {
+                text.clone()
+            } else {
+                String::new()
+            };
+            value.insert(key.clone(), serde_json::Value::String(text));
+        }
+    }
+
+    eprintln!(
+        "Creating [{}] with {} keys from [{}]...",
+        locale,
+        value.len(),
+        default_locale
+    );
+    eprintln!("Writing to {}\n", filename);
+
+    value.sort_keys();
+    let text = convert_value(&serde_json::Value::Object(value), format);
+    write_file(&output_path, &filename, &text)
+}
+
+/// The formats of [`export`].
+const EXPORT_FORMATS: &[&str] = &["yml", "yaml", "json", "json5", "toml", "csv", "tsv"];
+
+/// Export the translations of all locales into `output`, the format is inferred from the extension unless `format` is given.
+/// The output `-` is the stdout, which needs the `format`.
+///
+/// Only the `keys` are exported if given, e.g. the keys changed since a git revision.
+/// The output is written in the `encoding`, returns the number of the exported keys.
+///
+/// With [`SchemaVersion::V1`], each locale is written into its own file next to `output`,
+/// named with the locale before the extension, e.g. `i18n-export.en.yml`.
+///
+/// The `csv` and `tsv` outputs are a table of the keys and the locales, written with the `csv` options.
+/// With `canonical_locales`, the locales are written by [`canonicalize_locale`], see [`canonicalize_locales`].
+#[allow(clippy::too_many_arguments)]
+pub fn export<P: AsRef<Path>>(
+    load_paths: &[P],
+    output: &Path,
+    format: Option<&str>,
+    keys: Option<&HashSet<String>>,
+    key_separator: &str,
+    encoding: Encoding,
+    version: SchemaVersion,
+    csv: CsvOptions,
+    canonical_locales: bool,
+) -> Result<usize> {
+    let is_stdout = output == Path::new("-");
+    let format = match format {
+        Some(format) if EXPORT_FORMATS.contains(&format) => format,
+        Some(format) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Unsupported export format `{}`, expected `yml`, `yaml`, `json`, `json5`, `toml`, `csv` or `tsv`",
+                    format
+                ),
+            ))
+        }
+        None => match output.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if EXPORT_FORMATS.contains(&ext) => ext,
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!(
+                        "Unsupported export format of {}, expected `yml`, `yaml`, `json`, `json5`, `toml`, `csv` or `tsv`, or use `--format`",
+                        output.display()
+                    ),
+                ))
+            }
+        },
+    };
+    if is_stdout && version == SchemaVersion::V1 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "The schema version 1 writes one file per locale, it can't be written to the stdout",
+        ));
+    }
+    let is_table = matches!(format, "csv" | "tsv");
+    if is_table && version == SchemaVersion::V1 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "The `csv` and `tsv` exports are tables of all locales, schema version 1 is unsupported",
+        ));
+    }
+    if csv.key_column().trim().is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "The header of the key column can't be empty",
+        ));
+    }
+
+    let trs = load_export_translations(load_paths, keys, key_separator, canonical_locales)?;
+    let count = trs.len();
+
+    let write = |output: &Path, text: String| {
+        let bytes = encoding
+            .encode(&format!("{}\n", text.trim_end()))
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        if is_stdout {
+            return std::io::stdout().lock().write_all(&bytes);
+        }
+        write_atomic(output, bytes)
+    };
+    match version {
+        SchemaVersion::V1 => {
+            let stem = output
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            for (locale, texts) in convert_locales(&trs) {
+                let mut value = serde_json::Map::new();
+                value.insert("_version".into(), SchemaVersion::V1.as_u64().into());
+                value.extend(texts);
+                value.sort_keys();
+                let text = convert_value(&serde_json::Value::Object(value), format);
+                write(
+                    &output.with_file_name(format!("{}.{}.{}", stem, locale, format)),
+                    text,
+                )?;
+            }
+        }
+        SchemaVersion::V2 if is_table => {
+            // The `tsv` implies the tab delimiter
+            let delimiter = csv
+                .delimiter
+                .unwrap_or(if format == "tsv" { b'\t' } else { b',' });
+            if is_stdout {
+                write_csv(std::io::stdout().lock(), trs, delimiter, &csv, encoding)?
+            } else {
+                // Don't leave a truncated table if any text can't be encoded
+                let file = std::fs::File::create(output)?;
+                write_csv(file, trs, delimiter, &csv, encoding).inspect_err(|_| {
+                    let _ = std::fs::remove_file(output);
+                })?
+            }
+        }
+        SchemaVersion::V2 => {
+            // Keep the source texts of the minified keys and the review statuses for the translators
+            let mut sources = HashMap::new();
+            let mut statuses = HashMap::new();
+            for path in display_paths(load_paths) {
+                let invalid_data = |err| std::io::Error::new(std::io::ErrorKind::InvalidData, err);
+                sources.extend(
+                    load_source_texts_with_separator(&path, |_| false, key_separator)
+                        .map_err(invalid_data)?,
+                );
+                statuses.extend(
+                    load_status_keys_with_separator(&path, |_| false, key_separator)
+                        .map_err(invalid_data)?,
+                );
+            }
+            sources.retain(|key, _| trs.contains_key(key));
+            let statuses = statuses
+                .into_iter()
+                .filter(|(key, _)| trs.contains_key(key))
+                .map(|(key, statuses)| {
+                    let statuses = statuses
+                        .into_iter()
+                        .map(|(locale, status)| match canonical_locales {
+                            true => (canonicalize_locale(&locale), status),
+                            false => (locale, status),
+                        })
+                        .collect();
+                    (key, statuses)
+                })
+                .collect();
+            write(output, convert_text(&trs, &sources, &statuses, format))?
+        }
+    }
+
+    Ok(count)
+}
+
+/// Export the translations into one file per locale in the `output` directory, e.g. `en.json`, `fr.json`,
+/// each file is a flat map of the key to the text, e.g. for the i18n libraries of a frontend.
+///
+/// Only the given `locales` are exported if it's not empty, and only the `keys` if given.
+/// The `format` is `yml`, `yaml`, `json` or `toml`, returns the number of the written files.
+/// With `canonical_locales`, the files are named by [`canonicalize_locale`], e.g. `en-US.json` for `en-us.yml`.
+#[allow(clippy::too_many_arguments)]
+pub fn export_split<P: AsRef<Path>>(
+    load_paths: &[P],
+    output: &Path,
+    keys: Option<&HashSet<String>>,
+    locales: &[String],
+    key_separator: &str,
+    encoding: Encoding,
+    format: &str,
+    canonical_locales: bool,
+) -> Result<usize> {
+    if !matches!(format, "yml" | "yaml" | "json" | "json5" | "toml") {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "Unsupported split export format `{}`, expected `yml`, `yaml`, `json`, `json5` or `toml`",
+                format
+            ),
+        ));
+    }
+
+    let trs = load_export_translations(load_paths, keys, key_separator, canonical_locales)?;
+    std::fs::create_dir_all(output)?;
+
+    let mut count = 0;
+    for (locale, mut texts) in convert_locales(&trs) {
+        let is_selected = |l: &String| match canonical_locales {
+            true => canonicalize_locale(l) == locale,
+            false => l == locale,
+        };
+        if !locales.is_empty() && !locales.iter().any(is_selected) {
+            continue;
+        }
+        texts.sort_keys();
+        let text = convert_value(&serde_json::Value::Object(texts), format);
+        let bytes = encoding
+            .encode(&format!("{}\n", text.trim_end()))
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        write_atomic(output.join(format!("{}.{}", locale, format)), bytes)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Export the keys of all locales into `output` as a JSON manifest for the code generators of a frontend,
+/// e.g. the TypeScript union types of the keys. The output `-` is the stdout.
+///
+/// The manifest is a sorted list of the keys, or with `placeholders`, a map of each key to the sorted names of
+/// the placeholders in its translations of all locales, e.g. `{"greeting": ["name"]}`.
+/// The placeholders are wrapped in the `interpolation` delimiters, e.g. `("%{", "}")`.
+///
+/// Only the `keys` are exported if given, returns the number of the exported keys.
+pub fn export_manifest<P: AsRef<Path>>(
+    load_paths: &[P],
+    output: &Path,
+    keys: Option<&HashSet<String>>,
+    key_separator: &str,
+    interpolation: (&str, &str),
+    placeholders: bool,
+) -> Result<usize> {
+    // The manifest has no locales, they are kept as is
+    let trs = load_export_translations(load_paths, keys, key_separator, false)?;
+    let trs = trs.into_iter().collect::<BTreeMap<_, _>>();
+    let count = trs.len();
+
+    let value = if placeholders {
+        let (open, close) = interpolation;
+        let manifest = trs
+            .iter()
+            .map(|(key, texts)| {
+                let names = texts
+                    .values()
+                    .flat_map(|text| placeholder_names(text, open, close))
+                    .collect::<BTreeSet<_>>();
+                (key.clone(), serde_json::json!(names))
+            })
+            .collect::<serde_json::Map<_, _>>();
+        serde_json::Value::Object(manifest)
+    } else {
+        serde_json::json!(trs.keys().collect::<Vec<_>>())
+    };
+    let text = format!(
+        "{}
+",
+        serde_json::to_string_pretty(&value)?
+    );
+    if output == Path::new("-") {
+        std::io::stdout().lock().write_all(text.as_bytes())?;
+    } else {
+        write_atomic(output, text)?;
+    }
+
+    Ok(count)
+}
+
+/// Load the translations grouped by key for the exports, only the `keys` if given.
+///
+/// The locale files are loaded one by one into the translations grouped by key,
+/// so the translations grouped by locale are never loaded all at once.
+/// With `canonical_locales`, the translations are grouped by the locales of [`canonicalize_locale`].
+fn load_export_translations<P: AsRef<Path>>(
+    load_paths: &[P],
+    keys: Option<&HashSet<String>>,
+    key_separator: &str,
+    canonical_locales: bool,
+) -> Result<Translations> {
+    let mut trs = Translations::new();
+    let mut errors = vec![];
+    for load_path in display_paths(load_paths) {
+        for file in iter_locale_files(&load_path, |_| false, key_separator) {
+            let file = match file {
+                Ok(file) => file,
+                Err(err) => {
+                    errors.push(err.to_string());
+                    continue;
+                }
+            };
+            for (locale, texts) in file.translations {
+                let locale = match canonical_locales {
+                    true => canonicalize_locale(&locale),
+                    false => locale,
+                };
+                for (key, text) in texts {
+                    if key == "_version" || keys.is_some_and(|keys| !keys.contains(&key)) {
+                        continue;
+                    }
+                    trs.entry(key).or_default().insert(locale.clone(), text);
+                }
+            }
+        }
+    }
+    if !errors.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            errors.join("\n"),
+        ));
+    }
+    Ok(trs)
+}
+
+/// Get the keys whose `default_locale` value in `current` is added or modified since `previous`.
+pub fn changed_keys(
+    previous: &HashMap<String, HashMap<String, String>>,
+    current: &HashMap<String, HashMap<String, String>>,
+    default_locale: &str,
+) -> HashSet<String> {
+    let Some(texts) = current.get(default_locale) else {
+        return HashSet::new();
+    };
+    let previous = previous.get(default_locale);
+
+    texts
+        .iter()
+        .filter(|(key, text)| previous.and_then(|trs| trs.get(*key)) != Some(*text))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+/// The options of the `csv` and `tsv` exports.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// The delimiter of the fields, `None` for `,` in `csv` and `\t` in `tsv`
+    pub delimiter: Option<u8>,
+    /// When to quote the fields
+    pub quote_style: QuoteStyle,
+    /// The header of the key column, `None` for `key`, e.g. `id` for the tools that expect it
+    pub key_column: Option<String>,
+}
+
+impl CsvOptions {
+    /// The header of the key column, the import must match the same header as the export.
+    pub fn key_column(&self) -> &str {
+        self.key_column.as_deref().unwrap_or("key")
+    }
+}
+
+/// Parse the CSV delimiter, a single ASCII char, or `tab` for `\t`, e.g. `;`.
+pub fn parse_delimiter(s: &str) -> std::result::Result<u8, String> {
+    match s {
+        "tab" | "\\t" | "\t" => Ok(b'\t'),
+        _ if s.len() == 1 && s.is_ascii() && s != "\"" && s != "\n" => Ok(s.as_bytes()[0]),
+        _ => Err(format!(
+            "Invalid delimiter `{}`, expected a single ASCII char, e.g. `;`, or `tab`",
+            s
+        )),
+    }
+}
+
+/// When to quote the fields of the `csv` and `tsv` exports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// Only quote the fields containing the delimiter, quotes or line breaks
+    #[default]
+    Necessary,
+    /// Quote all fields, e.g. for the tools that mis-detect the unquoted numbers
+    Always,
+    /// Quote all fields that are not numbers
+    NonNumeric,
+    /// Never quote the fields, the fields containing the delimiter are written as is
+    Never,
+}
+
+impl std::str::FromStr for QuoteStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "necessary" => Ok(QuoteStyle::Necessary),
+            "always" => Ok(QuoteStyle::Always),
+            "non-numeric" => Ok(QuoteStyle::NonNumeric),
+            "never" => Ok(QuoteStyle::Never),
+            _ => Err(format!(
+                "Invalid quote style `{}`, expected `necessary`, `always`, `non-numeric` or `never`",
+                s
+            )),
+        }
+    }
+}
+
+/// The order of the keys for [`sort_locales`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortBy {
+    /// Lexicographic order, e.g. `item1`, `item10`, `item2`
+    #[default]
+    Key,
+    /// Numeric-aware order, e.g. `item1`, `item2`, `item10`
+    Natural,
+    /// Group by the namespace before the first key separator then lexicographic order, e.g. `a`, `a.b`, `a-c`
+    Namespace,
+}
+
+impl SortBy {
+    /// Compare the keys in this order, the namespaces are separated by `.`.
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        self.compare_with_separator(a, b, DEFAULT_KEY_SEPARATOR)
+    }
+
+    /// Compare the keys in this order, the namespaces are separated by the `separator`.
+    pub fn compare_with_separator(&self, a: &str, b: &str, separator: &str) -> Ordering {
+        match self {
+            SortBy::Key => a.cmp(b),
+            SortBy::Natural => natural_cmp(a, b),
+            SortBy::Namespace => {
+                let (ns_a, rest_a) = a.split_once(separator).unwrap_or((a, ""));
+                let (ns_b, rest_b) = b.split_once(separator).unwrap_or((b, ""));
+                ns_a.cmp(ns_b).then_with(|| rest_a.cmp(rest_b))
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "key" => Ok(SortBy::Key),
+            "natural" => Ok(SortBy::Natural),
+            "namespace" => Ok(SortBy::Namespace),
+            _ => Err(format!(
+                "Invalid sort order `{}`, expected `key`, `natural` or `namespace`",
+                s
+            )),
+        }
+    }
+}
+
+/// Compare the strings with the digit runs compared numerically, e.g. `item2` < `item10`.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(ca), Some(cb)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+        if ca.is_ascii_digit() && cb.is_ascii_digit() {
+            let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            let (na, nb) = (&a[..digits(a)], &b[..digits(b)]);
+            let (ta, tb) = (na.trim_start_matches('0'), nb.trim_start_matches('0'));
+            // The longer number is larger, then compare the digits, then the leading zeros
+            let ordering = ta
+                .len()
+                .cmp(&tb.len())
+                .then_with(|| ta.cmp(tb))
+                .then_with(|| na.len().cmp(&nb.len()));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            (a, b) = (&a[na.len()..], &b[nb.len()..]);
+        } else {
+            if ca != cb {
+                return ca.cmp(&cb);
+            }
+            (a, b) = (&a[ca.len_utf8()..], &b[cb.len_utf8()..]);
+        }
+    }
+}
+
+/// Sort the keys of all locale files in `output_path` recursively.
+///
+/// Returns the files that are not sorted, they are rewritten unless `check` is true.
+pub fn sort_locales<P: AsRef<Path>>(
+    output_path: P,
+    check: bool,
+    sort_by: SortBy,
+    key_separator: &str,
+) -> Result<Vec<PathBuf>> {
+    rewrite_locale_files(output_path, check, "Sorting", |value| {
+        sort_value(value, sort_by, key_separator)
+    })
+}
+
+/// Rewrite all locale files in `output_path` recursively in the canonical format, the keys are kept in their order.
+///
+/// The canonical format is the one written by `cargo i18n`: the YAML strings are plain, or single-quoted only if needed,
+/// the multi-line strings are block literals, the flow mappings are written as blocks and the keys are quoted only if needed,
+/// so the files with the same translations in the same order are byte-identical.
+///
+/// Returns the files that are not canonical, they are rewritten unless `check` is true.
+pub fn canonicalize_files<P: AsRef<Path>>(output_path: P, check: bool) -> Result<Vec<PathBuf>> {
+    rewrite_locale_files(output_path, check, "Canonicalizing", |value| value)
+}
+
+/// Rewrite all locale files in `output_path` recursively with the value converted by `f`, in the canonical format.
+///
+/// Returns the files that are changed, they are rewritten unless `check` is true.
+fn rewrite_locale_files<P: AsRef<Path>>(
+    output_path: P,
+    check: bool,
+    action: &str,
+    f: impl Fn(serde_json::Value) -> serde_json::Value,
+) -> Result<Vec<PathBuf>> {
+    let mut changed = vec![];
+
+    for entry in ignore::WalkBuilder::new(output_path).build() {
+        let path = entry.map_err(std::io::Error::other)?.into_path();
+        let Some(ext) = path.extension().and_then(|ext| ext.to_str()) else {
+            continue;
+        };
+        if !path.is_file() || !is_locale_extension(ext) {
+            continue;
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let value = parse_value(strip_bom(&content), ext).map_err(|err| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), err),
+            )
+        })?;
+        let text = convert_value(&f(value), ext);
+        if content.trim_end() == text.trim_end() {
+            continue;
+        }
+
+        if !check {
+            eprintln!("{} {}", action, path.display());
+            write_atomic(&path, format!("{}\n", text.trim_end()))?;
+        }
+        changed.push(path);
+    }
+
+    Ok(changed)
+}
+
+/// Sort the keys of the objects recursively, the plural forms are kept in the CLDR order, e.g. `one`, `other`.
+fn sort_value(value: serde_json::Value, sort_by: SortBy, separator: &str) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let is_plural = is_plural_map(&serde_json::Value::Object(map.clone()));
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            if is_plural {
+                entries.sort_by_key(|(key, _)| PluralCategory::from_key(key).map(|c| c as u8));
+            } else {
+                entries.sort_by(|(a, _), (b, _)| sort_by.compare_with_separator(a, b, separator));
+            }
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, sort_value(v, sort_by, separator)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(list) => serde_json::Value::Array(
+            list.into_iter()
+                .map(|value| sort_value(value, sort_by, separator))
+                .collect(),
+        ),
+        _ => value,
+    }
+}
+
+/// Group the translations grouped by key back by locale, for [`SchemaVersion::V1`].
+fn convert_locales(
+    trs: &Translations,
+) -> std::collections::BTreeMap<&str, serde_json::Map<String, serde_json::Value>> {
+    let mut locales = std::collections::BTreeMap::<_, serde_json::Map<_, _>>::new();
+    for (key, val) in trs {
+        for (locale, text) in val {
+            locales
+                .entry(locale.as_str())
+                .or_default()
+                .insert(key.clone(), serde_json::Value::String(text.clone()));
+        }
+    }
+    locales
+}
+
+/// Write the translations into a table with a key column and a column for each locale, sorted by key and locale.
+///
+/// The rows are written one by one, and the translations of each row are dropped once it's written.
+fn write_csv<W: Write>(
+    output: W,
+    mut trs: Translations,
+    delimiter: u8,
+    csv: &CsvOptions,
+    encoding: Encoding,
+) -> Result<()> {
+    let mut locales = trs
+        .values()
+        .flat_map(|texts| texts.keys().cloned())
+        .collect::<Vec<_>>();
+    locales.sort();
+    locales.dedup();
+    let mut keys = trs.keys().cloned().collect::<Vec<_>>();
+    keys.sort();
+
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .quote_style(match csv.quote_style {
+            QuoteStyle::Necessary => csv::QuoteStyle::Necessary,
+            QuoteStyle::Always => csv::QuoteStyle::Always,
+            QuoteStyle::NonNumeric => csv::QuoteStyle::NonNumeric,
+            QuoteStyle::Never => csv::QuoteStyle::Never,
+        })
+        .from_writer(output);
+    // The fields are encoded one by one, the delimiters and quotes are ASCII in all encodings
+    let mut write_record = |fields: Vec<&str>| -> Result<()> {
+        let fields = fields
+            .into_iter()
+            .map(|field| encoding.encode(field))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        writer.write_record(&fields)?;
+        Ok(())
+    };
+    write_record(
+        std::iter::once(csv.key_column())
+            .chain(locales.iter().map(|locale| locale.as_str()))
+            .collect(),
+    )?;
+    for key in keys {
+        let texts = trs.remove(&key).unwrap_or_default();
+        write_record(
+            std::iter::once(key.as_str())
+                .chain(
+                    locales
+                        .iter()
+                        .map(|locale| texts.get(locale).map_or("", |text| text.as_str())),
+                )
+                .collect(),
+        )?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Convert the translations into the V2 format, the keys of `sources` have their source texts in `_meta: { source: ... }`,
+/// and the keys of `statuses` have their review statuses in `_meta: { status: ... }`.
+fn convert_text(
+    trs: &Translations,
+    sources: &HashMap<String, String>,
+    statuses: &HashMap<String, BTreeMap<String, TranslationStatus>>,
+    format: &str,
+) -> String {
+    let mut value = serde_json::Map::new();
+    value.insert("_version".into(), SchemaVersion::V2.as_u64().into());
 
     for (key, val) in trs {
-        let mut obj = serde_json::Value::Object(serde_json::Map::new());
+        let mut obj = serde_json::Map::new();
+        let mut meta = serde_json::Map::new();
+        if let Some(source) = sources.get(key) {
+            meta.insert("source".into(), source.clone().into());
+        }
+        if let Some(statuses) = statuses.get(key) {
+            let statuses = statuses
+                .iter()
+                .map(|(locale, status)| (locale.clone(), status.as_str().into()))
+                .collect();
+            meta.insert("status".into(), serde_json::Value::Object(statuses));
+        }
+        if !meta.is_empty() {
+            obj.insert(META_KEY.into(), serde_json::Value::Object(meta));
+        }
         for (locale, text) in val {
-            obj[locale] = serde_json::Value::String(text.clone());
+            obj.insert(locale.clone(), serde_json::Value::String(text.clone()));
         }
-        value[key] = obj;
+        obj.sort_keys();
+        value.insert(key.clone(), serde_json::Value::Object(obj));
     }
+    // The maps keep the insertion order, sort them for a stable output
+    value.sort_keys();
 
+    convert_value(&serde_json::Value::Object(value), format)
+}
+
+pub(crate) fn convert_value(value: &serde_json::Value, format: &str) -> String {
     match format {
-        "json" => serde_json::to_string_pretty(&value).unwrap(),
+        // The comments of JSON5 are not kept, it's written as JSON
+        "json" | "json5" => serde_json::to_string_pretty(value).unwrap(),
         "yaml" | "yml" => {
-            let text = serde_yml::to_string(&value).unwrap();
+            let text = serde_yml::to_string(value).unwrap();
             // Remove leading `---`
             text.trim_start_matches("---").trim_start().to_string()
         }
-        "toml" => toml::to_string_pretty(&value).unwrap(),
+        "toml" => toml::to_string_pretty(value).unwrap(),
         _ => unreachable!(),
     }
 }
 
+/// Get the file format of the locale in `locale_formats` by the extension, default: `yml`.
+fn locale_format<'a>(
+    locale_formats: &'a BTreeMap<String, String>,
+    locale: &str,
+) -> Result<&'a str> {
+    match locale_formats.get(locale) {
+        None => Ok("yml"),
+        Some(format) if is_locale_extension(format) => Ok(format),
+        Some(format) => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "Unsupported format `{}` of locale `{}`, expected one of: {}",
+                format,
+                locale,
+                LOCALE_EXTENSIONS.join(", ")
+            ),
+        )),
+    }
+}
+
+/// Get the last load path, the generated files are written into it.
+fn last_load_path<P: AsRef<Path>>(load_paths: &[P]) -> Result<&Path> {
+    load_paths.last().map(|path| path.as_ref()).ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "No load path is given")
+    })
+}
+
+pub(crate) fn display_paths<P: AsRef<Path>>(paths: &[P]) -> Vec<String> {
+    paths
+        .iter()
+        .map(|path| path.as_ref().display().to_string())
+        .collect()
+}
+
+/// Load the translations of the load paths, the parse errors of all malformed files are returned as one error.
+pub(crate) fn load_translations<F: Fn(&str) -> bool>(
+    load_paths: &[String],
+    ignore_if: F,
+    separator: &str,
+) -> Result<Translations> {
+    try_load_locales_from_paths_with_separator(load_paths, ignore_if, separator).map_err(|errors| {
+        let message = errors
+            .iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+    })
+}
+
 fn generate_result<'a, P: AsRef<Path>>(
-    output_path: P,
-    output_filename: &str,
-    all_locales: &Vec<String>,
+    load_paths: &[P],
+    all_locales: &[String],
     messages: impl IntoIterator<Item = (&'a String, &'a Message)> + Clone,
-) -> Translations {
+    key_separator: &str,
+    canonical_locales: bool,
+) -> Result<Translations> {
     let mut trs = Translations::new();
 
+    let mut locales = Vec::<String>::new();
     for locale in all_locales {
+        let locale = match canonical_locales {
+            true => canonicalize_locale(locale),
+            false => locale.clone(),
+        };
+        if !locales.contains(&locale) {
+            locales.push(locale);
+        }
+    }
+    for locale in &locales {
         println!("Checking [{}] and generating untranslated texts...", locale);
 
         // ~/work/my-project/locales
-        let output_path = output_path.as_ref().display().to_string();
+        let load_paths = display_paths(load_paths);
 
-        let ignore_file = |fname: &str| fname.ends_with(&output_filename);
-        let data = load_locales(&output_path, ignore_file);
+        // The `TODO.yml` and `TODO.{locale}.{ext}` files are generated
+        let ignore_file = |fname: &str| {
+            Path::new(fname)
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with("TODO."))
+        };
+        let mut data = load_translations(&load_paths, ignore_file, key_separator)?;
+        if canonical_locales {
+            data = canonicalize_locales(data);
+        }
 
         for (key, m) in messages.clone() {
             if !m.locations.is_empty() {
@@ -91,19 +1143,29 @@ fn generate_result<'a, P: AsRef<Path>>(
                 }
             }
 
-            let value = if m.minify_key {
-                m.key.to_owned()
-            } else {
-                m.key.split('.').last().unwrap_or_default().to_string()
-            };
-
             trs.entry(key.clone())
                 .or_default()
-                .insert(locale.to_string(), value.to_string());
+                .insert(locale.to_string(), untranslated_text(m, key_separator));
         }
     }
 
-    trs
+    Ok(trs)
+}
+
+/// The placeholder text of an untranslated key, the value given by `cargo i18n -t`,
+/// the source text for `minify_key`, or the last segment of the key.
+fn untranslated_text(m: &Message, key_separator: &str) -> String {
+    if let Some(value) = &m.value {
+        value.clone()
+    } else if m.minify_key {
+        m.source.clone()
+    } else {
+        m.key
+            .rsplit(key_separator)
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    }
 }
 
 fn write_file<P: AsRef<Path>>(output: &P, filename: &str, data: &str) -> Result<()> {
@@ -112,21 +1174,49 @@ fn write_file<P: AsRef<Path>>(output: &P, filename: &str, data: &str) -> Result<
 
     // Ensure create folder
     if !folder.exists() {
-        std::fs::create_dir_all(folder).unwrap();
+        std::fs::create_dir_all(folder)?;
     }
 
-    let mut output = ::std::fs::File::create(&output_file)
-        .unwrap_or_else(|_| panic!("Unable to create {} file", &output_file.display()));
+    write_atomic(&output_file, format!("{}\n", data))
+}
 
-    writeln!(output, "{}", data).expect("Write YAML file error");
+/// Write the file atomically, the contents are written to a temporary file in the same folder and renamed over the file,
+/// so an error or a crash in the middle never leaves a partially written file.
+///
+/// The permissions of the existing file are kept.
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> Result<()> {
+    let path = path.as_ref();
+    let filename = path.file_name().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{} is not a file", path.display()),
+        )
+    })?;
+    let temp_path = path.with_file_name(format!(
+        ".{}.{}.tmp",
+        filename.to_string_lossy(),
+        std::process::id()
+    ));
 
-    Ok(())
+    let write = || -> Result<()> {
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(contents.as_ref())?;
+        file.sync_all()?;
+        if let Ok(metadata) = std::fs::metadata(path) {
+            std::fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        std::fs::rename(&temp_path, path)
+    };
+    write().inspect_err(|_| {
+        let _ = std::fs::remove_file(&temp_path);
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use indoc::indoc;
+    use rust_i18n_support::load_locales;
 
     fn assert_eq_json(left: &str, right: &str) {
         let left: serde_json::Value = serde_json::from_str(left).unwrap();
@@ -134,12 +1224,1094 @@ mod tests {
         assert_eq!(left, right);
     }
 
+    #[test]
+    fn test_export() {
+        let output_path = std::env::temp_dir().join("rust-i18n-test-export");
+        let _ = std::fs::remove_dir_all(&output_path);
+        write_file(&output_path, "en.yml", "hello: Hello\nfoo: Foo").unwrap();
+        write_file(&output_path, "fr.yml", "hello: Bonjour").unwrap();
+
+        // The output is outside the load path, or it would be loaded as a locale file
+        let output = std::env::temp_dir().join("rust-i18n-test-export.json");
+        let keys = HashSet::from(["hello".to_string()]);
+        let count = export(
+            &[&output_path],
+            &output,
+            None,
+            Some(&keys),
+            ".",
+            Encoding::Utf8,
+            SchemaVersion::V2,
+            CsvOptions::default(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(count, 1);
+        assert_eq_json(
+            &std::fs::read_to_string(&output).unwrap(),
+            r#"{"_version": 2, "hello": {"en": "Hello", "fr": "Bonjour"}}"#,
+        );
+
+        let count = export(
+            &[&output_path],
+            &output,
+            None,
+            None,
+            ".",
+            Encoding::Utf8,
+            SchemaVersion::V2,
+            CsvOptions::default(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+
+        let err = export(
+            &[&output_path],
+            &output_path.join("export.txt"),
+            None,
+            None,
+            ".",
+            Encoding::Utf8,
+            SchemaVersion::V2,
+            CsvOptions::default(),
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        // The format overrides the extension
+        let output_txt = std::env::temp_dir().join("rust-i18n-test-export.txt");
+        export(
+            &[&output_path],
+            &output_txt,
+            Some("json"),
+            Some(&keys),
+            ".",
+            Encoding::Utf8,
+            SchemaVersion::V2,
+            CsvOptions::default(),
+            true,
+        )
+        .unwrap();
+        assert_eq_json(
+            &std::fs::read_to_string(&output_txt).unwrap(),
+            r#"{"_version": 2, "hello": {"en": "Hello", "fr": "Bonjour"}}"#,
+        );
+        std::fs::remove_file(&output_txt).unwrap();
+        for (output, format, version) in [
+            (output.as_path(), Some("txt"), SchemaVersion::V2),
+            (Path::new("-"), None, SchemaVersion::V2),
+            (Path::new("-"), Some("yml"), SchemaVersion::V1),
+        ] {
+            let err = export(
+                &[&output_path],
+                output,
+                format,
+                None,
+                ".",
+                Encoding::Utf8,
+                version,
+                CsvOptions::default(),
+                true,
+            )
+            .unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        }
+
+        // Each locale is written into its own file
+        let output_v1 = std::env::temp_dir().join("rust-i18n-test-export-v1.yml");
+        let count = export(
+            &[&output_path],
+            &output_v1,
+            None,
+            None,
+            ".",
+            Encoding::Utf8,
+            SchemaVersion::V1,
+            CsvOptions::default(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+        let output_v1_en = std::env::temp_dir().join("rust-i18n-test-export-v1.en.yml");
+        let output_v1_fr = std::env::temp_dir().join("rust-i18n-test-export-v1.fr.yml");
+        assert_eq!(
+            std::fs::read_to_string(&output_v1_en).unwrap(),
+            "_version: 1\nfoo: Foo\nhello: Hello\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&output_v1_fr).unwrap(),
+            "_version: 1\nhello: Bonjour\n"
+        );
+        std::fs::remove_file(&output_v1_en).unwrap();
+        std::fs::remove_file(&output_v1_fr).unwrap();
+
+        write_file(&output_path, "fr.yml", "hello: Café").unwrap();
+        export(
+            &[&output_path],
+            &output,
+            None,
+            Some(&keys),
+            ".",
+            Encoding::Latin1,
+            SchemaVersion::V2,
+            CsvOptions::default(),
+            true,
+        )
+        .unwrap();
+        let bytes = std::fs::read(&output).unwrap();
+        assert!(bytes.ends_with(b"\"Caf\xe9\"\n  }\n}\n"));
+        write_file(&output_path, "fr.yml", "hello: 你好").unwrap();
+        let err = export(
+            &[&output_path],
+            &output,
+            None,
+            Some(&keys),
+            ".",
+            Encoding::Latin1,
+            SchemaVersion::V2,
+            CsvOptions::default(),
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        std::fs::remove_dir_all(&output_path).unwrap();
+        std::fs::remove_file(&output).unwrap();
+    }
+
+    #[test]
+    fn test_export_plural_map() {
+        let load_path = std::env::temp_dir().join("rust-i18n-test-export-plural-map");
+        let _ = std::fs::remove_dir_all(&load_path);
+        write_file(
+            &load_path,
+            "app.yml",
+            "_version: 2\nitems:\n  en:\n    one: 1 item\n    other: \"%{count} items\"\n  fr: \"%{count} articles\"",
+        )
+        .unwrap();
+
+        // The plural forms are exported as the keys of the categories, and loaded back the same
+        let output_path = std::env::temp_dir().join("rust-i18n-test-export-plural-map-output");
+        let _ = std::fs::remove_dir_all(&output_path);
+        std::fs::create_dir_all(&output_path).unwrap();
+        export(
+            &[&load_path],
+            &output_path.join("app.yml"),
+            None,
+            None,
+            ".",
+            Encoding::Utf8,
+            SchemaVersion::V2,
+            CsvOptions::default(),
+            true,
+        )
+        .unwrap();
+        let load = |path: &Path| {
+            try_load_locales_from_paths_with_separator(
+                &[path.display().to_string()],
+                |_| false,
+                ".",
+            )
+            .unwrap()
+        };
+        let trs = load(&output_path);
+        assert_eq!(trs, load(&load_path));
+        assert_eq!(trs["en"]["items.one"], "1 item");
+        assert_eq!(trs["fr"]["items"], "%{count} articles");
+
+        std::fs::remove_dir_all(&load_path).unwrap();
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_export_split() {
+        let load_path = std::env::temp_dir().join("rust-i18n-test-export-split");
+        let _ = std::fs::remove_dir_all(&load_path);
+        write_file(
+            &load_path,
+            "app.yml",
+            "_version: 2\nhello:\n  en: Hello\n  fr: Bonjour\n  de: Hallo\nfoo:\n  en: Foo",
+        )
+        .unwrap();
+
+        let output = std::env::temp_dir().join("rust-i18n-test-export-split-output");
+        let _ = std::fs::remove_dir_all(&output);
+        let locales = ["en".to_string(), "fr".to_string()];
+        let count = export_split(
+            &[&load_path],
+            &output,
+            None,
+            &locales,
+            ".",
+            Encoding::Utf8,
+            "json",
+            true,
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq_json(
+            &std::fs::read_to_string(output.join("en.json")).unwrap(),
+            r#"{"foo": "Foo", "hello": "Hello"}"#,
+        );
+        assert_eq_json(
+            &std::fs::read_to_string(output.join("fr.json")).unwrap(),
+            r#"{"hello": "Bonjour"}"#,
+        );
+        // Filtered out by the locales
+        assert!(!output.join("de.json").exists());
+
+        // All locales by default
+        let keys = HashSet::from(["hello".to_string()]);
+        let count = export_split(
+            &[&load_path],
+            &output,
+            Some(&keys),
+            &[],
+            ".",
+            Encoding::Utf8,
+            "yml",
+            true,
+        )
+        .unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(
+            std::fs::read_to_string(output.join("de.yml")).unwrap(),
+            "hello: Hallo\n"
+        );
+
+        let err = export_split(
+            &[&load_path],
+            &output,
+            None,
+            &[],
+            ".",
+            Encoding::Utf8,
+            "csv",
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        std::fs::remove_dir_all(&load_path).unwrap();
+        std::fs::remove_dir_all(&output).unwrap();
+    }
+
+    #[test]
+    fn test_export_manifest() {
+        let load_path = std::env::temp_dir().join("rust-i18n-test-export-manifest");
+        let _ = std::fs::remove_dir_all(&load_path);
+        write_file(
+            &load_path,
+            "app.yml",
+            "_version: 2\nhello:\n  en: Hello, %{name}\n  fr: Bonjour %{title} %{name}\nfoo:\n  en: 50%% {x}\nbar:\n  de: Bar",
+        )
+        .unwrap();
+
+        let output = std::env::temp_dir().join("rust-i18n-test-export-manifest.json");
+        let count = export_manifest(&[&load_path], &output, None, ".", ("%{", "}"), false).unwrap();
+        assert_eq!(count, 3);
+        assert_eq_json(
+            &std::fs::read_to_string(&output).unwrap(),
+            r#"["bar", "foo", "hello"]"#,
+        );
+
+        let keys = HashSet::from(["hello".to_string(), "foo".to_string()]);
+        let count =
+            export_manifest(&[&load_path], &output, Some(&keys), ".", ("%{", "}"), true).unwrap();
+        assert_eq!(count, 2);
+        assert_eq_json(
+            &std::fs::read_to_string(&output).unwrap(),
+            r#"{"foo": [], "hello": ["name", "title"]}"#,
+        );
+
+        std::fs::remove_dir_all(&load_path).unwrap();
+        std::fs::remove_file(&output).unwrap();
+    }
+
+    #[test]
+    fn test_export_csv() {
+        let output_path = std::env::temp_dir().join("rust-i18n-test-export-csv");
+        let _ = std::fs::remove_dir_all(&output_path);
+        write_file(&output_path, "en.yml", "hello: Hello, world\nfoo: Foo").unwrap();
+        write_file(&output_path, "fr.yml", "hello: Bonjour; \"monde\"").unwrap();
+
+        let export_csv = |name: &str, csv: CsvOptions| {
+            let output = std::env::temp_dir().join(name);
+            export(
+                &[&output_path],
+                &output,
+                None,
+                None,
+                ".",
+                Encoding::Utf8,
+                SchemaVersion::V2,
+                csv,
+                true,
+            )
+            .unwrap();
+            let text = std::fs::read_to_string(&output).unwrap();
+            std::fs::remove_file(&output).unwrap();
+            text
+        };
+
+        assert_eq!(
+            export_csv("rust-i18n-test-export.csv", CsvOptions::default()),
+            "key,en,fr\nfoo,Foo,\nhello,\"Hello, world\",\"Bonjour; \"\"monde\"\"\"\n"
+        );
+        assert_eq!(
+            export_csv("rust-i18n-test-export.tsv", CsvOptions::default()),
+            "key\ten\tfr\nfoo\tFoo\t\nhello\tHello, world\t\"Bonjour; \"\"monde\"\"\"\n"
+        );
+        assert_eq!(
+            export_csv(
+                "rust-i18n-test-export.csv",
+                CsvOptions {
+                    delimiter: Some(b';'),
+                    quote_style: QuoteStyle::Always,
+                    ..Default::default()
+                }
+            ),
+            "\"key\";\"en\";\"fr\"\n\"foo\";\"Foo\";\"\"\n\"hello\";\"Hello, world\";\"Bonjour; \"\"monde\"\"\"\n"
+        );
+        assert_eq!(
+            export_csv(
+                "rust-i18n-test-export.csv",
+                CsvOptions {
+                    key_column: Some("string_key".into()),
+                    ..Default::default()
+                }
+            ),
+            "string_key,en,fr\nfoo,Foo,\nhello,\"Hello, world\",\"Bonjour; \"\"monde\"\"\"\n"
+        );
+
+        let err = export(
+            &[&output_path],
+            &std::env::temp_dir().join("rust-i18n-test-export.csv"),
+            None,
+            None,
+            ".",
+            Encoding::Utf8,
+            SchemaVersion::V1,
+            CsvOptions::default(),
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        let err = export(
+            &[&output_path],
+            &std::env::temp_dir().join("rust-i18n-test-export.csv"),
+            None,
+            None,
+            ".",
+            Encoding::Utf8,
+            SchemaVersion::V2,
+            CsvOptions {
+                key_column: Some(" ".into()),
+                ..Default::default()
+            },
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        assert_eq!(parse_delimiter(";"), Ok(b';'));
+        assert_eq!(parse_delimiter("tab"), Ok(b'\t'));
+        assert!(parse_delimiter(";;").is_err());
+        assert_eq!("non-numeric".parse(), Ok(QuoteStyle::NonNumeric));
+
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_export_canonical_locales() {
+        let output_path = std::env::temp_dir().join("rust-i18n-test-export-canonical-locales");
+        let _ = std::fs::remove_dir_all(&output_path);
+        write_file(&output_path, "EN-us.yml", "hello: Hello").unwrap();
+        write_file(
+            &output_path,
+            "app.yml",
+            "_version: 2\nfoo:\n  en-US: Foo\n  pt_br: Fu\n",
+        )
+        .unwrap();
+
+        let export_csv = |canonical_locales: bool| {
+            let output = std::env::temp_dir().join("rust-i18n-test-export-canonical.csv");
+            export(
+                &[&output_path],
+                &output,
+                None,
+                None,
+                ".",
+                Encoding::Utf8,
+                SchemaVersion::V2,
+                CsvOptions::default(),
+                canonical_locales,
+            )
+            .unwrap();
+            let text = std::fs::read_to_string(&output).unwrap();
+            std::fs::remove_file(&output).unwrap();
+            text
+        };
+        assert_eq!(
+            export_csv(true),
+            "key,en-US,pt-BR\nfoo,Foo,Fu\nhello,Hello,\n"
+        );
+        assert_eq!(
+            export_csv(false),
+            "key,EN-us,en-US,pt_br\nfoo,,Foo,Fu\nhello,Hello,,\n"
+        );
+
+        let split_path = std::env::temp_dir().join("rust-i18n-test-export-canonical-split");
+        let _ = std::fs::remove_dir_all(&split_path);
+        let count = export_split(
+            &[&output_path],
+            &split_path,
+            None,
+            &["en-us".to_string()],
+            ".",
+            Encoding::Utf8,
+            "json",
+            true,
+        )
+        .unwrap();
+        assert_eq!(count, 1);
+        assert!(split_path.join("en-US.json").exists());
+
+        std::fs::remove_dir_all(&split_path).unwrap();
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_export_status() {
+        let output_path = std::env::temp_dir().join("rust-i18n-test-export-status");
+        let _ = std::fs::remove_dir_all(&output_path);
+        write_file(
+            &output_path,
+            "app.yml",
+            indoc! {"
+                _version: 2
+                hello:
+                  _meta:
+                    status:
+                      fr_fr: needs-review
+                      de: approved
+                  en: Hello
+                  fr_fr: Bonjour
+                  de: Hallo
+                foo:
+                  en: Foo
+            "},
+        )
+        .unwrap();
+
+        // The review statuses are kept in the export, with the canonical locales
+        let output = std::env::temp_dir().join("rust-i18n-test-export-status.json");
+        export(
+            &[&output_path],
+            &output,
+            None,
+            None,
+            ".",
+            Encoding::Utf8,
+            SchemaVersion::V2,
+            CsvOptions::default(),
+            true,
+        )
+        .unwrap();
+        assert_eq_json(
+            &std::fs::read_to_string(&output).unwrap(),
+            r#"{
+                "_version": 2,
+                "foo": {"en": "Foo"},
+                "hello": {
+                    "_meta": {"status": {"de": "approved", "fr-FR": "needs-review"}},
+                    "de": "Hallo",
+                    "en": "Hello",
+                    "fr-FR": "Bonjour"
+                }
+            }"#,
+        );
+
+        std::fs::remove_file(output).unwrap();
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_changed_keys() {
+        let trs = |texts: &[(&str, &str)]| {
+            let texts = texts
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            Translations::from([("en".to_string(), texts)])
+        };
+        let previous = trs(&[("hello", "Hello"), ("foo", "Foo"), ("removed", "Removed")]);
+        let current = trs(&[("hello", "Hello"), ("foo", "Foo!"), ("bar", "Bar")]);
+
+        let mut keys = changed_keys(&previous, &current, "en")
+            .into_iter()
+            .collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, vec!["bar", "foo"]);
+        assert!(changed_keys(&previous, &current, "fr").is_empty());
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let output_path = std::env::temp_dir().join("rust-i18n-test-write-atomic");
+        let _ = std::fs::remove_dir_all(&output_path);
+        std::fs::create_dir_all(&output_path).unwrap();
+
+        let path = output_path.join("en.yml");
+        write_atomic(&path, "hello: Hello\n").unwrap();
+        write_atomic(&path, "hello: Hello!\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "hello: Hello!\n");
+
+        // The file is kept as is on failure, e.g. the target is a folder
+        std::fs::create_dir_all(output_path.join("fr.yml")).unwrap();
+        assert!(write_atomic(output_path.join("fr.yml"), "hello: Bonjour\n").is_err());
+        assert!(write_atomic(&output_path, "").is_err());
+
+        // No temporary file is left
+        let mut files = std::fs::read_dir(&output_path)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(files, vec!["en.yml", "fr.yml"]);
+
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_generate_missing() {
+        let output_path = std::env::temp_dir().join("rust-i18n-test-generate-missing");
+        let _ = std::fs::remove_dir_all(&output_path);
+        write_file(&output_path, "en.yml", "hello: Hello").unwrap();
+        write_file(&output_path, "fr.yml", "hello: Bonjour").unwrap();
+        let todo = "_version: 2\n# Keep me\nold:\n  en: Old";
+        write_file(&output_path, "TODO.yml", todo).unwrap();
+
+        let messages = ["hello", "old", "b.new", "a.new"]
+            .iter()
+            .map(|key| {
+                let message = Message {
+                    key: key.to_string(),
+                    placeholders: if *key == "a.new" {
+                        vec!["name".into()]
+                    } else {
+                        vec![]
+                    },
+                    ..Default::default()
+                };
+                (key.to_string(), message)
+            })
+            .collect::<Vec<_>>();
+        let messages = messages.iter().map(|(k, m)| (k, m));
+        let count = generate_missing(
+            &[&output_path],
+            "en",
+            messages.clone(),
+            ".",
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            std::fs::read_to_string(output_path.join("TODO.yml")).unwrap(),
+            indoc! {"
+                _version: 2
+                # Keep me
+                old:
+                  en: Old
+                b.new:
+                  en: new
+                # Placeholders: name
+                a.new:
+                  en: new
+            "}
+        );
+
+        // The added keys are not added again
+        let count = generate_missing(
+            &[&output_path],
+            "en",
+            messages.clone(),
+            ".",
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert_eq!(count, 0);
+
+        let locale_formats = BTreeMap::from([("en".to_string(), "json".to_string())]);
+        write_file(&output_path, "TODO.en.json", "{\"z\": \"z\"}").unwrap();
+        let count =
+            generate_missing(&[&output_path], "en", messages, ".", &locale_formats).unwrap();
+        assert_eq!(count, 0);
+
+        std::fs::remove_file(output_path.join("TODO.yml")).unwrap();
+        let messages = [(&"old".to_string(), &Message::default())];
+        let count =
+            generate_missing(&[&output_path], "en", messages, ".", &locale_formats).unwrap();
+        assert_eq!(count, 1);
+        let data = load_locales(&output_path.display().to_string(), |_| false);
+        assert_eq!(data["en"]["z"], "z");
+        assert_eq!(data["en"]["old"], "");
+
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_generate_source_texts() {
+        let output_path = std::env::temp_dir().join("rust-i18n-test-generate-source-texts");
+        let _ = std::fs::remove_dir_all(&output_path);
+        write_file(&output_path, "en.yml", "hello: Hello").unwrap();
+
+        let key = "tr_4Cct6Q289b12SkvF47dXIx".to_string();
+        let message = Message {
+            key: key.clone(),
+            source: "Hello, world!".into(),
+            minify_key: true,
+            ..Default::default()
+        };
+        let count = generate_missing(
+            &[&output_path],
+            "en",
+            [(&key, &message)],
+            ".",
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(
+            std::fs::read_to_string(output_path.join("TODO.yml")).unwrap(),
+            indoc! {"
+                _version: 2
+                tr_4Cct6Q289b12SkvF47dXIx:
+                  _meta:
+                    source: Hello, world!
+                  en: Hello, world!
+            "}
+        );
+
+        // The source texts are kept in the export, but not loaded as a locale
+        let output = std::env::temp_dir().join("rust-i18n-test-generate-source-texts.json");
+        export(
+            &[&output_path],
+            &output,
+            None,
+            None,
+            ".",
+            Encoding::Utf8,
+            SchemaVersion::V2,
+            CsvOptions::default(),
+            true,
+        )
+        .unwrap();
+        assert_eq_json(
+            &std::fs::read_to_string(&output).unwrap(),
+            r#"{
+                "_version": 2,
+                "hello": {"en": "Hello"},
+                "tr_4Cct6Q289b12SkvF47dXIx": {"_meta": {"source": "Hello, world!"}, "en": "Hello, world!"}
+            }"#,
+        );
+        let data = load_locales(&output_path.display().to_string(), |_| false);
+        assert_eq!(data.keys().collect::<Vec<_>>(), vec!["en"]);
+
+        // The value given by `cargo i18n -t` is the untranslated text
+        let message = Message {
+            value: Some("Hola".into()),
+            ..message
+        };
+        assert_eq!(untranslated_text(&message, "."), "Hola");
+
+        std::fs::remove_file(output).unwrap();
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_generate_split_by_namespace() {
+        let output_path = std::env::temp_dir().join("rust-i18n-test-generate-split-by-namespace");
+        let _ = std::fs::remove_dir_all(&output_path);
+        write_file(&output_path, "en.yml", "hello: Hello").unwrap();
+
+        let messages = [
+            "hello",
+            "auth.login",
+            "auth.logout",
+            "billing.title",
+            "plain",
+            "overlays.x",
+        ]
+        .iter()
+        .map(|key| {
+            let message = Message {
+                key: key.to_string(),
+                ..Default::default()
+            };
+            (key.to_string(), message)
+        })
+        .collect::<Vec<_>>();
+        let locale_formats = BTreeMap::from([("fr".to_string(), "json".to_string())]);
+        let all_locales = vec!["en".to_string(), "fr".to_string()];
+        generate(
+            &[&output_path],
+            &all_locales,
+            messages.iter().map(|(k, m)| (k, m)),
+            ".",
+            &locale_formats,
+            true,
+            true,
+        )
+        .unwrap_err();
+
+        let read = |path: &str| {
+            let text = std::fs::read_to_string(output_path.join(path)).unwrap();
+            text.trim_end().to_string()
+        };
+        assert_eq!(
+            read("auth/TODO.yml"),
+            indoc! {"
+                _version: 2
+                auth.login:
+                  en: login
+                auth.logout:
+                  en: logout"}
+        );
+        assert_eq!(
+            read("billing/TODO.yml"),
+            "_version: 2\nbilling.title:\n  en: title"
+        );
+        assert_eq!(
+            read("billing/TODO.fr.json"),
+            "{\n  \"billing.title\": \"title\"\n}"
+        );
+        // The keys without a namespace and the overlays are kept in the load path
+        let todo = read("TODO.yml");
+        assert!(
+            todo.contains("plain:") && todo.contains("overlays.x:"),
+            "{}",
+            todo
+        );
+        assert!(!todo.contains("auth"), "{}", todo);
+
+        // The namespace directories are loaded recursively
+        let data = load_locales(&output_path.display().to_string(), |_| false);
+        assert_eq!(data["en"]["auth.login"], "login");
+        assert_eq!(data["fr"]["billing.title"], "title");
+
+        assert_eq!(namespace_dir("auth.login", "."), Some("auth"));
+        assert_eq!(namespace_dir("auth/login", "/"), Some("auth"));
+        assert_eq!(namespace_dir("../login.x", "/"), None);
+        assert_eq!(namespace_dir("...x", "."), None);
+        assert_eq!(namespace_dir("hello", "."), None);
+
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_generate_locale_formats() {
+        let output_path = std::env::temp_dir().join("rust-i18n-test-generate-locale-formats");
+        let _ = std::fs::remove_dir_all(&output_path);
+        write_file(&output_path, "en.yml", "hello: Hello").unwrap();
+        write_file(&output_path, "TODO.fr.json", "{\"stale\": \"\"}").unwrap();
+
+        let messages = ["hello", "greeting.world"]
+            .iter()
+            .map(|key| {
+                let message = Message {
+                    key: key.to_string(),
+                    ..Default::default()
+                };
+                (key.to_string(), message)
+            })
+            .collect::<Vec<_>>();
+        let locale_formats = BTreeMap::from([("fr".to_string(), "json".to_string())]);
+        let all_locales = vec!["en".to_string(), "fr".to_string()];
+        generate(
+            &[&output_path],
+            &all_locales,
+            messages.iter().map(|(k, m)| (k, m)),
+            ".",
+            &locale_formats,
+            false,
+            true,
+        )
+        .unwrap_err();
+
+        let data = load_locales(&output_path.display().to_string(), |_| false);
+        assert_eq!(data["en"]["greeting.world"], "world");
+        assert_eq!(data["fr"]["hello"], "hello");
+        assert_eq!(data["fr"]["greeting.world"], "world");
+        assert!(!data["fr"].contains_key("stale"));
+        let todo = std::fs::read_to_string(output_path.join("TODO.yml")).unwrap();
+        assert!(!todo.contains("fr:"), "{}", todo);
+
+        let toml_cfg = I18nConfig {
+            locale_formats: BTreeMap::from([("de".to_string(), "toml".to_string())]),
+            ..Default::default()
+        };
+        generate_locale(&[&output_path], &toml_cfg, "de", false, false).unwrap();
+        assert!(output_path.join("de.toml").exists());
+
+        let xml_cfg = I18nConfig {
+            locale_formats: BTreeMap::from([("ja".to_string(), "xml".to_string())]),
+            ..Default::default()
+        };
+        let err = generate_locale(&[&output_path], &xml_cfg, "ja", false, false).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_generate_locale() {
+        let output_path = std::env::temp_dir().join("rust-i18n-test-generate-locale");
+        let _ = std::fs::remove_dir_all(&output_path);
+        write_file(&output_path, "en.yml", "hello: Hello\nfoo.bar: Foo bar").unwrap();
+
+        generate_locale(&[&output_path], &I18nConfig::default(), "fr", false, false).unwrap();
+        let data = load_locales(&output_path.display().to_string(), |_| false);
+        assert_eq!(data["fr"]["hello"], "");
+        assert_eq!(data["fr"]["foo.bar"], "");
+
+        let err = generate_locale(&[&output_path], &I18nConfig::default(), "fr", true, false)
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+
+        generate_locale(&[&output_path], &I18nConfig::default(), "fr", true, true).unwrap();
+        let data = load_locales(&output_path.display().to_string(), |_| false);
+        assert_eq!(data["fr"]["hello"], "Hello");
+        assert_eq!(data["fr"]["foo.bar"], "Foo bar");
+
+        let de_cfg = I18nConfig {
+            default_locale: "de".to_string(),
+            ..Default::default()
+        };
+        let err = generate_locale(&[&output_path], &de_cfg, "ja", false, false).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+
+        write_file(
+            &output_path,
+            "de.yml",
+            "foo.bar: Foo Bar DE\nfoo.baz: Foo Baz DE",
+        )
+        .unwrap();
+        let ns_cfg = I18nConfig {
+            namespace_default_locales: BTreeMap::from([("foo".to_string(), "de".to_string())]),
+            ..Default::default()
+        };
+        generate_locale(&[&output_path], &ns_cfg, "ja", true, false).unwrap();
+        let data = load_locales(&output_path.display().to_string(), |_| false);
+        assert_eq!(data["ja"]["hello"], "Hello");
+        assert_eq!(data["ja"]["foo.bar"], "Foo Bar DE");
+        assert_eq!(data["ja"]["foo.baz"], "Foo Baz DE");
+
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_sort_locales() {
+        let output_path = std::env::temp_dir().join("rust-i18n-test-sort-locales");
+        let _ = std::fs::remove_dir_all(&output_path);
+        write_file(&output_path, "en.yml", "b: B\na:\n  d: D\n  c: \"1.50\"").unwrap();
+        write_file(&output_path, "zh.json", "{\"a\": \"A\", \"b\": \"B\"}").unwrap();
+
+        let unsorted = sort_locales(&output_path, true, SortBy::Key, ".").unwrap();
+        assert_eq!(unsorted.len(), 2);
+        let content = std::fs::read_to_string(output_path.join("en.yml")).unwrap();
+        assert_eq!(content, "b: B\na:\n  d: D\n  c: \"1.50\"\n");
+
+        let unsorted = sort_locales(&output_path, false, SortBy::Key, ".").unwrap();
+        assert_eq!(unsorted.len(), 2);
+        let content = std::fs::read_to_string(output_path.join("en.yml")).unwrap();
+        assert_eq!(content, "a:\n  c: '1.50'\n  d: D\nb: B\n");
+
+        assert!(sort_locales(&output_path, true, SortBy::Key, ".")
+            .unwrap()
+            .is_empty());
+
+        // The plural forms are in the CLDR order, not sorted by the key
+        write_file(
+            &output_path,
+            "plural.yml",
+            "items:\n  other: Items\n  one: Item\n  zero: None",
+        )
+        .unwrap();
+        sort_locales(&output_path, false, SortBy::Key, ".").unwrap();
+        let content = std::fs::read_to_string(output_path.join("plural.yml")).unwrap();
+        assert_eq!(
+            content,
+            "items:\n  zero: None\n  one: Item\n  other: Items\n"
+        );
+
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_canonicalize_files() {
+        let output_path = std::env::temp_dir().join("rust-i18n-test-canonicalize-files");
+        let _ = std::fs::remove_dir_all(&output_path);
+        // The same translations in different styles
+        write_file(
+            &output_path,
+            "en.yml",
+            "b: \"B\"\na: {d: \"1.50\", c: \"Line 1\\nLine 2\"}\n\"e\": It's",
+        )
+        .unwrap();
+        write_file(
+            &output_path,
+            "fr.yml",
+            indoc! {"
+                b: B
+                a:
+                  d: '1.50'
+                  c: |-
+                    Line 1
+                    Line 2
+                e: \"It's\"
+            "},
+        )
+        .unwrap();
+
+        let uncanonical = canonicalize_files(&output_path, true).unwrap();
+        assert_eq!(uncanonical.len(), 2);
+        canonicalize_files(&output_path, false).unwrap();
+        let content = std::fs::read_to_string(output_path.join("en.yml")).unwrap();
+        // The keys are kept in their order
+        assert_eq!(
+            content,
+            indoc! {"
+                b: B
+                a:
+                  d: '1.50'
+                  c: |-
+                    Line 1
+                    Line 2
+                e: It's
+            "}
+        );
+        assert_eq!(
+            std::fs::read_to_string(output_path.join("fr.yml")).unwrap(),
+            content
+        );
+        assert!(canonicalize_files(&output_path, true).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_sort_by() {
+        fn sort<'a>(sort_by: SortBy, keys: &[&'a str]) -> Vec<&'a str> {
+            let mut keys = keys.to_vec();
+            keys.sort_by(|a, b| sort_by.compare(a, b));
+            keys
+        }
+        let keys = ["item10", "item2", "item1", "item02", "b", "a"];
+        assert_eq!(
+            sort(SortBy::Key, &keys),
+            vec!["a", "b", "item02", "item1", "item10", "item2"]
+        );
+        assert_eq!(
+            sort(SortBy::Natural, &keys),
+            vec!["a", "b", "item1", "item2", "item02", "item10"]
+        );
+        assert_eq!(
+            sort(SortBy::Natural, &["v1.10.2", "v1.9.10", "v1.9.9", "v1"]),
+            vec!["v1", "v1.9.9", "v1.9.10", "v1.10.2"]
+        );
+
+        let keys = ["a-c", "a.b", "b", "a", "a.a.z"];
+        assert_eq!(
+            sort(SortBy::Key, &keys),
+            vec!["a", "a-c", "a.a.z", "a.b", "b"]
+        );
+        assert_eq!(
+            sort(SortBy::Namespace, &keys),
+            vec!["a", "a.a.z", "a.b", "a-c", "b"]
+        );
+        let mut keys = ["a-c", "a/b", "b", "a", "a.a"];
+        keys.sort_by(|a, b| SortBy::Namespace.compare_with_separator(a, b, "/"));
+        assert_eq!(keys, ["a", "a/b", "a-c", "a.a", "b"]);
+
+        assert_eq!("natural".parse::<SortBy>(), Ok(SortBy::Natural));
+        assert!("foo".parse::<SortBy>().is_err());
+    }
+
+    #[test]
+    fn test_sort_locales_by_natural() {
+        let output_path = std::env::temp_dir().join("rust-i18n-test-sort-locales-natural");
+        let _ = std::fs::remove_dir_all(&output_path);
+        write_file(
+            &output_path,
+            "en.yml",
+            "item10: X
+item2: B
+item1: A
+",
+        )
+        .unwrap();
+
+        let unsorted = sort_locales(&output_path, false, SortBy::Natural, ".").unwrap();
+        assert_eq!(unsorted.len(), 1);
+        let content = std::fs::read_to_string(output_path.join("en.yml")).unwrap();
+        assert_eq!(content, "item1: A\nitem2: B\nitem10: X\n");
+
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_annotate_placeholders() {
+        let mut trs = Translations::new();
+        for key in ["greeting", "Hello, %{name}: %{count}", "plain"] {
+            trs.entry(key.to_string())
+                .or_default()
+                .insert("en".to_string(), key.to_string());
+        }
+        let greeting = Message {
+            placeholders: vec!["name".into()],
+            ..Default::default()
+        };
+        let hello = Message {
+            placeholders: vec!["name".into(), "count".into()],
+            ..Default::default()
+        };
+        let keys = [
+            "greeting".to_string(),
+            "Hello, %{name}: %{count}".to_string(),
+        ];
+        let messages = vec![(&keys[0], &greeting), (&keys[1], &hello)];
+
+        let text = annotate_placeholders(
+            &convert_text(&trs, &HashMap::new(), &HashMap::new(), "yaml"),
+            messages,
+        );
+        assert_eq!(
+            text,
+            indoc! {r#"
+            # Placeholders: name, count
+            'Hello, %{name}: %{count}':
+              en: 'Hello, %{name}: %{count}'
+            _version: 2
+            # Placeholders: name
+            greeting:
+              en: greeting
+            plain:
+              en: plain
+            "#}
+        );
+    }
+
     #[test]
     fn test_convert_text() {
         let mut trs = Translations::new();
         let format = "json";
 
-        let result = convert_text(&trs, format);
+        let result = convert_text(&trs, &HashMap::new(), &HashMap::new(), format);
         let expect = r#"
         {
             "_version": 2
@@ -154,7 +2326,7 @@ mod tests {
             map
         });
 
-        let result = convert_text(&trs, format);
+        let result = convert_text(&trs, &HashMap::new(), &HashMap::new(), format);
         let expect = r#"
         {
             "_version": 2,
@@ -167,7 +2339,7 @@ mod tests {
         assert_eq_json(&result, expect);
 
         let format = "yaml";
-        let result = convert_text(&trs, format);
+        let result = convert_text(&trs, &HashMap::new(), &HashMap::new(), format);
         let expect = indoc! {r#"
         _version: 2
         hello:
@@ -177,7 +2349,7 @@ mod tests {
         assert_eq!(&result, &expect);
 
         let format = "toml";
-        let result = convert_text(&trs, format);
+        let result = convert_text(&trs, &HashMap::new(), &HashMap::new(), format);
         let expect = indoc! {r#"
         _version = 2
 
@@ -187,4 +2359,20 @@ mod tests {
         "#};
         assert_eq!(&result, &expect);
     }
+
+    #[test]
+    fn test_convert_text_keeps_format_fragments() {
+        let text = "Total: %{amount} (min %{min}) {:>10} {} {{x}} 50%";
+        let mut trs = Translations::new();
+        trs.insert(
+            text.to_string(),
+            HashMap::from([("en".to_string(), text.to_string())]),
+        );
+
+        for format in ["json", "yaml", "toml"] {
+            let result = convert_text(&trs, &HashMap::new(), &HashMap::new(), format);
+            let value = parse_value(&result, format).unwrap();
+            assert_eq!(value[text]["en"], text, "{}", format);
+        }
+    }
 }
@@ -0,0 +1,112 @@
+use crate::generator::{display_paths, load_translations, Translations};
+use rust_i18n_support::is_locale_extension;
+use std::io::Result;
+use std::path::Path;
+use std::process::Command;
+
+/// Run `git` in the directory and return the stdout, the stderr is returned as the error.
+fn git(dir: &Path, args: &[&str]) -> Result<Vec<u8>> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output()?;
+    if !output.status.success() {
+        return Err(std::io::Error::other(format!(
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+/// Check the directory is in a git work tree.
+pub fn is_git_repo<P: AsRef<Path>>(dir: P) -> bool {
+    git(dir.as_ref(), &["rev-parse", "--is-inside-work-tree"]).is_ok()
+}
+
+/// Load the translations of the load paths at the git revision `rev`, e.g. `main` or `v1.0`.
+///
+/// The locale files at the revision are checked out into a temporary directory to load them.
+/// Returns `None` if any load path is not in a git repository.
+pub fn load_translations_at<P: AsRef<Path>>(
+    load_paths: &[P],
+    rev: &str,
+    key_separator: &str,
+) -> Result<Option<Translations>> {
+    if !load_paths.iter().all(is_git_repo) {
+        return Ok(None);
+    }
+
+    let temp_dir = std::env::temp_dir().join(format!("rust-i18n-rev-{}", std::process::id()));
+    let mut rev_paths = vec![];
+    for (i, load_path) in load_paths.iter().enumerate() {
+        let load_path = load_path.as_ref();
+        let rev_path = temp_dir.join(i.to_string());
+        std::fs::create_dir_all(&rev_path)?;
+
+        // The listed files are relative to the load path
+        let files = git(load_path, &["ls-tree", "-r", "--name-only", rev, "--", "."])?;
+        for file in String::from_utf8_lossy(&files).lines() {
+            let is_locale_file = Path::new(file)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(is_locale_extension);
+            if !is_locale_file {
+                continue;
+            }
+
+            let content = git(load_path, &["show", &format!("{}:./{}", rev, file)])?;
+            let output_file = rev_path.join(file);
+            if let Some(parent) = output_file.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(output_file, content)?;
+        }
+        rev_paths.push(rev_path);
+    }
+
+    let data = load_translations(&display_paths(&rev_paths), |_| false, key_separator);
+    let _ = std::fs::remove_dir_all(&temp_dir);
+
+    data.map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_translations_at() {
+        let repo = std::env::temp_dir().join("rust-i18n-test-git");
+        let _ = std::fs::remove_dir_all(&repo);
+        let locales = repo.join("locales");
+        std::fs::create_dir_all(&locales).unwrap();
+        assert!(load_translations_at(&[&locales], "HEAD", ".")
+            .unwrap()
+            .is_none());
+
+        let commit = |message: &str| {
+            git(&repo, &["add", "-A"]).unwrap();
+            git(
+                &repo,
+                &["-c", "user.name=test", "-c", "user.email=test@example.com"]
+                    .into_iter()
+                    .chain(["commit", "-q", "-m", message])
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap();
+        };
+        git(&repo, &["init", "-q"]).unwrap();
+        std::fs::write(locales.join("en.yml"), "hello: Hello\nfoo:\n  bar: Bar").unwrap();
+        commit("first");
+        std::fs::write(locales.join("en.yml"), "hello: Hello!").unwrap();
+        commit("second");
+
+        let trs = load_translations_at(&[&locales], "HEAD~1", ".")
+            .unwrap()
+            .unwrap();
+        assert_eq!(trs["en"]["hello"], "Hello");
+        assert_eq!(trs["en"]["foo.bar"], "Bar");
+        assert!(load_translations_at(&[&locales], "not-exists", ".").is_err());
+
+        std::fs::remove_dir_all(&repo).unwrap();
+    }
+}
@@ -3,9 +3,18 @@ use std::fs::File;
 use std::io::Read;
 use std::path::PathBuf;
 
-pub fn iter_crate<F>(src_path: &str, mut callback: F) -> Result<(), Error>
+pub fn iter_crate<F>(src_path: &str, callback: F) -> Result<(), Error>
 where
     F: FnMut(&PathBuf, &str) -> Result<(), Error>,
+{
+    iter_files(src_path, &["rs"], callback)
+}
+
+/// Iterate the files with the given extensions, e.g. `["rs", "html"]`.
+pub fn iter_files<F, S>(src_path: &str, extensions: &[S], mut callback: F) -> Result<(), Error>
+where
+    F: FnMut(&PathBuf, &str) -> Result<(), Error>,
+    S: AsRef<str>,
 {
     let src_path = src_path.trim_end_matches('/');
 
@@ -24,7 +33,10 @@ where
                     continue;
                 }
 
-                if path.extension() != Some("rs".as_ref()) {
+                let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+                    continue;
+                };
+                if !extensions.iter().any(|ext| ext.as_ref() == extension) {
                     continue;
                 }
 
@@ -43,3 +55,26 @@ where
     }
     Ok(())
 }
+
+/// Iterate the given files with the given extensions instead of walking a directory, e.g. the staged files of a pre-commit hook.
+///
+/// The files with other extensions are skipped, e.g. the staged locale files.
+pub fn iter_paths<F, S>(paths: &[PathBuf], extensions: &[S], mut callback: F) -> Result<(), Error>
+where
+    F: FnMut(&PathBuf, &str) -> Result<(), Error>,
+    S: AsRef<str>,
+{
+    for path in paths {
+        let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+            continue;
+        };
+        if !extensions.iter().any(|ext| ext.as_ref() == extension) {
+            continue;
+        }
+
+        let s = std::fs::read_to_string(path)
+            .map_err(|err| Error::msg(format!("Failed to read {}: {}", path.display(), err)))?;
+        callback(path, &s)?;
+    }
+    Ok(())
+}
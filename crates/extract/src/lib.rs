@@ -1,3 +1,6 @@
 pub mod extractor;
 pub mod generator;
+pub mod git;
 pub mod iter;
+pub mod merge;
+pub mod prune;
@@ -0,0 +1,389 @@
+use crate::generator::{convert_value, write_atomic};
+use rust_i18n_support::{
+    get_version, is_locale_extension, parse_value, strip_bom, try_load_locales_with_separator,
+    OVERLAYS_DIR,
+};
+use std::collections::HashMap;
+use std::io::Result;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// The file that the new keys are written into, in the `_version: 2` format.
+pub const MERGED_FILENAME: &str = "merged.yml";
+
+/// How to resolve a key that has different values in both directories.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep our value
+    #[default]
+    Ours,
+    /// Take their value
+    Theirs,
+    /// Take the value of the locale file that is modified more recently
+    Newer,
+}
+
+impl std::str::FromStr for MergeStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "ours" => Ok(MergeStrategy::Ours),
+            "theirs" => Ok(MergeStrategy::Theirs),
+            "newer" => Ok(MergeStrategy::Newer),
+            _ => Err(format!(
+                "Invalid merge strategy `{}`, expected `ours`, `theirs` or `newer`",
+                s
+            )),
+        }
+    }
+}
+
+/// How a conflict is resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// Our value is kept
+    Ours,
+    /// Their value is written into our locale file
+    Theirs,
+    /// Their value is taken, but it can't be written into our locale file, e.g. a list item
+    Manual,
+}
+
+/// A key that has different values in both directories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub locale: String,
+    pub key: String,
+    pub ours: String,
+    pub theirs: String,
+    pub resolution: Resolution,
+}
+
+/// The result of [`merge`].
+#[derive(Debug, Default)]
+pub struct MergeReport {
+    /// The `(locale, key)` only in their directory, written into [`MERGED_FILENAME`]
+    pub added: Vec<(String, String)>,
+    /// The keys that have different values, sorted by locale and key
+    pub conflicts: Vec<Conflict>,
+}
+
+/// A translation and the locale file it's loaded from.
+struct Source {
+    text: String,
+    path: PathBuf,
+    modified: SystemTime,
+}
+
+/// Load the translations of each locale file in the directory, keyed by `(locale, key)`.
+///
+/// A key defined in multiple files has all of them, so they are all updated on conflict.
+fn load_sources(dir: &Path, separator: &str) -> Result<HashMap<(String, String), Vec<Source>>> {
+    let mut sources: HashMap<(String, String), Vec<Source>> = HashMap::new();
+
+    for path in locale_files(dir)? {
+        let modified = std::fs::metadata(&path)?.modified()?;
+        let display = path.display().to_string();
+        let data = try_load_locales_with_separator(
+            &dir.display().to_string(),
+            |file| file != display,
+            separator,
+        )
+        .map_err(|errors| {
+            let message = errors.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+            std::io::Error::new(std::io::ErrorKind::InvalidData, message.join("\n"))
+        })?;
+
+        for (locale, texts) in data {
+            for (key, text) in texts {
+                if key == "_version" {
+                    continue;
+                }
+                sources
+                    .entry((locale.clone(), key))
+                    .or_default()
+                    .push(Source {
+                        text,
+                        path: path.clone(),
+                        modified,
+                    });
+            }
+        }
+    }
+
+    Ok(sources)
+}
+
+/// List the locale files in the directory recursively, the overlays are excluded.
+fn locale_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in ignore::WalkBuilder::new(dir).build() {
+        let path = entry.map_err(std::io::Error::other)?.into_path();
+        let is_locale_file = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(is_locale_extension);
+        let is_overlay = path
+            .strip_prefix(dir)
+            .is_ok_and(|path| path.starts_with(OVERLAYS_DIR));
+        if path.is_file() && is_locale_file && !is_overlay {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Set the text of the flattened key in the parsed locale file, returns false if the key is not found.
+fn set_text(
+    value: &mut serde_json::Value,
+    locale: &str,
+    key: &str,
+    text: &str,
+    separator: &str,
+) -> bool {
+    if get_version(value) == 2 {
+        return match value.get_mut(key).and_then(|texts| texts.get_mut(locale)) {
+            Some(old) if old.is_string() => {
+                *old = serde_json::Value::String(text.into());
+                true
+            }
+            _ => false,
+        };
+    }
+    set_nested_text(value, key, text, separator)
+}
+
+/// Set the text of the key, the key may be nested or flat at any level, e.g. `a.b.c` in `a: { b.c: C }`.
+fn set_nested_text(value: &mut serde_json::Value, key: &str, text: &str, separator: &str) -> bool {
+    let serde_json::Value::Object(map) = value else {
+        return false;
+    };
+    if let Some(old) = map.get_mut(key).filter(|old| old.is_string()) {
+        *old = serde_json::Value::String(text.into());
+        return true;
+    }
+    for (i, _) in key.match_indices(separator) {
+        let (head, rest) = (&key[..i], &key[i + separator.len()..]);
+        if let Some(child) = map.get_mut(head) {
+            if set_nested_text(child, rest, text, separator) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Read and parse a locale file.
+fn read_value(path: &Path) -> Result<serde_json::Value> {
+    let content = std::fs::read_to_string(path)?;
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default();
+    parse_value(strip_bom(&content), ext).map_err(|err| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), err),
+        )
+    })
+}
+
+/// Merge the locale files of `theirs` into `ours`.
+///
+/// The conflicts resolved to their values are updated in place in our locale files, the new keys are
+/// written into [`MERGED_FILENAME`] of `ours`, the changed files are rewritten like `cargo i18n sort`.
+pub fn merge(
+    ours: &Path,
+    theirs: &Path,
+    strategy: MergeStrategy,
+    key_separator: &str,
+) -> Result<MergeReport> {
+    let our_sources = load_sources(ours, key_separator)?;
+    let their_sources = load_sources(theirs, key_separator)?;
+
+    let mut report = MergeReport::default();
+    let mut changed_files: HashMap<PathBuf, serde_json::Value> = HashMap::new();
+
+    let mut keys = their_sources.keys().collect::<Vec<_>>();
+    keys.sort();
+    for locale_key in keys {
+        let (locale, key) = locale_key;
+        // The last loaded file wins if a key is defined in multiple files
+        let their = their_sources[locale_key].last().unwrap();
+
+        let Some(our_list) = our_sources.get(locale_key) else {
+            let path = ours.join(MERGED_FILENAME);
+            if !changed_files.contains_key(&path) {
+                let value = match path.exists() {
+                    true => read_value(&path)?,
+                    false => serde_json::json!({ "_version": 2 }),
+                };
+                changed_files.insert(path.clone(), value);
+            }
+            let value = changed_files.get_mut(&path).unwrap();
+            value[key.as_str()][locale.as_str()] = serde_json::Value::String(their.text.clone());
+            report.added.push((locale.clone(), key.clone()));
+            continue;
+        };
+
+        let our = our_list.last().unwrap();
+        if our.text == their.text {
+            continue;
+        }
+
+        let take_theirs = match strategy {
+            MergeStrategy::Ours => false,
+            MergeStrategy::Theirs => true,
+            MergeStrategy::Newer => their.modified > our.modified,
+        };
+        let mut resolution = Resolution::Ours;
+        if take_theirs {
+            resolution = Resolution::Theirs;
+            for source in our_list {
+                if !changed_files.contains_key(&source.path) {
+                    changed_files.insert(source.path.clone(), read_value(&source.path)?);
+                }
+                let value = changed_files.get_mut(&source.path).unwrap();
+                if !set_text(value, locale, key, &their.text, key_separator) {
+                    resolution = Resolution::Manual;
+                }
+            }
+        }
+
+        report.conflicts.push(Conflict {
+            locale: locale.clone(),
+            key: key.clone(),
+            ours: our.text.clone(),
+            theirs: their.text.clone(),
+            resolution,
+        });
+    }
+
+    for (path, value) in changed_files {
+        let ext = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default();
+        let text = convert_value(&value, ext);
+        write_atomic(&path, format!("{}\n", text.trim_end()))?;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_i18n_support::load_locales;
+
+    fn write(dir: &Path, filename: &str, content: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join(filename), content).unwrap();
+    }
+
+    #[test]
+    fn test_merge() {
+        let base = std::env::temp_dir().join("rust-i18n-test-merge");
+        let _ = std::fs::remove_dir_all(&base);
+        let (ours, theirs) = (base.join("ours"), base.join("theirs"));
+        write(
+            &ours,
+            "en.yml",
+            "hello: Hello\nnested:\n  title: Title\n  flat.key: Flat",
+        );
+        write(
+            &ours,
+            "app.yml",
+            "_version: 2\nbye:\n  en: Bye\n  fr: Au revoir",
+        );
+        write(
+            &theirs,
+            "en.yml",
+            "hello: Hello\nnested.title: New title\nnested.flat.key: New flat\nnew: New",
+        );
+        write(
+            &theirs,
+            "fr.json",
+            r#"{"bye": "Salut", "hello": "Bonjour"}"#,
+        );
+
+        let report = merge(&ours, &theirs, MergeStrategy::Ours, ".").unwrap();
+        assert_eq!(
+            report.added,
+            vec![
+                ("en".to_string(), "new".to_string()),
+                ("fr".to_string(), "hello".to_string()),
+            ]
+        );
+        let resolutions = report
+            .conflicts
+            .iter()
+            .map(|c| (c.key.as_str(), c.resolution))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            resolutions,
+            vec![
+                ("nested.flat.key", Resolution::Ours),
+                ("nested.title", Resolution::Ours),
+                ("bye", Resolution::Ours),
+            ]
+        );
+        let trs = load_locales(&ours.display().to_string(), |_| false);
+        assert_eq!(trs["en"]["new"], "New");
+        assert_eq!(trs["fr"]["hello"], "Bonjour");
+        assert_eq!(trs["en"]["nested.title"], "Title");
+
+        // The added keys are not conflicts anymore
+        let report = merge(&ours, &theirs, MergeStrategy::Theirs, ".").unwrap();
+        assert!(report.added.is_empty());
+        assert!(report
+            .conflicts
+            .iter()
+            .all(|c| c.resolution == Resolution::Theirs));
+        let trs = load_locales(&ours.display().to_string(), |_| false);
+        assert_eq!(trs["en"]["nested.title"], "New title");
+        assert_eq!(trs["en"]["nested.flat.key"], "New flat");
+        assert_eq!(trs["fr"]["bye"], "Salut");
+        assert_eq!(trs["en"]["bye"], "Bye");
+
+        assert_eq!("newer".parse::<MergeStrategy>(), Ok(MergeStrategy::Newer));
+        assert!("foo".parse::<MergeStrategy>().is_err());
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_merge_newer() {
+        let base = std::env::temp_dir().join("rust-i18n-test-merge-newer");
+        let _ = std::fs::remove_dir_all(&base);
+        let (ours, theirs) = (base.join("ours"), base.join("theirs"));
+        write(&theirs, "en.yml", "hello: Old");
+        write(&ours, "en.yml", "hello: Hello\ntips:\n  - Tip");
+        write(&ours, "zh.yml", "hello: 您好");
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        write(&theirs, "zh.yml", "hello: 你好");
+        write(&theirs, "tips.en.yml", "tips:\n  - New tip");
+
+        let report = merge(&ours, &theirs, MergeStrategy::Newer, ".").unwrap();
+        let resolutions = report
+            .conflicts
+            .iter()
+            .map(|c| (c.locale.as_str(), c.key.as_str(), c.resolution))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            resolutions,
+            vec![
+                ("en", "hello", Resolution::Ours),
+                ("en", "tips.0", Resolution::Manual),
+                ("zh", "hello", Resolution::Theirs),
+            ]
+        );
+        let trs = load_locales(&ours.display().to_string(), |_| false);
+        assert_eq!(trs["zh"]["hello"], "你好");
+        assert_eq!(trs["en"]["tips.0"], "Tip");
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+}
@@ -0,0 +1,381 @@
+use crate::extractor::Results;
+use crate::generator::{convert_value, write_atomic};
+use rust_i18n_support::{
+    is_locale_extension, is_manual_key_with_separator, is_plural_map, parse_value, strip_bom,
+    PluralCategory, SchemaVersion,
+};
+use std::collections::{HashMap, HashSet};
+use std::io::Result;
+use std::path::{Path, PathBuf};
+
+/// Find the keys of the default locale that are not used in the source code, sorted.
+///
+/// The wildcard keys (e.g. `error.*`) and the keys marked with `_meta: manual` are not orphans.
+/// A list item (e.g. `tips.0`) or a plural form (e.g. `items.one`) is used if its key is used, e.g. `t!("tips")`,
+/// and so is a variant (e.g. `cta.title#variant_b`) of a used key.
+pub fn find_orphan_keys(
+    trs: &HashMap<String, String>,
+    results: &Results,
+    manual_keys: &HashSet<String>,
+    key_separator: &str,
+) -> Vec<String> {
+    let is_used = |key: &str| {
+        let key = strip_variant(key, key_separator);
+        let key = key.as_str();
+        if results.contains_key(key) {
+            return true;
+        }
+        let Some((parent, last)) = key.rsplit_once(key_separator) else {
+            return false;
+        };
+        let is_item = !last.is_empty() && last.chars().all(|c| c.is_ascii_digit());
+        (is_item || PluralCategory::from_key(last).is_some()) && results.contains_key(parent)
+    };
+
+    let mut orphans = trs
+        .keys()
+        .filter(|key| *key != "_version")
+        .filter(|key| !key.ends_with(&format!("{}*", key_separator)))
+        .filter(|key| !is_used(key))
+        .filter(|key| !is_manual_key_with_separator(manual_keys, key, key_separator))
+        .cloned()
+        .collect::<Vec<_>>();
+    orphans.sort();
+    orphans
+}
+
+/// Find the keys used in the source code that are missing in the default locale, sorted, the inverse of [`find_orphan_keys`].
+///
+/// A key is not missing if it has the list items or the plural forms, e.g. `tips.0` of `t!("tips")`,
+/// or if a wildcard key of its namespace resolves it, e.g. `error.*` of `t!("error.not_found")`.
+pub fn find_missing_keys(
+    trs: &HashMap<String, String>,
+    results: &Results,
+    key_separator: &str,
+) -> Vec<String> {
+    let children = trs
+        .keys()
+        .filter_map(|key| key.rsplit_once(key_separator))
+        .filter(|(_, last)| {
+            let is_item = !last.is_empty() && last.chars().all(|c| c.is_ascii_digit());
+            is_item || PluralCategory::from_key(last).is_some()
+        })
+        .map(|(parent, _)| parent)
+        .collect::<HashSet<_>>();
+    let is_translated = |key: &str| {
+        if trs.contains_key(key) || children.contains(key) {
+            return true;
+        }
+        let mut namespace = key;
+        while let Some((parent, _)) = namespace.rsplit_once(key_separator) {
+            if trs.contains_key(&format!("{}{}*", parent, key_separator)) {
+                return true;
+            }
+            namespace = parent;
+        }
+        false
+    };
+
+    let mut missing = results
+        .keys()
+        .filter(|key| !is_translated(key))
+        .cloned()
+        .collect::<Vec<_>>();
+    missing.sort();
+    missing
+}
+
+/// Remove the variant suffix of the key, e.g. `cta.title#variant_b.one` -> `cta.title.one`.
+fn strip_variant(key: &str, key_separator: &str) -> String {
+    let Some(start) = key.find('#') else {
+        return key.to_owned();
+    };
+    let end = key[start..]
+        .find(key_separator)
+        .map_or(key.len(), |end| start + end);
+    format!("{}{}", &key[..start], &key[end..])
+}
+
+/// A locale file and the keys removed from it by [`prune`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrunedFile {
+    pub path: PathBuf,
+    /// The removed keys in the order of the file
+    pub keys: Vec<String>,
+}
+
+/// Remove the `keys` from all locale files of the load paths, e.g. the orphans of [`find_orphan_keys`].
+///
+/// The list items and the plural forms are removed with their key, e.g. `tips` for `tips.0`,
+/// the objects that become empty are removed too. The files are rewritten like `cargo i18n sort`, the comments are not kept.
+/// Nothing is written with `dry_run`, returns the files that have any of the keys.
+pub fn prune<P: AsRef<Path>>(
+    load_paths: &[P],
+    keys: &HashSet<String>,
+    key_separator: &str,
+    dry_run: bool,
+) -> Result<Vec<PrunedFile>> {
+    let mut pruned = vec![];
+    for load_path in load_paths {
+        for entry in ignore::WalkBuilder::new(load_path).build() {
+            let path = entry.map_err(std::io::Error::other)?.into_path();
+            let Some(ext) = path.extension().and_then(|ext| ext.to_str()) else {
+                continue;
+            };
+            if !path.is_file() || !is_locale_extension(ext) {
+                continue;
+            }
+
+            let content = std::fs::read_to_string(&path)?;
+            let invalid = |err: String| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), err),
+                )
+            };
+            let mut value = parse_value(strip_bom(&content), ext).map_err(invalid)?;
+            let version = SchemaVersion::from_value(&value).map_err(invalid)?;
+            let serde_json::Value::Object(map) = &mut value else {
+                continue;
+            };
+
+            let mut removed = vec![];
+            remove_keys(
+                map,
+                "",
+                keys,
+                key_separator,
+                version == SchemaVersion::V2,
+                &mut removed,
+            );
+            if removed.is_empty() {
+                continue;
+            }
+
+            if !dry_run {
+                let text = convert_value(&value, ext);
+                write_atomic(&path, format!("{}\n", text.trim_end()))?;
+            }
+            pruned.push(PrunedFile {
+                path,
+                keys: removed,
+            });
+        }
+    }
+
+    Ok(pruned)
+}
+
+/// Remove the keys from the object recursively, the nested keys are joined to the `prefix`.
+fn remove_keys(
+    map: &mut serde_json::Map<String, serde_json::Value>,
+    prefix: &str,
+    keys: &HashSet<String>,
+    key_separator: &str,
+    is_v2: bool,
+    removed: &mut Vec<String>,
+) {
+    let names = map.keys().cloned().collect::<Vec<_>>();
+    for name in names {
+        if name.starts_with('_') && (name == "_meta" || name == "_version") {
+            continue;
+        }
+        let key = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}{}{}", prefix, key_separator, name)
+        };
+        let Some(value) = map.get_mut(&name) else {
+            continue;
+        };
+        // The texts of the key, or the texts of the locales in the v2 format, e.g. `{ en: Hello, fr: Bonjour }`
+        let loaded_keys = if is_v2 && is_locales_map(value) {
+            value
+                .as_object()
+                .into_iter()
+                .flat_map(|locales| locales.iter().filter(|(name, _)| *name != "_meta"))
+                .flat_map(|(_, text)| loaded_keys(&key, text, key_separator))
+                .collect()
+        } else {
+            loaded_keys(&key, value, key_separator)
+        };
+        if !loaded_keys.is_empty() {
+            if loaded_keys.iter().any(|key| keys.contains(key)) {
+                map.remove(&name);
+                removed.push(key);
+            }
+            continue;
+        }
+
+        let serde_json::Value::Object(child) = value else {
+            continue;
+        };
+        remove_keys(child, &key, keys, key_separator, is_v2, removed);
+        if child.keys().all(|name| name == "_meta") {
+            map.remove(&name);
+        }
+    }
+}
+
+/// Get the keys that a text is loaded as, e.g. `tips.0` of a list item, `items.one` of a plural form,
+/// empty if the value is an object of the nested keys.
+fn loaded_keys(key: &str, value: &serde_json::Value, key_separator: &str) -> Vec<String> {
+    match value {
+        serde_json::Value::Array(list) => (0..list.len())
+            .map(|i| format!("{}{}{}", key, key_separator, i))
+            .collect(),
+        serde_json::Value::Object(forms) if is_plural_map(value) => forms
+            .keys()
+            .map(|form| format!("{}{}{}", key, key_separator, form))
+            .collect(),
+        serde_json::Value::Object(_) => vec![],
+        _ => vec![key.to_string()],
+    }
+}
+
+/// Check if the object is the texts of a key in the v2 format, all values are the texts, lists or plural forms.
+fn is_locales_map(value: &serde_json::Value) -> bool {
+    let serde_json::Value::Object(map) = value else {
+        return false;
+    };
+    map.iter()
+        .filter(|(name, _)| *name != "_meta")
+        .all(|(_, v)| v.is_string() || v.is_array() || v.is_null() || is_plural_map(v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::Message;
+    use rust_i18n_support::load_locales;
+
+    fn write(dir: &Path, filename: &str, content: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join(filename), content).unwrap();
+    }
+
+    #[test]
+    fn test_find_orphan_keys() {
+        let trs = [
+            "hello",
+            "unused",
+            "tips.0",
+            "tips.1",
+            "items.one",
+            "items.other",
+            "error.*",
+            "dynamic.a",
+            "hello#variant_b",
+            "items#variant_b.one",
+            "unused#variant_b",
+        ]
+        .iter()
+        .map(|key| (key.to_string(), String::new()))
+        .collect::<HashMap<_, _>>();
+        let results = ["hello", "tips", "items"]
+            .iter()
+            .map(|key| (key.to_string(), Message::default()))
+            .collect::<Results>();
+        let manual_keys = HashSet::from(["dynamic".to_string()]);
+
+        assert_eq!(
+            find_orphan_keys(&trs, &results, &manual_keys, "."),
+            vec!["unused", "unused#variant_b"]
+        );
+    }
+
+    #[test]
+    fn test_find_missing_keys() {
+        let trs = ["hello", "tips.0", "items.one", "error.*", "a.b.*"]
+            .iter()
+            .map(|key| (key.to_string(), String::new()))
+            .collect::<HashMap<_, _>>();
+        let results = [
+            "hello",
+            "tips",
+            "items",
+            "error.not_found",
+            "a.b.c.d",
+            "a.c",
+            "new",
+            "hello.world",
+        ]
+        .iter()
+        .map(|key| (key.to_string(), Message::default()))
+        .collect::<Results>();
+
+        assert_eq!(
+            find_missing_keys(&trs, &results, "."),
+            vec!["a.c", "hello.world", "new"]
+        );
+    }
+
+    #[test]
+    fn test_prune() {
+        let base = std::env::temp_dir().join("rust-i18n-test-prune");
+        let _ = std::fs::remove_dir_all(&base);
+        write(
+            &base,
+            "en.yml",
+            "hello: Hello\nunused: Unused\nnested:\n  old: Old\ntips:\n  - Tip 1\n  - Tip 2\n",
+        );
+        write(
+            &base,
+            "fr.json",
+            r#"{"hello": "Bonjour", "unused": "Inutilisé"}"#,
+        );
+        write(
+            &base,
+            "app.yml",
+            "_version: 2\nhello:\n  en: Hello\nnested:\n  old:\n    en: Old\n    fr: Vieux\n",
+        );
+        write(&base, "other.yml", "hello: Hello\n");
+
+        let keys = HashSet::from([
+            "unused".to_string(),
+            "nested.old".to_string(),
+            "tips.0".to_string(),
+            "tips.1".to_string(),
+        ]);
+        let mut pruned = prune(&[&base], &keys, ".", true).unwrap();
+        pruned.sort_by(|a, b| a.path.cmp(&b.path));
+        let pruned = pruned
+            .iter()
+            .map(|file| {
+                let name = file.path.file_name().unwrap().to_str().unwrap();
+                (
+                    name,
+                    file.keys.iter().map(String::as_str).collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pruned,
+            vec![
+                ("app.yml", vec!["nested.old"]),
+                ("en.yml", vec!["unused", "nested.old", "tips"]),
+                ("fr.json", vec!["unused"]),
+            ]
+        );
+        // Nothing is written in the dry run
+        let data = load_locales(&base.display().to_string(), |_| false);
+        assert_eq!(data["en"]["unused"], "Unused");
+
+        prune(&[&base], &keys, ".", false).unwrap();
+        let data = load_locales(&base.display().to_string(), |_| false);
+        assert_eq!(data["en"]["hello"], "Hello");
+        assert_eq!(data["fr"]["hello"], "Bonjour");
+        assert!(!data["en"].contains_key("unused"));
+        assert!(!data["en"].contains_key("nested.old"));
+        assert!(!data["en"].contains_key("tips.0"));
+        assert!(!data["fr"].contains_key("unused"));
+        assert!(!data["fr"].contains_key("nested.old"));
+        assert_eq!(
+            std::fs::read_to_string(base.join("en.yml")).unwrap(),
+            "hello: Hello\n"
+        );
+        assert!(prune(&[&base], &keys, ".", false).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+}
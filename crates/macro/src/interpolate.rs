@@ -0,0 +1,92 @@
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, LitStr};
+
+/// A type representing the `#[derive(Interpolate)]` proc macro.
+pub struct Interpolate {
+    ident: Ident,
+    generics: syn::Generics,
+    /// The fields and the names of their patterns
+    fields: Vec<(Ident, String)>,
+}
+
+impl Interpolate {
+    fn into_token_stream(self) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let fields = self.fields.iter().map(|(field, name)| {
+            quote! { (#name, rust_i18n::__private::format!("{}", self.#field)) }
+        });
+
+        quote! {
+            impl #impl_generics rust_i18n::Interpolate for #ident #ty_generics #where_clause {
+                fn interpolate_fields(&self) -> rust_i18n::__private::Vec<(&'static str, rust_i18n::__private::String)> {
+                    rust_i18n::__private::Vec::from([#(#fields),*])
+                }
+            }
+        }
+    }
+}
+
+/// Parse `#[interpolate(rename = "name")]` or `#[interpolate(skip)]` of a field.
+fn parse_field_attribute(attrs: &[syn::Attribute]) -> syn::Result<(Option<String>, bool)> {
+    let (mut rename, mut skip) = (None, false);
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("interpolate"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `rename` or `skip`"))
+            }
+        })?;
+    }
+    Ok((rename, skip))
+}
+
+impl syn::parse::Parse for Interpolate {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let input = input.parse::<DeriveInput>()?;
+        let Data::Struct(data) = &input.data else {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`Interpolate` can only be derived for structs",
+            ));
+        };
+        let Fields::Named(named) = &data.fields else {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`Interpolate` can only be derived for structs with named fields",
+            ));
+        };
+
+        let mut fields = Vec::new();
+        for field in named.named.iter() {
+            let (rename, skip) = parse_field_attribute(&field.attrs)?;
+            if skip {
+                continue;
+            }
+            let ident = field.ident.clone().unwrap();
+            // The raw identifiers are named without the `r#`, e.g. `r#type` => `type`
+            let name = rename.unwrap_or_else(|| ident.to_string().trim_start_matches("r#").into());
+            fields.push((ident, name));
+        }
+
+        Ok(Self {
+            ident: input.ident,
+            generics: input.generics,
+            fields,
+        })
+    }
+}
+
+impl From<Interpolate> for proc_macro::TokenStream {
+    fn from(val: Interpolate) -> Self {
+        val.into_token_stream().into()
+    }
+}
@@ -0,0 +1,118 @@
+use once_cell::sync::Lazy;
+use proc_macro2::{Group, Literal, TokenStream, TokenTree};
+use quote::quote;
+use rust_i18n_support::{I18nConfig, DEFAULT_KEY_SEPARATOR};
+use syn::{LitStr, Token};
+
+/// The macros whose literal key is prefixed.
+static MACRO_NAMES: &[&str] = &["t", "t_string", "t_markdown", "t_html"];
+
+/// The `key-separator` of the `[package.metadata.i18n]` in Cargo.toml.
+static KEY_SEPARATOR: Lazy<String> = Lazy::new(|| {
+    std::env::var("CARGO_MANIFEST_DIR")
+        .ok()
+        .and_then(|dir| I18nConfig::load(std::path::Path::new(&dir)).ok())
+        .map(|cfg| cfg.key_separator)
+        .unwrap_or_else(|| DEFAULT_KEY_SEPARATOR.to_owned())
+});
+
+/// A type representing the `with_key_prefix!` proc macro.
+pub struct KeyPrefix {
+    prefix: String,
+    body: TokenStream,
+}
+
+impl KeyPrefix {
+    fn into_token_stream(self) -> TokenStream {
+        let body = prefix_keys(self.body, &self.prefix, &KEY_SEPARATOR);
+        quote! { #body }
+    }
+}
+
+impl syn::parse::Parse for KeyPrefix {
+    /// with_key_prefix!("billing.invoice", { t!("title") })
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let prefix = input.parse::<LitStr>()?;
+        if prefix.value().is_empty() {
+            return Err(syn::Error::new(
+                prefix.span(),
+                "The prefix must not be empty",
+            ));
+        }
+        let _comma = input.parse::<Token![,]>()?;
+        let body = input.parse::<TokenStream>()?;
+        Ok(Self {
+            prefix: prefix.value(),
+            body,
+        })
+    }
+}
+
+impl From<KeyPrefix> for proc_macro::TokenStream {
+    fn from(val: KeyPrefix) -> Self {
+        val.into_token_stream().into()
+    }
+}
+
+/// Prefix the literal key of the `t!` calls in the tokens, the nested `with_key_prefix!` gets the joined prefix.
+fn prefix_keys(stream: TokenStream, prefix: &str, separator: &str) -> TokenStream {
+    let mut output = vec![];
+    let mut token_iter = stream.into_iter().peekable();
+
+    while let Some(token) = token_iter.next() {
+        let TokenTree::Ident(ident) = &token else {
+            if let TokenTree::Group(group) = &token {
+                let mut new_group = Group::new(
+                    group.delimiter(),
+                    prefix_keys(group.stream(), prefix, separator),
+                );
+                new_group.set_span(group.span());
+                output.push(TokenTree::Group(new_group));
+            } else {
+                output.push(token);
+            }
+            continue;
+        };
+
+        let name = ident.to_string();
+        let is_macro =
+            matches!(token_iter.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '!');
+        output.push(token);
+        if !is_macro || (name != "with_key_prefix" && !MACRO_NAMES.contains(&name.as_str())) {
+            continue;
+        }
+        output.extend(token_iter.next());
+
+        let Some(TokenTree::Group(group)) = token_iter.next() else {
+            continue;
+        };
+        // The nested prefix is joined and its body is left to the nested macro, so the keys are prefixed once
+        let mut args = group.stream().into_iter();
+        let first = args.next();
+        let key = first.as_ref().and_then(|token| match token {
+            TokenTree::Literal(lit) => {
+                syn::parse2::<LitStr>(TokenTree::Literal(lit.clone()).into()).ok()
+            }
+            _ => None,
+        });
+        let mut stream = match key {
+            Some(key) => {
+                let mut lit = Literal::string(&format!("{}{}{}", prefix, separator, key.value()));
+                lit.set_span(key.span());
+                TokenStream::from(TokenTree::Literal(lit))
+            }
+            None => first.into_iter().collect(),
+        };
+        if name == "with_key_prefix" {
+            stream.extend(args);
+        } else {
+            stream.extend(prefix_keys(args.collect(), prefix, separator));
+        }
+
+        let mut new_group = Group::new(group.delimiter(), stream);
+        new_group.set_span(group.span());
+        output.push(TokenTree::Group(new_group));
+    }
+
+    output.into_iter().collect()
+}
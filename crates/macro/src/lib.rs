@@ -1,34 +1,205 @@
 use quote::quote;
 use rust_i18n_support::{
-    is_debug, load_locales, I18nConfig, DEFAULT_MINIFY_KEY, DEFAULT_MINIFY_KEY_LEN,
-    DEFAULT_MINIFY_KEY_PREFIX, DEFAULT_MINIFY_KEY_THRESH,
+    expand_load_path, find_empty_translations, find_low_coverage_translations,
+    find_missing_translations, find_too_long_translations, is_debug,
+    load_deprecated_keys_with_separator, load_max_len_keys_with_separator, locale_file_paths,
+    try_load_locales_from_paths_with_separator, I18nConfig, OnMissing, DEFAULT_KEY_SEPARATOR,
+    DEFAULT_MINIFY_KEY, DEFAULT_MINIFY_KEY_LEN, DEFAULT_MINIFY_KEY_PREFIX,
+    DEFAULT_MINIFY_KEY_THRESH, OVERLAYS_DIR,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use syn::{parse_macro_input, Expr, Ident, LitBool, LitStr, Token};
 
+mod interpolate;
+mod key_prefix;
+mod localize;
 mod minify_key;
 mod tr;
 
 struct Args {
-    locales_path: String,
+    locales_path: Vec<String>,
     default_locale: Option<String>,
     fallback: Option<Vec<String>>,
+    include: Option<Vec<String>>,
     extend: Option<Expr>,
+    transform: Option<Expr>,
     minify_key: bool,
     minify_key_len: usize,
     minify_key_prefix: String,
     minify_key_thresh: usize,
+    on_missing: OnMissing,
+    interpolation: (String, String),
+    static_only: Option<String>,
+    forbid_empty: bool,
+    format_numbers: bool,
+    generate_keys: bool,
+    bidi_isolate: bool,
+    static_table: bool,
+    packed: bool,
+    chunk_size: usize,
+    max_inline_keys: usize,
+    runtime_prune: bool,
+    pseudo_locale: bool,
+    pseudo_expansion: usize,
+    overlay: Option<String>,
+    namespace: Option<String>,
+    aliases: Vec<(String, String)>,
+    key_separator: String,
+    no_fallback_locales: Vec<String>,
+    namespace_default_locales: Vec<(String, String)>,
+    required_locales: Vec<String>,
+    min_coverage: BTreeMap<String, u8>,
+    check_args: bool,
+    check_max_len: bool,
+    smart_quotes: bool,
+}
+
+/// Parse `env("NAME")` and read the environment variable at compile time, `None` if the input is not `env(...)`.
+///
+/// The value is `None` if the variable is unset or empty.
+fn parse_env_var(
+    input: syn::parse::ParseStream,
+) -> syn::parse::Result<Option<(LitStr, Option<String>)>> {
+    if !input.peek(Ident) || !input.peek2(syn::token::Paren) {
+        return Ok(None);
+    }
+    let ident = input.parse::<Ident>()?;
+    if ident != "env" {
+        return Err(syn::Error::new(
+            ident.span(),
+            "expected a string literal or `env(\"NAME\")`",
+        ));
+    }
+    let content;
+    syn::parenthesized!(content in input);
+    let name = content.parse::<LitStr>()?;
+    let value = std::env::var(name.value())
+        .ok()
+        .filter(|value| !value.trim().is_empty());
+    Ok(Some((name, value)))
+}
+
+/// The error of an unset environment variable that has no config value to fall back to.
+fn env_var_error(name: &LitStr, option: &str) -> syn::Error {
+    syn::Error::new(
+        name.span(),
+        format!(
+            "The environment variable `{}` of `{}` is not set, and there is no `{}` in the config to fall back to",
+            name.value(),
+            option,
+            option
+        ),
+    )
+}
+
+/// Replace the environment variables in the locales path at compile time, e.g. `${OUT_DIR}/locales`.
+///
+/// It's an error if a variable is unset, or the `${` is not closed.
+fn expand_path_env_vars(path: &LitStr) -> syn::parse::Result<String> {
+    let value = path.value();
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value.as_str();
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            return Err(syn::Error::new(
+                path.span(),
+                "The `${` of the locales path is not closed, expected `${NAME}`",
+            ));
+        };
+        let name = &rest[start + 2..start + 2 + len];
+        let var = std::env::var(name).map_err(|_| {
+            syn::Error::new(
+                path.span(),
+                format!(
+                    "The environment variable `{}` of the locales path is not set",
+                    name
+                ),
+            )
+        })?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&var);
+        rest = &rest[start + 2 + len + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Parse the interpolation delimiters separated by whitespace, e.g. `"{{ }}"` => `("{{", "}}")`.
+pub(crate) fn parse_interpolation(value: &str) -> Result<(String, String), String> {
+    let mut parts = value.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(open), Some(close), None) => Ok((open.to_owned(), close.to_owned())),
+        _ => Err(format!(
+            "Invalid interpolation `{}`, expected the open and close delimiters separated by a space, e.g. \"{{{{ }}}}\"",
+            value
+        )),
+    }
 }
 
 impl Args {
     fn consume_path(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
-        let path = input.parse::<LitStr>()?;
-        self.locales_path = path.value();
+        if let Ok(path) = input.parse::<LitStr>() {
+            self.locales_path = vec![expand_path_env_vars(&path)?];
+            return Ok(());
+        }
+        let val = input.parse::<syn::ExprArray>()?;
+        let paths = val
+            .elems
+            .into_iter()
+            .map(|expr| {
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                }) = expr
+                {
+                    expand_path_env_vars(&lit_str)
+                } else {
+                    Err(input.error(
+                        "The locales path must be a string literal or an array of string literals",
+                    ))
+                }
+            })
+            .collect::<syn::parse::Result<Vec<String>>>()?;
+        self.locales_path = paths;
 
         Ok(())
     }
 
+    fn consume_default_locale(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        // The config value is kept if the variable is unset
+        if let Some((name, value)) = parse_env_var(input)? {
+            match value {
+                Some(value) => self.default_locale = Some(value.trim().to_owned()),
+                None if self.default_locale.is_some() => {}
+                None => return Err(env_var_error(&name, "default_locale")),
+            }
+            return Ok(());
+        }
+        let lit_str = input.parse::<LitStr>()?;
+        if lit_str.value().is_empty() {
+            return Err(input.error("`default_locale` must not be empty"));
+        }
+        self.default_locale = Some(lit_str.value());
+        Ok(())
+    }
+
     fn consume_fallback(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        // A comma separated list in the variable, e.g. `APP_FALLBACK=de,en`
+        if let Some((name, value)) = parse_env_var(input)? {
+            match value {
+                Some(value) => {
+                    let fallback = value
+                        .split(',')
+                        .map(|locale| locale.trim().to_owned())
+                        .filter(|locale| !locale.is_empty())
+                        .collect();
+                    self.fallback = Some(fallback);
+                }
+                None if self.fallback.is_some() => {}
+                None => return Err(env_var_error(&name, "fallback")),
+            }
+            return Ok(());
+        }
         if let Ok(val) = input.parse::<LitStr>() {
             self.fallback = Some(vec![val.value()]);
             return Ok(());
@@ -55,6 +226,39 @@ impl Args {
         Ok(())
     }
 
+    fn consume_include(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        // A comma separated string, e.g. "en, fr"
+        if let Ok(val) = input.parse::<LitStr>() {
+            let include = val
+                .value()
+                .split(',')
+                .map(|locale| locale.trim().to_owned())
+                .filter(|locale| !locale.is_empty())
+                .collect();
+            self.include = Some(include);
+            return Ok(());
+        }
+        let val = input.parse::<syn::ExprArray>()?;
+        let include = val
+            .elems
+            .into_iter()
+            .map(|expr| {
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                }) = expr
+                {
+                    Ok(lit_str.value())
+                } else {
+                    Err(input
+                        .error("`include` must be a string literal or an array of string literals"))
+                }
+            })
+            .collect::<syn::parse::Result<Vec<String>>>()?;
+        self.include = Some(include);
+        Ok(())
+    }
+
     fn consume_minify_key(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
         let lit_bool = input.parse::<LitBool>()?;
         self.minify_key = lit_bool.value;
@@ -85,19 +289,304 @@ impl Args {
         Ok(())
     }
 
+    fn consume_on_missing(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_str = input.parse::<syn::LitStr>()?;
+        self.on_missing = lit_str
+            .value()
+            .parse()
+            .map_err(|e: String| syn::Error::new(lit_str.span(), e))?;
+        Ok(())
+    }
+
+    fn consume_interpolation(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_str = input.parse::<syn::LitStr>()?;
+        self.interpolation = parse_interpolation(&lit_str.value())
+            .map_err(|e| syn::Error::new(lit_str.span(), e))?;
+        Ok(())
+    }
+
+    fn consume_static_only(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_str = input.parse::<syn::LitStr>()?;
+        self.static_only = Some(lit_str.value());
+        Ok(())
+    }
+
+    fn consume_forbid_empty(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_bool = input.parse::<LitBool>()?;
+        self.forbid_empty = lit_bool.value;
+        Ok(())
+    }
+
+    fn consume_format_numbers(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_bool = input.parse::<LitBool>()?;
+        self.format_numbers = lit_bool.value;
+        Ok(())
+    }
+
+    fn consume_generate_keys(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_bool = input.parse::<LitBool>()?;
+        self.generate_keys = lit_bool.value;
+        Ok(())
+    }
+
+    fn consume_bidi_isolate(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_bool = input.parse::<LitBool>()?;
+        self.bidi_isolate = lit_bool.value;
+        Ok(())
+    }
+
+    fn consume_static_table(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_bool = input.parse::<LitBool>()?;
+        self.static_table = lit_bool.value;
+        Ok(())
+    }
+
+    fn consume_packed(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_bool = input.parse::<LitBool>()?;
+        self.packed = lit_bool.value;
+        Ok(())
+    }
+
+    fn consume_chunk_size(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_int = input.parse::<syn::LitInt>()?;
+        self.chunk_size = lit_int.base10_parse()?;
+        Ok(())
+    }
+
+    fn consume_runtime_prune(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_bool = input.parse::<LitBool>()?;
+        self.runtime_prune = lit_bool.value;
+        Ok(())
+    }
+
+    fn consume_max_inline_keys(
+        &mut self,
+        input: syn::parse::ParseStream,
+    ) -> syn::parse::Result<()> {
+        let lit_int = input.parse::<syn::LitInt>()?;
+        self.max_inline_keys = lit_int.base10_parse()?;
+        Ok(())
+    }
+
+    fn consume_pseudo_locale(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_bool = input.parse::<LitBool>()?;
+        self.pseudo_locale = lit_bool.value;
+        Ok(())
+    }
+
+    fn consume_pseudo_expansion(
+        &mut self,
+        input: syn::parse::ParseStream,
+    ) -> syn::parse::Result<()> {
+        let lit_int = input.parse::<syn::LitInt>()?;
+        self.pseudo_expansion = lit_int.base10_parse()?;
+        Ok(())
+    }
+
+    fn consume_overlay(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_str = input.parse::<LitStr>()?;
+        self.overlay = Some(lit_str.value());
+        Ok(())
+    }
+
+    fn consume_namespace(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_str = input.parse::<LitStr>()?;
+        if lit_str.value().is_empty() {
+            return Err(input.error("`namespace` must not be empty"));
+        }
+        self.namespace = Some(lit_str.value());
+        Ok(())
+    }
+
+    fn consume_aliases(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let val = input.parse::<syn::ExprArray>()?;
+        let aliases = val
+            .elems
+            .into_iter()
+            .map(|expr| {
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                }) = &expr
+                {
+                    if let Some((alias, locale)) = lit_str.value().split_once('=') {
+                        return Ok((alias.trim().to_owned(), locale.trim().to_owned()));
+                    }
+                }
+                Err(input.error(
+                    "`aliases` must be an array of string literals like \"alias=locale\", e.g. [\"zh=zh-CN\"]",
+                ))
+            })
+            .collect::<syn::parse::Result<Vec<_>>>()?;
+        self.aliases = aliases;
+        Ok(())
+    }
+
+    fn consume_no_fallback_locales(
+        &mut self,
+        input: syn::parse::ParseStream,
+    ) -> syn::parse::Result<()> {
+        let val = input.parse::<syn::ExprArray>()?;
+        let locales = val
+            .elems
+            .into_iter()
+            .map(|expr| {
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                }) = expr
+                {
+                    Ok(lit_str.value())
+                } else {
+                    Err(input.error("`no_fallback_locales` must be an array of string literals"))
+                }
+            })
+            .collect::<syn::parse::Result<Vec<String>>>()?;
+        self.no_fallback_locales = locales;
+        Ok(())
+    }
+
+    fn consume_namespace_default_locales(
+        &mut self,
+        input: syn::parse::ParseStream,
+    ) -> syn::parse::Result<()> {
+        let val = input.parse::<syn::ExprArray>()?;
+        let namespaces = val
+            .elems
+            .into_iter()
+            .map(|expr| {
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                }) = &expr
+                {
+                    if let Some((namespace, locale)) = lit_str.value().split_once('=') {
+                        return Ok((namespace.trim().to_owned(), locale.trim().to_owned()));
+                    }
+                }
+                Err(input.error(
+                    "`namespace_default_locales` must be an array of string literals like \"namespace=locale\", e.g. [\"vendor.payments=fr\"]",
+                ))
+            })
+            .collect::<syn::parse::Result<Vec<_>>>()?;
+        self.namespace_default_locales = namespaces;
+        Ok(())
+    }
+
+    fn consume_required_locales(
+        &mut self,
+        input: syn::parse::ParseStream,
+    ) -> syn::parse::Result<()> {
+        let val = input.parse::<syn::ExprArray>()?;
+        let locales = val
+            .elems
+            .into_iter()
+            .map(|expr| {
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                }) = expr
+                {
+                    Ok(lit_str.value())
+                } else {
+                    Err(input.error("`required_locales` must be an array of string literals"))
+                }
+            })
+            .collect::<syn::parse::Result<Vec<String>>>()?;
+        self.required_locales = locales;
+        Ok(())
+    }
+
+    /// Parse `min_coverage = 90` for all locales, or `min_coverage = ["fr=90", "*=80"]` for each locale.
+    fn consume_min_coverage(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let parse_percent = |lit: &syn::LitInt| -> syn::parse::Result<u8> {
+            match lit.base10_parse::<u8>()? {
+                percent @ 0..=100 => Ok(percent),
+                _ => Err(syn::Error::new(
+                    lit.span(),
+                    "`min_coverage` must be a percentage from 0 to 100",
+                )),
+            }
+        };
+        if input.peek(syn::LitInt) {
+            let lit_int = input.parse::<syn::LitInt>()?;
+            self.min_coverage = BTreeMap::from([("*".to_owned(), parse_percent(&lit_int)?)]);
+            return Ok(());
+        }
+
+        let val = input.parse::<syn::ExprArray>()?;
+        self.min_coverage = val
+            .elems
+            .into_iter()
+            .map(|expr| {
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                }) = &expr
+                {
+                    if let Some((locale, percent)) = lit_str.value().split_once('=') {
+                        let lit_int = syn::LitInt::new(percent.trim(), lit_str.span());
+                        return Ok((locale.trim().to_owned(), parse_percent(&lit_int)?));
+                    }
+                }
+                Err(input.error(
+                    "`min_coverage` must be a percentage or an array of string literals like \"locale=percentage\", e.g. [\"fr=90\"]",
+                ))
+            })
+            .collect::<syn::parse::Result<_>>()?;
+        Ok(())
+    }
+
+    fn consume_check_args(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_bool = input.parse::<LitBool>()?;
+        self.check_args = lit_bool.value;
+        Ok(())
+    }
+
+    fn consume_check_max_len(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_bool = input.parse::<LitBool>()?;
+        self.check_max_len = lit_bool.value;
+        Ok(())
+    }
+
+    fn consume_smart_quotes(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_bool = input.parse::<LitBool>()?;
+        self.smart_quotes = lit_bool.value;
+        Ok(())
+    }
+
+    fn consume_key_separator(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        let lit_str = input.parse::<LitStr>()?;
+        if lit_str.value().is_empty() {
+            return Err(input.error("`key_separator` must not be empty"));
+        }
+        self.key_separator = lit_str.value();
+        Ok(())
+    }
+
     fn consume_options(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
         let ident = input.parse::<Ident>()?.to_string();
         input.parse::<Token![=]>()?;
 
         // If there have any option in arguments, it will override the config from metadata
         match ident.as_str() {
+            "default_locale" => {
+                self.consume_default_locale(input)?;
+            }
             "fallback" => {
                 self.consume_fallback(input)?;
             }
+            "include" => {
+                self.consume_include(input)?;
+            }
             "backend" => {
                 let val = input.parse::<Expr>()?;
                 self.extend = Some(val);
             }
+            "transform" => {
+                let val = input.parse::<Expr>()?;
+                self.transform = Some(val);
+            }
             "minify_key" => {
                 self.consume_minify_key(input)?;
             }
@@ -110,25 +599,105 @@ impl Args {
             "minify_key_thresh" => {
                 self.consume_minify_key_thresh(input)?;
             }
-            _ => {}
-        }
-
-        // Continue to consume reset of options
-        if input.parse::<Token![,]>().is_ok() {
-            self.consume_options(input)?;
-        }
-
-        Ok(())
-    }
-
-    /// Load the config from Cargo.toml. This can be overridden by subsequent options.
-    fn load_metadata(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
-        // CARGO_MANIFEST_DIR is current build directory
+            "on_missing" => {
+                self.consume_on_missing(input)?;
+            }
+            "interpolation" => {
+                self.consume_interpolation(input)?;
+            }
+            "static_only" => {
+                self.consume_static_only(input)?;
+            }
+            "forbid_empty" => {
+                self.consume_forbid_empty(input)?;
+            }
+            "format_numbers" => {
+                self.consume_format_numbers(input)?;
+            }
+            "generate_keys" => {
+                self.consume_generate_keys(input)?;
+            }
+            "bidi_isolate" => {
+                self.consume_bidi_isolate(input)?;
+            }
+            "static_table" => {
+                self.consume_static_table(input)?;
+            }
+            "packed" => {
+                self.consume_packed(input)?;
+            }
+            "chunk_size" => {
+                self.consume_chunk_size(input)?;
+            }
+            "max_inline_keys" => {
+                self.consume_max_inline_keys(input)?;
+            }
+            "runtime_prune" => {
+                self.consume_runtime_prune(input)?;
+            }
+            "pseudo_locale" => {
+                self.consume_pseudo_locale(input)?;
+            }
+            "pseudo_expansion" => {
+                self.consume_pseudo_expansion(input)?;
+            }
+            "overlay" => {
+                self.consume_overlay(input)?;
+            }
+            "namespace" => {
+                self.consume_namespace(input)?;
+            }
+            "aliases" => {
+                self.consume_aliases(input)?;
+            }
+            "key_separator" => {
+                self.consume_key_separator(input)?;
+            }
+            "no_fallback_locales" => {
+                self.consume_no_fallback_locales(input)?;
+            }
+            "namespace_default_locales" => {
+                self.consume_namespace_default_locales(input)?;
+            }
+            "required_locales" => {
+                self.consume_required_locales(input)?;
+            }
+            "min_coverage" => {
+                self.consume_min_coverage(input)?;
+            }
+            "check_args" => {
+                self.consume_check_args(input)?;
+            }
+            "check_max_len" => {
+                self.consume_check_max_len(input)?;
+            }
+            "smart_quotes" => {
+                self.consume_smart_quotes(input)?;
+            }
+            _ => {}
+        }
+
+        // Continue to consume reset of options
+        if input.parse::<Token![,]>().is_ok() {
+            self.consume_options(input)?;
+        }
+
+        Ok(())
+    }
+
+    /// Load the config from Cargo.toml. This can be overridden by subsequent options.
+    fn load_metadata(&mut self, input: syn::parse::ParseStream) -> syn::parse::Result<()> {
+        // CARGO_MANIFEST_DIR is current build directory
         if let Ok(cargo_dir) = std::env::var("CARGO_MANIFEST_DIR") {
             let current_dir = std::path::PathBuf::from(cargo_dir);
-            let cfg = I18nConfig::load(&current_dir)
-                .map_err(|_| input.error("Failed to load config from Cargo.toml for `metadata`"))?;
+            let cfg = I18nConfig::load(&current_dir).map_err(|err| {
+                input.error(format!(
+                    "Failed to load config from Cargo.toml for `metadata`, {}",
+                    err
+                ))
+            })?;
 
+            let minify_key_thresh = cfg.effective_minify_key_thresh();
             self.locales_path = cfg.load_path;
             self.default_locale = Some(cfg.default_locale.clone());
             if !cfg.fallback.is_empty() {
@@ -136,8 +705,30 @@ impl Args {
             }
             self.minify_key = cfg.minify_key;
             self.minify_key_len = cfg.minify_key_len;
+            self.minify_key_thresh = minify_key_thresh;
             self.minify_key_prefix = cfg.minify_key_prefix;
-            self.minify_key_thresh = cfg.minify_key_thresh;
+            self.on_missing = cfg.on_missing;
+            self.forbid_empty = cfg.forbid_empty;
+            self.format_numbers = cfg.format_numbers;
+            self.bidi_isolate = cfg.bidi_isolate;
+            self.static_table = cfg.static_table;
+            self.packed = cfg.packed;
+            self.chunk_size = cfg.chunk_size;
+            self.max_inline_keys = cfg.max_inline_keys;
+            self.runtime_prune = cfg.runtime_prune;
+            self.pseudo_locale = cfg.pseudo_locale;
+            self.pseudo_expansion = cfg.pseudo_expansion;
+            self.aliases = cfg.aliases.into_iter().collect();
+            self.key_separator = cfg.key_separator;
+            self.no_fallback_locales = cfg.no_fallback_locales;
+            self.namespace_default_locales = cfg.namespace_default_locales.into_iter().collect();
+            self.required_locales = cfg.required_locales;
+            self.min_coverage = cfg.min_coverage;
+            self.check_args = cfg.check_args;
+            self.check_max_len = cfg.check_max_len;
+            self.smart_quotes = cfg.smart_quotes;
+            self.interpolation = parse_interpolation(&cfg.interpolation)
+                .map_err(|e| input.error(format!("{} in Cargo.toml", e)))?;
         } else if rust_i18n_support::is_debug() {
             return Err(input.error("The CARGO_MANIFEST_DIR is required fo `metadata`"));
         }
@@ -178,19 +769,48 @@ impl syn::parse::Parse for Args {
         // The config from metadata is lower priority
 
         let mut result = Self {
-            locales_path: String::from("locales"),
+            locales_path: vec![String::from("locales")],
             default_locale: None,
             fallback: None,
+            include: None,
             extend: None,
+            transform: None,
             minify_key: DEFAULT_MINIFY_KEY,
             minify_key_len: DEFAULT_MINIFY_KEY_LEN,
             minify_key_prefix: DEFAULT_MINIFY_KEY_PREFIX.to_owned(),
             minify_key_thresh: DEFAULT_MINIFY_KEY_THRESH,
+            on_missing: OnMissing::default(),
+            interpolation: (String::from("%{"), String::from("}")),
+            static_only: None,
+            forbid_empty: false,
+            format_numbers: false,
+            generate_keys: false,
+            bidi_isolate: false,
+            static_table: false,
+            packed: false,
+            chunk_size: I18nConfig::default().chunk_size,
+            max_inline_keys: I18nConfig::default().max_inline_keys,
+            runtime_prune: false,
+            pseudo_locale: false,
+            pseudo_expansion: rust_i18n_support::DEFAULT_PSEUDO_EXPANSION,
+            overlay: std::env::var("RUST_I18N_OVERLAY")
+                .ok()
+                .filter(|overlay| !overlay.is_empty()),
+            namespace: None,
+            aliases: Vec::new(),
+            key_separator: DEFAULT_KEY_SEPARATOR.to_owned(),
+            no_fallback_locales: Vec::new(),
+            namespace_default_locales: Vec::new(),
+            required_locales: Vec::new(),
+            min_coverage: BTreeMap::new(),
+            check_args: false,
+            check_max_len: false,
+            smart_quotes: false,
         };
 
         result.load_metadata(input)?;
 
-        if lookahead.peek(LitStr) {
+        if lookahead.peek(LitStr) || lookahead.peek(syn::token::Bracket) {
             result.consume_path(input)?;
 
             if input.parse::<Token![,]>().is_ok() {
@@ -208,10 +828,29 @@ impl syn::parse::Parse for Args {
 ///
 /// This will load all translations by glob `**/*.yml` from the given path, default: `${CARGO_MANIFEST_DIR}/locales`.
 ///
+/// A list of paths is also supported, they are merged and the later paths override the earlier on key conflict,
+/// e.g. `i18n!(["vendor-locales", "locales"])`.
+///
+/// The path can be a glob pattern of the directories with `*`, `**` and `{a,b}`, e.g. `i18n!("locales/**/active")`
+/// or `i18n!("{locales,shared-locales}")`, the matched directories are merged in the order of their paths.
+///
+/// The environment variables in the path are replaced at compile time, e.g. `i18n!("${OUT_DIR}/locales")` for the
+/// locale files generated by `build.rs`, it's a compile error if a variable is not set.
+///
 /// # Attributes
 ///
+/// - `default_locale` for set the default locale, default: the `default-locale` of [package.metadata.i18n] or `"en"`.
 /// - `fallback` for set the fallback locale, if present [`t!`](macro.t.html) macro will use it as the fallback locale.
+///   * Both `default_locale` and `fallback` can be read from an environment variable at compile time by `env("NAME")`,
+///     e.g. `default_locale = env("APP_DEFAULT_LOCALE")`, the `fallback` variable is comma separated, e.g. `de,en`.
+///   * The config value is used if the variable is unset or empty, it's a compile error if there is no config value.
+/// - `include` for only include the given locales into the binary, e.g. `["en", "fr"]` or `"en, fr"`.
+///   * The `fallback` locales are always included, the excluded locales are translated by the fallback at runtime.
 /// - `backend` for set the backend, if present [`t!`](macro.t.html) macro will use it as the backend.
+/// - `transform` for a function to transform the translations when the backend is initialized, e.g. to normalize `...` to `…`.
+///   * It's called as `transform(locale, key, value)` and returns the new value as `String` or `Cow<str>`, the key is without the `namespace`.
+///   * The value is transformed before the interpolation, so the placeholders are kept in it, and the arguments are not transformed.
+///   * The translations inlined by `static_only`, the hot reloaded translations, and the compile-time checks are not transformed.
 /// - `metadata` to enable/disable loading of the [package.metadata.i18n] config from Cargo.toml, default: `true`.
 /// - `minify_key` for enable/disable minify key, default: [`DEFAULT_MINIFY_KEY`](constant.DEFAULT_MINIFY_KEY.html).
 /// - `minify_key_len` for set the minify key length, default: [`DEFAULT_MINIFY_KEY_LEN`](constant.DEFAULT_MINIFY_KEY_LEN.html),
@@ -219,6 +858,68 @@ impl syn::parse::Parse for Args {
 /// - `minify_key_prefix` for set the minify key prefix, default: [`DEFAULT_MINIFY_KEY_PREFIX`](constant.DEFAULT_MINIFY_KEY_PREFIX.html).
 /// - `minify_key_thresh` for set the minify key threshold, default: [`DEFAULT_MINIFY_KEY_THRESH`](constant.DEFAULT_MINIFY_KEY_THRESH.html).
 ///   * If the length of the value is less than or equal to this value, the value will not be minified.
+/// - `on_missing` for set the text to display for a missing translation, default: `"key"`.
+///   * `"key"` displays the key (or the message) as is.
+///   * `"humanize"` displays the humanized last segment of the key, e.g. `settings.email_frequency` => `Email frequency`.
+/// - `static_only` for only include the given locale, and inline its translations into each [`t!`](macro.t.html) call with a literal key.
+///   * The backend is only used for the non-literal keys, or if the `locale` argument is present.
+/// - `forbid_empty` to fail the build if any translation of the non-default locales is empty or whitespace-only, default: `false`.
+/// - `format_numbers` to format the numeric arguments with the separators of the locale, e.g. `1234` => `1,234` in `en`, `1.234` in `de`, default: `false`.
+/// - `generate_keys` to generate a `keys` module with a constant for each key of the default locale, default: `false`.
+///   * The constant name is the key in upper case with the non-alphanumeric chars replaced by `_`, e.g. `auth.login-title` => `keys::AUTH_LOGIN_TITLE`.
+///   * Use it as `t!(keys::AUTH_LOGIN_TITLE)` to get a compile error if the key is renamed or removed.
+/// - `bidi_isolate` to wrap the interpolated values in the Unicode bidi isolates (`U+2068` and `U+2069`) for all locales, default: `false`.
+///   * The values are always wrapped for the RTL locales, e.g. `ar`, `he`, `fa` and `ur`.
+/// - `static_table` to generate the sorted translation tables of all locales for [`t_static!`](macro.t_static.html), default: `false`.
+///   * The lookup is a binary search without allocation and hashing, but the backend, fallback and aliases are bypassed.
+/// - `packed` to store the keys and values of each locale in one string with an offset index, instead of a `HashMap` built at runtime, default: `false`.
+///   * It's smaller and nothing is copied to the heap, the lookup is a binary search, see [`PackedBackend`](https://docs.rs/rust-i18n/latest/rust_i18n/struct.PackedBackend.html).
+///   * The hot reloading and `transform` are not supported.
+/// - `chunk_size` to split the translations of a locale into the chunks of this many keys, default: `4096`, `0` to never split.
+///   * Each chunk is a separate constant, so the locales with tens of thousands of keys build faster, the lookup is the same.
+/// - `max_inline_keys` to inline at most this many keys of each locale, default: `0` to inline all.
+///   * The other keys are packed into one string literal that is unpacked when the backend is initialized,
+///     so the huge locales build faster, but the unpacking costs a bit more at the startup. `transform` is not supported.
+/// - `runtime_prune` to keep the translations as the static texts, so the unused locales can be freed at runtime by [`prune_locales!`](macro.prune_locales.html), default: `false`.
+///   * A pruned locale is loaded again from the static texts when it's looked up, so switching to it still works.
+///   * The hot reloading, `packed` and `transform` are not supported.
+/// - `pseudo_locale` to generate the pseudo-locale `en-XA` from the default locale at runtime, default: `false`.
+///   * The texts are accented and padded, e.g. `[!! Ṁéśśàĝé !]`, the placeholders are kept, e.g. `%{name}`.
+///   * Use [`set_locale("en-XA")`](fn.set_locale.html) to find the hard-coded and truncated texts in the UI.
+/// - `pseudo_expansion` for the percentage of the length that the pseudo-localized texts are padded by, default: `30`.
+/// - `overlay` for merge the per-environment overlay `{path}/overlays/{overlay}` on top of the translations, default: the `RUST_I18N_OVERLAY` environment variable.
+///   * The overlay values override the base values key-by-key, e.g. `i18n!("locales", overlay = "staging")`.
+/// - `namespace` for prefix all keys of the crate in the backend with a segment, e.g. `"billing"` for `billing.invoice.title`.
+///   * The [`t!`](macro.t.html) keys are still written without the namespace, they are prefixed on lookup, so the crates
+///     that share a backend (e.g. by `extend` or [`set_backend_for_tests`]) don't clobber each other's keys.
+/// - `aliases` for map the locales to the preferred locales before lookup, e.g. `["zh=zh-CN", "no=nb"]`.
+///   * The alias is matched case-insensitively and replaced in [`set_locale`](fn.set_locale.html) and [`t!`](macro.t.html),
+///     then the locale is looked up as usual, falling back to the less specific locales and the `fallback` locales.
+/// - `key_separator` for set the separator of the nested keys, default: `"."`, e.g. `"/"` for `auth/login/title`.
+///   * Mixing separators within one project is unsupported, the same `key-separator` must be configured for `cargo i18n`.
+/// - `no_fallback_locales` for the strict locales that never fall back to other locales, e.g. `["de", "fr"]`.
+///   * A missing translation of these locales is reported as missing instead of showing the text of the `fallback` locales.
+/// - `namespace_default_locales` for the default locales of the key namespaces, e.g. `["vendor.payments=fr"]` for the keys written in French.
+///   * A missing key of the namespace falls back to its default locale before the `fallback` locales, the longest matching namespace wins.
+///   * The namespace matches the whole segments of the keys as written in [`t!`](macro.t.html), without the `namespace` of the crate.
+/// - `required_locales` for the locales that must have all keys of the default locale, e.g. `["fr"]`, default: `[]`.
+///   * The build fails with the missing keys of each locale, the other locales can be partial.
+/// - `min_coverage` for the minimum percentage of the keys of the default locale that the locales must have, e.g. `90`, default: none.
+///   * Or a percentage of each locale, e.g. `["fr=90", "*=80"]`, the `*` is for all locales that are not listed.
+///   * The build fails with the coverage of each locale that is lower, a gate between no check and `required_locales`.
+/// - `check_args` to check the arguments of [`t!`](macro.t.html) against the placeholders of the default locale at compile time, default: `false`.
+///   * It's a compile error if an argument is not used by the translation, or a placeholder has no argument, e.g. `t!("hello")` for `Hello, %{name}!`.
+///   * Only the literal keys that are found in the default locale are checked, the dynamic keys are skipped.
+/// - `check_max_len` to check the translations against the `max_len` of their keys at compile time, default: `false`.
+///   * The keys are marked by `_meta: { max_len: 12 }` in the files, the build fails with the translations that are longer.
+/// - `smart_quotes` to replace the straight quotes in the translations with the typographic quotes of each locale, default: `false`.
+///   * E.g. `"Hello"` => `“Hello”` in `en`, `«Hello»` in `fr` and `„Hello“` in `de`, the `'` between two letters is an apostrophe `’`.
+///   * The placeholders, the escaped placeholders and the quotes escaped by `\` are kept, the locales without known quotes are not changed.
+/// - `interpolation` for set the delimiters of the variables in the translations, default: `"%{ }"`.
+///   * The open and close delimiters are separated by a space, e.g. `"{{ }}"` for `{{name}}`.
+///
+/// With the `serde` feature of `rust-i18n`, a `LocalizedKey(String)` type is also generated next to the `i18n!`,
+/// it's serialized as the translation of the key in the current locale.
 ///
 /// ```no_run
 /// # use rust_i18n::i18n;
@@ -244,18 +945,244 @@ impl syn::parse::Parse for Args {
 /// # fn v6() {
 /// i18n!();
 /// # }
+/// # fn v7() {
+/// i18n!("locales", on_missing = "humanize");
+/// # }
+/// # fn v8() {
+/// i18n!("locales", static_only = "en");
+/// # }
+/// # fn v10() {
+/// i18n!("locales", interpolation = "{{ }}");
+/// # }
+/// # fn v11() {
+/// i18n!("locales", include = ["en", "fr"], fallback = "en");
+/// # }
+/// # fn v12() {
+/// i18n!("locales", generate_keys = true);
+/// # }
+/// # fn v9() {
+/// i18n!(["vendor-locales", "locales"], fallback = "en");
+/// # }
+/// # fn v13() {
+/// i18n!("locales", aliases = ["zh=zh-CN", "no=nb"]);
+/// # }
+/// # fn v14() {
+/// i18n!("locales", key_separator = "/");
+/// # }
+/// # fn v15() {
+/// i18n!("locales", fallback = "en", no_fallback_locales = ["de", "fr"]);
+/// # }
+/// # fn v16() {
+/// i18n!("locales", static_table = true);
+/// # }
+/// # fn v17() {
+/// i18n!("locales", required_locales = ["fr", "de"]);
+/// # }
+/// # fn v18() {
+/// i18n!("locales", namespace = "billing");
+/// # }
+/// # fn v19() {
+/// i18n!("locales", default_locale = env("APP_DEFAULT_LOCALE"));
+/// # }
+/// # fn v20() {
+/// i18n!("locales", check_args = true);
+/// # }
+/// # fn v21() {
+/// i18n!("locales", check_max_len = true);
+/// # }
+/// # fn v22() {
+/// # fn normalize(_: &str, _: &str, value: &str) -> String { value.replace("...", "…") }
+/// i18n!("locales", transform = normalize);
+/// # }
+/// # fn v23() {
+/// i18n!("locales", packed = true);
+/// # }
+/// # fn v24() {
+/// i18n!("locales", chunk_size = 1000);
+/// # }
+/// # fn v26() {
+/// i18n!("locales", pseudo_locale = true, pseudo_expansion = 40);
+/// # }
+/// # fn v25() {
+/// i18n!("locales", fallback = "en", namespace_default_locales = ["vendor.payments=fr"]);
+/// # }
+/// # fn v27() {
+/// i18n!("locales", smart_quotes = true);
+/// # }
+/// # fn v28() {
+/// i18n!("locales", min_coverage = ["fr=90", "*=80"]);
+/// # }
+/// # fn v29() {
+/// i18n!("locales", max_inline_keys = 2000);
+/// # }
+/// # fn v30() {
+/// i18n!("locales", runtime_prune = true);
+/// # }
 /// ```
 #[proc_macro]
 pub fn i18n(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let args = parse_macro_input!(input as Args);
+    let mut args = parse_macro_input!(input as Args);
 
     // CARGO_MANIFEST_DIR is current build directory
     let cargo_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is empty");
     let current_dir = std::path::PathBuf::from(cargo_dir);
-    let locales_path = current_dir.join(&args.locales_path);
-
-    let data = load_locales(&locales_path.display().to_string(), |_| false);
-    let code = generate_code(data, args);
+    // The glob patterns are expanded to the matched directories, e.g. `{locales,shared-locales}`
+    let mut locales_paths = vec![];
+    for path in &args.locales_path {
+        let path = current_dir.join(path).display().to_string();
+        let paths = expand_load_path(&path);
+        if paths.is_empty() {
+            let msg = format!("The locales path `{}` matches no directory", path);
+            return quote! { compile_error!(#msg); }.into();
+        }
+        locales_paths.extend(paths);
+    }
+    // The overlays override the base translations, e.g. `locales/overlays/staging`
+    if let Some(overlay) = &args.overlay {
+        let overlay_paths = locales_paths
+            .iter()
+            .map(|path| {
+                std::path::Path::new(path)
+                    .join(OVERLAYS_DIR)
+                    .join(overlay)
+                    .display()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        locales_paths.extend(overlay_paths);
+    }
+    let mut data = match try_load_locales_from_paths_with_separator(
+        &locales_paths,
+        |_| false,
+        &args.key_separator,
+    ) {
+        Ok(data) => data,
+        Err(errors) => {
+            let errors = errors.iter().map(|err| {
+                let msg = err.to_string();
+                quote! { compile_error!(#msg); }
+            });
+            return quote! { #(#errors)* }.into();
+        }
+    };
+    if let Some(include) = &args.include {
+        // The fallback locales are kept to translate the excluded locales
+        let fallback = args.fallback.as_deref().unwrap_or_default();
+        data.retain(|locale, _| include.contains(locale) || fallback.contains(locale));
+    }
+    if args.packed && args.transform.is_some() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`transform` is not supported with `packed`",
+        )
+        .to_compile_error()
+        .into();
+    }
+    if args.max_inline_keys > 0 && args.transform.is_some() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`transform` is not supported with `max_inline_keys`",
+        )
+        .to_compile_error()
+        .into();
+    }
+    if args.runtime_prune && (args.packed || args.transform.is_some()) {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`packed` and `transform` are not supported with `runtime_prune`",
+        )
+        .to_compile_error()
+        .into();
+    }
+    if args.forbid_empty {
+        let default_locale = args.default_locale.as_deref().unwrap_or("en");
+        let empty = find_empty_translations(&data, default_locale);
+        if !empty.is_empty() {
+            let keys = empty
+                .iter()
+                .map(|(locale, key)| format!("{}.{}", locale, key))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let msg = format!(
+                "Found {} empty translations, they are forbidden by `forbid_empty`: {}",
+                empty.len(),
+                keys
+            );
+            return syn::Error::new(proc_macro2::Span::call_site(), msg)
+                .to_compile_error()
+                .into();
+        }
+    }
+    if !args.required_locales.is_empty() {
+        let default_locale = args.default_locale.as_deref().unwrap_or("en");
+        let missing = find_missing_translations(&data, default_locale, &args.required_locales);
+        if !missing.is_empty() {
+            let errors = missing.iter().map(|(locale, keys)| {
+                let msg = format!(
+                    "Found {} missing translations of `{}`, it's required by `required_locales`: {}",
+                    keys.len(),
+                    locale,
+                    keys.join(", ")
+                );
+                syn::Error::new(proc_macro2::Span::call_site(), msg).to_compile_error()
+            });
+            return quote! { #(#errors)* }.into();
+        }
+    }
+    if !args.min_coverage.is_empty() {
+        let default_locale = args.default_locale.as_deref().unwrap_or("en");
+        let low = find_low_coverage_translations(&data, default_locale, &args.min_coverage);
+        if !low.is_empty() {
+            let errors = low.iter().map(|(locale, translated, total, min)| {
+                let msg = format!(
+                    "The translations of `{}` cover {:.1}% of the keys ({}/{}), it's below the `min_coverage` of {}%",
+                    locale,
+                    *translated as f64 * 100.0 / *total as f64,
+                    translated,
+                    total,
+                    min
+                );
+                syn::Error::new(proc_macro2::Span::call_site(), msg).to_compile_error()
+            });
+            return quote! { #(#errors)* }.into();
+        }
+    }
+    if args.check_max_len {
+        let mut max_len_keys = HashMap::new();
+        for path in &locales_paths {
+            match load_max_len_keys_with_separator(path, |_| false, &args.key_separator) {
+                Ok(keys) => max_len_keys.extend(keys),
+                Err(err) => {
+                    let msg = err.to_string();
+                    return quote! { compile_error!(#msg); }.into();
+                }
+            }
+        }
+        let too_long = find_too_long_translations(&data, &max_len_keys, &args.key_separator);
+        if !too_long.is_empty() {
+            let errors = too_long.iter().map(|(locale, key, len, max_len)| {
+                let msg = format!(
+                    "The translation of `{}` in `{}` is longer than the `max_len` of the key: {} > {}",
+                    key, locale, len, max_len
+                );
+                syn::Error::new(proc_macro2::Span::call_site(), msg).to_compile_error()
+            });
+            return quote! { #(#errors)* }.into();
+        }
+    }
+    if args.smart_quotes {
+        let (open, close) = &args.interpolation;
+        for (locale, trs) in data.iter_mut() {
+            for value in trs.values_mut() {
+                *value = rust_i18n_support::smart_quotes(value, locale, open, close);
+            }
+        }
+    }
+    if let Some(static_only) = &args.static_only {
+        data.retain(|locale, _| locale == static_only);
+        args.default_locale = Some(static_only.clone());
+    }
+    let code = generate_code(data, args, &locales_paths);
 
     if is_debug() {
         println!(
@@ -267,35 +1194,307 @@ pub fn i18n(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     code.into()
 }
 
+/// Convert the key into a constant name, e.g. `auth.login-title` => `AUTH_LOGIN_TITLE`.
+fn key_to_const_name(key: &str) -> String {
+    let name = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+/// Generate the `keys` module with a constant for each key of the default locale.
+///
+/// The keys that have the same constant name are skipped except the first one in order.
+fn generate_keys(trs: Option<&HashMap<String, String>>) -> proc_macro2::TokenStream {
+    let mut keys = trs
+        .map(|trs| trs.iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+    keys.sort();
+
+    let mut names = std::collections::HashSet::new();
+    let consts = keys
+        .into_iter()
+        .filter(|(key, _)| *key != "_version")
+        .filter_map(|(key, value)| {
+            let name = key_to_const_name(key);
+            if name.is_empty() || name == "_" || !names.insert(name.clone()) {
+                return None;
+            }
+            let ident = Ident::new(&name, proc_macro2::Span::call_site());
+            let doc = format!("`{}`: {}", key, value);
+            Some(quote! {
+                #[doc = #doc]
+                pub const #ident: &str = #key;
+            })
+        });
+
+    quote! {
+        /// The translation keys of the default locale, generated by `generate_keys`.
+        #[allow(missing_docs, dead_code)]
+        pub mod keys {
+            #(#consts)*
+        }
+    }
+}
+
+/// Generate the `LocalizedKey` type that is serialized as the translation in the current locale.
+fn generate_localized_key() -> proc_macro2::TokenStream {
+    quote! {
+        /// A translation key that is serialized as its translation in the current locale of `rust_i18n::locale()`.
+        #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+        #[allow(missing_docs)]
+        pub struct LocalizedKey(pub rust_i18n::__private::String);
+
+        impl From<&str> for LocalizedKey {
+            fn from(key: &str) -> Self {
+                Self(key.into())
+            }
+        }
+
+        impl From<rust_i18n::__private::String> for LocalizedKey {
+            fn from(key: rust_i18n::__private::String) -> Self {
+                Self(key)
+            }
+        }
+
+        impl rust_i18n::__private::serde::Serialize for LocalizedKey {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: rust_i18n::__private::serde::Serializer,
+            {
+                serializer.serialize_str(&_rust_i18n_translate(&rust_i18n::locale(), &self.0))
+            }
+        }
+    }
+}
+
+/// Generate the translation tables of all locales sorted by the locale and key, and the `_rust_i18n_t_static` lookup.
+fn generate_static_table(
+    translations: &HashMap<String, HashMap<String, String>>,
+) -> proc_macro2::TokenStream {
+    let mut locales = translations.iter().collect::<Vec<_>>();
+    locales.sort_by_key(|(locale, _)| *locale);
+
+    let tables = locales.into_iter().map(|(locale, trs)| {
+        let mut trs = trs.iter().collect::<Vec<_>>();
+        trs.sort();
+        let (keys, values): (Vec<_>, Vec<_>) = trs.into_iter().unzip();
+        quote! {
+            (#locale, &[#((#keys, #values)),*])
+        }
+    });
+
+    quote! {
+        const _RUST_I18N_STATIC_TABLE: &[(&str, &[(&str, &str)])] = &[#(#tables),*];
+
+        /// Get I18n text from the static table by the exact locale and key, without allocation and hashing
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub const fn _rust_i18n_t_static(locale: &str, key: &str) -> Option<&'static str> {
+            match rust_i18n::lookup_static_table(_RUST_I18N_STATIC_TABLE, locale) {
+                Some(trs) => rust_i18n::lookup_static_table(trs, key),
+                None => None,
+            }
+        }
+    }
+}
+
+/// Generate the packed translations of all locales for the `packed` option, see `PackedLocale`.
+fn generate_packed(
+    translations: &HashMap<String, HashMap<String, String>>,
+    args: &Args,
+) -> proc_macro2::TokenStream {
+    let mut locales = translations.iter().collect::<Vec<_>>();
+    locales.sort_by_key(|(locale, _)| *locale);
+
+    let locales = locales.into_iter().map(|(locale, trs)| {
+        let mut trs = trs
+            .iter()
+            .map(|(key, value)| match &args.namespace {
+                Some(namespace) => (format!("{}{}{}", namespace, args.key_separator, key), value),
+                None => (key.clone(), value),
+            })
+            .collect::<Vec<_>>();
+        // The keys are sorted by bytes, the same as the binary search of `str`
+        trs.sort();
+
+        let mut blob = String::new();
+        let mut index = vec![];
+        let offset = |blob: &String| {
+            u32::try_from(blob.len()).expect("The translations of a locale must be less than 4 GiB")
+        };
+        for (key, value) in trs {
+            let key_start = offset(&blob);
+            blob.push_str(&key);
+            let value_start = offset(&blob);
+            blob.push_str(value);
+            index.push(quote! { (#key_start, #value_start) });
+        }
+        quote! {
+            rust_i18n::PackedLocale::new(#locale, #blob, &[#(#index),*])
+        }
+    });
+
+    quote! {
+        static _RUST_I18N_PACKED: &[rust_i18n::PackedLocale] = &[#(#locales),*];
+    }
+}
+
 fn generate_code(
     translations: HashMap<String, HashMap<String, String>>,
     args: Args,
+    locales_paths: &[String],
 ) -> proc_macro2::TokenStream {
+    let keys_code = if args.generate_keys {
+        let default_locale = args.default_locale.as_deref().unwrap_or("en");
+        generate_keys(translations.get(default_locale))
+    } else {
+        quote! {}
+    };
+    let static_table_code = if args.static_table {
+        generate_static_table(&translations)
+    } else {
+        quote! {}
+    };
+    let localized_key_code = if cfg!(feature = "serde") {
+        generate_localized_key()
+    } else {
+        quote! {}
+    };
+    let packed_code = if args.packed {
+        generate_packed(&translations, &args)
+    } else {
+        quote! {}
+    };
     let mut all_translations = Vec::<proc_macro2::TokenStream>::new();
+    // The chunks of the large locales, e.g. `const _RUST_I18N_TRS_0_1: &[(&str, &str)] = &[...];`
+    let mut chunk_consts = Vec::<proc_macro2::TokenStream>::new();
+    // The arms of `_rust_i18n_load_locale` for `runtime_prune`, each loads the static translations of a locale
+    let mut load_locale_arms = Vec::<proc_macro2::TokenStream>::new();
 
-    translations.iter().for_each(|(locale, trs)| {
-        let mut sub_trs = Vec::<proc_macro2::TokenStream>::new();
+    translations
+        .iter()
+        .enumerate()
+        .for_each(|(n, (locale, trs))| {
+            let mut sub_trs = Vec::<proc_macro2::TokenStream>::new();
+            // The iterators of the `(key, value)` of the locale
+            let mut iters = Vec::<proc_macro2::TokenStream>::new();
 
-        trs.iter().for_each(|(k, v)| {
-            let k = k.to_string();
-            let v = v.to_string();
-            sub_trs.push(quote! {
-                (#k, #v)
+            // The keys beyond `max_inline_keys` are packed into one string instead of a tuple each
+            let mut trs = trs.iter().collect::<Vec<_>>();
+            let max_inline_keys = args.max_inline_keys;
+            if max_inline_keys > 0 && trs.len() > max_inline_keys {
+                trs.sort();
+                let overflow =
+                    trs.split_off(max_inline_keys).into_iter().map(|(key, v)| {
+                        match &args.namespace {
+                            Some(namespace) => {
+                                (format!("{}{}{}", namespace, args.key_separator, key), v)
+                            }
+                            None => (key.to_string(), v),
+                        }
+                    });
+                let overflow = overflow.collect::<Vec<_>>();
+                let blob = rust_i18n_support::pack_translations(
+                    overflow.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+                );
+                let name = Ident::new(
+                    &format!("_RUST_I18N_OVERFLOW_{}", n),
+                    proc_macro2::Span::call_site(),
+                );
+                chunk_consts.push(quote! {
+                    static #name: &str = #blob;
+                });
+                iters.push(quote! { rust_i18n::__private::unpack_translations(#name) });
+            }
+
+            trs.into_iter().for_each(|(key, v)| {
+                let k = match &args.namespace {
+                    Some(namespace) => format!("{}{}{}", namespace, args.key_separator, key),
+                    None => key.to_string(),
+                };
+                let v = v.to_string();
+                match &args.transform {
+                    Some(transform) => {
+                        sub_trs.push(quote! {
+                            (#k, #transform(#locale, #key, #v))
+                        });
+                    }
+                    None => sub_trs.push(quote! {
+                        (#k, #v)
+                    }),
+                }
             });
-        });
 
-        all_translations.push(quote! {
-            let trs = [#(#sub_trs),*];
-            backend.add_translations(#locale, &trs.into_iter().collect());
+            let chunk_size = args.chunk_size;
+            if chunk_size > 0 && sub_trs.len() > chunk_size && args.transform.is_none() {
+                let names = sub_trs
+                    .chunks(chunk_size)
+                    .enumerate()
+                    .map(|(m, chunk)| {
+                        let name = Ident::new(
+                            &format!("_RUST_I18N_TRS_{}_{}", n, m),
+                            proc_macro2::Span::call_site(),
+                        );
+                        chunk_consts.push(quote! {
+                            const #name: &[(&str, &str)] = &[#(#chunk),*];
+                        });
+                        name
+                    })
+                    .collect::<Vec<_>>();
+                iters.push(quote! { [#(#names),*].iter().flat_map(|trs| trs.iter().copied()) });
+            } else if args.transform.is_some() {
+                iters.push(
+                    quote! { [#(#sub_trs),*].iter().map(|(k, v)| (*k, AsRef::<str>::as_ref(v))) },
+                );
+            } else {
+                iters.push(quote! { [#(#sub_trs),*].into_iter() });
+            }
+
+            if args.runtime_prune {
+                load_locale_arms.push(quote! {
+                    #locale => {
+                        let mut trs = rust_i18n::__private::Vec::new();
+                        #(trs.extend(#iters);)*
+                        Some(trs)
+                    }
+                });
+            } else {
+                all_translations.extend(iters.iter().map(|iter| {
+                    quote! {
+                        backend.add_translations(#locale, &#iter.collect());
+                    }
+                }));
+            }
         });
-    });
 
+    // The arguments are checked against the translations of the default locale
+    let check_args = if args.check_args {
+        let default_locale = args.default_locale.as_deref().unwrap_or("en");
+        quote! { , _check_args = #default_locale }
+    } else {
+        quote! {}
+    };
     let default_locale = if let Some(default_locale) = args.default_locale {
         quote! {
-            rust_i18n::set_locale(#default_locale);
+            Some(#default_locale)
         }
     } else {
-        quote! {}
+        quote! {
+            None
+        }
     };
 
     let fallback = if let Some(fallback) = args.fallback {
@@ -308,6 +1507,38 @@ fn generate_code(
         }
     };
 
+    // The backend is held as the concrete type to avoid the dynamic dispatch, unless it's extended by `backend`
+    let (backend_type, backend_deref, backend_value) = if args.extend.is_some() {
+        (
+            quote! { rust_i18n::__private::Box<dyn rust_i18n::Backend> },
+            quote! { &**_RUST_I18N_BACKEND },
+            quote! { rust_i18n::__private::Box::new(backend) },
+        )
+    } else if args.packed {
+        (
+            quote! { rust_i18n::PackedBackend },
+            quote! { &*_RUST_I18N_BACKEND },
+            quote! { backend },
+        )
+    } else if args.runtime_prune {
+        (
+            quote! { &'static rust_i18n::PrunableBackend },
+            quote! { &*_RUST_I18N_BACKEND },
+            quote! { backend },
+        )
+    } else {
+        (
+            quote! { rust_i18n::__private::CompiledBackend },
+            quote! { &*_RUST_I18N_BACKEND },
+            quote! { backend },
+        )
+    };
+    let backend_ref_type = if args.extend.is_some() {
+        quote! { dyn rust_i18n::Backend }
+    } else {
+        backend_type.clone()
+    };
+
     let extend_code = if let Some(extend) = args.extend {
         quote! {
             let backend = backend.extend(#extend);
@@ -320,30 +1551,163 @@ fn generate_code(
     let minify_key_len = args.minify_key_len;
     let minify_key_prefix = args.minify_key_prefix;
     let minify_key_thresh = args.minify_key_thresh;
+    let (interpolation_open, interpolation_close) = args.interpolation;
+    let format_numbers = args.format_numbers;
+    let bidi_isolate = args.bidi_isolate;
+    let pseudo_locale = args.pseudo_locale;
+    let pseudo_expansion = args.pseudo_expansion;
+    let key_separator = args.key_separator;
+    let namespace = args.namespace.unwrap_or_default();
+    // The translations are loaded at compile time by `_tr!` for `static_only`, `check_args` and the keys of `t_ns!`
+    let static_path = {
+        let interpolation = format!("{} {}", interpolation_open, interpolation_close);
+        quote! {
+            , _static_path = [#(#locales_paths),*], _static_interpolation = #interpolation, _static_key_separator = #key_separator
+        }
+    };
+    let static_only = if let Some(static_only) = args.static_only {
+        let format_numbers = args.format_numbers;
+        let bidi_isolate = args.bidi_isolate;
+        let smart_quotes = args.smart_quotes;
+        quote! {
+            , _static_only = #static_only, _static_format_numbers = #format_numbers, _static_bidi_isolate = #bidi_isolate, _static_smart_quotes = #smart_quotes
+        }
+    } else {
+        quote! {}
+    };
+    let (alias_names, alias_locales): (Vec<_>, Vec<_>) = args.aliases.into_iter().unzip();
+    let (namespace_names, namespace_locales): (Vec<_>, Vec<_>) =
+        args.namespace_default_locales.into_iter().unzip();
+    // The locale files are included as bytes, so the crate is rebuilt when they are changed
+    let locale_files = locales_paths
+        .iter()
+        .flat_map(|path| locale_file_paths(path))
+        .map(|file| file.display().to_string());
+    let mut deprecated_keys = vec![];
+    for path in locales_paths {
+        match load_deprecated_keys_with_separator(path, |_| false, &key_separator) {
+            Ok(keys) => deprecated_keys.extend(keys),
+            Err(err) => {
+                let msg = err.to_string();
+                return quote! { compile_error!(#msg); };
+            }
+        }
+    }
+    deprecated_keys.sort();
+    let (deprecated_keys, deprecated_messages): (Vec<_>, Vec<_>) =
+        deprecated_keys.into_iter().unzip();
+    let no_fallback_locales = args.no_fallback_locales;
+    let backend_code = if args.packed {
+        quote! {
+            let backend = rust_i18n::PackedBackend::new(_RUST_I18N_PACKED);
+        }
+    } else if args.runtime_prune {
+        // The prunable backend is not hot reloaded, the `backend` option extends the reference of its static
+        quote! {
+            let backend = &*_RUST_I18N_PRUNABLE;
+        }
+    } else {
+        quote! {
+            let mut backend = rust_i18n::SimpleBackend::new();
+            #(#all_translations)*
+            let backend = rust_i18n::__private::hot_reload(backend, &[#(#locales_paths),*], _RUST_I18N_KEY_SEPARATOR, _RUST_I18N_NAMESPACE);
+        }
+    };
+    let (prunable_code, prune_code) = if args.runtime_prune {
+        let locales = translations.keys();
+        (
+            quote! {
+                /// The backend of `runtime_prune`, its unused locales are freed by `prune_locales!`
+                static _RUST_I18N_PRUNABLE: rust_i18n::__private::Lazy<rust_i18n::PrunableBackend> = rust_i18n::__private::Lazy::new(|| {
+                    rust_i18n::PrunableBackend::new(&[#(#locales),*], _rust_i18n_load_locale)
+                });
+
+                /// Load the static translations of the locale for `runtime_prune`
+                #[doc(hidden)]
+                #[allow(missing_docs)]
+                pub fn _rust_i18n_load_locale(locale: &str) -> Option<rust_i18n::__private::Vec<(&'static str, &'static str)>> {
+                    match locale {
+                        #(#load_locale_arms)*
+                        _ => None,
+                    }
+                }
+            },
+            quote! {
+                let mut keep = _rust_i18n_fallback_chain(locale, None);
+                keep.extend(_RUST_I18N_NAMESPACE_DEFAULT_LOCALES.iter().map(|(_, locale)| rust_i18n::__private::String::from(*locale)));
+                let keep = keep.iter().map(|locale| locale.as_str()).collect::<rust_i18n::__private::Vec<_>>();
+                _RUST_I18N_PRUNABLE.prune(&keep)
+            },
+        )
+    } else {
+        (
+            quote! {},
+            quote! {
+                let _ = locale;
+                rust_i18n::__private::Vec::new()
+            },
+        )
+    };
+    let on_missing = match args.on_missing {
+        OnMissing::Key => quote! { rust_i18n::OnMissing::Key },
+        OnMissing::Humanize => quote! { rust_i18n::OnMissing::Humanize },
+    };
 
     quote! {
         use rust_i18n::{BackendExt, CowStr, MinifyKey};
-        use std::borrow::Cow;
+        use rust_i18n::__private::Cow;
+
+        #keys_code
+
+        #static_table_code
+
+        #localized_key_code
+
+        #packed_code
+
+        #(#chunk_consts)*
+
+        #prunable_code
+
+        #(const _: &[u8] = include_bytes!(#locale_files);)*
 
         /// I18n backend instance
         ///
         /// [PUBLIC] This is a public API, and as an example in examples/
+        ///
+        /// It's the concrete backend unless the `backend` option is used, import `rust_i18n::Backend` to call its methods.
         #[allow(missing_docs)]
-        static _RUST_I18N_BACKEND: rust_i18n::once_cell::sync::Lazy<Box<dyn rust_i18n::Backend>> = rust_i18n::once_cell::sync::Lazy::new(|| {
-            let mut backend = rust_i18n::SimpleBackend::new();
-            #(#all_translations)*
+        static _RUST_I18N_BACKEND: rust_i18n::__private::Lazy<#backend_type> = rust_i18n::__private::Lazy::new(|| {
+            #backend_code
             #extend_code
 
-            #default_locale
+            rust_i18n::add_locale_aliases(_RUST_I18N_ALIASES);
 
-            Box::new(backend)
+            if let Some(locale) = _RUST_I18N_DEFAULT_LOCALE {
+                rust_i18n::set_default_locale(locale);
+            }
+
+            #backend_value
         });
 
+        static _RUST_I18N_DEFAULT_LOCALE: Option<&'static str> = #default_locale;
         static _RUST_I18N_FALLBACK_LOCALE: Option<&[&'static str]> = #fallback;
         static _RUST_I18N_MINIFY_KEY: bool = #minify_key;
         static _RUST_I18N_MINIFY_KEY_LEN: usize = #minify_key_len;
         static _RUST_I18N_MINIFY_KEY_PREFIX: &str = #minify_key_prefix;
         static _RUST_I18N_MINIFY_KEY_THRESH: usize = #minify_key_thresh;
+        static _RUST_I18N_ON_MISSING: rust_i18n::OnMissing = #on_missing;
+        static _RUST_I18N_INTERPOLATION: (&str, &str) = (#interpolation_open, #interpolation_close);
+        static _RUST_I18N_FORMAT_NUMBERS: bool = #format_numbers;
+        static _RUST_I18N_BIDI_ISOLATE: bool = #bidi_isolate;
+        static _RUST_I18N_KEY_SEPARATOR: &str = #key_separator;
+        static _RUST_I18N_NAMESPACE: &str = #namespace;
+        static _RUST_I18N_ALIASES: &[(&str, &str)] = &[#((#alias_names, #alias_locales)),*];
+        static _RUST_I18N_NO_FALLBACK_LOCALES: &[&str] = &[#(#no_fallback_locales),*];
+        static _RUST_I18N_PSEUDO_LOCALE: bool = #pseudo_locale;
+        static _RUST_I18N_PSEUDO_EXPANSION: usize = #pseudo_expansion;
+        static _RUST_I18N_NAMESPACE_DEFAULT_LOCALES: &[(&str, &str)] = &[#((#namespace_names, #namespace_locales)),*];
+        static _RUST_I18N_DEPRECATED_KEYS: &[(&str, &str)] = &[#((#deprecated_keys, #deprecated_messages)),*];
 
         /// Lookup fallback locales
         ///
@@ -354,7 +1718,50 @@ fn generate_code(
         #[doc(hidden)]
         #[allow(missing_docs)]
         pub fn _rust_i18n_lookup_fallback(locale: &str) -> Option<&str> {
-            locale.rfind('-').map(|n| locale[..n].trim_end_matches("-x"))
+            rust_i18n::lookup_fallback(locale)
+        }
+
+        /// Get the backend compiled by `i18n!`, it's the concrete type unless the `backend` option is used
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_compiled_backend() -> &'static #backend_ref_type {
+            #backend_deref
+        }
+
+        /// Get the backend, it's replaced by `rust_i18n::set_backend_for_tests` in tests
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_backend() -> &'static dyn rust_i18n::Backend {
+            // Initialize the compiled backend anyway, it also sets the default locale and aliases
+            let backend: &'static dyn rust_i18n::Backend = _rust_i18n_compiled_backend();
+            rust_i18n::__private::test_backend().unwrap_or(backend)
+        }
+
+        /// Initialize the backend eagerly, a no-op if it's already initialized
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_warm_up() {
+            rust_i18n::__private::Lazy::force(&_RUST_I18N_BACKEND);
+        }
+
+        /// Free the translations of the locales that are not looked up for the locale, returns the pruned locales
+        ///
+        /// The locales of its fallback chain and the default locales of the namespaces are kept, it's a no-op without `runtime_prune`.
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_prune_locales(locale: &str) -> rust_i18n::__private::Vec<&'static str> {
+            #prune_code
+        }
+
+        /// Map the locale alias to the preferred locale, e.g. `zh` -> `zh-CN`
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_resolve_alias(locale: &str) -> &str {
+            rust_i18n::resolve_alias(_RUST_I18N_ALIASES, locale)
         }
 
         /// Get I18n text by locale and key
@@ -363,50 +1770,518 @@ fn generate_code(
         #[doc(hidden)]
         pub fn _rust_i18n_translate<'r>(locale: &str, key: &'r str) -> Cow<'r, str> {
             _rust_i18n_try_translate(locale, key).unwrap_or_else(|| {
-                if locale.is_empty() {
+                rust_i18n::__private::report_missing(locale, key);
+                if _RUST_I18N_ON_MISSING != rust_i18n::OnMissing::Key {
+                    _rust_i18n_missing(key.into())
+                } else if locale.is_empty() {
                     key.into()
                 } else {
-                    format!("{}.{}", locale, key).into()
+                    rust_i18n::__private::format!("{}.{}", locale, key).into()
                 }
             })
         }
 
+        /// Get the key of a list item by index, e.g. `tips` -> `tips.1` with the default key separator
+        ///
+        /// The index wraps around the length of the list, returns the key as is if it's not a list.
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_select_key(locale: &str, key: impl AsRef<str>, index: Option<usize>) -> rust_i18n::__private::String {
+            let key = key.as_ref();
+            if let Some(index) = index {
+                let len = (0..)
+                    // The wildcard keys are not list items, or the list would be endless
+                    .take_while(|i| _rust_i18n_lookup(locale, &rust_i18n::__private::format!("{}{}{}", key, _RUST_I18N_KEY_SEPARATOR, i), false).is_some())
+                    .count();
+                if len > 0 {
+                    return rust_i18n::__private::format!("{}{}{}", key, _RUST_I18N_KEY_SEPARATOR, index % len);
+                }
+            }
+            rust_i18n::__private::String::from(key)
+        }
+
+        /// Get the key of the variant, e.g. `cta.title` -> `cta.title#variant_b` for `variant_b`
+        ///
+        /// The variant of a list or plural forms is translated if its first item or `other` form is, e.g. `tips#variant_b.0`.
+        /// Falls back to the key as is if the variant is not translated in the locale and its fallback locales.
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_select_variant_key(locale: &str, key: impl AsRef<str>, variant: impl AsRef<str>) -> rust_i18n::__private::String {
+            let (key, variant) = (key.as_ref(), variant.as_ref());
+            if !variant.is_empty() {
+                let variant_key = rust_i18n::__private::format!("{}#{}", key, variant);
+                let translated = _rust_i18n_lookup(locale, &variant_key, false).is_some()
+                    || ["0", rust_i18n::PluralCategory::Other.as_str()].iter().any(|item| {
+                        _rust_i18n_lookup(locale, &rust_i18n::__private::format!("{}{}{}", variant_key, _RUST_I18N_KEY_SEPARATOR, item), false).is_some()
+                    });
+                if translated {
+                    return variant_key;
+                }
+            }
+            rust_i18n::__private::String::from(key)
+        }
+
+        /// Get the key of the ordinal plural form of the number, e.g. `place` -> `place.two` for `2` in `en`
+        ///
+        /// Falls back to the `other` form, returns the key as is if there is no plural form.
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_select_ordinal_key(locale: &str, key: impl AsRef<str>, n: u64) -> rust_i18n::__private::String {
+            let key = key.as_ref();
+            let category = rust_i18n::ordinal_category(_rust_i18n_resolve_alias(locale), n);
+            [category, rust_i18n::PluralCategory::Other]
+                .iter()
+                .map(|category| rust_i18n::__private::format!("{}{}{}", key, _RUST_I18N_KEY_SEPARATOR, category.as_str()))
+                // The wildcard keys are not plural forms
+                .find(|plural_key| _rust_i18n_lookup(locale, plural_key, false).is_some())
+                .unwrap_or_else(|| rust_i18n::__private::String::from(key))
+        }
+
+        /// Get the key of the cardinal plural form of the `count`, e.g. `files` -> `files.one` for `1` in `en`
+        ///
+        /// Falls back to the `other` form, returns the key as is if there is no plural form or the `count` is not a number,
+        /// so a flat translation with `%{count}` works too.
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_select_plural_key(locale: &str, key: impl AsRef<str>, count: &str) -> rust_i18n::__private::String {
+            let key = key.as_ref();
+            // Check the integers without `f64::fract`, which requires std
+            let category = match count.trim().parse::<f64>() {
+                Ok(n) if n >= 0.0 && n == (n as u64) as f64 => rust_i18n::cardinal_category(_rust_i18n_resolve_alias(locale), n as u64),
+                Ok(_) => rust_i18n::PluralCategory::Other,
+                Err(_) => return rust_i18n::__private::String::from(key),
+            };
+            [category, rust_i18n::PluralCategory::Other]
+                .iter()
+                .map(|category| rust_i18n::__private::format!("{}{}{}", key, _RUST_I18N_KEY_SEPARATOR, category.as_str()))
+                // The wildcard keys are not plural forms
+                .find(|plural_key| _rust_i18n_lookup(locale, plural_key, false).is_some())
+                .unwrap_or_else(|| rust_i18n::__private::String::from(key))
+        }
+
+        /// Unescape the escaped open delimiter in the text without variables, e.g. `%%{` => `%{`
+        ///
+        /// The patterns with a default are replaced by their defaults, e.g. `%{name:guest}` => `guest`.
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_unescape<'r>(input: Cow<'r, str>) -> Cow<'r, str> {
+            let (open, close) = _RUST_I18N_INTERPOLATION;
+            if input.contains(open) && input.contains(':') {
+                return Cow::Owned(rust_i18n::replace_patterns(&input, open, close, &[], &[], None));
+            }
+            rust_i18n::unescape_patterns(input, open)
+        }
+
+        /// Translate the values of the key placeholders in the text as the keys in the locale, e.g. `status.active` of `%{@status}`
+        ///
+        /// Returns the placeholders with the `@` and their translated values, `None` if the text has no key placeholder.
+        /// The translated values are replaced in a single pass, their `%{...}` are never expanded again,
+        /// so a key that refers to itself can't recurse or grow without bound.
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_key_values(locale: &str, input: &str, patterns: &[&str], values: &[rust_i18n::__private::String]) -> Option<(rust_i18n::__private::Vec<rust_i18n::__private::String>, rust_i18n::__private::Vec<rust_i18n::__private::String>)> {
+            let (open, close) = _RUST_I18N_INTERPOLATION;
+            if !input.contains(&[open, "@"].concat()) {
+                return None;
+            }
+            let key_values = patterns
+                .iter()
+                .zip(values)
+                .filter(|(pattern, _)| {
+                    input.contains(&[open, "@", pattern, close].concat()) || input.contains(&[open, "@", pattern, ":"].concat())
+                })
+                .map(|(pattern, value)| {
+                    let translated = match _RUST_I18N_MINIFY_KEY {
+                        true => _rust_i18n_try_translate(locale, rust_i18n::MinifyKey::minify_key(value, _RUST_I18N_MINIFY_KEY_LEN, _RUST_I18N_MINIFY_KEY_PREFIX, _RUST_I18N_MINIFY_KEY_THRESH)),
+                        false => _rust_i18n_try_translate(locale, value),
+                    };
+                    let translated = translated.map_or_else(|| _rust_i18n_missing(Cow::Borrowed(value)), _rust_i18n_unescape);
+                    (rust_i18n::__private::format!("@{}", pattern), translated.into_owned())
+                })
+                .unzip();
+            Some(key_values)
+        }
+
+        /// Replace the variables in the text, e.g. `%{name}`
+        ///
+        /// The values of the key placeholders are translated in the locale first, e.g. `%{@status}`.
+        /// The numeric values are formatted for the locale if `format_numbers` is enabled,
+        /// and the values are wrapped in the bidi isolates if `bidi_isolate` is enabled or the locale is RTL.
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_interpolate(locale: &str, input: &str, patterns: &[&str], values: &[rust_i18n::__private::String]) -> rust_i18n::__private::String {
+            let key_values = _rust_i18n_key_values(locale, input, patterns, values);
+            let (all_patterns, all_values);
+            let (patterns, values) = match &key_values {
+                Some((key_patterns, key_values)) => {
+                    all_patterns = patterns.iter().copied().chain(key_patterns.iter().map(|pattern| pattern.as_str())).collect::<rust_i18n::__private::Vec<_>>();
+                    all_values = values.iter().chain(key_values).cloned().collect::<rust_i18n::__private::Vec<_>>();
+                    (all_patterns.as_slice(), all_values.as_slice())
+                }
+                None => (patterns, values),
+            };
+            let (open, close) = _RUST_I18N_INTERPOLATION;
+            let isolate = _RUST_I18N_BIDI_ISOLATE || rust_i18n::is_rtl(locale);
+            let format: rust_i18n::FormatValue = &|value| {
+                let value = match _RUST_I18N_FORMAT_NUMBERS {
+                    true => rust_i18n::format_number(locale, value).map_or(Cow::Borrowed(value), Cow::Owned),
+                    false => Cow::Borrowed(value),
+                };
+                match isolate {
+                    true => Cow::Owned(rust_i18n::bidi_isolate(&value)),
+                    false => value,
+                }
+            };
+            let format = (_RUST_I18N_FORMAT_NUMBERS || isolate).then_some(format);
+            rust_i18n::replace_patterns(input, open, close, patterns, values, format)
+        }
+
+        /// Get the text to display for a missing translation
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_missing<'r>(key: Cow<'r, str>) -> Cow<'r, str> {
+            _RUST_I18N_ON_MISSING.resolve(key)
+        }
+
         /// Try to get I18n text by locale and key
         #[inline]
         #[doc(hidden)]
         #[allow(missing_docs)]
         pub fn _rust_i18n_try_translate<'r>(locale: &str, key: impl AsRef<str>) -> Option<Cow<'r, str>> {
-            _RUST_I18N_BACKEND.translate(locale, key.as_ref())
-                .map(Cow::from)
-                .or_else(|| {
-                    let mut current_locale = locale;
-                    while let Some(fallback_locale) = _rust_i18n_lookup_fallback(current_locale) {
-                        if let Some(value) = _RUST_I18N_BACKEND.translate(fallback_locale, key.as_ref()) {
-                            return Some(Cow::from(value));
-                        }
-                        current_locale = fallback_locale;
-                    }
+            _rust_i18n_try_translate_minified(locale, key.as_ref())
+        }
 
-                    _RUST_I18N_FALLBACK_LOCALE.and_then(|fallback| {
-                        fallback.iter().find_map(|locale| _RUST_I18N_BACKEND.translate(locale, key.as_ref()).map(Cow::from))
-                    })
+        /// Try to get I18n text by locale and a key that is already minified
+        ///
+        /// The `t!` macro calls this with the key computed at compile time for literal messages,
+        /// so no hashing is done at runtime.
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_try_translate_minified<'r>(locale: &str, key: &str) -> Option<Cow<'r, str>> {
+            if rust_i18n::__private::debug_show_keys() {
+                return Some(Cow::from(rust_i18n::__private::String::from(key)));
+            }
+            _rust_i18n_try_translate_with_locale(locale, key).map(|(value, _)| value)
+        }
+
+        /// Try to get I18n text by locale and key, with the locale that the text is actually found in
+        ///
+        /// The lookup order is: the exact key -> the wildcard key of the locale -> the fallback locales (each in the same order).
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_try_translate_with_locale<'r, 'l>(locale: &'l str, key: &str) -> Option<(Cow<'r, str>, &'l str)> {
+            rust_i18n::__private::warn_deprecated_key(_RUST_I18N_DEPRECATED_KEYS, key, _RUST_I18N_KEY_SEPARATOR);
+            _rust_i18n_lookup(locale, key, true)
+        }
+
+        /// Get I18n text in exactly the locale, or the nearest wildcard key of the namespaces if `wildcard` is enabled
+        ///
+        /// For example: `error.db.timeout` -> `error.db.*` -> `error.*`.
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_translate_in_locale(locale: &str, key: &str, wildcard: bool) -> Option<&'static str> {
+            // Initialize the compiled backend anyway, it also sets the default locale and aliases
+            let compiled = _rust_i18n_compiled_backend();
+            match rust_i18n::__private::test_backend() {
+                Some(backend) => _rust_i18n_translate_in_backend(backend, locale, key, wildcard),
+                None => _rust_i18n_translate_in_backend(compiled, locale, key, wildcard),
+            }
+        }
+
+        /// Same as `_rust_i18n_translate_in_locale`, but generic over the backend to call it without the dynamic dispatch
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_translate_in_backend<B: rust_i18n::Backend + ?Sized>(backend: &'static B, locale: &str, key: &str, wildcard: bool) -> Option<&'static str> {
+            let key = _rust_i18n_namespaced_key(key);
+            backend.translate(locale, &key).or_else(|| {
+                if !wildcard {
+                    return None;
+                }
+                _rust_i18n_translate_wildcard_in_backend(backend, locale, &key)
+            })
+        }
+
+        /// Get I18n text of the nearest wildcard key of the namespaced key in exactly the locale, the exact key is not looked up
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_translate_wildcard_in_backend<B: rust_i18n::Backend + ?Sized>(backend: &'static B, locale: &str, namespaced_key: &str) -> Option<&'static str> {
+            // The namespace itself has no wildcard key
+            let namespace_len = _rust_i18n_namespaced_key("").len();
+            namespaced_key[namespace_len..].rmatch_indices(_RUST_I18N_KEY_SEPARATOR).find_map(|(i, _)| {
+                backend.translate(locale, &rust_i18n::__private::format!("{}{}*", &namespaced_key[..namespace_len + i], _RUST_I18N_KEY_SEPARATOR))
+            })
+        }
+
+        /// Prefix the key with the `namespace` of `i18n!`, e.g. `title` -> `billing.title`
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_namespaced_key(key: &str) -> Cow<'_, str> {
+            if _RUST_I18N_NAMESPACE.is_empty() {
+                Cow::from(key)
+            } else {
+                Cow::from(rust_i18n::__private::format!("{}{}{}", _RUST_I18N_NAMESPACE, _RUST_I18N_KEY_SEPARATOR, key))
+            }
+        }
+
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_lookup<'r, 'l>(locale: &'l str, key: &str, wildcard: bool) -> Option<(Cow<'r, str>, &'l str)> {
+            let locale = _rust_i18n_resolve_alias(locale);
+            // The pseudo-locale is generated from the translations of the default locale
+            if _RUST_I18N_PSEUDO_LOCALE && locale.eq_ignore_ascii_case(rust_i18n::PSEUDO_LOCALE) {
+                let default_locale = _RUST_I18N_DEFAULT_LOCALE.unwrap_or_else(rust_i18n::default_locale);
+                if !default_locale.eq_ignore_ascii_case(rust_i18n::PSEUDO_LOCALE) {
+                    let (open, close) = _RUST_I18N_INTERPOLATION;
+                    return _rust_i18n_lookup(default_locale, key, wildcard)
+                        .map(|(value, _)| (Cow::from(rust_i18n::pseudo_localize(&value, open, close, _RUST_I18N_PSEUDO_EXPANSION)), locale));
+                }
+            }
+            _rust_i18n_translate_in_locale(locale, key, wildcard)
+                .map(|value| (Cow::from(value), locale))
+                .or_else(|| _rust_i18n_lookup_fallbacks(locale, key, wildcard))
+        }
+
+        /// Get I18n text in the fallback locales of the locale, the locale itself is not looked up
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_lookup_fallbacks<'r, 'l>(locale: &'l str, key: &str, wildcard: bool) -> Option<(Cow<'r, str>, &'l str)> {
+            // The strict locales never show the text of other locales
+            if _RUST_I18N_NO_FALLBACK_LOCALES.iter().any(|strict| strict.eq_ignore_ascii_case(locale)) {
+                return None;
+            }
+
+            let mut current_locale = locale;
+            while let Some(fallback_locale) = _rust_i18n_lookup_fallback(current_locale) {
+                if let Some(value) = _rust_i18n_translate_in_locale(fallback_locale, key, wildcard) {
+                    return Some((Cow::from(value), fallback_locale));
+                }
+                current_locale = fallback_locale;
+            }
+
+            // The namespace of the key may be written in another locale than the `fallback`
+            if let Some(namespace_locale) = rust_i18n::namespace_default_locale(_RUST_I18N_NAMESPACE_DEFAULT_LOCALES, key, _RUST_I18N_KEY_SEPARATOR) {
+                if let Some(value) = _rust_i18n_translate_in_locale(namespace_locale, key, wildcard) {
+                    return Some((Cow::from(value), namespace_locale));
+                }
+            }
+
+            _RUST_I18N_FALLBACK_LOCALE.and_then(|fallback| {
+                fallback.iter().find_map(|locale| _rust_i18n_translate_in_locale(locale, key, wildcard).map(|value| (Cow::from(value), *locale)))
+            })
+        }
+
+        /// Get the locales that are looked up in order for the locale, in the same order as `_rust_i18n_lookup`
+        ///
+        /// The default locale of the key's namespace is included only for the key.
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_fallback_chain(locale: &str, key: Option<&str>) -> rust_i18n::__private::Vec<rust_i18n::__private::String> {
+            let locale = _rust_i18n_resolve_alias(locale);
+            if _RUST_I18N_PSEUDO_LOCALE && locale.eq_ignore_ascii_case(rust_i18n::PSEUDO_LOCALE) {
+                let default_locale = _RUST_I18N_DEFAULT_LOCALE.unwrap_or_else(rust_i18n::default_locale);
+                if !default_locale.eq_ignore_ascii_case(rust_i18n::PSEUDO_LOCALE) {
+                    return _rust_i18n_fallback_chain(default_locale, key);
+                }
+            }
+
+            let mut chain = rust_i18n::__private::Vec::from([locale]);
+            if !_RUST_I18N_NO_FALLBACK_LOCALES.iter().any(|strict| strict.eq_ignore_ascii_case(locale)) {
+                let mut current_locale = locale;
+                while let Some(fallback_locale) = _rust_i18n_lookup_fallback(current_locale) {
+                    chain.push(fallback_locale);
+                    current_locale = fallback_locale;
+                }
+                if let Some(namespace_locale) = key.and_then(|key| rust_i18n::namespace_default_locale(_RUST_I18N_NAMESPACE_DEFAULT_LOCALES, key, _RUST_I18N_KEY_SEPARATOR)) {
+                    chain.push(namespace_locale);
+                }
+                chain.extend(_RUST_I18N_FALLBACK_LOCALE.unwrap_or_default());
+            }
+
+            // The locales that are looked up again are not tried twice
+            let mut locales = rust_i18n::__private::Vec::<rust_i18n::__private::String>::with_capacity(chain.len());
+            for locale in chain {
+                if !locales.iter().any(|tried| tried == locale) {
+                    locales.push(locale.into());
+                }
+            }
+            locales
+        }
+
+        /// Get I18n text by locale and key, with the locale that the text is resolved from
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_t_resolved<'r>(locale: &str, key: &'r str) -> (Cow<'r, str>, rust_i18n::ResolvedFrom) {
+            match _rust_i18n_try_translate_with_locale(locale, key) {
+                Some((value, found)) if found == _rust_i18n_resolve_alias(locale) => {
+                    (value, rust_i18n::ResolvedFrom::Exact(found.into()))
+                }
+                Some((value, found)) => (value, rust_i18n::ResolvedFrom::Fallback(found.into())),
+                None => (_rust_i18n_translate(locale, key), rust_i18n::ResolvedFrom::Missing),
+            }
+        }
+
+        /// Try to get I18n texts by locale and keys, in the same order as the keys
+        ///
+        /// The keys are looked up in bulk, and only the missing keys are looked up by the wildcard keys and the fallback locales,
+        /// the same results as `_rust_i18n_try_translate` of each key.
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_try_translate_many(locale: &str, keys: &[&str]) -> rust_i18n::__private::Vec<Option<Cow<'static, str>>> {
+            let locale = _rust_i18n_resolve_alias(locale);
+            // The keys are shown as is, and the pseudo-locale is generated from the default locale
+            if rust_i18n::__private::debug_show_keys()
+                || (_RUST_I18N_PSEUDO_LOCALE && locale.eq_ignore_ascii_case(rust_i18n::PSEUDO_LOCALE))
+            {
+                return keys.iter().map(|key| _rust_i18n_try_translate(locale, key)).collect();
+            }
+            let namespaced_keys = keys.iter().map(|key| _rust_i18n_namespaced_key(key)).collect::<rust_i18n::__private::Vec<_>>();
+            let namespaced_key_refs = namespaced_keys.iter().map(|key| key.as_ref()).collect::<rust_i18n::__private::Vec<_>>();
+            let backend = _rust_i18n_backend();
+            backend
+                .translate_many(locale, &namespaced_key_refs)
+                .into_iter()
+                .zip(keys.iter().zip(&namespaced_key_refs))
+                .map(|(value, (key, namespaced_key))| {
+                    rust_i18n::__private::warn_deprecated_key(_RUST_I18N_DEPRECATED_KEYS, key, _RUST_I18N_KEY_SEPARATOR);
+                    value
+                        .or_else(|| _rust_i18n_translate_wildcard_in_backend(backend, locale, namespaced_key))
+                        .map(Cow::from)
+                        .or_else(|| _rust_i18n_lookup_fallbacks(locale, key, true).map(|(value, _)| value))
                 })
+                .collect()
         }
 
+        /// Export all translations of the locale as a flat JSON object, only the keys starting with `prefix` are included
         #[inline]
         #[doc(hidden)]
         #[allow(missing_docs)]
-        pub fn _rust_i18n_available_locales() -> Vec<&'static str> {
-            let mut locales = _RUST_I18N_BACKEND.available_locales();
+        pub fn _rust_i18n_export_locale_json(locale: &str, prefix: &str) -> rust_i18n::__private::String {
+            // The keys are exported without the namespace
+            let namespace = _rust_i18n_namespaced_key("");
+            let translations = _rust_i18n_backend()
+                .translations(locale)
+                .into_iter()
+                .filter_map(|(key, value)| key.strip_prefix(namespace.as_ref()).map(|key| (key, value)));
+            rust_i18n::translations_to_json(translations, prefix)
+        }
+
+        /// Get the missing keys of each locale, compared with the `reference` locale or the union of all keys
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_missing_keys(reference: Option<&str>) -> rust_i18n::__private::BTreeMap<rust_i18n::__private::String, rust_i18n::__private::Vec<rust_i18n::__private::String>> {
+            _rust_i18n_backend()
+                .missing_keys(reference)
+                .into_iter()
+                .map(|(locale, keys)| {
+                    let namespace = _rust_i18n_namespaced_key("");
+                    (locale.into(), keys.into_iter().filter_map(|key| key.strip_prefix(namespace.as_ref())).map(Into::into).collect())
+                })
+                .collect()
+        }
+
+        /// Iterate all `(locale, key, value)` entries of the backend, the keys are without the namespace
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_all_entries() -> impl Iterator<Item = (&'static str, &'static str, &'static str)> {
+            let namespace = _rust_i18n_namespaced_key("");
+            _rust_i18n_backend()
+                .entries()
+                .filter_map(move |(locale, key, value)| key.strip_prefix(namespace.as_ref()).map(|key| (locale, key, value)))
+        }
+
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_available_locales() -> rust_i18n::__private::Vec<&'static str> {
+            let mut locales = _rust_i18n_backend().available_locales();
             locales.sort();
             locales
         }
 
+        /// Get the locales that have any translation of the keys starting with the prefix, the prefix is without the namespace
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_available_locales_for_prefix(prefix: &str) -> rust_i18n::__private::Vec<&'static str> {
+            _rust_i18n_backend().available_locales_for_prefix(&_rust_i18n_namespaced_key(prefix))
+        }
+
+        /// Check if the locale is available, the aliases are resolved the same as `_rust_i18n_set_locale_checked`
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_is_locale_available(locale: &str) -> bool {
+            let locale = _rust_i18n_resolve_alias(locale);
+            _rust_i18n_backend().available_locales().contains(&locale)
+        }
+
+        /// Pick the best available locale for the `Accept-Language` header, the aliases are matched as well
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_negotiate_locale(accept_language: &str) -> Option<&'static str> {
+            let mut locales = _rust_i18n_available_locales();
+            // The default locale is the first for the wildcard `*`
+            if let Some(default_locale) = locales.iter().position(|locale| *locale == rust_i18n::default_locale()) {
+                let default_locale = locales.remove(default_locale);
+                locales.insert(0, default_locale);
+            }
+            let aliases = _RUST_I18N_ALIASES
+                .iter()
+                .filter(|(_, preferred)| locales.contains(preferred))
+                .map(|(alias, _)| *alias)
+                .collect::<rust_i18n::__private::Vec<_>>();
+            locales.extend(aliases);
+            rust_i18n::negotiate_locale(accept_language, &locales).map(_rust_i18n_resolve_alias)
+        }
+
+        /// Check if the key is translated in exactly the locale, without the fallback locales
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_has_translation(locale: &str, key: &str) -> bool {
+            let locale = _rust_i18n_resolve_alias(locale);
+            _rust_i18n_backend().contains_key(locale, &_rust_i18n_namespaced_key(key))
+        }
+
+        /// Check if the translation is changed since the base of `rust_i18n::set_base_backend`, both resolved through the fallback chain
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_changed_since_base(locale: &str, key: &str) -> bool {
+            let Some(base) = rust_i18n::__private::base_backend() else {
+                return false;
+            };
+            let current = _rust_i18n_lookup(locale, key, true).map(|(value, _)| value);
+            let previous = _rust_i18n_fallback_chain(locale, Some(key))
+                .iter()
+                .find_map(|locale| base.translate(locale, key));
+            current.as_deref() != previous
+        }
+
+        #[inline]
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        pub fn _rust_i18n_set_locale_checked(locale: &str) -> Result<(), rust_i18n::UnknownLocale> {
+            let locale = _rust_i18n_resolve_alias(locale);
+            if !_rust_i18n_is_locale_available(locale) {
+                return Err(rust_i18n::UnknownLocale::new(locale));
+            }
+            rust_i18n::set_locale(locale);
+            Ok(())
+        }
+
         #[doc(hidden)]
         #[allow(unused_macros)]
         macro_rules! __rust_i18n_t {
             ($($all_tokens:tt)*) => {
-                rust_i18n::_tr!($($all_tokens)*, _minify_key = #minify_key, _minify_key_len = #minify_key_len, _minify_key_prefix = #minify_key_prefix, _minify_key_thresh = #minify_key_thresh)
+                rust_i18n::_tr!($($all_tokens)*, _minify_key = #minify_key, _minify_key_len = #minify_key_len, _minify_key_prefix = #minify_key_prefix, _minify_key_thresh = #minify_key_thresh #static_only #static_path #check_args)
             }
         }
 
@@ -427,6 +2302,93 @@ fn generate_code(
     }
 }
 
+/// Derive the localized display of the enum variants.
+///
+/// This generates `fn localize_key(&self) -> &'static str` and `fn localized(&self, locale: &str) -> Cow<str>`,
+/// each variant is mapped to the key in snake case, e.g. `InProgress` => `in_progress`.
+///
+/// - `#[localize(prefix = "status")]` on the enum to prepend the prefix, e.g. `status.in_progress`.
+/// - `#[localize(key = "common.done")]` on the variant to use the key as is.
+///
+/// ```no_run
+/// rust_i18n::i18n!("locales");
+///
+/// #[derive(rust_i18n::Localize)]
+/// #[localize(prefix = "status")]
+/// enum Status {
+///     Active,
+///     InProgress { percent: u8 },
+///     #[localize(key = "common.done")]
+///     Done,
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Status::Active.localize_key(), "status.active");
+/// println!("{}", Status::Active.localized("en"));
+/// # }
+/// ```
+#[proc_macro_derive(Localize, attributes(localize))]
+pub fn derive_localize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    parse_macro_input!(input as localize::Localize).into()
+}
+
+/// Derive `rust_i18n::Interpolate` to pass the named fields of the struct to `t!` with `..value`.
+///
+/// Each field is mapped to the `%{field}` pattern and formatted with `Display`, the arguments before the `..value` take precedence.
+///
+/// - `#[interpolate(rename = "name")]` on the field to use another name of the pattern.
+/// - `#[interpolate(skip)]` on the field to leave it out.
+///
+/// ```no_run
+/// rust_i18n::i18n!("locales");
+///
+/// #[derive(rust_i18n::Interpolate)]
+/// struct Line {
+///     name: String,
+///     qty: u32,
+///     #[interpolate(rename = "price")]
+///     unit_price: f32,
+///     #[interpolate(skip)]
+///     id: u64,
+/// }
+///
+/// # fn main() {
+/// let line = Line { name: "Apple".into(), qty: 3, unit_price: 1.5, id: 1 };
+/// // invoice.line: "%{qty} x %{name} @ %{price}"
+/// println!("{}", rust_i18n::t!("invoice.line", ..&line));
+/// println!("{}", rust_i18n::t!("invoice.line", qty = 5, ..line));
+/// # }
+/// ```
+#[proc_macro_derive(Interpolate, attributes(interpolate))]
+pub fn derive_interpolate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    parse_macro_input!(input as interpolate::Interpolate).into()
+}
+
+/// Prefix the literal keys of the `t!` calls in the block, to avoid repeating the namespace of the keys.
+///
+/// The prefix and the key are joined by the `key-separator` in `[package.metadata.i18n]` of Cargo.toml,
+/// default: `"."`, the nested `with_key_prefix!` are joined to the outer prefix.
+/// The keys that are not string literals are left as is, e.g. `t!(key)`.
+///
+/// ```no_run
+/// # use rust_i18n::with_key_prefix;
+/// # macro_rules! t { ($($all:tt)*) => { "" } }
+/// # fn main() {
+/// with_key_prefix!("billing.invoice", {
+///     let title = t!("title");
+///     // => t!("billing.invoice.title")
+///     with_key_prefix!("items", {
+///         let name = t!("name");
+///         // => t!("billing.invoice.items.name")
+///     });
+/// });
+/// # }
+/// ```
+#[proc_macro]
+pub fn with_key_prefix(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    parse_macro_input!(input as key_prefix::KeyPrefix).into()
+}
+
 /// A procedural macro that generates a translation key from a value.
 #[proc_macro]
 #[doc(hidden)]
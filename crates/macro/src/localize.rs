@@ -0,0 +1,116 @@
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, LitStr};
+
+/// A type representing the `#[derive(Localize)]` proc macro.
+pub struct Localize {
+    ident: Ident,
+    generics: syn::Generics,
+    /// The variants and their translation keys
+    variants: Vec<(Ident, Fields, String)>,
+}
+
+impl Localize {
+    fn into_token_stream(self) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let arms = self.variants.iter().map(|(variant, fields, key)| {
+            let pattern = match fields {
+                Fields::Named(_) => quote! { Self::#variant { .. } },
+                Fields::Unnamed(_) => quote! { Self::#variant(..) },
+                Fields::Unit => quote! { Self::#variant },
+            };
+            quote! { #pattern => #key }
+        });
+
+        quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Get the translation key of the variant
+                #[allow(missing_docs)]
+                pub fn localize_key(&self) -> &'static str {
+                    match self {
+                        #(#arms,)*
+                    }
+                }
+
+                /// Get the translation of the variant in the given locale
+                #[allow(missing_docs)]
+                pub fn localized(&self, locale: &str) -> rust_i18n::__private::Cow<'static, str> {
+                    crate::_rust_i18n_t!(self.localize_key(), locale = locale)
+                }
+            }
+        }
+    }
+}
+
+/// Parse `#[localize(prefix = "status")]` or `#[localize(key = "status.on")]`.
+fn parse_attribute(attrs: &[syn::Attribute], name: &str) -> syn::Result<Option<String>> {
+    let mut value = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("localize")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(name) {
+                value = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else {
+                Err(meta.error(format!("expected `{}`", name)))
+            }
+        })?;
+    }
+    Ok(value)
+}
+
+/// Convert the variant name into snake case, e.g. `InProgress` => `in_progress`.
+fn to_snake_case(name: &str) -> String {
+    let mut output = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                output.push('_');
+            }
+            output.extend(c.to_lowercase());
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+impl syn::parse::Parse for Localize {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let input = input.parse::<DeriveInput>()?;
+        let Data::Enum(data) = &input.data else {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`Localize` can only be derived for enums",
+            ));
+        };
+        let prefix = parse_attribute(&input.attrs, "prefix")?;
+
+        let variants = data
+            .variants
+            .iter()
+            .map(|variant| {
+                // The `key` of the variant is the full key, the `prefix` is not prepended
+                let key = match (parse_attribute(&variant.attrs, "key")?, &prefix) {
+                    (Some(key), _) => key,
+                    (None, Some(prefix)) => {
+                        format!("{}.{}", prefix, to_snake_case(&variant.ident.to_string()))
+                    }
+                    (None, None) => to_snake_case(&variant.ident.to_string()),
+                };
+                Ok((variant.ident.clone(), variant.fields.clone(), key))
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            ident: input.ident,
+            generics: input.generics,
+            variants,
+        })
+    }
+}
+
+impl From<Localize> for proc_macro::TokenStream {
+    fn from(val: Localize) -> Self {
+        val.into_token_stream().into()
+    }
+}
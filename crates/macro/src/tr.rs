@@ -1,7 +1,14 @@
+use once_cell::sync::Lazy;
 use quote::{quote, ToTokens};
 use rust_i18n_support::{
-    MinifyKey, DEFAULT_MINIFY_KEY_LEN, DEFAULT_MINIFY_KEY_PREFIX, DEFAULT_MINIFY_KEY_THRESH,
+    locale_file_paths, placeholder_default, placeholder_names, required_placeholder_names,
+    try_load_locales_from_paths_with_separator, MinifyKey, PluralCategory, DEFAULT_KEY_SEPARATOR,
+    DEFAULT_MINIFY_KEY_LEN, DEFAULT_MINIFY_KEY_PREFIX, DEFAULT_MINIFY_KEY_THRESH,
 };
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
 use syn::{parse::discouraged::Speculative, token::Brace, Expr, Ident, LitStr, Token};
 
 #[derive(Clone, Debug, Default)]
@@ -38,6 +45,15 @@ impl Value {
         None
     }
 
+    /// Convert the value into tokens without borrowing the paths.
+    fn to_owned_token_stream(&self) -> proc_macro2::TokenStream {
+        match self {
+            Self::Empty => quote! {},
+            Self::Expr(expr) => quote! { #expr },
+            Self::Ident(ident) => quote! { #ident },
+        }
+    }
+
     fn to_tupled_token_streams(
         &self,
     ) -> syn::parse::Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
@@ -81,18 +97,13 @@ impl quote::ToTokens for Value {
 }
 
 impl syn::parse::Parse for Value {
+    /// Parse any expression, e.g. a block `{ compute() }`, a method chain or a reference `&x`,
+    /// the error of the invalid expression is kept to point at the exact token.
     fn parse(input: syn::parse::ParseStream) -> syn::parse::Result<Self> {
-        let fork = input.fork();
-        if let Ok(expr) = fork.parse::<Expr>() {
-            input.advance_to(&fork);
-            return Ok(expr.into());
+        if input.is_empty() || input.peek(Token![,]) {
+            return Err(input.error("Expected a expression or an identifier"));
         }
-        let fork = input.fork();
-        if let Ok(expr) = fork.parse::<Ident>() {
-            input.advance_to(&fork);
-            return Ok(expr.into());
-        }
-        Err(input.error("Expected a expression or an identifier"))
+        Ok(input.parse::<Expr>()?.into())
     }
 }
 
@@ -132,10 +143,6 @@ impl Argument {
 
 impl syn::parse::Parse for Argument {
     fn parse(input: syn::parse::ParseStream) -> syn::parse::Result<Self> {
-        // Ignore leading commas.
-        while input.peek(Token![,]) {
-            let _ = input.parse::<Token![,]>()?;
-        }
         // Parse the argument name.
         let name = Self::try_ident(input)
             .or_else(|_| Self::try_literal(input))
@@ -153,17 +160,16 @@ impl syn::parse::Parse for Argument {
         // Parse the specifiers [optinal].
         let specifiers = if input.peek(Token![:]) {
             let _ = input.parse::<Token![:]>()?;
-            if input.peek(Brace) {
-                let content;
-                let _ = syn::braced!(content in input);
-                let mut specifiers = String::new();
-                while let Ok(s) = content.parse::<proc_macro2::TokenTree>() {
-                    specifiers.push_str(&s.to_string());
-                }
-                Some(specifiers)
-            } else {
-                None
+            if !input.peek(Brace) {
+                return Err(input.error("Expected the format specifiers in braces, e.g. `: {:08}`"));
+            }
+            let content;
+            let _ = syn::braced!(content in input);
+            let mut specifiers = String::new();
+            while let Ok(s) = content.parse::<proc_macro2::TokenTree>() {
+                specifiers.push_str(&s.to_string());
             }
+            Some(specifiers)
         } else {
             None
         };
@@ -178,11 +184,13 @@ impl syn::parse::Parse for Argument {
 #[derive(Default)]
 pub struct Arguments {
     pub args: Vec<Argument>,
+    /// The `..value` of a type implementing `rust_i18n::Interpolate`
+    pub spread: Option<Expr>,
 }
 
 impl Arguments {
     pub fn is_empty(&self) -> bool {
-        self.args.is_empty()
+        self.args.is_empty() && self.spread.is_none()
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &Argument> {
@@ -213,11 +221,32 @@ impl AsMut<Vec<Argument>> for Arguments {
 
 impl syn::parse::Parse for Arguments {
     fn parse(input: syn::parse::ParseStream) -> syn::parse::Result<Self> {
-        let args = input
-            .parse_terminated(Argument::parse, Token![,])?
-            .into_iter()
-            .collect();
-        Ok(Self { args })
+        let mut args = Vec::new();
+        let mut spread = None;
+        while !input.is_empty() {
+            // The empty arguments are skipped, e.g. the trailing comma before the options appended by `t!`
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+                continue;
+            }
+            if input.peek(Token![..]) {
+                let dot2 = input.parse::<Token![..]>()?;
+                if spread.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        dot2,
+                        "Only one `..value` is allowed",
+                    ));
+                }
+                spread = Some(input.parse::<Expr>()?);
+            } else {
+                args.push(input.parse::<Argument>()?);
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok(Self { args, spread })
     }
 }
 
@@ -258,15 +287,91 @@ impl syn::parse::Parse for Messsage {
     }
 }
 
+type Translations = HashMap<String, HashMap<String, String>>;
+
+/// The paths, the modified times and the sizes of the locale files.
+type Fingerprint = Vec<(PathBuf, Option<SystemTime>, u64)>;
+
+/// The translations loaded from the locale files with the fingerprint of the files,
+/// by the locales paths and the key separator.
+type CachedTranslations = HashMap<(Vec<String>, String), (Fingerprint, Translations)>;
+
+/// The translations loaded for `static_only` mode, cached by the locales paths and the key separator.
+///
+/// The proc-macro server may live across the builds, so the translations are loaded again
+/// if any locale file is changed, added or removed.
+static STATIC_TRANSLATIONS: Lazy<Mutex<CachedTranslations>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Get the fingerprint of the locale files in the paths.
+fn fingerprint(paths: &[String]) -> Fingerprint {
+    paths
+        .iter()
+        .flat_map(|path| locale_file_paths(path))
+        .map(|file| {
+            let metadata = std::fs::metadata(&file).ok();
+            let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+            (file, modified, metadata.map_or(0, |m| m.len()))
+        })
+        .collect()
+}
+
+/// Run `f` with the translations loaded at compile time.
+fn with_static_translations<R>(
+    paths: &[String],
+    separator: &str,
+    f: impl FnOnce(&Translations) -> R,
+) -> R {
+    let fingerprint = fingerprint(paths);
+    let mut cache = STATIC_TRANSLATIONS.lock().unwrap();
+    let cache_key = (paths.to_vec(), separator.to_owned());
+    let cached = cache
+        .get(&cache_key)
+        .filter(|(cached, _)| *cached == fingerprint);
+    if cached.is_none() {
+        // The malformed files are reported by `i18n!`
+        let trs = try_load_locales_from_paths_with_separator(paths, |_| false, separator)
+            .unwrap_or_default();
+        cache.insert(cache_key.clone(), (fingerprint, trs));
+    }
+    f(&cache[&cache_key].1)
+}
+
+/// Lookup the translation at compile time.
+fn static_translate(paths: &[String], separator: &str, locale: &str, key: &str) -> Option<String> {
+    with_static_translations(paths, separator, |trs| trs.get(locale)?.get(key).cloned())
+}
+
+/// Escape the braces for `format!`.
+fn escape_braces(s: &str) -> String {
+    s.replace('{', "{{").replace('}', "}}")
+}
+
 /// A type representing the `tr!` proc macro.
 pub(crate) struct Tr {
     pub msg: Messsage,
     pub args: Arguments,
     pub locale: Option<Value>,
+    pub index: Option<Value>,
+    pub random: Option<Value>,
+    pub default: Option<Value>,
+    pub ordinal: Option<Value>,
+    pub variant: Option<Value>,
     pub minify_key: bool,
     pub minify_key_len: usize,
     pub minify_key_prefix: String,
     pub minify_key_thresh: usize,
+    pub static_only: Option<String>,
+    pub static_path: Option<Vec<String>>,
+    pub static_interpolation: Option<(String, String)>,
+    pub static_format_numbers: bool,
+    pub static_bidi_isolate: bool,
+    pub static_smart_quotes: bool,
+    pub static_key_separator: String,
+    /// The default locale to check the arguments against its placeholders
+    pub check_args: Option<String>,
+    /// The namespace of `t_ns!` joined before the literal key
+    pub namespace: Option<String>,
 }
 
 impl Tr {
@@ -275,10 +380,24 @@ impl Tr {
             msg: Messsage::default(),
             args: Arguments::default(),
             locale: None,
+            index: None,
+            random: None,
+            default: None,
+            ordinal: None,
+            variant: None,
             minify_key: false,
             minify_key_len: DEFAULT_MINIFY_KEY_LEN,
             minify_key_prefix: DEFAULT_MINIFY_KEY_PREFIX.into(),
             minify_key_thresh: DEFAULT_MINIFY_KEY_THRESH,
+            static_only: None,
+            static_path: None,
+            static_interpolation: None,
+            static_format_numbers: false,
+            static_bidi_isolate: false,
+            static_smart_quotes: false,
+            static_key_separator: DEFAULT_KEY_SEPARATOR.into(),
+            check_args: None,
+            namespace: None,
         }
     }
 
@@ -339,12 +458,83 @@ impl Tr {
         ))
     }
 
+    fn parse_static_str(value: &Value, name: &str) -> syn::parse::Result<String> {
+        if let Value::Expr(Expr::Lit(expr_lit)) = value {
+            if let syn::Lit::Str(lit_str) = &expr_lit.lit {
+                return Ok(lit_str.value());
+            }
+        }
+        Err(syn::Error::new_spanned(
+            value,
+            format!("`{}` Expected a string literal", name),
+        ))
+    }
+
+    fn parse_static_strs(value: &Value, name: &str) -> syn::parse::Result<Vec<String>> {
+        if let Value::Expr(Expr::Array(expr_array)) = value {
+            return expr_array
+                .elems
+                .iter()
+                .map(|elem| Self::parse_static_str(&Value::Expr(elem.clone()), name))
+                .collect();
+        }
+        Err(syn::Error::new_spanned(
+            value,
+            format!("`{}` Expected an array of string literals", name),
+        ))
+    }
+
+    fn parse_static_bool(value: &Value, name: &str) -> syn::parse::Result<bool> {
+        if let Value::Expr(Expr::Lit(expr_lit)) = value {
+            if let syn::Lit::Bool(lit_bool) = &expr_lit.lit {
+                return Ok(lit_bool.value);
+            }
+        }
+        Err(syn::Error::new_spanned(
+            value,
+            format!("`{}` Expected a bool literal", name),
+        ))
+    }
+
+    fn parse_namespace(value: &Value) -> syn::parse::Result<String> {
+        match value {
+            Value::Expr(Expr::Path(expr_path)) if expr_path.path.get_ident().is_some() => {
+                return Ok(expr_path.path.get_ident().unwrap().to_string());
+            }
+            Value::Ident(ident) => return Ok(ident.to_string()),
+            _ => {}
+        }
+        match value.to_string() {
+            Some(namespace) if !namespace.is_empty() => Ok(namespace),
+            _ => Err(syn::Error::new_spanned(
+                value,
+                "`t_ns!` Expected an identifier or a string literal for the namespace",
+            )),
+        }
+    }
+
     fn filter_arguments(&mut self) -> syn::parse::Result<()> {
         for arg in self.args.iter() {
             match arg.name.as_str() {
                 "locale" => {
                     self.locale = Some(arg.value.clone());
                 }
+                "index" => {
+                    self.index = Some(arg.value.clone());
+                }
+                "random" => {
+                    self.random = Some(arg.value.clone());
+                }
+                "default" => {
+                    self.default = Some(arg.value.clone());
+                }
+                "variant" => {
+                    self.variant = Some(arg.value.clone());
+                }
+                // Kept in the arguments for the `%{ordinal}` variable
+                "ordinal" => {
+                    self.ordinal = Some(arg.value.clone());
+                }
                 "_minify_key" => {
                     self.minify_key = Self::parse_minify_key(&arg.value)?;
                 }
@@ -357,6 +547,41 @@ impl Tr {
                 "_minify_key_thresh" => {
                     self.minify_key_thresh = Self::parse_minify_key_thresh(&arg.value)?;
                 }
+                "_static_only" => {
+                    self.static_only = Some(Self::parse_static_str(&arg.value, "_static_only")?);
+                }
+                "_static_path" => {
+                    self.static_path = Some(Self::parse_static_strs(&arg.value, "_static_path")?);
+                }
+                "_static_interpolation" => {
+                    let value = Self::parse_static_str(&arg.value, "_static_interpolation")?;
+                    self.static_interpolation = Some(
+                        crate::parse_interpolation(&value)
+                            .map_err(|e| syn::Error::new_spanned(&arg.value, e))?,
+                    );
+                }
+                "_static_format_numbers" => {
+                    self.static_format_numbers =
+                        Self::parse_static_bool(&arg.value, "_static_format_numbers")?;
+                }
+                "_static_bidi_isolate" => {
+                    self.static_bidi_isolate =
+                        Self::parse_static_bool(&arg.value, "_static_bidi_isolate")?;
+                }
+                "_static_smart_quotes" => {
+                    self.static_smart_quotes =
+                        Self::parse_static_bool(&arg.value, "_static_smart_quotes")?;
+                }
+                "_static_key_separator" => {
+                    self.static_key_separator =
+                        Self::parse_static_str(&arg.value, "_static_key_separator")?;
+                }
+                "_check_args" => {
+                    self.check_args = Some(Self::parse_static_str(&arg.value, "_check_args")?);
+                }
+                "_namespace" => {
+                    self.namespace = Some(Self::parse_namespace(&arg.value)?);
+                }
                 _ => {}
             }
         }
@@ -364,10 +589,23 @@ impl Tr {
         self.args.as_mut().retain(|v| {
             ![
                 "locale",
+                "index",
+                "random",
+                "default",
+                "variant",
                 "_minify_key",
                 "_minify_key_len",
                 "_minify_key_prefix",
                 "_minify_key_thresh",
+                "_static_only",
+                "_static_path",
+                "_static_interpolation",
+                "_static_format_numbers",
+                "_static_bidi_isolate",
+                "_static_smart_quotes",
+                "_static_key_separator",
+                "_check_args",
+                "_namespace",
             ]
             .contains(&v.name.as_str())
         });
@@ -387,7 +625,266 @@ impl Tr {
         quote! {}
     }
 
+    /// Get the key of a literal message, minified if `minify_key` is enabled.
+    fn literal_key(&self) -> Option<String> {
+        let msg_val = self.msg.val.to_string()?;
+        if self.minify_key {
+            Some(
+                MinifyKey::minify_key(
+                    &msg_val,
+                    self.minify_key_len,
+                    self.minify_key_prefix.as_str(),
+                    self.minify_key_thresh,
+                )
+                .into_owned(),
+            )
+        } else {
+            Some(msg_val)
+        }
+    }
+
+    /// Join the namespace of `t_ns!` before the literal key, e.g. `auth.login.title` of `t_ns!(auth, "login.title")`.
+    ///
+    /// The key must be found in a locale if any key of the namespace is loaded at compile time,
+    /// the namespaces without any key are skipped, e.g. their translations are added at runtime.
+    fn apply_namespace(&mut self) -> syn::parse::Result<()> {
+        let Some(namespace) = self.namespace.as_ref() else {
+            return Ok(());
+        };
+        let Value::Expr(Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit_str),
+            ..
+        })) = &self.msg.val
+        else {
+            return Err(syn::Error::new_spanned(
+                &self.msg.val,
+                "The key of `t_ns!` must be a string literal",
+            ));
+        };
+        let separator = self.static_key_separator.as_str();
+        let key = format!("{}{}{}", namespace, separator, lit_str.value());
+
+        if let Some(paths) = self.static_path.as_ref() {
+            let namespace_prefix = format!("{}{}", namespace, separator);
+            let key_prefix = format!("{}{}", key, separator);
+            let (has_namespace, has_key) = with_static_translations(paths, separator, |trs| {
+                let keys = trs.values().flat_map(|trs| trs.keys());
+                let has_namespace = keys.clone().any(|k| k.starts_with(&namespace_prefix));
+                let has_key = keys
+                    .clone()
+                    .any(|k| *k == key || k.starts_with(&key_prefix));
+                (has_namespace, has_key)
+            });
+            if has_namespace && !has_key {
+                return Err(syn::Error::new_spanned(
+                    lit_str,
+                    format!(
+                        "The key `{}` is not found in the namespace `{}`",
+                        key, namespace
+                    ),
+                ));
+            }
+        }
+
+        self.msg.val = Value::Expr(Expr::Lit(syn::ExprLit {
+            attrs: vec![],
+            lit: syn::Lit::Str(LitStr::new(&key, lit_str.span())),
+        }));
+        Ok(())
+    }
+
+    /// Check the arguments against the placeholders of the translation in the default locale, for `check_args`.
+    ///
+    /// The dynamic keys and the keys that are not found at compile time are skipped.
+    fn check_arguments(&self) -> Vec<syn::Error> {
+        let (Some(locale), Some(paths)) = (self.check_args.as_ref(), self.static_path.as_ref())
+        else {
+            return vec![];
+        };
+        let Some(msg_key) = self.literal_key() else {
+            return vec![];
+        };
+        let Some(translated) =
+            static_translate(paths, &self.static_key_separator, locale, &msg_key)
+        else {
+            return vec![];
+        };
+        let (open, close) = self
+            .static_interpolation
+            .as_ref()
+            .map_or(("%{", "}"), |(open, close)| (open.as_str(), close.as_str()));
+        let placeholders = placeholder_names(&translated, open, close);
+
+        let mut errors = Vec::new();
+        // The `ordinal` is also used to select the plural form
+        for arg in self.args.iter().filter(|arg| arg.name != "ordinal") {
+            if !placeholders.contains(&arg.name.as_str()) {
+                errors.push(syn::Error::new_spanned(
+                    &arg.value,
+                    format!(
+                        "The argument `{}` is not used by the translation of `{}` in `{}`: {:?}",
+                        arg.name, msg_key, locale, translated
+                    ),
+                ));
+            }
+        }
+        // The fields of the `..value` are only known at runtime, the placeholders with a default are optional
+        for name in required_placeholder_names(&translated, open, close)
+            .into_iter()
+            .filter(|_| self.args.spread.is_none())
+        {
+            if !self.args.iter().any(|arg| arg.name == name) {
+                errors.push(syn::Error::new_spanned(
+                    &self.msg.val,
+                    format!(
+                        "Missing the argument `{}` of the translation of `{}` in `{}`: {:?}",
+                        name, msg_key, locale, translated
+                    ),
+                ));
+            }
+        }
+
+        errors
+    }
+
+    /// Inline the translation if the key is a literal and it's found at compile time in `static_only` mode.
+    fn try_static_token_stream(&self) -> Option<proc_macro2::TokenStream> {
+        let (locale, paths) = (self.static_only.as_ref()?, self.static_path.as_ref()?);
+        if self.locale.is_some()
+            || self.index.is_some()
+            || self.random.is_some()
+            || self.ordinal.is_some()
+            || self.variant.is_some()
+            || self.args.spread.is_some()
+        {
+            return None;
+        }
+        // The numbers are formatted and the values are isolated at runtime
+        let isolate = self.static_bidi_isolate || rust_i18n_support::is_rtl(locale);
+        if (self.static_format_numbers || isolate) && !self.args.is_empty() {
+            return None;
+        }
+        let msg_key = self.literal_key()?;
+        // The plural form is selected by the runtime value of `count`
+        if self.args.iter().any(|arg| arg.name == "count")
+            && PluralCategory::ALL.iter().any(|category| {
+                let plural_key = format!(
+                    "{}{}{}",
+                    msg_key,
+                    self.static_key_separator,
+                    category.as_str()
+                );
+                static_translate(paths, &self.static_key_separator, locale, &plural_key).is_some()
+            })
+        {
+            return None;
+        }
+        let translated = static_translate(paths, &self.static_key_separator, locale, &msg_key)?;
+
+        // Convert the `%{name}` patterns into `format!` arguments
+        let (open, close) = self
+            .static_interpolation
+            .as_ref()
+            .map_or(("%{", "}"), |(open, close)| (open.as_str(), close.as_str()));
+        let translated = match self.static_smart_quotes {
+            true => rust_i18n_support::smart_quotes(&translated, locale, open, close),
+            false => translated,
+        };
+        let mut format_str = String::new();
+        let mut values = Vec::new();
+        let mut used_names = Vec::new();
+        let mut literal = String::new();
+        let escape = if open.starts_with('%') { "%" } else { "\\" };
+        let mut rest = translated.as_str();
+        while let Some(start) = rest.find(open) {
+            // The escaped open delimiter is a literal, e.g. `%%{` => `%{`
+            if rest[..start].ends_with(escape) {
+                let text = &rest[..start - escape.len()];
+                format_str.push_str(&escape_braces(text));
+                format_str.push_str(&escape_braces(open));
+                literal.push_str(text);
+                literal.push_str(open);
+                rest = &rest[start + open.len()..];
+                continue;
+            }
+            let Some(len) = rest[start + open.len()..].find(close) else {
+                break;
+            };
+            let end = start + open.len() + len + close.len();
+            let name = &rest[start + open.len()..start + open.len() + len];
+            // The inline plural pattern depends on the runtime value, e.g. `%{count | item | items}`
+            if name.contains('|') {
+                let plural_name = name.split('|').next().unwrap_or_default().trim();
+                if self.args.iter().any(|arg| arg.name == plural_name) {
+                    return None;
+                }
+            }
+            // The default is used if the argument is not given, e.g. `%{name:guest}`
+            let (name, default) = match placeholder_default(name) {
+                Some((name, default)) => (name, Some(default)),
+                None => (name, None),
+            };
+            // The value of the key placeholder is translated at runtime, e.g. `%{@status}`
+            if let Some(key_name) = name.strip_prefix('@') {
+                if self.args.iter().any(|arg| arg.name == key_name) {
+                    return None;
+                }
+            }
+            format_str.push_str(&escape_braces(&rest[..start]));
+            literal.push_str(&rest[..start]);
+            if let Some(arg) = self.args.iter().find(|arg| arg.name == name) {
+                let index = used_names
+                    .iter()
+                    .position(|v| *v == name)
+                    .unwrap_or_else(|| {
+                        used_names.push(name);
+                        values.push(arg.value.to_token_stream());
+                        used_names.len() - 1
+                    });
+                let specifiers = arg.specifiers.as_deref().unwrap_or_default();
+                format_str.push_str(&format!("{{{}{}}}", index, specifiers));
+            } else if let Some(default) = default {
+                format_str.push_str(&escape_braces(default));
+                literal.push_str(default);
+            } else {
+                format_str.push_str(&escape_braces(&rest[start..end]));
+                literal.push_str(&rest[start..end]);
+            }
+            rest = &rest[end..];
+        }
+        format_str.push_str(&escape_braces(rest));
+        literal.push_str(rest);
+
+        if values.is_empty() {
+            Some(quote! { rust_i18n::__private::Cow::<str>::Borrowed(#literal) })
+        } else {
+            Some(
+                quote! { rust_i18n::__private::Cow::<str>::Owned(rust_i18n::__private::format!(#format_str, #(#values),*)) },
+            )
+        }
+    }
+
     fn into_token_stream(self) -> proc_macro2::TokenStream {
+        // All errors are reported at once, the block keeps the call an expression
+        let errors = self.check_arguments();
+        if !errors.is_empty() {
+            let errors = errors.iter().map(syn::Error::to_compile_error);
+            return quote! {
+                {
+                    #(#errors;)*
+                    rust_i18n::__private::Cow::<str>::Borrowed("")
+                }
+            };
+        }
+        if let Some(code) = self.try_static_token_stream() {
+            return code;
+        }
+        // Literal messages are minified at compile time, so the runtime hashing can be skipped.
+        let try_translate = if self.minify_key && self.msg.val.is_expr_lit_str() {
+            quote! { crate::_rust_i18n_try_translate_minified }
+        } else {
+            quote! { crate::_rust_i18n_try_translate }
+        };
         let (msg_key, msg_val) = if self.minify_key && self.msg.val.is_expr_lit_str() {
             let msg_val = self.msg.val.to_string().unwrap();
             let msg_key = MinifyKey::minify_key(
@@ -415,6 +912,52 @@ impl Tr {
             || quote! { &rust_i18n::locale() },
             |locale| quote! { #locale },
         );
+        // Select the variant of the key, e.g. `cta.title#variant_b`
+        let select_variant_key = self.variant.as_ref().map(|variant| {
+            let variant = variant.to_owned_token_stream();
+            quote! {
+                let msg_key = crate::_rust_i18n_select_variant_key(#locale, &msg_key, #variant);
+            }
+        });
+        // Select an item of the list by index or at random
+        let index = self
+            .index
+            .as_ref()
+            .map(|index| {
+                let index = index.to_owned_token_stream();
+                quote! { Some((#index) as usize) }
+            })
+            .unwrap_or_else(|| quote! { None });
+        let select_key = match self.random.as_ref() {
+            Some(random) => {
+                let random = random.to_owned_token_stream();
+                quote! {
+                    let msg_key = crate::_rust_i18n_select_key(#locale, &msg_key, if #random { Some(rust_i18n::random()) } else { #index });
+                }
+            }
+            None if self.index.is_some() => quote! {
+                let msg_key = crate::_rust_i18n_select_key(#locale, &msg_key, #index);
+            },
+            None => quote! {},
+        };
+        // Select the ordinal plural form, e.g. `place.two`
+        let select_ordinal_key = self.ordinal.as_ref().map(|ordinal| {
+            let ordinal = ordinal.to_owned_token_stream();
+            quote! {
+                let msg_key = crate::_rust_i18n_select_ordinal_key(#locale, &msg_key, (#ordinal) as u64);
+            }
+        });
+        // Select the cardinal plural form by the `count` argument, e.g. `files.one`, unless the `ordinal` is selected
+        let select_plural_key = self
+            .args
+            .iter()
+            .position(|arg| arg.name == "count")
+            .filter(|_| self.ordinal.is_none())
+            .map(|index| {
+                quote! {
+                    let msg_key = crate::_rust_i18n_select_plural_key(#locale, &msg_key, &values[#index]);
+                }
+            });
         let keys: Vec<_> = self.args.keys().iter().map(|v| quote! { #v }).collect();
         let values: Vec<_> = self
             .args
@@ -426,20 +969,54 @@ impl Tr {
                     .specifiers
                     .as_ref()
                     .map_or("{}".to_owned(), |s| format!("{{{}}}", s));
-                quote! { format!(#sepecifiers, #value) }
+                quote! { rust_i18n::__private::format!(#sepecifiers, #value) }
             })
             .collect();
+        // The arguments go first, so they take precedence over the fields of the `..value`
+        let keys_values = match self.args.spread.as_ref() {
+            Some(spread) => quote! {
+                let fields = rust_i18n::Interpolate::interpolate_fields(&(#spread));
+                let mut keys: rust_i18n::__private::Vec<&str> = rust_i18n::__private::Vec::from([#(#keys),*]);
+                let mut values: rust_i18n::__private::Vec<rust_i18n::__private::String> = rust_i18n::__private::Vec::from([#(#values),*]);
+                for (key, value) in fields {
+                    keys.push(key);
+                    values.push(value);
+                }
+                let (keys, values) = (keys.as_slice(), values.as_slice());
+            },
+            None => quote! {
+                let keys = &[#(#keys),*];
+                let values = &[#(#values),*];
+            },
+        };
         let logging = Self::log_missing();
+        // The inline default is only used after the fallback locales, it replaces the text of `on_missing`, e.g. the key
+        let missing = match self.default.as_ref() {
+            Some(default) => quote! {
+                {
+                    let _ = &msg_val;
+                    rust_i18n::CowStr::from(#default).into_inner()
+                }
+            },
+            None => {
+                quote! { crate::_rust_i18n_missing(rust_i18n::CowStr::from(msg_val).into_inner()) }
+            }
+        };
         if self.args.is_empty() {
             quote! {
                 {
                     let msg_val = #msg_val;
                     let msg_key = #msg_key;
-                    if let Some(translated) = crate::_rust_i18n_try_translate(#locale, &msg_key) {
-                        translated.into()
+                    #select_variant_key
+                    #select_key
+                    #select_ordinal_key
+                    if let Some(translated) = #try_translate(#locale, &msg_key) {
+                        crate::_rust_i18n_unescape(translated.into())
                     } else {
+                        rust_i18n::__private::report_missing(#locale, &msg_key);
                         #logging
-                        rust_i18n::CowStr::from(msg_val).into_inner()
+                        // The missing text is unescaped the same as the translation, e.g. `%%{` => `%{`
+                        crate::_rust_i18n_unescape(#missing)
                     }
                 }
             }
@@ -448,16 +1025,21 @@ impl Tr {
                 {
                     let msg_val = #msg_val;
                     let msg_key = #msg_key;
-                    let keys = &[#(#keys),*];
-                    let values = &[#(#values),*];
+                    #select_variant_key
+                    #select_key
+                    #select_ordinal_key
+                    #keys_values
+                    #select_plural_key
                     {
-                    if let Some(translated) = crate::_rust_i18n_try_translate(#locale, &msg_key) {
-                        let replaced = rust_i18n::replace_patterns(&translated, keys, values);
-                        std::borrow::Cow::from(replaced)
+                    if let Some(translated) = #try_translate(#locale, &msg_key) {
+                        let replaced = crate::_rust_i18n_interpolate(#locale, &translated, keys, values);
+                        rust_i18n::__private::Cow::from(replaced)
                     } else {
+                        rust_i18n::__private::report_missing(#locale, &msg_key);
                         #logging
-                        let replaced = rust_i18n::replace_patterns(rust_i18n::CowStr::from(msg_val).as_str(), keys, values);
-                        std::borrow::Cow::from(replaced)
+                        let missing = #missing;
+                        let replaced = crate::_rust_i18n_interpolate(#locale, &missing, keys, values);
+                        rust_i18n::__private::Cow::from(replaced)
                     }
                 }
                 }
@@ -489,6 +1071,7 @@ impl syn::parse::Parse for Tr {
         };
 
         result.filter_arguments()?;
+        result.apply_namespace()?;
 
         Ok(result)
     }
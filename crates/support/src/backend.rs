@@ -1,3 +1,10 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 /// I18n backend trait
@@ -6,6 +13,83 @@ pub trait Backend: Send + Sync + 'static {
     fn available_locales(&self) -> Vec<&str>;
     /// Get the translation for the given locale and key
     fn translate(&self, locale: &str, key: &str) -> Option<&str>;
+    /// Check if the key is translated in exactly the given locale, without the fallback locales
+    fn contains_key(&self, locale: &str, key: &str) -> bool {
+        self.translate(locale, key).is_some()
+    }
+    /// Get the translations for the given locale and keys, in the same order as the keys
+    fn translate_many(&self, locale: &str, keys: &[&str]) -> Vec<Option<&str>> {
+        keys.iter().map(|key| self.translate(locale, key)).collect()
+    }
+    /// Get all translations of the given locale, sorted by the key
+    ///
+    /// Returns an empty map by default if the backend can't list the translations.
+    fn translations(&self, locale: &str) -> BTreeMap<&str, &str> {
+        let _ = locale;
+        BTreeMap::new()
+    }
+    /// Iterate all `(locale, key, value)` entries of all locales, the order is unspecified
+    ///
+    /// Returns the entries of [`translations`](Backend::translations) of each available locale by default.
+    fn entries(&self) -> Box<dyn Iterator<Item = (&str, &str, &str)> + '_> {
+        Box::new(
+            self.available_locales()
+                .into_iter()
+                .flat_map(move |locale| {
+                    self.translations(locale)
+                        .into_iter()
+                        .map(move |(key, value)| (locale, key, value))
+                }),
+        )
+    }
+    /// Get the locales that have any translation of the keys starting with the prefix, sorted and deduplicated
+    ///
+    /// e.g. `checkout.` for the locales of the checkout. All [`entries`](Backend::entries) are scanned by default,
+    /// it's O(keys), override it if the backend has an index of the keys.
+    fn available_locales_for_prefix(&self, prefix: &str) -> Vec<&str> {
+        let mut locales = self
+            .entries()
+            .filter(|(_, key, _)| key.starts_with(prefix))
+            .map(|(locale, _, _)| locale)
+            .collect::<Vec<_>>();
+        locales.sort();
+        locales.dedup();
+        locales
+    }
+    /// Get the missing keys of each available locale, sorted by the locale and key
+    ///
+    /// The keys are compared with the `reference` locale, or the union of the keys of all locales if it's `None`.
+    fn missing_keys(&self, reference: Option<&str>) -> BTreeMap<&str, Vec<&str>> {
+        let locales = self.available_locales();
+        let all_translations = locales
+            .iter()
+            .map(|locale| (*locale, self.translations(locale)))
+            .collect::<BTreeMap<_, _>>();
+        let all_keys = match reference {
+            Some(reference) => self.translations(reference).into_keys().collect::<Vec<_>>(),
+            None => {
+                let mut keys = all_translations
+                    .values()
+                    .flat_map(|trs| trs.keys().copied())
+                    .collect::<Vec<_>>();
+                keys.sort();
+                keys.dedup();
+                keys
+            }
+        };
+
+        all_translations
+            .iter()
+            .map(|(locale, trs)| {
+                let missing = all_keys
+                    .iter()
+                    .filter(|key| **key != "_version" && !trs.contains_key(*key))
+                    .copied()
+                    .collect();
+                (*locale, missing)
+            })
+            .collect()
+    }
 }
 
 pub trait BackendExt: Backend {
@@ -41,6 +125,30 @@ where
             .translate(locale, key)
             .or_else(|| self.0.translate(locale, key))
     }
+
+    #[inline]
+    fn contains_key(&self, locale: &str, key: &str) -> bool {
+        self.1.contains_key(locale, key) || self.0.contains_key(locale, key)
+    }
+
+    fn translate_many(&self, locale: &str, keys: &[&str]) -> Vec<Option<&str>> {
+        let mut values = self.1.translate_many(locale, keys);
+        if values.iter().any(Option::is_none) {
+            let others = self.0.translate_many(locale, keys);
+            for (value, other) in values.iter_mut().zip(others) {
+                if value.is_none() {
+                    *value = other;
+                }
+            }
+        }
+        values
+    }
+
+    fn translations(&self, locale: &str) -> BTreeMap<&str, &str> {
+        let mut translations = self.0.translations(locale);
+        translations.extend(self.1.translations(locale));
+        translations
+    }
 }
 
 /// Simple KeyValue storage backend
@@ -101,6 +209,38 @@ impl Backend for SimpleBackend {
 
         None
     }
+
+    fn contains_key(&self, locale: &str, key: &str) -> bool {
+        self.translations
+            .get(locale)
+            .is_some_and(|trs| trs.contains_key(key))
+    }
+
+    fn translate_many(&self, locale: &str, keys: &[&str]) -> Vec<Option<&str>> {
+        // Lookup the locale only once
+        match self.translations.get(locale) {
+            Some(trs) => keys
+                .iter()
+                .map(|key| trs.get(*key).map(|s| s.as_str()))
+                .collect(),
+            None => keys.iter().map(|_| None).collect(),
+        }
+    }
+
+    fn translations(&self, locale: &str) -> BTreeMap<&str, &str> {
+        self.translations
+            .get(locale)
+            .map(|trs| trs.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect())
+            .unwrap_or_default()
+    }
+
+    fn entries(&self) -> Box<dyn Iterator<Item = (&str, &str, &str)> + '_> {
+        // Iterate the maps directly without collecting the sorted translations of each locale
+        Box::new(self.translations.iter().flat_map(|(locale, trs)| {
+            trs.iter()
+                .map(move |(k, v)| (locale.as_str(), k.as_str(), v.as_str()))
+        }))
+    }
 }
 
 impl BackendExt for SimpleBackend {}
@@ -137,6 +277,51 @@ mod tests {
         assert_eq!(backend.translate("zh-CN", "foo"), Some("Foo 测试"));
 
         assert_eq!(backend.available_locales(), vec!["en", "zh-CN"]);
+
+        assert!(backend.contains_key("en", "hello"));
+        assert!(!backend.contains_key("en", "missing"));
+        assert!(!backend.contains_key("de", "hello"));
+
+        assert_eq!(
+            backend.translate_many("zh-CN", &["hello", "missing", "foo"]),
+            vec![Some("你好"), None, Some("Foo 测试")]
+        );
+        assert_eq!(backend.translate_many("de", &["hello"]), vec![None]);
+
+        assert_eq!(
+            backend.translations("en").into_iter().collect::<Vec<_>>(),
+            vec![("foo", "Foo bar"), ("hello", "Hello")]
+        );
+        assert!(backend.translations("de").is_empty());
+
+        let mut entries = backend.entries().collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![
+                ("en", "foo", "Foo bar"),
+                ("en", "hello", "Hello"),
+                ("zh-CN", "foo", "Foo 测试"),
+                ("zh-CN", "hello", "你好"),
+            ]
+        );
+
+        let mut data_cn = HashMap::<&str, &str>::new();
+        data_cn.insert("bar", "Bar 测试");
+        backend.add_translations("zh-CN", &data_cn);
+        let missing = backend.missing_keys(None);
+        assert_eq!(missing["en"], vec!["bar"]);
+        assert!(missing["zh-CN"].is_empty());
+        let missing = backend.missing_keys(Some("en"));
+        assert!(missing["en"].is_empty());
+        assert!(missing["zh-CN"].is_empty());
+
+        assert_eq!(backend.available_locales_for_prefix("ba"), vec!["zh-CN"]);
+        assert_eq!(
+            backend.available_locales_for_prefix("hello"),
+            vec!["en", "zh-CN"]
+        );
+        assert!(backend.available_locales_for_prefix("missing").is_empty());
     }
 
     #[test]
@@ -164,7 +349,31 @@ mod tests {
         let combined = backend.extend(backend2);
         assert_eq!(combined.translate("en", "hello"), Some("Hello2"));
         assert_eq!(combined.translate("zh-CN", "hello"), Some("你好2"));
+        assert!(combined.contains_key("en", "foo"));
+        assert!(!combined.contains_key("en", "missing"));
 
         assert_eq!(combined.available_locales(), vec!["en", "zh-CN"]);
+
+        assert_eq!(
+            combined.translate_many("en", &["hello", "foo", "missing"]),
+            vec![Some("Hello2"), Some("Foo bar"), None]
+        );
+        assert_eq!(
+            combined.translations("en").into_iter().collect::<Vec<_>>(),
+            vec![("foo", "Foo bar"), ("hello", "Hello2")]
+        );
+
+        // The entries of the extended backend override the others
+        let mut entries = combined.entries().collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![
+                ("en", "foo", "Foo bar"),
+                ("en", "hello", "Hello2"),
+                ("zh-CN", "foo", "Foo 测试"),
+                ("zh-CN", "hello", "你好2"),
+            ]
+        );
     }
 }
@@ -0,0 +1,61 @@
+use alloc::string::String;
+
+/// The languages written from right to left.
+static RTL_LANGUAGES: &[&str] = &[
+    "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ks", "ps", "sd", "ug", "ur", "yi",
+];
+
+/// Check if the locale is written from right to left by its language subtag, e.g. `ar-EG`.
+///
+/// ```
+/// use rust_i18n_support::is_rtl;
+///
+/// assert!(is_rtl("ar-EG"));
+/// assert!(is_rtl("he_IL"));
+/// assert!(!is_rtl("en"));
+/// ```
+pub fn is_rtl(locale: &str) -> bool {
+    let language = locale.split(['-', '_']).next().unwrap_or_default();
+    RTL_LANGUAGES
+        .iter()
+        .any(|rtl| rtl.eq_ignore_ascii_case(language))
+}
+
+/// Wrap the value in the Unicode bidi isolates, `U+2068` (FIRST STRONG ISOLATE) and `U+2069` (POP DIRECTIONAL ISOLATE).
+///
+/// The isolated value does not affect the direction of the surrounding text,
+/// e.g. an English name interpolated into an Arabic text.
+///
+/// ```
+/// use rust_i18n_support::bidi_isolate;
+///
+/// assert_eq!(bidi_isolate("Jason"), "\u{2068}Jason\u{2069}");
+/// ```
+pub fn bidi_isolate(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 6);
+    output.push('\u{2068}');
+    output.push_str(value);
+    output.push('\u{2069}');
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_rtl() {
+        for locale in ["ar", "AR", "ar-EG", "he", "he_IL", "fa-IR", "ur", "yi"] {
+            assert!(is_rtl(locale), "{}", locale);
+        }
+        for locale in ["en", "zh-CN", "fr", "", "a", "arx"] {
+            assert!(!is_rtl(locale), "{}", locale);
+        }
+    }
+
+    #[test]
+    fn test_bidi_isolate() {
+        assert_eq!(bidi_isolate(""), "\u{2068}\u{2069}");
+        assert_eq!(bidi_isolate("Jason"), "\u{2068}Jason\u{2069}");
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use crate::{Backend, BackendExt};
+
+/// A cached translation of a found key, `None` if the key is removed from the store.
+struct Entry {
+    value: Option<&'static str>,
+    /// `None` if the entry is invalidated
+    loaded_at: Option<Instant>,
+}
+
+#[derive(Default)]
+struct Cache {
+    /// The keys that were ever found by the loader, the keys are leaked to be returned by `translations`
+    found: HashMap<String, HashMap<&'static str, Entry>>,
+    /// The keys that were never found with the time they are loaded, they are not leaked
+    missing: HashMap<String, HashMap<String, Option<Instant>>>,
+    /// The leaked values, an equal value is reused instead of leaked again
+    values: HashSet<&'static str>,
+}
+
+impl Cache {
+    fn intern(&mut self, value: String) -> &'static str {
+        if let Some(value) = self.values.get(value.as_str()) {
+            return value;
+        }
+        let value = Box::leak(value.into_boxed_str());
+        self.values.insert(value);
+        value
+    }
+}
+
+/// A backend that loads the translations from an external store on demand, e.g. a SQL database.
+///
+/// The `loader` is called with the locale and key on a cache miss, and the result (including `None`)
+/// is cached until the TTL is expired, or forever if no TTL is given.
+///
+/// The loaded values and the found keys are leaked to keep the returned `&str` valid, and they are never freed.
+/// Each distinct value and key is leaked only once, a value that changes back to an earlier value reuses it,
+/// so the leaked memory grows with all distinct values ever loaded and all keys ever found, don't use it
+/// for a store with the unbounded values. The keys that are not found are cached as the owned strings,
+/// they are freed by [`CachingBackend::invalidate`].
+///
+/// ```
+/// use std::time::Duration;
+/// use rust_i18n_support::{Backend, CachingBackend};
+///
+/// let backend = CachingBackend::new(|locale: &str, key: &str| match (locale, key) {
+///     ("en", "hello") => Some("Hello".to_string()),
+///     _ => None,
+/// })
+/// .with_ttl(Duration::from_secs(60))
+/// .with_locales(&["en"]);
+///
+/// assert_eq!(backend.translate("en", "hello"), Some("Hello"));
+/// assert_eq!(backend.translate("en", "missing"), None);
+/// ```
+pub struct CachingBackend<F> {
+    loader: F,
+    ttl: Option<Duration>,
+    locales: Vec<String>,
+    cache: RwLock<Cache>,
+}
+
+impl<F> CachingBackend<F>
+where
+    F: Fn(&str, &str) -> Option<String> + Send + Sync + 'static,
+{
+    /// Create a new CachingBackend with the loader, the results are cached forever.
+    pub fn new(loader: F) -> Self {
+        Self {
+            loader,
+            ttl: None,
+            locales: vec![],
+            cache: RwLock::new(Cache::default()),
+        }
+    }
+
+    /// Reload the cached translations after the `ttl`.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Set the locales returned by `available_locales`, the loader can't list them.
+    pub fn with_locales(mut self, locales: &[&str]) -> Self {
+        self.locales = locales.iter().map(|locale| locale.to_string()).collect();
+        self
+    }
+
+    /// Invalidate the cache, e.g. when the store is changed, the translations are reloaded on demand.
+    ///
+    /// The cached misses are freed.
+    pub fn invalidate(&self) {
+        let mut cache = self.cache.write().unwrap();
+        cache.missing.clear();
+        for entry in cache.found.values_mut().flat_map(|trs| trs.values_mut()) {
+            entry.loaded_at = None;
+        }
+    }
+
+    fn is_fresh(&self, loaded_at: Option<Instant>) -> bool {
+        loaded_at.is_some_and(|loaded_at| self.ttl.is_none_or(|ttl| loaded_at.elapsed() < ttl))
+    }
+}
+
+impl<F> Backend for CachingBackend<F>
+where
+    F: Fn(&str, &str) -> Option<String> + Send + Sync + 'static,
+{
+    fn available_locales(&self) -> Vec<&str> {
+        self.locales.iter().map(|locale| locale.as_str()).collect()
+    }
+
+    fn translate(&self, locale: &str, key: &str) -> Option<&str> {
+        {
+            let cache = self.cache.read().unwrap();
+            if let Some(entry) = cache.found.get(locale).and_then(|trs| trs.get(key)) {
+                if self.is_fresh(entry.loaded_at) {
+                    return entry.value;
+                }
+            } else if let Some(loaded_at) = cache.missing.get(locale).and_then(|keys| keys.get(key))
+            {
+                if self.is_fresh(*loaded_at) {
+                    return None;
+                }
+            }
+        }
+
+        // Load without the lock, the concurrent misses of the same key may load it more than once
+        let value = (self.loader)(locale, key);
+        let loaded_at = Some(Instant::now());
+        let mut guard = self.cache.write().unwrap();
+        let cache = &mut *guard;
+        let value = value.map(|value| cache.intern(value));
+        if let Some(entry) = cache.found.get_mut(locale).and_then(|trs| trs.get_mut(key)) {
+            *entry = Entry { value, loaded_at };
+        } else if value.is_some() {
+            if let Some(keys) = cache.missing.get_mut(locale) {
+                keys.remove(key);
+            }
+            cache
+                .found
+                .entry(locale.to_string())
+                .or_default()
+                .insert(Box::leak(key.into()), Entry { value, loaded_at });
+        } else {
+            cache
+                .missing
+                .entry(locale.to_string())
+                .or_default()
+                .insert(key.to_string(), loaded_at);
+        }
+        value
+    }
+
+    fn translations(&self, locale: &str) -> BTreeMap<&str, &str> {
+        // Only the cached translations are known
+        self.cache
+            .read()
+            .unwrap()
+            .found
+            .get(locale)
+            .map(|trs| {
+                trs.iter()
+                    .filter_map(|(key, entry)| Some((*key, entry.value?)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl<F> BackendExt for CachingBackend<F> where
+    F: Fn(&str, &str) -> Option<String> + Send + Sync + 'static
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn test_caching_backend() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let backend = CachingBackend::new(move |locale: &str, key: &str| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            (key == "hello").then(|| format!("Hello ({})", locale))
+        })
+        .with_locales(&["en", "fr"]);
+
+        assert_eq!(backend.available_locales(), vec!["en", "fr"]);
+        assert_eq!(backend.translate("en", "hello"), Some("Hello (en)"));
+        assert_eq!(backend.translate("en", "hello"), Some("Hello (en)"));
+        assert_eq!(backend.translate("en", "missing"), None);
+        assert_eq!(backend.translate("en", "missing"), None);
+        assert_eq!(backend.translate("fr", "hello"), Some("Hello (fr)"));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        backend.invalidate();
+        assert_eq!(backend.translate("en", "hello"), Some("Hello (en)"));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_caching_backend_with_ttl() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let backend = CachingBackend::new(move |_: &str, _: &str| {
+            Some(format!("v{}", counter.fetch_add(1, Ordering::SeqCst) / 2))
+        })
+        .with_ttl(Duration::from_millis(10));
+
+        let first = backend.translate("en", "hello").unwrap();
+        assert_eq!(first, "v0");
+        std::thread::sleep(Duration::from_millis(20));
+        // The unchanged value is reused
+        let second = backend.translate("en", "hello").unwrap();
+        assert!(std::ptr::eq(first, second));
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(backend.translate("en", "hello"), Some("v1"));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_caching_backend_leaks() {
+        let values = Arc::new(RwLock::new(HashMap::<&str, &str>::new()));
+        let store = values.clone();
+        let backend = CachingBackend::new(move |_: &str, key: &str| {
+            store
+                .read()
+                .unwrap()
+                .get(key)
+                .map(|value| value.to_string())
+        });
+
+        // The misses are not leaked
+        assert_eq!(backend.translate("en", "hello"), None);
+        assert_eq!(backend.cache.read().unwrap().missing["en"].len(), 1);
+        assert!(backend.cache.read().unwrap().found.is_empty());
+
+        values.write().unwrap().insert("hello", "A");
+        backend.invalidate();
+        assert!(backend.cache.read().unwrap().missing.is_empty());
+        let first = backend.translate("en", "hello").unwrap();
+        assert_eq!(first, "A");
+
+        // The value that changes back is not leaked again
+        values.write().unwrap().insert("hello", "B");
+        backend.invalidate();
+        assert_eq!(backend.translate("en", "hello"), Some("B"));
+        values.write().unwrap().insert("hello", "A");
+        backend.invalidate();
+        assert!(std::ptr::eq(
+            first,
+            backend.translate("en", "hello").unwrap()
+        ));
+        assert_eq!(backend.cache.read().unwrap().values.len(), 2);
+
+        // The removed key is kept as a found key without a value
+        values.write().unwrap().clear();
+        backend.invalidate();
+        assert_eq!(backend.translate("en", "hello"), None);
+        assert!(backend.translations("en").is_empty());
+        assert!(backend.cache.read().unwrap().missing.is_empty());
+    }
+}
@@ -3,12 +3,12 @@
 //!
 //! See `Manifest::from_slice`.
 
+use crate::{I18nError, OnMissing};
 use itertools::Itertools;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::io;
-use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -17,8 +17,9 @@ pub struct I18nConfig {
     pub default_locale: String,
     #[serde(default = "available_locales")]
     pub available_locales: Vec<String>,
-    #[serde(default = "load_path")]
-    pub load_path: String,
+    /// The paths of the locales, the later paths override the earlier on key conflict.
+    #[serde(default = "load_path", deserialize_with = "deserialize_load_path")]
+    pub load_path: Vec<String>,
     #[serde(default = "fallback")]
     pub fallback: Vec<String>,
     #[serde(default = "minify_key")]
@@ -29,6 +30,110 @@ pub struct I18nConfig {
     pub minify_key_prefix: String,
     #[serde(default = "minify_key_thresh")]
     pub minify_key_thresh: usize,
+    #[serde(default = "on_missing")]
+    pub on_missing: OnMissing,
+    /// The open and close delimiters of the variables separated by a space, e.g. `"{{ }}"`.
+    #[serde(default = "interpolation")]
+    pub interpolation: String,
+    /// The paths of the attributes to extract the first string literal as a key by `cargo i18n`, e.g. `["error"]`.
+    #[serde(default = "extract_attributes")]
+    pub extract_attributes: Vec<String>,
+    /// The method names to extract the first string literal as a key by `cargo i18n`, e.g. `["label"]`.
+    #[serde(default = "extract_methods")]
+    pub extract_methods: Vec<String>,
+    /// The macros wrapping `t!` to extract the first string literal as a key by `cargo i18n`, e.g. `["tr_label"]`.
+    ///
+    /// The `macro_rules!` wrappers defined in the same file are detected without it.
+    #[serde(default = "extract_macros")]
+    pub extract_macros: Vec<String>,
+    /// Fail the build if any translation of the non-default locales is empty or whitespace-only.
+    #[serde(default = "forbid_empty")]
+    pub forbid_empty: bool,
+    /// Format the numeric arguments with the group and decimal separators of the locale, e.g. `1,234` in `en`.
+    #[serde(default = "format_numbers")]
+    pub format_numbers: bool,
+    /// Wrap the interpolated values in the Unicode bidi isolates for all locales, they are always wrapped for the RTL locales.
+    #[serde(default = "bidi_isolate")]
+    pub bidi_isolate: bool,
+    /// Warn on the literal keys longer than this length by `cargo i18n`, `0` to disable.
+    /// With `minify-key`, the longer keys are hashed even if they are under `minify-key-thresh`.
+    #[serde(default = "max_key_len")]
+    pub max_key_len: usize,
+    /// The locale aliases mapped to the preferred locales before lookup, e.g. `{ zh = "zh-CN" }`.
+    #[serde(default = "aliases")]
+    pub aliases: BTreeMap<String, String>,
+    /// The file formats that `cargo i18n` writes the locales in, by the extension, e.g. `{ fr = "json" }`, default: `yml`.
+    #[serde(default = "locale_formats")]
+    pub locale_formats: BTreeMap<String, String>,
+    /// The file extensions of the templates to extract the keys by `cargo i18n`, e.g. `["html", "tera"]`.
+    #[serde(default = "template_extensions")]
+    pub template_extensions: Vec<String>,
+    /// The translation function name in the templates, e.g. `t` for `{{ t(key="hello") }}`.
+    #[serde(default = "template_function")]
+    pub template_function: String,
+    /// The separator of the nested keys, e.g. `/` for `auth/login/title`, mixing separators in one project is unsupported.
+    #[serde(default = "key_separator")]
+    pub key_separator: String,
+    /// The strict locales that never fall back to other locales, e.g. `["de", "fr"]` for the legal texts.
+    #[serde(default = "no_fallback_locales")]
+    pub no_fallback_locales: Vec<String>,
+    /// The default locales of the key namespaces, e.g. `{ "vendor.payments" = "fr" }` for the keys written in French.
+    /// The longest matching namespace wins, and the other keys use `default-locale`.
+    #[serde(default = "namespace_default_locales")]
+    pub namespace_default_locales: BTreeMap<String, String>,
+    /// Generate the sorted translation tables of all locales for `t_static!`, the lookup without allocation and hashing.
+    #[serde(default = "static_table")]
+    pub static_table: bool,
+    /// Store the translations of each locale in one string with an offset index, for the smaller binaries.
+    #[serde(default = "packed")]
+    pub packed: bool,
+    /// Split the generated translations of a locale into the chunks of this many keys, for the faster builds of the large locales, `0` to never split.
+    #[serde(default = "chunk_size")]
+    pub chunk_size: usize,
+    /// Inline at most this many keys of each locale in the generated code, the others are packed into one string
+    /// that is unpacked when the backend is initialized, for the faster builds of the huge locales, `0` to inline all.
+    #[serde(default = "max_inline_keys")]
+    pub max_inline_keys: usize,
+    /// Keep the translations of each locale as the static texts, so the locales that are not used can be freed at runtime
+    /// by `prune_locales!`, for the deployments that only serve one language.
+    #[serde(default = "runtime_prune")]
+    pub runtime_prune: bool,
+    /// Generate the pseudo-locale `en-XA` from the default locale at runtime, for testing the layout and finding the hard-coded texts.
+    #[serde(default = "pseudo_locale")]
+    pub pseudo_locale: bool,
+    /// The percentage of the length that the pseudo-localized texts are padded by, e.g. `30` for `[!! Ṁéśśàĝé !]`.
+    #[serde(default = "pseudo_expansion")]
+    pub pseudo_expansion: usize,
+    /// The locales that must have all keys of the default locale, the build fails if any key is missing, e.g. `["fr"]`.
+    #[serde(default = "required_locales")]
+    pub required_locales: Vec<String>,
+    /// The minimum percentages of the keys of the default locale that the locales must have, the build fails if any is lower,
+    /// e.g. `{ fr = 90 }`, the `*` is for all locales that are not listed.
+    #[serde(default = "min_coverage")]
+    pub min_coverage: BTreeMap<String, u8>,
+    /// Check the arguments of `t!` with a literal key against the placeholders of the default locale at compile time.
+    #[serde(default = "check_args")]
+    pub check_args: bool,
+    /// Check the translations against the `max_len` of their keys at compile time, the build fails if any is longer.
+    #[serde(default = "check_max_len")]
+    pub check_max_len: bool,
+    /// Replace the straight quotes in the translations with the typographic quotes of each locale, e.g. `„Hallo“` in `de`.
+    #[serde(default = "smart_quotes")]
+    pub smart_quotes: bool,
+    /// Write the untranslated texts of `cargo i18n` into the directory of their namespace, e.g. `auth/TODO.yml` for `auth.login.title`.
+    #[serde(default = "split_by_namespace")]
+    pub split_by_namespace: bool,
+    /// Canonicalize the locales of the files in the outputs of `cargo i18n`, e.g. `en-us` and `EN-US` are merged into `en-US`.
+    #[serde(default = "canonical_locales")]
+    pub canonical_locales: bool,
+    /// The locales whose translations must be approved, `cargo i18n lint` fails if any key is marked `needs-review`
+    /// by `_meta: { status: ... }`, e.g. `["fr"]`, the `*` is for all locales.
+    #[serde(default = "require_approved")]
+    pub require_approved: Vec<String>,
+    /// Extract the keys of `cargo i18n` as the exact values of the literals, the whitespace of the multi-line keys
+    /// is kept instead of collapsed into one space, the same keys looked up by `t!`.
+    #[serde(default = "verbatim_keys")]
+    pub verbatim_keys: bool,
 }
 
 impl Default for I18nConfig {
@@ -36,12 +141,44 @@ impl Default for I18nConfig {
         Self {
             default_locale: "en".to_string(),
             available_locales: vec!["en".to_string()],
-            load_path: "./locales".to_string(),
+            load_path: vec!["./locales".to_string()],
             fallback: vec![],
             minify_key: crate::DEFAULT_MINIFY_KEY,
             minify_key_len: crate::DEFAULT_MINIFY_KEY_LEN,
             minify_key_prefix: crate::DEFAULT_MINIFY_KEY_PREFIX.to_string(),
             minify_key_thresh: crate::DEFAULT_MINIFY_KEY_THRESH,
+            on_missing: OnMissing::default(),
+            interpolation: "%{ }".to_string(),
+            extract_attributes: vec![],
+            extract_methods: vec![],
+            extract_macros: vec![],
+            forbid_empty: false,
+            format_numbers: false,
+            bidi_isolate: false,
+            max_key_len: 0,
+            aliases: BTreeMap::new(),
+            locale_formats: BTreeMap::new(),
+            template_extensions: vec![],
+            template_function: "t".to_string(),
+            key_separator: crate::DEFAULT_KEY_SEPARATOR.to_string(),
+            no_fallback_locales: vec![],
+            namespace_default_locales: BTreeMap::new(),
+            static_table: false,
+            packed: false,
+            chunk_size: 4096,
+            max_inline_keys: 0,
+            runtime_prune: false,
+            pseudo_locale: false,
+            pseudo_expansion: crate::DEFAULT_PSEUDO_EXPANSION,
+            required_locales: vec![],
+            min_coverage: BTreeMap::new(),
+            check_args: false,
+            check_max_len: false,
+            smart_quotes: false,
+            split_by_namespace: false,
+            canonical_locales: true,
+            require_approved: vec![],
+            verbatim_keys: false,
         }
     }
 }
@@ -51,36 +188,154 @@ impl I18nConfig {
         Self::default()
     }
 
-    pub fn load(cargo_root: &Path) -> io::Result<Self> {
+    /// Get the threshold of `minify-key` lowered by `max-key-len`, the same for the extractor and the runtime.
+    pub fn effective_minify_key_thresh(&self) -> usize {
+        match self.max_key_len {
+            0 => self.minify_key_thresh,
+            max_key_len => self.minify_key_thresh.min(max_key_len),
+        }
+    }
+
+    /// Get the default locale of the key, by the longest matching namespace of `namespace-default-locales`.
+    pub fn default_locale_of(&self, key: &str) -> &str {
+        let namespaces = self
+            .namespace_default_locales
+            .iter()
+            .map(|(namespace, locale)| (namespace.as_str(), locale.as_str()))
+            .collect::<Vec<_>>();
+        crate::namespace_default_locale(&namespaces, key, &self.key_separator)
+            .unwrap_or(&self.default_locale)
+    }
+
+    /// Load the config from `package.metadata.i18n` of the `Cargo.toml` in `cargo_root`.
+    ///
+    /// The config of the workspace is inherited, from `workspace.metadata.i18n` (or `package.metadata.i18n`)
+    /// of the nearest `Cargo.toml` with `[workspace]` in `cargo_root` or its parents.
+    /// Each key of the crate replaces the same key of the workspace as a whole, e.g. `aliases` is not merged.
+    /// The `load-path` inherited from the workspace is relative to the workspace root.
+    ///
+    /// Returns [`I18nError::Io`] if the `Cargo.toml` can't be read, or [`I18nError::Config`] if the config is invalid.
+    pub fn load(cargo_root: &Path) -> Result<Self, I18nError> {
         let cargo_file = cargo_root.join("Cargo.toml");
-        let mut file = fs::File::open(&cargo_file)
-            .unwrap_or_else(|e| panic!("Fail to open {}, {}", cargo_file.display(), e));
+        let contents = fs::read_to_string(&cargo_file).map_err(|source| I18nError::Io {
+            path: cargo_file.clone(),
+            source,
+        })?;
 
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
+        let config = match Self::load_workspace_table(cargo_root)? {
+            Some((workspace_root, workspace)) => {
+                Self::parse_with_workspace(&contents, workspace, &workspace_root)
+            }
+            None => Self::parse(&contents),
+        };
+        config.map_err(|err| with_config_file(err, cargo_file))
+    }
 
-        Self::parse(&contents)
+    /// Find the nearest workspace of the crate, and its `workspace.metadata.i18n` or `package.metadata.i18n`.
+    fn load_workspace_table(
+        cargo_root: &Path,
+    ) -> Result<Option<(PathBuf, toml::Table)>, I18nError> {
+        for dir in cargo_root.ancestors() {
+            let cargo_file = dir.join("Cargo.toml");
+            let Ok(contents) = fs::read_to_string(&cargo_file) else {
+                continue;
+            };
+            let manifest =
+                parse_table(&contents).map_err(|err| with_config_file(err, cargo_file))?;
+            if !manifest.contains_key("workspace") {
+                continue;
+            }
+            let table = ["workspace", "package"].iter().find_map(|section| {
+                match manifest.get(*section)?.get("metadata")?.get("i18n")? {
+                    toml::Value::Table(table) => Some(table.clone()),
+                    _ => None,
+                }
+            });
+            return Ok(table.map(|table| (dir.to_path_buf(), table)));
+        }
+        Ok(None)
     }
 
-    pub fn parse(contents: &str) -> io::Result<Self> {
+    /// Parse the config of the crate over the config of the workspace in `workspace_root`.
+    fn parse_with_workspace(
+        contents: &str,
+        mut workspace: toml::Table,
+        workspace_root: &Path,
+    ) -> Result<Self, I18nError> {
+        // The inherited paths are relative to the workspace root, the crate joins the absolute paths as is
+        if let Some(load_path) = workspace.get_mut("load-path") {
+            let rebase = |path: &mut toml::Value| {
+                if let toml::Value::String(path) = path {
+                    *path = workspace_root.join(&*path).display().to_string();
+                }
+            };
+            match load_path {
+                toml::Value::Array(paths) => paths.iter_mut().for_each(rebase),
+                path => rebase(path),
+            }
+        }
+
+        let manifest = parse_table(contents)?;
+        let package = manifest
+            .get("package")
+            .and_then(|package| package.get("metadata")?.get("i18n"));
+        if let Some(toml::Value::Table(table)) = manifest.get("i18n").or(package) {
+            workspace.extend(table.clone());
+        }
+
+        let config: Self = toml::Value::Table(workspace)
+            .try_into()
+            .map_err(config_error)?;
+        Ok(config.with_default_locale())
+    }
+
+    /// Parse the config from the contents of a `Cargo.toml`, the default config if there is no `[package.metadata.i18n]`.
+    ///
+    /// Returns [`I18nError::Config`] without the file if the config is invalid.
+    pub fn parse(contents: &str) -> Result<Self, I18nError> {
         if !contents.contains("[i18n]") && !contents.contains("[package.metadata.i18n]") {
             return Ok(I18nConfig::default());
         }
         let contents = contents.replace("[package.metadata.i18n]", "[i18n]");
-        let mut config: MainConfig = toml::from_str(&contents)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        let config: MainConfig = toml::from_str(&contents).map_err(config_error)?;
+
+        Ok(config.i18n.with_default_locale())
+    }
 
-        // Push default_locale
-        config
-            .i18n
-            .available_locales
-            .insert(0, config.i18n.default_locale.clone());
+    /// Push the default locale to the front of the available locales.
+    fn with_default_locale(mut self) -> Self {
+        self.available_locales
+            .insert(0, self.default_locale.clone());
 
         // unqiue
-        config.i18n.available_locales =
-            config.i18n.available_locales.into_iter().unique().collect();
+        self.available_locales = self.available_locales.into_iter().unique().collect();
+        self
+    }
+}
+
+fn parse_table(contents: &str) -> Result<toml::Table, I18nError> {
+    toml::from_str(contents).map_err(config_error)
+}
+
+/// The config error of the TOML, the file is set by [`with_config_file`].
+fn config_error(err: impl std::fmt::Display) -> I18nError {
+    I18nError::Config {
+        file: None,
+        message: err.to_string(),
+    }
+}
 
-        Ok(config.i18n)
+/// Set the file of the config error that is parsed from the file.
+fn with_config_file(err: I18nError, cargo_file: PathBuf) -> I18nError {
+    match err {
+        I18nError::Config {
+            file: None,
+            message,
+        } => I18nError::Config {
+            file: Some(cargo_file),
+            message,
+        },
+        err => err,
     }
 }
 
@@ -92,10 +347,28 @@ fn available_locales() -> Vec<String> {
     I18nConfig::default().available_locales
 }
 
-fn load_path() -> String {
+fn load_path() -> Vec<String> {
     I18nConfig::default().load_path
 }
 
+/// Deserialize the `load-path` from a path or a list of paths.
+fn deserialize_load_path<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum LoadPath {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match LoadPath::deserialize(deserializer)? {
+        LoadPath::One(path) => vec![path],
+        LoadPath::Many(paths) => paths,
+    })
+}
+
 fn fallback() -> Vec<String> {
     I18nConfig::default().fallback
 }
@@ -116,6 +389,134 @@ fn minify_key_thresh() -> usize {
     I18nConfig::default().minify_key_thresh
 }
 
+fn on_missing() -> OnMissing {
+    I18nConfig::default().on_missing
+}
+
+fn interpolation() -> String {
+    I18nConfig::default().interpolation
+}
+
+fn extract_attributes() -> Vec<String> {
+    I18nConfig::default().extract_attributes
+}
+
+fn extract_methods() -> Vec<String> {
+    I18nConfig::default().extract_methods
+}
+
+fn extract_macros() -> Vec<String> {
+    I18nConfig::default().extract_macros
+}
+
+fn forbid_empty() -> bool {
+    I18nConfig::default().forbid_empty
+}
+
+fn format_numbers() -> bool {
+    I18nConfig::default().format_numbers
+}
+
+fn bidi_isolate() -> bool {
+    I18nConfig::default().bidi_isolate
+}
+
+fn max_key_len() -> usize {
+    I18nConfig::default().max_key_len
+}
+
+fn aliases() -> BTreeMap<String, String> {
+    I18nConfig::default().aliases
+}
+
+fn namespace_default_locales() -> BTreeMap<String, String> {
+    I18nConfig::default().namespace_default_locales
+}
+
+fn locale_formats() -> BTreeMap<String, String> {
+    I18nConfig::default().locale_formats
+}
+
+fn template_extensions() -> Vec<String> {
+    I18nConfig::default().template_extensions
+}
+
+fn template_function() -> String {
+    I18nConfig::default().template_function
+}
+
+fn key_separator() -> String {
+    I18nConfig::default().key_separator
+}
+
+fn no_fallback_locales() -> Vec<String> {
+    I18nConfig::default().no_fallback_locales
+}
+
+fn static_table() -> bool {
+    I18nConfig::default().static_table
+}
+
+fn packed() -> bool {
+    I18nConfig::default().packed
+}
+
+fn runtime_prune() -> bool {
+    I18nConfig::default().runtime_prune
+}
+
+fn chunk_size() -> usize {
+    I18nConfig::default().chunk_size
+}
+
+fn max_inline_keys() -> usize {
+    I18nConfig::default().max_inline_keys
+}
+
+fn pseudo_locale() -> bool {
+    I18nConfig::default().pseudo_locale
+}
+
+fn pseudo_expansion() -> usize {
+    I18nConfig::default().pseudo_expansion
+}
+
+fn required_locales() -> Vec<String> {
+    I18nConfig::default().required_locales
+}
+
+fn min_coverage() -> BTreeMap<String, u8> {
+    I18nConfig::default().min_coverage
+}
+
+fn check_args() -> bool {
+    I18nConfig::default().check_args
+}
+
+fn check_max_len() -> bool {
+    I18nConfig::default().check_max_len
+}
+
+fn smart_quotes() -> bool {
+    I18nConfig::default().smart_quotes
+}
+
+fn split_by_namespace() -> bool {
+    I18nConfig::default().split_by_namespace
+}
+
+fn canonical_locales() -> bool {
+    I18nConfig::default().canonical_locales
+}
+
+fn require_approved() -> Vec<String> {
+    I18nConfig::default().require_approved
+}
+
+fn verbatim_keys() -> bool {
+    I18nConfig::default().verbatim_keys
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct MainConfig {
@@ -134,17 +535,86 @@ fn test_parse() {
         minify-key-len = 12
         minify-key-prefix = "T_"
         minify-key-thresh = 16
+        on-missing = "humanize"
+        interpolation = "{{ }}"
+        extract-attributes = ["error"]
+        extract-methods = ["label", "title"]
+        extract-macros = ["tr_label"]
+        forbid-empty = true
+        format-numbers = true
+        bidi-isolate = true
+        max-key-len = 10
+        aliases = { zh = "zh-CN", no = "nb" }
+        locale-formats = { zh-CN = "json" }
+        template-extensions = ["html", "tera"]
+        template-function = "translate"
+        key-separator = "/"
+        no-fallback-locales = ["de"]
+        namespace-default-locales = { "vendor/payments" = "zh-CN" }
+        static-table = true
+        packed = true
+        chunk-size = 1000
+        max-inline-keys = 2000
+        runtime-prune = true
+        pseudo-locale = true
+        pseudo-expansion = 50
+        required-locales = ["zh-CN"]
+        min-coverage = { "*" = 80, zh-CN = 95 }
+        check-args = true
+        check-max-len = true
+        smart-quotes = true
+        split-by-namespace = true
+        canonical-locales = false
+        require-approved = ["zh-CN"]
+        verbatim-keys = true
     "#;
 
     let cfg = I18nConfig::parse(contents).unwrap();
     assert_eq!(cfg.default_locale, "en");
     assert_eq!(cfg.available_locales, vec!["en", "zh-CN"]);
-    assert_eq!(cfg.load_path, "./my-locales");
+    assert_eq!(cfg.load_path, vec!["./my-locales"]);
     assert_eq!(cfg.fallback, vec!["zh"]);
     assert!(cfg.minify_key);
     assert_eq!(cfg.minify_key_len, 12);
     assert_eq!(cfg.minify_key_prefix, "T_");
     assert_eq!(cfg.minify_key_thresh, 16);
+    assert_eq!(cfg.on_missing, OnMissing::Humanize);
+    assert_eq!(cfg.interpolation, "{{ }}");
+    assert_eq!(cfg.extract_attributes, vec!["error"]);
+    assert_eq!(cfg.extract_methods, vec!["label", "title"]);
+    assert_eq!(cfg.extract_macros, vec!["tr_label"]);
+    assert!(cfg.forbid_empty);
+    assert!(cfg.format_numbers);
+    assert!(cfg.bidi_isolate);
+    assert_eq!(cfg.max_key_len, 10);
+    assert_eq!(cfg.effective_minify_key_thresh(), 10);
+    assert_eq!(cfg.aliases["zh"], "zh-CN");
+    assert_eq!(cfg.aliases["no"], "nb");
+    assert_eq!(cfg.locale_formats["zh-CN"], "json");
+    assert_eq!(cfg.template_extensions, vec!["html", "tera"]);
+    assert_eq!(cfg.template_function, "translate");
+    assert_eq!(cfg.key_separator, "/");
+    assert_eq!(cfg.no_fallback_locales, vec!["de"]);
+    assert_eq!(cfg.namespace_default_locales["vendor/payments"], "zh-CN");
+    assert_eq!(cfg.default_locale_of("vendor/payments/title"), "zh-CN");
+    assert_eq!(cfg.default_locale_of("vendor/title"), "en");
+    assert!(cfg.static_table);
+    assert!(cfg.packed);
+    assert_eq!(cfg.chunk_size, 1000);
+    assert_eq!(cfg.max_inline_keys, 2000);
+    assert!(cfg.runtime_prune);
+    assert!(cfg.pseudo_locale);
+    assert_eq!(cfg.pseudo_expansion, 50);
+    assert_eq!(cfg.required_locales, vec!["zh-CN"]);
+    assert_eq!(cfg.min_coverage["*"], 80);
+    assert_eq!(cfg.min_coverage["zh-CN"], 95);
+    assert!(cfg.check_args);
+    assert!(cfg.check_max_len);
+    assert!(cfg.smart_quotes);
+    assert!(cfg.split_by_namespace);
+    assert!(!cfg.canonical_locales);
+    assert_eq!(cfg.require_approved, vec!["zh-CN"]);
+    assert!(cfg.verbatim_keys);
 
     let contents = r#"
         [i18n]
@@ -154,13 +624,57 @@ fn test_parse() {
     let cfg = I18nConfig::parse(contents).unwrap();
     assert_eq!(cfg.default_locale, "en");
     assert_eq!(cfg.available_locales, vec!["en", "zh-CN", "de"]);
-    assert_eq!(cfg.load_path, "./my-locales");
+    assert_eq!(cfg.load_path, vec!["./my-locales"]);
 
     let contents = "";
     let cfg = I18nConfig::parse(contents).unwrap();
     assert_eq!(cfg.default_locale, "en");
     assert_eq!(cfg.available_locales, vec!["en"]);
-    assert_eq!(cfg.load_path, "./locales");
+    assert_eq!(cfg.load_path, vec!["./locales"]);
+    assert_eq!(cfg.on_missing, OnMissing::Key);
+    assert_eq!(cfg.interpolation, "%{ }");
+    assert!(cfg.extract_attributes.is_empty());
+    assert!(cfg.extract_methods.is_empty());
+    assert!(cfg.extract_macros.is_empty());
+    assert!(!cfg.forbid_empty);
+    assert!(!cfg.format_numbers);
+    assert!(!cfg.bidi_isolate);
+    assert_eq!(cfg.max_key_len, 0);
+    assert_eq!(cfg.effective_minify_key_thresh(), cfg.minify_key_thresh);
+    assert!(cfg.aliases.is_empty());
+    assert!(cfg.locale_formats.is_empty());
+    assert!(cfg.template_extensions.is_empty());
+    assert_eq!(cfg.template_function, "t");
+    assert_eq!(cfg.key_separator, ".");
+    assert!(cfg.no_fallback_locales.is_empty());
+    assert!(cfg.namespace_default_locales.is_empty());
+    assert_eq!(cfg.default_locale_of("vendor.payments.title"), "en");
+    assert!(!cfg.static_table);
+    assert!(!cfg.packed);
+    assert_eq!(cfg.chunk_size, 4096);
+    assert_eq!(cfg.max_inline_keys, 0);
+    assert!(!cfg.runtime_prune);
+    assert!(!cfg.pseudo_locale);
+    assert_eq!(cfg.pseudo_expansion, 30);
+    assert!(cfg.required_locales.is_empty());
+    assert!(cfg.min_coverage.is_empty());
+    assert!(!cfg.check_args);
+    assert!(!cfg.check_max_len);
+    assert!(!cfg.smart_quotes);
+    assert!(!cfg.split_by_namespace);
+    assert!(cfg.canonical_locales);
+    assert!(cfg.require_approved.is_empty());
+    assert!(!cfg.verbatim_keys);
+}
+
+#[test]
+fn test_parse_with_load_paths() {
+    let contents = r#"
+        [i18n]
+        load-path = ["./vendor-locales", "./locales"]
+    "#;
+    let cfg = I18nConfig::parse(contents).unwrap();
+    assert_eq!(cfg.load_path, vec!["./vendor-locales", "./locales"]);
 }
 
 #[test]
@@ -180,7 +694,7 @@ fn test_parse_with_metadata() {
     let cfg = I18nConfig::parse(contents).unwrap();
     assert_eq!(cfg.default_locale, "en");
     assert_eq!(cfg.available_locales, vec!["en", "zh-CN"]);
-    assert_eq!(cfg.load_path, "./my-locales");
+    assert_eq!(cfg.load_path, vec!["./my-locales"]);
     assert_eq!(cfg.fallback, vec!["zh"]);
     assert!(cfg.minify_key);
     assert_eq!(cfg.minify_key_len, 12);
@@ -195,7 +709,7 @@ fn test_load_default() {
     let cfg = I18nConfig::load(workdir).unwrap();
     assert_eq!(cfg.default_locale, "en");
     assert_eq!(cfg.available_locales, vec!["en"]);
-    assert_eq!(cfg.load_path, "./locales");
+    assert_eq!(cfg.load_path, vec!["./locales"]);
 }
 
 #[test]
@@ -207,3 +721,103 @@ fn test_load() {
     assert_eq!(cfg.default_locale, "en");
     assert_eq!(cfg.available_locales, vec!["en", "zh-CN"]);
 }
+
+#[test]
+fn test_load_errors() {
+    let crate_root = std::env::temp_dir().join("rust-i18n-test-config-errors");
+    let _ = fs::remove_dir_all(&crate_root);
+    fs::create_dir_all(&crate_root).unwrap();
+
+    // The missing `Cargo.toml` is an IO error instead of panic
+    match I18nConfig::load(&crate_root) {
+        Err(I18nError::Io { path, source }) => {
+            assert_eq!(path, crate_root.join("Cargo.toml"));
+            assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        }
+        result => panic!("Unexpected result: {:?}", result),
+    }
+
+    fs::write(
+        crate_root.join("Cargo.toml"),
+        "[package.metadata.i18n]\ndefault-locale = 1\n",
+    )
+    .unwrap();
+    let err = I18nConfig::load(&crate_root).unwrap_err();
+    assert!(
+        matches!(&err, I18nError::Config { file: Some(file), message } if *file == crate_root.join("Cargo.toml") && message.contains("default-locale")),
+        "{:?}",
+        err
+    );
+    assert!(err.to_string().contains("Cargo.toml"), "{}", err);
+
+    let err = I18nConfig::parse("[i18n]\nfallback = [").unwrap_err();
+    assert!(
+        matches!(err, I18nError::Config { file: None, .. }),
+        "{:?}",
+        err
+    );
+
+    fs::remove_dir_all(&crate_root).unwrap();
+}
+
+#[test]
+fn test_load_workspace() {
+    let workspace_root = std::env::temp_dir().join("rust-i18n-test-config-workspace");
+    let _ = fs::remove_dir_all(&workspace_root);
+    let crate_root = workspace_root.join("crates/app");
+    fs::create_dir_all(&crate_root).unwrap();
+    fs::write(
+        workspace_root.join("Cargo.toml"),
+        r#"
+        [workspace]
+        members = ["crates/app"]
+
+        [workspace.metadata.i18n]
+        default-locale = "fr"
+        available-locales = ["de"]
+        load-path = ["locales", "shared-locales"]
+        aliases = { zh = "zh-CN" }
+        "#,
+    )
+    .unwrap();
+    fs::write(
+        crate_root.join("Cargo.toml"),
+        r#"
+        [package]
+        name = "app"
+
+        [package.metadata.i18n]
+        available-locales = ["en"]
+        aliases = { no = "nb" }
+        "#,
+    )
+    .unwrap();
+
+    // The keys of the crate take precedence over the workspace
+    let cfg = I18nConfig::load(&crate_root).unwrap();
+    assert_eq!(cfg.default_locale, "fr");
+    assert_eq!(cfg.available_locales, vec!["fr", "en"]);
+    assert_eq!(
+        cfg.aliases,
+        BTreeMap::from([("no".to_string(), "nb".to_string())])
+    );
+    // The inherited paths are relative to the workspace root
+    assert_eq!(
+        cfg.load_path,
+        vec![
+            workspace_root.join("locales").display().to_string(),
+            workspace_root.join("shared-locales").display().to_string(),
+        ]
+    );
+
+    fs::write(
+        crate_root.join("Cargo.toml"),
+        "[package]\nname = \"app\"\n\n[package.metadata.i18n]\nload-path = \"./my-locales\"\n",
+    )
+    .unwrap();
+    let cfg = I18nConfig::load(&crate_root).unwrap();
+    assert_eq!(cfg.available_locales, vec!["fr", "de"]);
+    assert_eq!(cfg.load_path, vec!["./my-locales"]);
+
+    fs::remove_dir_all(&workspace_root).unwrap();
+}
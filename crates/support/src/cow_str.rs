@@ -1,5 +1,8 @@
-use std::borrow::Cow;
-use std::sync::Arc;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
 
 /// A wrapper for `Cow<'a, str>` that is specifically designed for use with the `t!` macro.
 ///
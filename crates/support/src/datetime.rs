@@ -0,0 +1,716 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::lookup_fallback;
+
+/// The units of the relative time, from the smallest, with their length in seconds.
+static UNITS: [u64; 7] = [1, 60, 3_600, 86_400, 604_800, 2_592_000, 31_536_000];
+
+/// The relative time patterns of a locale, `{0}` is the number or the unit.
+struct RelativeTime {
+    now: &'static str,
+    past: &'static str,
+    future: &'static str,
+    /// The plural forms of second, minute, hour, day, week, month and year, selected by `plural`
+    units: [[&'static str; 3]; 7],
+    plural: fn(u64) -> usize,
+}
+
+/// The date patterns of a locale, the CLDR date format symbols `y`, `M`, `d` and the quoted literals are supported.
+struct DateFormat {
+    /// The patterns of [`DateStyle::Short`], [`DateStyle::Medium`] and [`DateStyle::Long`]
+    patterns: [&'static str; 3],
+    abbr_months: &'static [&'static str; 12],
+    months: &'static [&'static str; 12],
+}
+
+/// The plural forms `[one, few, other]`, the languages without `few` repeat the `other` form.
+fn plural_one(n: u64) -> usize {
+    if n == 1 {
+        0
+    } else {
+        2
+    }
+}
+
+fn plural_zero_one(n: u64) -> usize {
+    if n <= 1 {
+        0
+    } else {
+        2
+    }
+}
+
+fn plural_none(_: u64) -> usize {
+    2
+}
+
+fn plural_slavic(n: u64) -> usize {
+    match (n % 10, n % 100) {
+        (1, _) if n % 100 != 11 => 0,
+        (2..=4, _) if !(12..=14).contains(&(n % 100)) => 1,
+        _ => 2,
+    }
+}
+
+/// The relative time patterns of the common locales, sorted by the locale.
+static RELATIVE_TIMES: &[(&str, RelativeTime)] = &[
+    (
+        "de",
+        RelativeTime {
+            now: "jetzt",
+            past: "vor {0}",
+            future: "in {0}",
+            units: [
+                ["{0} Sekunde", "{0} Sekunden", "{0} Sekunden"],
+                ["{0} Minute", "{0} Minuten", "{0} Minuten"],
+                ["{0} Stunde", "{0} Stunden", "{0} Stunden"],
+                ["{0} Tag", "{0} Tagen", "{0} Tagen"],
+                ["{0} Woche", "{0} Wochen", "{0} Wochen"],
+                ["{0} Monat", "{0} Monaten", "{0} Monaten"],
+                ["{0} Jahr", "{0} Jahren", "{0} Jahren"],
+            ],
+            plural: plural_one,
+        },
+    ),
+    (
+        "en",
+        RelativeTime {
+            now: "now",
+            past: "{0} ago",
+            future: "in {0}",
+            units: [
+                ["{0} second", "{0} seconds", "{0} seconds"],
+                ["{0} minute", "{0} minutes", "{0} minutes"],
+                ["{0} hour", "{0} hours", "{0} hours"],
+                ["{0} day", "{0} days", "{0} days"],
+                ["{0} week", "{0} weeks", "{0} weeks"],
+                ["{0} month", "{0} months", "{0} months"],
+                ["{0} year", "{0} years", "{0} years"],
+            ],
+            plural: plural_one,
+        },
+    ),
+    (
+        "es",
+        RelativeTime {
+            now: "ahora",
+            past: "hace {0}",
+            future: "dentro de {0}",
+            units: [
+                ["{0} segundo", "{0} segundos", "{0} segundos"],
+                ["{0} minuto", "{0} minutos", "{0} minutos"],
+                ["{0} hora", "{0} horas", "{0} horas"],
+                ["{0} día", "{0} días", "{0} días"],
+                ["{0} semana", "{0} semanas", "{0} semanas"],
+                ["{0} mes", "{0} meses", "{0} meses"],
+                ["{0} año", "{0} años", "{0} años"],
+            ],
+            plural: plural_one,
+        },
+    ),
+    (
+        "fr",
+        RelativeTime {
+            now: "maintenant",
+            past: "il y a {0}",
+            future: "dans {0}",
+            units: [
+                ["{0} seconde", "{0} secondes", "{0} secondes"],
+                ["{0} minute", "{0} minutes", "{0} minutes"],
+                ["{0} heure", "{0} heures", "{0} heures"],
+                ["{0} jour", "{0} jours", "{0} jours"],
+                ["{0} semaine", "{0} semaines", "{0} semaines"],
+                ["{0} mois", "{0} mois", "{0} mois"],
+                ["{0} an", "{0} ans", "{0} ans"],
+            ],
+            plural: plural_zero_one,
+        },
+    ),
+    (
+        "it",
+        RelativeTime {
+            now: "ora",
+            past: "{0} fa",
+            future: "tra {0}",
+            units: [
+                ["{0} secondo", "{0} secondi", "{0} secondi"],
+                ["{0} minuto", "{0} minuti", "{0} minuti"],
+                ["{0} ora", "{0} ore", "{0} ore"],
+                ["{0} giorno", "{0} giorni", "{0} giorni"],
+                ["{0} settimana", "{0} settimane", "{0} settimane"],
+                ["{0} mese", "{0} mesi", "{0} mesi"],
+                ["{0} anno", "{0} anni", "{0} anni"],
+            ],
+            plural: plural_one,
+        },
+    ),
+    (
+        "ja",
+        RelativeTime {
+            now: "今",
+            past: "{0}前",
+            future: "{0}後",
+            units: [
+                ["{0} 秒", "{0} 秒", "{0} 秒"],
+                ["{0} 分", "{0} 分", "{0} 分"],
+                ["{0} 時間", "{0} 時間", "{0} 時間"],
+                ["{0} 日", "{0} 日", "{0} 日"],
+                ["{0} 週間", "{0} 週間", "{0} 週間"],
+                ["{0} か月", "{0} か月", "{0} か月"],
+                ["{0} 年", "{0} 年", "{0} 年"],
+            ],
+            plural: plural_none,
+        },
+    ),
+    (
+        "ko",
+        RelativeTime {
+            now: "지금",
+            past: "{0} 전",
+            future: "{0} 후",
+            units: [
+                ["{0}초", "{0}초", "{0}초"],
+                ["{0}분", "{0}분", "{0}분"],
+                ["{0}시간", "{0}시간", "{0}시간"],
+                ["{0}일", "{0}일", "{0}일"],
+                ["{0}주", "{0}주", "{0}주"],
+                ["{0}개월", "{0}개월", "{0}개월"],
+                ["{0}년", "{0}년", "{0}년"],
+            ],
+            plural: plural_none,
+        },
+    ),
+    (
+        "nl",
+        RelativeTime {
+            now: "nu",
+            past: "{0} geleden",
+            future: "over {0}",
+            units: [
+                ["{0} seconde", "{0} seconden", "{0} seconden"],
+                ["{0} minuut", "{0} minuten", "{0} minuten"],
+                ["{0} uur", "{0} uur", "{0} uur"],
+                ["{0} dag", "{0} dagen", "{0} dagen"],
+                ["{0} week", "{0} weken", "{0} weken"],
+                ["{0} maand", "{0} maanden", "{0} maanden"],
+                ["{0} jaar", "{0} jaar", "{0} jaar"],
+            ],
+            plural: plural_one,
+        },
+    ),
+    (
+        "pt",
+        RelativeTime {
+            now: "agora",
+            past: "há {0}",
+            future: "em {0}",
+            units: [
+                ["{0} segundo", "{0} segundos", "{0} segundos"],
+                ["{0} minuto", "{0} minutos", "{0} minutos"],
+                ["{0} hora", "{0} horas", "{0} horas"],
+                ["{0} dia", "{0} dias", "{0} dias"],
+                ["{0} semana", "{0} semanas", "{0} semanas"],
+                ["{0} mês", "{0} meses", "{0} meses"],
+                ["{0} ano", "{0} anos", "{0} anos"],
+            ],
+            plural: plural_zero_one,
+        },
+    ),
+    (
+        "ru",
+        RelativeTime {
+            now: "сейчас",
+            past: "{0} назад",
+            future: "через {0}",
+            units: [
+                ["{0} секунду", "{0} секунды", "{0} секунд"],
+                ["{0} минуту", "{0} минуты", "{0} минут"],
+                ["{0} час", "{0} часа", "{0} часов"],
+                ["{0} день", "{0} дня", "{0} дней"],
+                ["{0} неделю", "{0} недели", "{0} недель"],
+                ["{0} месяц", "{0} месяца", "{0} месяцев"],
+                ["{0} год", "{0} года", "{0} лет"],
+            ],
+            plural: plural_slavic,
+        },
+    ),
+    (
+        "zh",
+        RelativeTime {
+            now: "现在",
+            past: "{0}前",
+            future: "{0}后",
+            units: [
+                ["{0}秒钟", "{0}秒钟", "{0}秒钟"],
+                ["{0}分钟", "{0}分钟", "{0}分钟"],
+                ["{0}小时", "{0}小时", "{0}小时"],
+                ["{0}天", "{0}天", "{0}天"],
+                ["{0}周", "{0}周", "{0}周"],
+                ["{0}个月", "{0}个月", "{0}个月"],
+                ["{0}年", "{0}年", "{0}年"],
+            ],
+            plural: plural_none,
+        },
+    ),
+];
+
+static EN_ABBR_MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+static EN_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+/// The numeric months of the locales that have no month names in their patterns
+static NUMERIC_MONTHS: [&str; 12] = [
+    "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+];
+
+/// The date patterns of the common locales from CLDR, sorted by the locale.
+static DATE_FORMATS: &[(&str, DateFormat)] = &[
+    (
+        "de",
+        DateFormat {
+            patterns: ["dd.MM.yy", "dd.MM.y", "d. MMMM y"],
+            abbr_months: &[
+                "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.",
+                "Nov.", "Dez.",
+            ],
+            months: &[
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+        },
+    ),
+    (
+        "en",
+        DateFormat {
+            patterns: ["M/d/yy", "MMM d, y", "MMMM d, y"],
+            abbr_months: &EN_ABBR_MONTHS,
+            months: &EN_MONTHS,
+        },
+    ),
+    (
+        "en-GB",
+        DateFormat {
+            patterns: ["dd/MM/y", "d MMM y", "d MMMM y"],
+            abbr_months: &EN_ABBR_MONTHS,
+            months: &EN_MONTHS,
+        },
+    ),
+    (
+        "es",
+        DateFormat {
+            patterns: ["d/M/yy", "d MMM y", "d 'de' MMMM 'de' y"],
+            abbr_months: &[
+                "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+            ],
+            months: &[
+                "enero",
+                "febrero",
+                "marzo",
+                "abril",
+                "mayo",
+                "junio",
+                "julio",
+                "agosto",
+                "septiembre",
+                "octubre",
+                "noviembre",
+                "diciembre",
+            ],
+        },
+    ),
+    (
+        "fr",
+        DateFormat {
+            patterns: ["dd/MM/y", "d MMM y", "d MMMM y"],
+            abbr_months: &[
+                "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+                "nov.", "déc.",
+            ],
+            months: &[
+                "janvier",
+                "février",
+                "mars",
+                "avril",
+                "mai",
+                "juin",
+                "juillet",
+                "août",
+                "septembre",
+                "octobre",
+                "novembre",
+                "décembre",
+            ],
+        },
+    ),
+    (
+        "it",
+        DateFormat {
+            patterns: ["dd/MM/yy", "d MMM y", "d MMMM y"],
+            abbr_months: &[
+                "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+            ],
+            months: &[
+                "gennaio",
+                "febbraio",
+                "marzo",
+                "aprile",
+                "maggio",
+                "giugno",
+                "luglio",
+                "agosto",
+                "settembre",
+                "ottobre",
+                "novembre",
+                "dicembre",
+            ],
+        },
+    ),
+    (
+        "ja",
+        DateFormat {
+            patterns: ["y/MM/dd", "y/MM/dd", "y年M月d日"],
+            abbr_months: &NUMERIC_MONTHS,
+            months: &NUMERIC_MONTHS,
+        },
+    ),
+    (
+        "ko",
+        DateFormat {
+            patterns: ["yy. M. d.", "y. M. d.", "y년 M월 d일"],
+            abbr_months: &NUMERIC_MONTHS,
+            months: &NUMERIC_MONTHS,
+        },
+    ),
+    (
+        "nl",
+        DateFormat {
+            patterns: ["dd-MM-y", "d MMM y", "d MMMM y"],
+            abbr_months: &[
+                "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+            ],
+            months: &[
+                "januari",
+                "februari",
+                "maart",
+                "april",
+                "mei",
+                "juni",
+                "juli",
+                "augustus",
+                "september",
+                "oktober",
+                "november",
+                "december",
+            ],
+        },
+    ),
+    (
+        "pt",
+        DateFormat {
+            patterns: ["dd/MM/y", "d 'de' MMM 'de' y", "d 'de' MMMM 'de' y"],
+            abbr_months: &[
+                "jan.", "fev.", "mar.", "abr.", "mai.", "jun.", "jul.", "ago.", "set.", "out.",
+                "nov.", "dez.",
+            ],
+            months: &[
+                "janeiro",
+                "fevereiro",
+                "março",
+                "abril",
+                "maio",
+                "junho",
+                "julho",
+                "agosto",
+                "setembro",
+                "outubro",
+                "novembro",
+                "dezembro",
+            ],
+        },
+    ),
+    (
+        "ru",
+        DateFormat {
+            patterns: ["dd.MM.y", "d MMM y 'г.'", "d MMMM y 'г.'"],
+            abbr_months: &[
+                "янв.",
+                "февр.",
+                "мар.",
+                "апр.",
+                "мая",
+                "июн.",
+                "июл.",
+                "авг.",
+                "сент.",
+                "окт.",
+                "нояб.",
+                "дек.",
+            ],
+            months: &[
+                "января",
+                "февраля",
+                "марта",
+                "апреля",
+                "мая",
+                "июня",
+                "июля",
+                "августа",
+                "сентября",
+                "октября",
+                "ноября",
+                "декабря",
+            ],
+        },
+    ),
+    (
+        "zh",
+        DateFormat {
+            patterns: ["y/M/d", "y年M月d日", "y年M月d日"],
+            abbr_months: &NUMERIC_MONTHS,
+            months: &NUMERIC_MONTHS,
+        },
+    ),
+];
+
+/// A calendar date of the proleptic Gregorian calendar, for [`format_date`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Create a date, returns `None` if the month or the day is out of range, e.g. `2023-02-29`.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days)
+            .contains(&day)
+            .then_some(Self { year, month, day })
+    }
+}
+
+/// The length of the date in [`format_date`], like the CLDR date formats.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DateStyle {
+    /// The numeric date, e.g. `1/5/24` in `en`
+    Short,
+    /// The date with the abbreviated month, e.g. `Jan 5, 2024` in `en`
+    #[default]
+    Medium,
+    /// The date with the full month, e.g. `January 5, 2024` in `en`
+    Long,
+}
+
+/// Format the relative time of the offset in seconds, the negative offset is in the past,
+/// e.g. `-259200` => `3 days ago` in `en`, `vor 3 Tagen` in `de`.
+///
+/// The offset is truncated to the largest unit that fits: second, minute, hour, day, week, month (30 days) or year (365 days).
+/// The unknown locale falls back to the less specific locale, e.g. `fr-CA` to `fr`, returns `None` if it's still unknown.
+///
+/// ```
+/// use rust_i18n_support::format_relative_time;
+///
+/// assert_eq!(format_relative_time("en", -3 * 86400).as_deref(), Some("3 days ago"));
+/// assert_eq!(format_relative_time("fr-CA", 3600).as_deref(), Some("dans 1 heure"));
+/// assert_eq!(format_relative_time("zh-CN", -90).as_deref(), Some("1分钟前"));
+/// assert_eq!(format_relative_time("xx", 60), None);
+/// ```
+pub fn format_relative_time(locale: &str, seconds: i64) -> Option<String> {
+    let relative = find_locale(RELATIVE_TIMES, locale)?;
+    let abs = seconds.unsigned_abs();
+    if abs == 0 {
+        return Some(relative.now.into());
+    }
+
+    let unit = UNITS.iter().rposition(|len| abs >= *len).unwrap_or(0);
+    let n = abs / UNITS[unit];
+    let mut number = String::new();
+    let _ = write!(number, "{}", n);
+    let value = relative.units[unit][(relative.plural)(n)].replace("{0}", &number);
+    let pattern = if seconds < 0 {
+        relative.past
+    } else {
+        relative.future
+    };
+    Some(pattern.replace("{0}", &value))
+}
+
+/// Format the date in the style of the locale, e.g. `January 5, 2024` in `en`, `5. Januar 2024` in `de`.
+///
+/// The unknown locale falls back to the less specific locale, e.g. `de-AT` to `de`, returns `None` if it's still unknown.
+///
+/// ```
+/// use rust_i18n_support::{format_date, Date, DateStyle};
+///
+/// let date = Date::new(2024, 1, 5).unwrap();
+/// assert_eq!(format_date("en", date, DateStyle::Long).as_deref(), Some("January 5, 2024"));
+/// assert_eq!(format_date("en-GB", date, DateStyle::Short).as_deref(), Some("05/01/2024"));
+/// assert_eq!(format_date("de-AT", date, DateStyle::Medium).as_deref(), Some("05.01.2024"));
+/// assert_eq!(format_date("ja", date, DateStyle::Long).as_deref(), Some("2024年1月5日"));
+/// ```
+pub fn format_date(locale: &str, date: Date, style: DateStyle) -> Option<String> {
+    let format = find_locale(DATE_FORMATS, locale)?;
+    let pattern = match style {
+        DateStyle::Short => format.patterns[0],
+        DateStyle::Medium => format.patterns[1],
+        DateStyle::Long => format.patterns[2],
+    };
+
+    let mut output = String::with_capacity(pattern.len() + 8);
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            output.extend(chars.by_ref().take_while(|c| *c != '\''));
+            continue;
+        }
+        if !matches!(c, 'y' | 'M' | 'd') {
+            output.push(c);
+            continue;
+        }
+
+        let mut count = 1;
+        while chars.next_if_eq(&c).is_some() {
+            count += 1;
+        }
+        let month = usize::from(date.month - 1);
+        let _ = match (c, count) {
+            ('y', 2) => write!(output, "{:02}", date.year.rem_euclid(100)),
+            ('y', _) => write!(output, "{}", date.year),
+            ('M', 1) => write!(output, "{}", date.month),
+            ('M', 2) => write!(output, "{:02}", date.month),
+            ('M', 3) => write!(output, "{}", format.abbr_months[month]),
+            ('M', _) => write!(output, "{}", format.months[month]),
+            ('d', 1) => write!(output, "{}", date.day),
+            _ => write!(output, "{:02}", date.day),
+        };
+    }
+    Some(output)
+}
+
+fn find_locale<'a, T>(table: &'a [(&str, T)], locale: &str) -> Option<&'a T> {
+    let mut locale = locale;
+    loop {
+        if let Some((_, value)) = table
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(locale))
+        {
+            return Some(value);
+        }
+        locale = lookup_fallback(locale)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_relative_time() {
+        let cases = [
+            ("en", 0, "now"),
+            ("en", -1, "1 second ago"),
+            ("en", 59, "in 59 seconds"),
+            ("en", -60, "1 minute ago"),
+            ("en", -7200, "2 hours ago"),
+            ("en", 86400 * 6, "in 6 days"),
+            ("en", -86400 * 14, "2 weeks ago"),
+            ("en", -86400 * 45, "1 month ago"),
+            ("en", 86400 * 800, "in 2 years"),
+            ("de", -86400, "vor 1 Tag"),
+            ("de", 86400 * 3, "in 3 Tagen"),
+            ("fr", -60, "il y a 1 minute"),
+            ("ru", -60, "1 минуту назад"),
+            ("ru", -180, "3 минуты назад"),
+            ("ru", -660, "11 минут назад"),
+            ("ru", -86400 * 21, "3 недели назад"),
+            ("ja", 86400 * 3, "3 日後"),
+            ("ko", -3600, "1시간 전"),
+            ("pt-BR", -86400 * 60, "há 2 meses"),
+        ];
+        for (locale, seconds, expected) in cases {
+            assert_eq!(
+                format_relative_time(locale, seconds).as_deref(),
+                Some(expected),
+                "{} {}",
+                locale,
+                seconds
+            );
+        }
+        assert_eq!(format_relative_time("xx", -60), None);
+        assert_eq!(
+            format_relative_time("en", i64::MIN).as_deref(),
+            Some("292471208677 years ago")
+        );
+    }
+
+    #[test]
+    fn test_format_date() {
+        let date = Date::new(2024, 3, 9).unwrap();
+        let cases = [
+            ("en", DateStyle::Short, "3/9/24"),
+            ("en-US", DateStyle::Medium, "Mar 9, 2024"),
+            ("en-GB", DateStyle::Long, "9 March 2024"),
+            ("de", DateStyle::Short, "09.03.24"),
+            ("de", DateStyle::Long, "9. März 2024"),
+            ("es", DateStyle::Long, "9 de marzo de 2024"),
+            ("fr", DateStyle::Medium, "9 mars 2024"),
+            ("pt", DateStyle::Medium, "9 de mar. de 2024"),
+            ("ru", DateStyle::Long, "9 марта 2024 г."),
+            ("zh-CN", DateStyle::Short, "2024/3/9"),
+            ("ko", DateStyle::Short, "24. 3. 9."),
+        ];
+        for (locale, style, expected) in cases {
+            assert_eq!(
+                format_date(locale, date, style).as_deref(),
+                Some(expected),
+                "{} {:?}",
+                locale,
+                style
+            );
+        }
+        assert_eq!(format_date("xx", date, DateStyle::Short), None);
+    }
+
+    #[test]
+    fn test_date_new() {
+        assert!(Date::new(2024, 2, 29).is_some());
+        assert!(Date::new(2023, 2, 29).is_none());
+        assert!(Date::new(1900, 2, 29).is_none());
+        assert!(Date::new(2000, 2, 29).is_some());
+        assert!(Date::new(2024, 13, 1).is_none());
+        assert!(Date::new(2024, 4, 31).is_none());
+        assert!(Date::new(2024, 1, 0).is_none());
+    }
+}
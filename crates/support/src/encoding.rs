@@ -0,0 +1,145 @@
+/// The byte order mark at the start of the UTF-8 files written by some editors and vendors.
+const UTF8_BOM: &str = "\u{feff}";
+
+/// Remove the UTF-8 byte order mark at the start of the text if present.
+pub fn strip_bom(text: &str) -> &str {
+    text.strip_prefix(UTF8_BOM).unwrap_or(text)
+}
+
+/// The text encoding of the files read and written by `cargo i18n`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Encoding {
+    /// UTF-8, the byte order mark is stripped when decoding and never written
+    #[default]
+    Utf8,
+    /// ISO-8859-1, each byte is a Unicode code point from U+0000 to U+00FF
+    Latin1,
+}
+
+impl Encoding {
+    /// Decode the bytes into a string, the UTF-8 BOM is stripped.
+    ///
+    /// The error has the line and column of the first invalid UTF-8 sequence, see [`Encoding::decode_lossy`].
+    pub fn decode(&self, bytes: &[u8]) -> Result<String, String> {
+        let (text, invalid) = self.decode_lossy(bytes);
+        match invalid.first() {
+            Some((line, column)) => {
+                Err(format!("invalid UTF-8 at line {}, column {}", line, column))
+            }
+            None => Ok(text),
+        }
+    }
+
+    /// Decode the bytes into a string, the invalid UTF-8 sequences (e.g. the lone surrogates) are replaced by U+FFFD,
+    /// the UTF-8 BOM is stripped.
+    ///
+    /// Returns the 1-based line and column of each replaced sequence too, the column is counted in characters.
+    pub fn decode_lossy(&self, bytes: &[u8]) -> (String, Vec<(usize, usize)>) {
+        match self {
+            Encoding::Utf8 => {
+                let bytes = bytes.strip_prefix(UTF8_BOM.as_bytes()).unwrap_or(bytes);
+                let mut text = String::with_capacity(bytes.len());
+                let mut invalid = vec![];
+                let (mut line, mut column) = (1, 1);
+                for chunk in bytes.utf8_chunks() {
+                    for c in chunk.valid().chars() {
+                        if c == '\n' {
+                            line += 1;
+                            column = 1;
+                        } else {
+                            column += 1;
+                        }
+                    }
+                    text.push_str(chunk.valid());
+                    if !chunk.invalid().is_empty() {
+                        invalid.push((line, column));
+                        text.push(char::REPLACEMENT_CHARACTER);
+                        column += 1;
+                    }
+                }
+                (text, invalid)
+            }
+            Encoding::Latin1 => (bytes.iter().map(|&b| b as char).collect(), vec![]),
+        }
+    }
+
+    /// Encode the string into bytes, returns an error if a character can't be represented.
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Encoding::Utf8 => Ok(text.as_bytes().to_vec()),
+            Encoding::Latin1 => text
+                .chars()
+                .map(|c| {
+                    u8::try_from(c).map_err(|_| format!("`{}` can't be encoded in Latin-1", c))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl std::str::FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" => Ok(Encoding::Utf8),
+            "latin1" | "latin-1" | "iso-8859-1" => Ok(Encoding::Latin1),
+            _ => Err(format!(
+                "Unsupported encoding `{}`, expected `utf-8` or `latin1`",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(strip_bom("\u{feff}hello: Hello"), "hello: Hello");
+        assert_eq!(strip_bom("hello: Hello"), "hello: Hello");
+        assert_eq!(
+            Encoding::Utf8.decode(b"\xef\xbb\xbfhello").unwrap(),
+            "hello"
+        );
+        assert!(Encoding::Utf8.decode(b"caf\xe9").is_err());
+        assert_eq!(Encoding::Latin1.decode(b"caf\xe9").unwrap(), "café");
+        assert_eq!(
+            Encoding::Utf8
+                .decode(b"ok\nna\xc3\xafve caf\xe9")
+                .unwrap_err(),
+            "invalid UTF-8 at line 2, column 10"
+        );
+    }
+
+    #[test]
+    fn test_decode_lossy() {
+        assert_eq!(
+            Encoding::Utf8.decode_lossy(b"\xef\xbb\xbfhello"),
+            ("hello".to_string(), vec![])
+        );
+        // The lone surrogate `\u{d800}` in CESU-8 and an invalid byte
+        assert_eq!(
+            Encoding::Utf8.decode_lossy(b"a\xed\xa0\x80b\n\xff"),
+            (
+                "a\u{fffd}\u{fffd}\u{fffd}b\n\u{fffd}".to_string(),
+                vec![(1, 2), (1, 3), (1, 4), (2, 1)]
+            )
+        );
+        assert_eq!(
+            Encoding::Latin1.decode_lossy(b"caf\xe9"),
+            ("café".to_string(), vec![])
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(Encoding::Latin1.encode("café").unwrap(), b"caf\xe9");
+        assert!(Encoding::Latin1.encode("你好").is_err());
+        assert_eq!(Encoding::Utf8.encode("你好").unwrap(), "你好".as_bytes());
+        assert_eq!("ISO-8859-1".parse::<Encoding>(), Ok(Encoding::Latin1));
+        assert!("gbk".parse::<Encoding>().is_err());
+    }
+}
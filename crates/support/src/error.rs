@@ -0,0 +1,69 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The error of loading the config or the locale files, match the variants to handle the failures by kind.
+#[derive(Debug)]
+pub enum I18nError {
+    /// Failed to read a file or walk a directory
+    Io {
+        /// The path of the file or the directory
+        path: PathBuf,
+        /// The error of the file system
+        source: io::Error,
+    },
+    /// Failed to parse a locale file
+    Parse {
+        /// The path of the locale file
+        file: PathBuf,
+        /// The error message, including the line and column if available
+        message: String,
+    },
+    /// The config of `package.metadata.i18n` is invalid
+    Config {
+        /// The path of the `Cargo.toml`, `None` if the config is parsed from a string
+        file: Option<PathBuf>,
+        /// The error message
+        message: String,
+    },
+}
+
+impl I18nError {
+    /// Get the path of the file that failed, if any.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            I18nError::Io { path, .. } => Some(path),
+            I18nError::Parse { file, .. } => Some(file),
+            I18nError::Config { file, .. } => file.as_deref(),
+        }
+    }
+}
+
+impl std::fmt::Display for I18nError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            I18nError::Io { path, source } => {
+                write!(f, "Read `{}` failed, {}", path.display(), source)
+            }
+            I18nError::Parse { file, message } => {
+                write!(f, "Parse file `{}` failed, {}", file.display(), message)
+            }
+            I18nError::Config {
+                file: Some(file),
+                message,
+            } => write!(f, "Invalid config in `{}`, {}", file.display(), message),
+            I18nError::Config {
+                file: None,
+                message,
+            } => write!(f, "Invalid config, {}", message),
+        }
+    }
+}
+
+impl std::error::Error for I18nError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            I18nError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
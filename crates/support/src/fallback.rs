@@ -0,0 +1,145 @@
+/// Get the next fallback locale of the language tag by the "Lookup" scheme of RFC 4647.
+///
+/// The last subtag is removed, and then the trailing singleton (e.g. `-x` for the private use,
+/// or `-u` for the extension) is also removed, because it's meaningless without the following subtags.
+///
+/// For example: `"zh-Hant-CN-x-private1-private2"` -> `"zh-Hant-CN-x-private1"` -> `"zh-Hant-CN"` -> `"zh-Hant"` -> `"zh"`.
+///
+/// https://datatracker.ietf.org/doc/html/rfc4647#section-3.4
+pub fn lookup_fallback(locale: &str) -> Option<&str> {
+    let mut fallback = &locale[..locale.rfind('-')?];
+    if let Some(n) = fallback.rfind('-') {
+        if fallback.len() - n == 2 {
+            fallback = &fallback[..n];
+        }
+    }
+
+    // The tag starts with a singleton has no language, e.g. `x-private`
+    if fallback.len() == 1 {
+        return None;
+    }
+
+    Some(fallback)
+}
+
+/// Iterate the key and its ancestors split by the `separator`, the key itself first and then the nearest ancestor,
+/// e.g. `"a.b.c"` -> `"a.b"` -> `"a"`.
+///
+/// The nested keys take the attributes of their nearest marked ancestor, e.g. `_meta: { max_len: 12 }`.
+///
+/// ```
+/// use rust_i18n_support::key_ancestors;
+///
+/// assert_eq!(key_ancestors("a.b.c", ".").collect::<Vec<_>>(), ["a.b.c", "a.b", "a"]);
+/// assert_eq!(key_ancestors("a::b", "::").collect::<Vec<_>>(), ["a::b", "a"]);
+/// ```
+pub fn key_ancestors<'a>(key: &'a str, separator: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    let ancestors = key
+        .rmatch_indices(separator)
+        .filter(move |_| !separator.is_empty())
+        .map(move |(i, _)| &key[..i]);
+    core::iter::once(key).chain(ancestors)
+}
+
+/// Get the default locale of the key by the longest matching namespace, e.g. `"fr"` of `"vendor.payments.title"`
+/// with the namespaces `[("vendor", "de"), ("vendor.payments", "fr")]`.
+///
+/// The namespace matches the whole segments of the key, so `"vendor.pay"` doesn't match `"vendor.payments.title"`.
+pub fn namespace_default_locale<'a>(
+    namespaces: &[(&str, &'a str)],
+    key: &str,
+    separator: &str,
+) -> Option<&'a str> {
+    key_ancestors(key, separator)
+        .find_map(|key| {
+            namespaces
+                .iter()
+                .find(|(namespace, _)| !namespace.is_empty() && *namespace == key)
+        })
+        .map(|(_, locale)| *locale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{key_ancestors, lookup_fallback, namespace_default_locale};
+
+    #[test]
+    fn test_key_ancestors() {
+        let ancestors = |key, separator| key_ancestors(key, separator).collect::<Vec<_>>();
+        assert_eq!(ancestors("a.b.c", "."), ["a.b.c", "a.b", "a"]);
+        assert_eq!(ancestors("a", "."), ["a"]);
+        assert_eq!(ancestors("a/b.c", "/"), ["a/b.c", "a"]);
+        assert_eq!(ancestors("", "."), [""]);
+        assert_eq!(ancestors("a.b", ""), ["a.b"]);
+    }
+
+    #[test]
+    fn test_namespace_default_locale() {
+        let namespaces = [("vendor", "de"), ("vendor.payments", "fr"), ("", "ja")];
+        let cases = [
+            ("vendor.payments.title", Some("fr")),
+            ("vendor.payments", Some("fr")),
+            ("vendor.shipping.title", Some("de")),
+            ("vendor", Some("de")),
+            ("vendor.pay.title", Some("de")),
+            ("vendors.title", None),
+            ("hello", None),
+        ];
+        for (key, expected) in cases {
+            assert_eq!(
+                namespace_default_locale(&namespaces, key, "."),
+                expected,
+                "key: {:?}",
+                key
+            );
+        }
+
+        let namespaces = [("vendor", "de"), ("vendor::payments", "fr")];
+        assert_eq!(
+            namespace_default_locale(&namespaces, "vendor::payments::title", "::"),
+            Some("fr")
+        );
+        assert_eq!(
+            namespace_default_locale(&namespaces, "vendor.payments.title", "::"),
+            None
+        );
+    }
+
+    fn lookup_chain(locale: &str) -> Vec<&str> {
+        let mut chain = vec![];
+        let mut current = locale;
+        while let Some(fallback) = lookup_fallback(current) {
+            chain.push(fallback);
+            current = fallback;
+        }
+        chain
+    }
+
+    #[test]
+    fn test_lookup_fallback() {
+        let cases: &[(&str, &[&str])] = &[
+            ("en", &[]),
+            ("", &[]),
+            ("zh-CN", &["zh"]),
+            ("zh-Hant-CN", &["zh-Hant", "zh"]),
+            (
+                "zh-Hant-CN-x-private1-private2",
+                &["zh-Hant-CN-x-private1", "zh-Hant-CN", "zh-Hant", "zh"],
+            ),
+            ("en-x-a", &["en"]),
+            ("en-x-foo", &["en"]),
+            ("zh-Hant-x", &["zh-Hant", "zh"]),
+            ("de-DE-u-co-phonebk", &["de-DE-u-co", "de-DE", "de"]),
+            (
+                "en-US-u-ca-gregory-x-private",
+                &["en-US-u-ca-gregory", "en-US-u-ca", "en-US", "en"],
+            ),
+            ("x-private", &[]),
+            ("x-a-b", &[]),
+        ];
+
+        for (locale, expected) in cases {
+            assert_eq!(&lookup_chain(locale), expected, "locale: {:?}", locale);
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use crate::{
+    is_debug, try_load_locales_from_paths_with_separator, Backend, BackendExt, SimpleBackend,
+    DEFAULT_KEY_SEPARATOR,
+};
+
+/// The interval to check the locale files for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A backend that reloads the translations when the locale files are changed.
+///
+/// The locale files are only watched when `RUST_I18N_DEBUG=1` is set, otherwise it is the same as
+/// the given [`SimpleBackend`]. This is intended for development only: the replaced translations
+/// are leaked to keep the returned `&str` valid, so every reload costs the memory of the locales.
+pub struct HotReloadBackend {
+    current: Arc<RwLock<&'static SimpleBackend>>,
+}
+
+impl HotReloadBackend {
+    /// Create a new HotReloadBackend that watches the given locales paths.
+    pub fn new<P: AsRef<str>>(backend: SimpleBackend, locales_paths: &[P]) -> Self {
+        Self::new_with_separator(backend, locales_paths, DEFAULT_KEY_SEPARATOR)
+    }
+
+    /// Same as [`HotReloadBackend::new`], but the nested keys are joined with the `separator`.
+    pub fn new_with_separator<P: AsRef<str>>(
+        backend: SimpleBackend,
+        locales_paths: &[P],
+        separator: &str,
+    ) -> Self {
+        Self::new_with_namespace(backend, locales_paths, separator, "")
+    }
+
+    /// Same as [`HotReloadBackend::new_with_separator`], but the reloaded keys are prefixed with the `namespace`,
+    /// e.g. `billing.title`, the same as the `namespace` of `i18n!`.
+    pub fn new_with_namespace<P: AsRef<str>>(
+        backend: SimpleBackend,
+        locales_paths: &[P],
+        separator: &str,
+        namespace: &str,
+    ) -> Self {
+        let current = Arc::new(RwLock::new(&*Box::leak(Box::new(backend))));
+
+        if is_debug() {
+            let locales_paths = locales_paths
+                .iter()
+                .map(|path| path.as_ref().to_string())
+                .collect::<Vec<_>>();
+            let last_modified = modified_times(&locales_paths);
+            let current = current.clone();
+            let separator = separator.to_string();
+            let prefix = if namespace.is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", namespace, separator)
+            };
+            std::thread::spawn(move || {
+                watch(&locales_paths, &separator, &prefix, &current, last_modified)
+            });
+        }
+
+        Self { current }
+    }
+
+    fn backend(&self) -> &'static SimpleBackend {
+        *self.current.read().unwrap()
+    }
+}
+
+impl Backend for HotReloadBackend {
+    fn available_locales(&self) -> Vec<&str> {
+        self.backend().available_locales()
+    }
+
+    fn translate(&self, locale: &str, key: &str) -> Option<&str> {
+        self.backend().translate(locale, key)
+    }
+
+    fn contains_key(&self, locale: &str, key: &str) -> bool {
+        self.backend().contains_key(locale, key)
+    }
+
+    fn translate_many(&self, locale: &str, keys: &[&str]) -> Vec<Option<&str>> {
+        self.backend().translate_many(locale, keys)
+    }
+
+    fn translations(&self, locale: &str) -> BTreeMap<&str, &str> {
+        self.backend().translations(locale)
+    }
+
+    fn entries(&self) -> Box<dyn Iterator<Item = (&str, &str, &str)> + '_> {
+        self.backend().entries()
+    }
+
+    fn available_locales_for_prefix(&self, prefix: &str) -> Vec<&str> {
+        self.backend().available_locales_for_prefix(prefix)
+    }
+}
+
+impl BackendExt for HotReloadBackend {}
+
+/// Poll the locale files and replace the backend when any of them is changed.
+fn watch(
+    locales_paths: &[String],
+    separator: &str,
+    prefix: &str,
+    current: &RwLock<&'static SimpleBackend>,
+    mut last_modified: Vec<(PathBuf, SystemTime)>,
+) {
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let modified = modified_times(locales_paths);
+        if modified == last_modified {
+            continue;
+        }
+        last_modified = modified;
+
+        // Keep the current translations until the broken files are fixed
+        let data =
+            match try_load_locales_from_paths_with_separator(locales_paths, |_| false, separator) {
+                Ok(data) => data,
+                Err(errors) => {
+                    for err in errors {
+                        eprintln!("rust-i18n: {}", err);
+                    }
+                    continue;
+                }
+            };
+
+        let mut backend = SimpleBackend::new();
+        for (locale, trs) in &data {
+            let trs = trs
+                .iter()
+                .map(|(k, v)| (format!("{}{}", prefix, k), v.as_str()))
+                .collect::<Vec<_>>();
+            let trs = trs.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+            backend.add_translations(locale, &trs);
+        }
+        *current.write().unwrap() = Box::leak(Box::new(backend));
+
+        eprintln!(
+            "rust-i18n: reloaded locales from {}",
+            locales_paths.join(", ")
+        );
+    }
+}
+
+/// Get the modified times of all locale files, sorted by path.
+fn modified_times(locales_paths: &[String]) -> Vec<(PathBuf, SystemTime)> {
+    let mut result = vec![];
+
+    for locales_path in locales_paths {
+        let path_pattern = crate::loader::locale_files_pattern(locales_path);
+        let Ok(walker) = globwalk::glob(&path_pattern) else {
+            continue;
+        };
+        for entry in walker.flatten() {
+            let path = entry.into_path();
+            if let Ok(modified) = path.metadata().and_then(|m| m.modified()) {
+                result.push((path, modified));
+            }
+        }
+    }
+
+    result.sort();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hot_reload_backend() {
+        let locales_path = std::env::temp_dir().join("rust-i18n-test-hot-reload");
+        let _ = std::fs::remove_dir_all(&locales_path);
+        std::fs::create_dir_all(&locales_path).unwrap();
+        let locales_paths = [locales_path.display().to_string()];
+        let current = RwLock::new(&*Box::leak(Box::new(SimpleBackend::new())));
+
+        assert!(modified_times(&locales_paths).is_empty());
+        std::fs::write(locales_path.join("en.yml"), "hello: Hello").unwrap();
+        assert_eq!(modified_times(&locales_paths).len(), 1);
+
+        let last_modified = modified_times(&locales_paths);
+        let locales_paths = locales_paths.to_vec();
+        let backend = HotReloadBackend {
+            current: Arc::new(current),
+        };
+        let watched = backend.current.clone();
+        std::thread::spawn(move || watch(&locales_paths, ".", "", &watched, last_modified));
+
+        std::thread::sleep(Duration::from_millis(10));
+        std::fs::write(locales_path.join("en.yml"), "hello: Hello, World!").unwrap();
+        std::thread::sleep(POLL_INTERVAL * 3);
+        assert_eq!(backend.translate("en", "hello"), Some("Hello, World!"));
+
+        std::fs::remove_dir_all(&locales_path).unwrap();
+    }
+}
@@ -0,0 +1,26 @@
+use alloc::string::String;
+
+/// Escape the HTML special chars of the value by the entities, so it's rendered as plain text in HTML.
+///
+/// The `&`, `<`, `>`, `"` and `'` are escaped, so the value is also safe in a quoted attribute.
+///
+/// ```
+/// use rust_i18n_support::escape_html;
+///
+/// assert_eq!(escape_html("<b>Tom & Jerry</b>"), "&lt;b&gt;Tom &amp; Jerry&lt;/b&gt;");
+/// assert_eq!(escape_html("\"it's\""), "&quot;it&#39;s&quot;");
+/// ```
+pub fn escape_html(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            _ => output.push(c),
+        }
+    }
+    output
+}
@@ -0,0 +1,129 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// The named values of a type to interpolate the `%{name}` patterns, used by the `..value` argument of `t!`.
+///
+/// Usually derived with `#[derive(rust_i18n::Interpolate)]`, each named field is formatted with `Display`.
+///
+/// ```
+/// # use rust_i18n_support::Interpolate;
+/// struct Line {
+///     name: &'static str,
+///     qty: u32,
+/// }
+///
+/// impl Interpolate for Line {
+///     fn interpolate_fields(&self) -> Vec<(&'static str, String)> {
+///         vec![("name", self.name.to_string()), ("qty", self.qty.to_string())]
+///     }
+/// }
+///
+/// let line = Line { name: "Apple", qty: 3 };
+/// assert_eq!(line.interpolate_fields()[1], ("qty", "3".to_string()));
+/// ```
+pub trait Interpolate {
+    /// Get the names of the patterns and the formatted values.
+    fn interpolate_fields(&self) -> Vec<(&'static str, String)>;
+}
+
+impl<T: Interpolate + ?Sized> Interpolate for &T {
+    fn interpolate_fields(&self) -> Vec<(&'static str, String)> {
+        (**self).interpolate_fields()
+    }
+}
+
+/// Get the names of the placeholders in the translation, e.g. `name` of `Hello, %{name}!`, without duplicates.
+///
+/// The escaped placeholders are skipped, e.g. `%%{name}`, the name of an inline plural pattern is its variable,
+/// e.g. `count` of `%{count | item | items}`, the name of a key placeholder is without the `@`, e.g. `status` of `%{@status}`,
+/// and the name of a placeholder with a default is without the default, e.g. `name` of `%{name:guest}`.
+///
+/// ```
+/// use rust_i18n_support::placeholder_names;
+///
+/// assert_eq!(placeholder_names("Hi %{name}, %%{x} %{n | item | items} %{n}", "%{", "}"), ["name", "n"]);
+/// assert_eq!(placeholder_names("Hi {{name}}", "{{", "}}"), ["name"]);
+/// assert_eq!(placeholder_names("Status: %{@status}", "%{", "}"), ["status"]);
+/// assert_eq!(placeholder_names("Hi %{name:guest}", "%{", "}"), ["name"]);
+/// ```
+pub fn placeholder_names<'a>(text: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    for (name, _) in placeholders(text, open, close) {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Get the names of the placeholders without a default in the translation, the arguments required by the translation.
+///
+/// A name used by both a placeholder with a default and one without is required.
+///
+/// ```
+/// use rust_i18n_support::required_placeholder_names;
+///
+/// assert_eq!(required_placeholder_names("Hi %{name:guest}, %{n} %{x:1} %{x}", "%{", "}"), ["n", "x"]);
+/// ```
+pub fn required_placeholder_names<'a>(text: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    for (name, default) in placeholders(text, open, close) {
+        if default.is_none() && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Split the placeholder into the name and the default, e.g. `name` and `guest` of `%{name:guest}`.
+///
+/// The default is the text after the first `:`, the name must not be empty or contain whitespace or `|`,
+/// so the `:` of an inline plural pattern is part of its forms, e.g. `%{n | 1:1 | n:1}`.
+///
+/// ```
+/// use rust_i18n_support::placeholder_default;
+///
+/// assert_eq!(placeholder_default("name:guest"), Some(("name", "guest")));
+/// assert_eq!(placeholder_default("time:12:00"), Some(("time", "12:00")));
+/// assert_eq!(placeholder_default("name:"), Some(("name", "")));
+/// assert_eq!(placeholder_default("name"), None);
+/// assert_eq!(placeholder_default("n | a:b | c"), None);
+/// ```
+pub fn placeholder_default(placeholder: &str) -> Option<(&str, &str)> {
+    let (name, default) = placeholder.split_once(':')?;
+    if name.is_empty() || name.contains(|c: char| c.is_whitespace() || c == '|') {
+        return None;
+    }
+    Some((name, default))
+}
+
+/// Get the names of the placeholders in the translation with their defaults, in order.
+fn placeholders<'a>(text: &'a str, open: &str, close: &str) -> Vec<(&'a str, Option<&'a str>)> {
+    let escape = if open.starts_with('%') { "%" } else { "\\" };
+    let mut placeholders = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(open) {
+        let after_open = &rest[start + open.len()..];
+        if rest[..start].ends_with(escape) {
+            rest = after_open;
+            continue;
+        }
+        let Some(len) = after_open.find(close) else {
+            break;
+        };
+        let placeholder = &after_open[..len];
+        let (name, default) = match placeholder_default(placeholder) {
+            Some((name, default)) => (name, Some(default)),
+            None => (
+                placeholder.split('|').next().unwrap_or_default().trim(),
+                None,
+            ),
+        };
+        let name = name.strip_prefix('@').unwrap_or(name);
+        if !name.is_empty() {
+            placeholders.push((name, default));
+        }
+        rest = &after_open[len + close.len()..];
+    }
+    placeholders
+}
@@ -0,0 +1,150 @@
+use serde::de::{self, Deserialize, MapAccess, SeqAccess, Visitor};
+use serde_json::Value;
+use std::fmt;
+
+/// Parse JSON into a JSON value, a key defined twice in the same object is an error.
+///
+/// `serde_json` keeps the last value of the duplicate keys silently, so a copy-pasted key would
+/// override the translation above it, the YAML and TOML parsers already reject them.
+pub(crate) fn from_str(content: &str) -> Result<Value, serde_json::Error> {
+    let mut deserializer = serde_json::Deserializer::from_str(content);
+    let value = UniqueKeys::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value.0)
+}
+
+/// Parse JSON5 into a JSON value, a key defined twice in the same object is an error.
+#[cfg(feature = "json5")]
+pub(crate) fn from_json5_str(content: &str) -> Result<Value, json5::Error> {
+    json5::from_str::<UniqueKeys>(content).map(|value| value.0)
+}
+
+/// A JSON value without the duplicate keys in its objects.
+struct UniqueKeys(Value);
+
+impl<'de> Deserialize<'de> for UniqueKeys {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_any(UniqueKeysVisitor)
+            .map(UniqueKeys)
+    }
+}
+
+struct UniqueKeysVisitor;
+
+impl<'de> Visitor<'de> for UniqueKeysVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any valid JSON value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Value::String(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        UniqueKeys::deserialize(deserializer).map(|value| value.0)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut result = Vec::new();
+        while let Some(value) = seq.next_element::<UniqueKeys>()? {
+            result.push(value.0);
+        }
+        Ok(Value::Array(result))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut result = serde_json::Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if result.contains_key(&key) {
+                // The same message as the YAML parser
+                return Err(de::Error::custom(format!(
+                    "duplicate entry with key {:?}",
+                    key
+                )));
+            }
+            let value = map.next_value::<UniqueKeys>()?;
+            result.insert(key, value.0);
+        }
+        Ok(Value::Object(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_str;
+
+    #[test]
+    fn test_from_str_rejects_duplicate_keys() {
+        let value =
+            from_str(r#"{"foo": {"en": "Foo", "fr": [1, true, null]}, "bar": 1.5}"#).unwrap();
+        assert_eq!(value["foo"]["en"], "Foo");
+        assert_eq!(value["foo"]["fr"], serde_json::json!([1, true, null]));
+        assert_eq!(value["bar"], 1.5);
+
+        let err = from_str(r#"{"foo": "Foo", "bar": "Bar", "foo": "Foo 2"}"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(r#"duplicate entry with key "foo""#));
+        // The same key in the different objects is fine
+        assert!(from_str(r#"{"a": {"foo": "A"}, "b": {"foo": "B"}}"#).is_ok());
+        let err = from_str(r#"{"a": {"foo": "A", "foo": "B"}}"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(r#"duplicate entry with key "foo""#));
+
+        assert!(from_str(r#"{"foo": "Foo"} x"#).is_err());
+    }
+
+    #[cfg(feature = "json5")]
+    #[test]
+    fn test_from_json5_str_rejects_duplicate_keys() {
+        assert!(super::from_json5_str("{ foo: 'Foo', bar: 'Bar' }").is_ok());
+        let err = super::from_json5_str("{ foo: 'Foo', foo: 'Foo 2', }").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(r#"duplicate entry with key "foo""#));
+    }
+}
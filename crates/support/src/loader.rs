@@ -0,0 +1,2323 @@
+use normpath::PathExt;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    canonicalize_locale, json, key_ancestors, strip_bom, yaml, I18nError, PluralCategory,
+    SchemaVersion,
+};
+
+type Locale = String;
+type Value = serde_json::Value;
+type Map = serde_json::Map<String, Value>;
+type Translations = HashMap<Locale, Value>;
+
+pub fn is_debug() -> bool {
+    std::env::var("RUST_I18N_DEBUG").unwrap_or_else(|_| "0".to_string()) == "1"
+}
+
+/// Merge JSON Values, merge b into a
+fn merge_value(a: &mut Value, b: &Value) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (k, v) in b {
+                merge_value(a.entry(k.clone()).or_insert(Value::Null), v);
+            }
+        }
+        (a, b) => {
+            *a = b.clone();
+        }
+    }
+}
+
+/// The extensions of the locale files, `json5` is only supported with the `json5` feature.
+pub const LOCALE_EXTENSIONS: &[&str] = &[
+    "yml",
+    "yaml",
+    "json",
+    "toml",
+    #[cfg(feature = "json5")]
+    "json5",
+];
+
+/// Check if the file extension is one of the [`LOCALE_EXTENSIONS`].
+pub fn is_locale_extension(ext: &str) -> bool {
+    LOCALE_EXTENSIONS.contains(&ext)
+}
+
+/// Get the glob pattern of all locale files in the path, e.g. `locales/**/*.{yml,yaml,json,toml}`.
+pub(crate) fn locale_files_pattern(locales_path: &str) -> String {
+    format!("{}/**/*.{{{}}}", locales_path, LOCALE_EXTENSIONS.join(","))
+}
+
+/// The key of the metadata of a translation key, e.g. `_meta: manual`.
+const META_KEY: &str = "_meta";
+
+/// The directory of the per-environment overlays in a locales path, e.g. `locales/overlays/staging`.
+///
+/// The overlays are not loaded as the base translations, they are loaded as the extra locales paths.
+pub const OVERLAYS_DIR: &str = "overlays";
+
+/// The default separator of the nested keys, e.g. `auth.login.title`.
+pub const DEFAULT_KEY_SEPARATOR: &str = ".";
+
+/// The review status of a translation, marked by `_meta: { status: { fr: needs-review } }` of its key.
+///
+/// The translations without a mark are `translated`, and the missing or empty translations are `untranslated`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TranslationStatus {
+    /// The translation is missing or empty
+    Untranslated,
+    /// The translation is written but not reviewed
+    Translated,
+    /// The translation must be reviewed, e.g. the text of the default locale is changed
+    NeedsReview,
+    /// The translation is reviewed and approved
+    Approved,
+}
+
+impl TranslationStatus {
+    /// All statuses in the order of the workflow.
+    pub const ALL: [TranslationStatus; 4] = [
+        TranslationStatus::Untranslated,
+        TranslationStatus::Translated,
+        TranslationStatus::NeedsReview,
+        TranslationStatus::Approved,
+    ];
+
+    /// The name of the status in the locale files, e.g. `needs-review`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TranslationStatus::Untranslated => "untranslated",
+            TranslationStatus::Translated => "translated",
+            TranslationStatus::NeedsReview => "needs-review",
+            TranslationStatus::Approved => "approved",
+        }
+    }
+}
+
+impl std::fmt::Display for TranslationStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for TranslationStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TranslationStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == s)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid status `{}`, expected `untranslated`, `translated`, `needs-review` or `approved`",
+                    s
+                )
+            })
+    }
+}
+
+/// Join the errors into one message, one error per line.
+fn join_errors(errors: &[I18nError]) -> String {
+    errors
+        .iter()
+        .map(|err| err.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Load locales into flatten key, value HashMap
+//
+// Panics if any locale file is failed to read or parse, use `try_load_locales` to handle the errors.
+pub fn load_locales<F: Fn(&str) -> bool>(
+    locales_path: &str,
+    ignore_if: F,
+) -> HashMap<String, HashMap<String, String>> {
+    try_load_locales(locales_path, ignore_if)
+        .unwrap_or_else(|errors| panic!("{}", join_errors(&errors)))
+}
+
+/// Load locales into flatten key, value HashMap, or returns the errors of all unreadable and malformed locale files.
+pub fn try_load_locales<F: Fn(&str) -> bool>(
+    locales_path: &str,
+    ignore_if: F,
+) -> Result<HashMap<String, HashMap<String, String>>, Vec<I18nError>> {
+    try_load_locales_with_separator(locales_path, ignore_if, DEFAULT_KEY_SEPARATOR)
+}
+
+/// Same as [`try_load_locales`], but the nested keys are joined with the `separator`, e.g. `auth/login/title`.
+pub fn try_load_locales_with_separator<F: Fn(&str) -> bool>(
+    locales_path: &str,
+    ignore_if: F,
+    separator: &str,
+) -> Result<HashMap<String, HashMap<String, String>>, Vec<I18nError>> {
+    let mut result: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut translations = HashMap::new();
+    let mut errors = vec![];
+
+    for file in read_locale_files(locales_path, ignore_if) {
+        let (entry, content) = match file {
+            Ok(file) => file,
+            Err(err) => {
+                errors.push(err);
+                continue;
+            }
+        };
+        let trs = match parse_locale_file(&entry, &content, separator) {
+            Ok(trs) => trs,
+            Err(message) => {
+                errors.push(I18nError::Parse {
+                    file: entry,
+                    message,
+                });
+                continue;
+            }
+        };
+
+        trs.into_iter().for_each(|(k, new_value)| {
+            translations
+                .entry(k)
+                .and_modify(|old_value| merge_value(old_value, &new_value))
+                .or_insert(new_value);
+        });
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    translations.iter().for_each(|(locale, trs)| {
+        result.insert(locale.to_string(), flatten_keys("", trs, separator));
+    });
+
+    Ok(result)
+}
+
+/// The flatten translations of one locale file, see [`iter_locale_files`].
+#[derive(Debug, Clone)]
+pub struct LocaleFile {
+    /// The path of the locale file
+    pub path: PathBuf,
+    /// The translations of each locale in the file, e.g. `{"en": {"hello": "Hello"}}`
+    pub translations: HashMap<String, HashMap<String, String>>,
+}
+
+/// Load the locale files in the path one by one, the nested keys are joined with the `separator`.
+///
+/// Only one file is read and parsed at a time, so the tools can process the large projects incrementally,
+/// the macro loads all files at once by [`try_load_locales_with_separator`] instead.
+/// When the files are merged, the later files override the earlier by key.
+pub fn iter_locale_files<'a, F: Fn(&str) -> bool + 'a>(
+    locales_path: &str,
+    ignore_if: F,
+    separator: &'a str,
+) -> impl Iterator<Item = Result<LocaleFile, I18nError>> + 'a {
+    read_locale_files(locales_path, ignore_if).map(move |file| {
+        let (path, content) = file?;
+        match parse_locale_file(&path, &content, separator) {
+            Ok(trs) => Ok(LocaleFile {
+                translations: trs
+                    .iter()
+                    .map(|(locale, trs)| (locale.clone(), flatten_keys("", trs, separator)))
+                    .collect(),
+                path,
+            }),
+            Err(message) => Err(I18nError::Parse {
+                file: path,
+                message,
+            }),
+        }
+    })
+}
+
+/// Expand the glob pattern in the locales path into the matched directories, sorted by the path.
+///
+/// The `*`, `**` and `{a,b}` patterns are supported, e.g. `locales/**/active` or `{locales,shared-locales}`,
+/// a path without the glob pattern is returned as is, even if it doesn't exist.
+/// The directories inside another matched directory are skipped, they are already loaded by the outer one.
+pub fn expand_load_path(path: &str) -> Vec<String> {
+    if !path.contains(['*', '{']) {
+        return vec![path.to_owned()];
+    }
+
+    // Walk from the longest path without the glob pattern, e.g. `/app` of `/app/{locales,shared}`
+    let mut base = PathBuf::new();
+    let mut pattern = PathBuf::new();
+    for component in Path::new(path).components() {
+        let part = component.as_os_str();
+        if pattern.as_os_str().is_empty() && !part.to_string_lossy().contains(['*', '{', '}']) {
+            base.push(part);
+        } else {
+            pattern.push(part);
+        }
+    }
+    if base.as_os_str().is_empty() {
+        base.push(".");
+    }
+    // The leading `/` anchors the pattern to the base, the patterns are matched like `.gitignore`
+    let pattern = format!("/{}", pattern.display()).replace('\\', "/");
+    let Ok(walker) = globwalk::GlobWalkerBuilder::new(&base, &pattern)
+        .file_type(globwalk::FileType::DIR)
+        .build()
+    else {
+        return vec![];
+    };
+
+    let mut dirs = walker
+        .filter_map(Result::ok)
+        .map(|entry| entry.into_path())
+        .collect::<Vec<_>>();
+    dirs.sort();
+    let mut result = Vec::<PathBuf>::new();
+    for dir in dirs {
+        if !result.iter().any(|parent| dir.starts_with(parent)) {
+            result.push(dir);
+        }
+    }
+    result
+        .into_iter()
+        .map(|dir| dir.display().to_string())
+        .collect()
+}
+
+/// Load locales from multiple paths and merge them, the later paths override the earlier on key conflict.
+///
+/// Panics if any locale file is failed to read or parse, use `try_load_locales_from_paths` to handle the errors.
+pub fn load_locales_from_paths<P: AsRef<str>, F: Fn(&str) -> bool>(
+    locales_paths: &[P],
+    ignore_if: F,
+) -> HashMap<String, HashMap<String, String>> {
+    try_load_locales_from_paths(locales_paths, ignore_if)
+        .unwrap_or_else(|errors| panic!("{}", join_errors(&errors)))
+}
+
+/// Load locales from multiple paths and merge them, or returns the errors of all unreadable and malformed locale files.
+pub fn try_load_locales_from_paths<P: AsRef<str>, F: Fn(&str) -> bool>(
+    locales_paths: &[P],
+    ignore_if: F,
+) -> Result<HashMap<String, HashMap<String, String>>, Vec<I18nError>> {
+    try_load_locales_from_paths_with_separator(locales_paths, ignore_if, DEFAULT_KEY_SEPARATOR)
+}
+
+/// Same as [`try_load_locales_from_paths`], but the nested keys are joined with the `separator`.
+pub fn try_load_locales_from_paths_with_separator<P: AsRef<str>, F: Fn(&str) -> bool>(
+    locales_paths: &[P],
+    ignore_if: F,
+    separator: &str,
+) -> Result<HashMap<String, HashMap<String, String>>, Vec<I18nError>> {
+    let mut result: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut errors = vec![];
+
+    for locales_path in locales_paths {
+        let locales_path = locales_path.as_ref();
+        let data = match try_load_locales_with_separator(locales_path, &ignore_if, separator) {
+            Ok(data) => data,
+            Err(errs) => {
+                errors.extend(errs);
+                continue;
+            }
+        };
+        for (locale, trs) in data {
+            let merged = result.entry(locale.clone()).or_default();
+            for (key, value) in trs {
+                if is_debug() && merged.get(&key).is_some_and(|old| *old != value) {
+                    println!(
+                        "cargo:i18n-override={}.{} from {}",
+                        locale, key, locales_path
+                    );
+                }
+                merged.insert(key, value);
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(result)
+}
+
+/// Key the translations by the canonical locales of [`canonicalize_locale`], e.g. `en-us` and `EN-US` to `en-US`.
+///
+/// The translations of the same canonical locale are merged, the locale already in the canonical form wins on key conflict,
+/// the others are merged in the order of their names, so the result is the same in every run.
+pub fn canonicalize_locales(
+    data: HashMap<String, HashMap<String, String>>,
+) -> HashMap<String, HashMap<String, String>> {
+    let mut locales = data
+        .into_iter()
+        .map(|(locale, trs)| (canonicalize_locale(&locale), locale, trs))
+        .collect::<Vec<_>>();
+    locales.sort_by(|(a, a_locale, _), (b, b_locale, _)| {
+        (a, *a == *a_locale, a_locale).cmp(&(b, *b == *b_locale, b_locale))
+    });
+
+    let mut result = HashMap::<String, HashMap<String, String>>::new();
+    for (canonical, _, trs) in locales {
+        result.entry(canonical).or_default().extend(trs);
+    }
+    result
+}
+
+/// Load the keys that are marked as manual by `_meta: manual` (or `_meta: { manual: true }`).
+///
+/// A manual key is added for dynamic texts and intentionally not present in the source code,
+/// the tools should not report it as orphaned. All keys nested under a manual key are also manual.
+///
+/// ```yml
+/// _version: 2
+/// greeting.dynamic:
+///   _meta: manual
+///   en: Hello
+///   zh-CN: 你好
+/// ```
+///
+/// Returns the error of the first unreadable or malformed locale file, the same for the other `load_*_keys` functions.
+pub fn load_manual_keys<F: Fn(&str) -> bool>(
+    locales_path: &str,
+    ignore_if: F,
+) -> Result<HashSet<String>, I18nError> {
+    load_manual_keys_with_separator(locales_path, ignore_if, DEFAULT_KEY_SEPARATOR)
+}
+
+/// Same as [`load_manual_keys`], but the nested keys are joined with the `separator`.
+pub fn load_manual_keys_with_separator<F: Fn(&str) -> bool>(
+    locales_path: &str,
+    ignore_if: F,
+    separator: &str,
+) -> Result<HashSet<String>, I18nError> {
+    let manual_keys = load_meta_keys(locales_path, ignore_if, separator, meta_manual)?;
+    Ok(manual_keys.into_keys().collect())
+}
+
+/// Check if the key is a manual key or nested under a manual key.
+pub fn is_manual_key(manual_keys: &HashSet<String>, key: &str) -> bool {
+    is_manual_key_with_separator(manual_keys, key, DEFAULT_KEY_SEPARATOR)
+}
+
+/// Same as [`is_manual_key`], but the nested keys are separated by the `separator`.
+pub fn is_manual_key_with_separator(
+    manual_keys: &HashSet<String>,
+    key: &str,
+    separator: &str,
+) -> bool {
+    key_ancestors(key, separator).any(|key| manual_keys.contains(key))
+}
+
+/// Load the keys that are marked as deprecated by `_meta: { deprecated: "use new.key" }`, with the messages.
+///
+/// The message is empty for `_meta: deprecated` (or `deprecated: true`), all keys nested under a deprecated key are also deprecated.
+///
+/// ```yml
+/// _version: 2
+/// old.key:
+///   _meta:
+///     deprecated: use new.key
+///   en: Hello
+/// ```
+pub fn load_deprecated_keys_with_separator<F: Fn(&str) -> bool>(
+    locales_path: &str,
+    ignore_if: F,
+    separator: &str,
+) -> Result<HashMap<String, String>, I18nError> {
+    load_meta_keys(locales_path, ignore_if, separator, meta_deprecated)
+}
+
+/// Get the deprecated key and its message if the key is deprecated or nested under a deprecated key.
+pub fn find_deprecated_key<'a>(
+    deprecated_keys: &'a HashMap<String, String>,
+    key: &str,
+    separator: &str,
+) -> Option<(&'a str, &'a str)> {
+    key_ancestors(key, separator)
+        .find_map(|key| deprecated_keys.get_key_value(key))
+        .map(|(key, message)| (key.as_str(), message.as_str()))
+}
+
+/// Format the warning of the deprecated key, e.g. "the key `old.key` is deprecated: use new.key".
+pub fn deprecated_key_message(key: &str, deprecated: &str, message: &str) -> String {
+    let mut text = format!("the key `{}` is deprecated", key);
+    if key != deprecated {
+        text.push_str(&format!(" by `{}`", deprecated));
+    }
+    if !message.is_empty() {
+        text.push_str(": ");
+        text.push_str(message);
+    }
+    text
+}
+
+/// Load the maximum lengths of the translations marked by `_meta: { max_len: 12 }`, for the texts that must fit the UI.
+///
+/// The nested keys (e.g. the plural forms and the list items) take the constraint of the nearest marked key.
+///
+/// ```yml
+/// _version: 2
+/// button.save:
+///   _meta:
+///     max_len: 12
+///   en: Save
+///   de: Speichern
+/// ```
+pub fn load_max_len_keys_with_separator<F: Fn(&str) -> bool>(
+    locales_path: &str,
+    ignore_if: F,
+    separator: &str,
+) -> Result<HashMap<String, usize>, I18nError> {
+    load_meta_keys(locales_path, ignore_if, separator, meta_max_len)
+}
+
+/// Load the byte budgets of the translations marked by `_meta: { max_bytes: ... }`, for the texts of the channels
+/// that are limited in the bytes of UTF-8 instead of the characters, e.g. the SMS and the push notifications.
+///
+/// The budgets are a map of the channel to the bytes, e.g. `{ sms: 160, push: 178 }`, or the bytes of all channels,
+/// which is the empty channel. The nested keys take the budgets of the nearest marked key, like `max_len`.
+///
+/// ```yml
+/// _version: 2
+/// sms.verify:
+///   _meta:
+///     max_bytes:
+///       sms: 160
+///       push: 178
+///   en: Your code is %{code}
+/// ```
+pub fn load_max_bytes_keys_with_separator<F: Fn(&str) -> bool>(
+    locales_path: &str,
+    ignore_if: F,
+    separator: &str,
+) -> Result<HashMap<String, BTreeMap<String, usize>>, I18nError> {
+    load_meta_keys(locales_path, ignore_if, separator, meta_max_bytes)
+}
+
+/// Load the review statuses of the translations marked by `_meta: { status: ... }`, a map of the locale to the status,
+/// see [`TranslationStatus`]. The nested keys take the statuses of the nearest marked key, like `max_len`.
+///
+/// ```yml
+/// _version: 2
+/// checkout.title:
+///   _meta:
+///     status:
+///       fr: approved
+///       de: needs-review
+///   en: Checkout
+///   fr: Paiement
+///   de: Kasse
+/// ```
+pub fn load_status_keys_with_separator<F: Fn(&str) -> bool>(
+    locales_path: &str,
+    ignore_if: F,
+    separator: &str,
+) -> Result<HashMap<String, BTreeMap<String, TranslationStatus>>, I18nError> {
+    load_meta_keys(locales_path, ignore_if, separator, meta_status)
+}
+
+/// Load the source texts of the keys marked by `_meta: { source: "Hello, world!" }`, e.g. the texts of the minified keys.
+///
+/// ```yml
+/// _version: 2
+/// tr_4Cct6Q289b12SkvF47dXIx:
+///   _meta:
+///     source: Hello, world!
+///   en: Hello, world!
+///   zh-CN: 你好，世界！
+/// ```
+pub fn load_source_texts_with_separator<F: Fn(&str) -> bool>(
+    locales_path: &str,
+    ignore_if: F,
+    separator: &str,
+) -> Result<HashMap<String, String>, I18nError> {
+    load_meta_keys(locales_path, ignore_if, separator, meta_source)
+}
+
+/// Find the translations that are longer (in characters) than the `max_len` of their keys,
+/// sorted by the locale and the key, as `(locale, key, len, max_len)`.
+pub fn find_too_long_translations(
+    data: &HashMap<String, HashMap<String, String>>,
+    max_len_keys: &HashMap<String, usize>,
+    separator: &str,
+) -> Vec<(String, String, usize, usize)> {
+    if max_len_keys.is_empty() {
+        return vec![];
+    }
+
+    let mut result = vec![];
+    for (locale, trs) in data {
+        for (key, value) in trs {
+            // The nearest marked key wins, e.g. `button.save` for `button.save.one`
+            let max_len = key_ancestors(key, separator).find_map(|key| max_len_keys.get(key));
+            let len = value.chars().count();
+            if let Some(&max_len) = max_len.filter(|max_len| len > **max_len) {
+                result.push((locale.clone(), key.clone(), len, max_len));
+            }
+        }
+    }
+    result.sort();
+    result
+}
+
+/// Find the translations that are longer (in the bytes of UTF-8) than the `max_bytes` of any channel of their keys,
+/// sorted by the locale, the key and the channel, as `(locale, key, channel, bytes, max_bytes)`.
+///
+/// The channel is empty for the budget of all channels.
+pub fn find_too_many_bytes_translations(
+    data: &HashMap<String, HashMap<String, String>>,
+    max_bytes_keys: &HashMap<String, BTreeMap<String, usize>>,
+    separator: &str,
+) -> Vec<(String, String, String, usize, usize)> {
+    if max_bytes_keys.is_empty() {
+        return vec![];
+    }
+
+    let mut result = vec![];
+    for (locale, trs) in data {
+        for (key, value) in trs {
+            // The nearest marked key wins, e.g. `sms.verify` for `sms.verify.one`
+            let Some(budgets) =
+                key_ancestors(key, separator).find_map(|key| max_bytes_keys.get(key))
+            else {
+                continue;
+            };
+            for (channel, &max_bytes) in budgets {
+                if value.len() > max_bytes {
+                    result.push((
+                        locale.clone(),
+                        key.clone(),
+                        channel.clone(),
+                        value.len(),
+                        max_bytes,
+                    ));
+                }
+            }
+        }
+    }
+    result.sort();
+    result
+}
+
+/// Count the keys of the default locale in each status for each locale, see [`TranslationStatus`].
+///
+/// A missing or empty translation is always `untranslated`, the others are in the status of the nearest marked key,
+/// or `translated` if none is marked.
+pub fn count_translation_status(
+    data: &HashMap<String, HashMap<String, String>>,
+    default_locale: &str,
+    status_keys: &HashMap<String, BTreeMap<String, TranslationStatus>>,
+    separator: &str,
+) -> BTreeMap<String, BTreeMap<TranslationStatus, usize>> {
+    let Some(default_trs) = data.get(default_locale) else {
+        return BTreeMap::new();
+    };
+
+    let mut result = BTreeMap::<String, BTreeMap<TranslationStatus, usize>>::new();
+    for (locale, trs) in data {
+        let counts = result
+            .entry(locale.clone())
+            .or_insert_with(|| TranslationStatus::ALL.map(|status| (status, 0)).into());
+        for key in default_trs.keys() {
+            let status = match trs.get(key) {
+                Some(value) if !value.trim().is_empty() => key_ancestors(key, separator)
+                    .find_map(|key| status_keys.get(key)?.get(locale))
+                    .copied()
+                    .unwrap_or(TranslationStatus::Translated),
+                _ => TranslationStatus::Untranslated,
+            };
+            *counts.entry(status).or_default() += 1;
+        }
+    }
+    result
+}
+
+/// Find the keys marked as `needs-review` in the `locales` (`*` for all), sorted by `(locale, key)`.
+pub fn find_needs_review_translations(
+    status_keys: &HashMap<String, BTreeMap<String, TranslationStatus>>,
+    locales: &[String],
+) -> Vec<(String, String)> {
+    let mut result = status_keys
+        .iter()
+        .flat_map(|(key, statuses)| {
+            statuses
+                .iter()
+                .filter(|(locale, status)| {
+                    **status == TranslationStatus::NeedsReview
+                        && locales.iter().any(|l| l == "*" || l == *locale)
+                })
+                .map(|(locale, _)| (locale.clone(), key.clone()))
+        })
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+/// Find the empty or whitespace-only translations of the non-default locales, sorted by `(locale, key)`.
+///
+/// An empty translation is usually an unfinished translation, the default locale is exempted.
+pub fn find_empty_translations(
+    data: &HashMap<String, HashMap<String, String>>,
+    default_locale: &str,
+) -> Vec<(String, String)> {
+    let mut result = data
+        .iter()
+        .filter(|(locale, _)| *locale != default_locale)
+        .flat_map(|(locale, trs)| {
+            trs.iter()
+                .filter(|(_, value)| value.trim().is_empty())
+                .map(move |(key, _)| (locale.clone(), key.clone()))
+        })
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+/// Find the translations whose placeholders differ from the default locale, sorted by `(locale, key)`,
+/// as `(locale, key, missing, extra)`, e.g. the missing `name` and the extra `nom` of `%{nom}` for `%{name}`.
+///
+/// The placeholders are compared by their names, see [`placeholder_names`](crate::placeholder_names),
+/// the keys that are not in both the locale and the default locale are skipped.
+pub fn find_placeholder_mismatches(
+    data: &HashMap<String, HashMap<String, String>>,
+    default_locale: &str,
+    open: &str,
+    close: &str,
+) -> Vec<(String, String, Vec<String>, Vec<String>)> {
+    let Some(default_trs) = data.get(default_locale) else {
+        return vec![];
+    };
+    let names = |text| {
+        crate::placeholder_names(text, open, close)
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+    };
+
+    let mut result = data
+        .iter()
+        .filter(|(locale, _)| *locale != default_locale)
+        .flat_map(|(locale, trs)| {
+            trs.iter().filter_map(move |(key, value)| {
+                let expected = names(default_trs.get(key)?);
+                let actual = names(value);
+                if expected == actual {
+                    return None;
+                }
+                let missing = expected.difference(&actual).map(|name| name.to_string());
+                let extra = actual.difference(&expected).map(|name| name.to_string());
+                Some((
+                    locale.clone(),
+                    key.clone(),
+                    missing.collect(),
+                    extra.collect(),
+                ))
+            })
+        })
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+/// Find the keys of the default locale that are missing in each of the `locales`, sorted by the key.
+///
+/// The locales that are complete are omitted, a locale that has no translations is missing all keys.
+pub fn find_missing_translations(
+    data: &HashMap<String, HashMap<String, String>>,
+    default_locale: &str,
+    locales: &[String],
+) -> Vec<(String, Vec<String>)> {
+    let Some(default_trs) = data.get(default_locale) else {
+        return vec![];
+    };
+    let empty = HashMap::new();
+
+    locales
+        .iter()
+        .filter(|locale| *locale != default_locale)
+        .filter_map(|locale| {
+            let trs = data.get(locale).unwrap_or(&empty);
+            let mut missing = default_trs
+                .keys()
+                .filter(|key| *key != "_version" && !trs.contains_key(*key))
+                .cloned()
+                .collect::<Vec<_>>();
+            if missing.is_empty() {
+                return None;
+            }
+            missing.sort();
+            Some((locale.clone(), missing))
+        })
+        .collect()
+}
+
+/// Find the locales whose coverage of the keys of the default locale is below the `min_coverage` percentage,
+/// sorted by the locale, as `(locale, translated_keys, total_keys, min_coverage)`.
+///
+/// The `min_coverage` maps the locales to the percentages, e.g. `{ fr = 90 }`, the `*` is for all loaded locales
+/// that are not listed. The default locale is skipped, a listed locale that has no translations covers no key.
+pub fn find_low_coverage_translations(
+    data: &HashMap<String, HashMap<String, String>>,
+    default_locale: &str,
+    min_coverage: &BTreeMap<String, u8>,
+) -> Vec<(String, usize, usize, u8)> {
+    let Some(default_trs) = data.get(default_locale) else {
+        return vec![];
+    };
+    let keys = default_trs
+        .keys()
+        .filter(|key| *key != "_version")
+        .collect::<Vec<_>>();
+    if keys.is_empty() {
+        return vec![];
+    }
+    let empty = HashMap::new();
+
+    let mut locales = min_coverage
+        .iter()
+        .filter(|(locale, _)| *locale != "*")
+        .map(|(locale, min)| (locale.as_str(), *min))
+        .collect::<BTreeMap<_, _>>();
+    if let Some(min) = min_coverage.get("*") {
+        for locale in data.keys() {
+            locales.entry(locale.as_str()).or_insert(*min);
+        }
+    }
+
+    locales
+        .into_iter()
+        .filter(|(locale, _)| *locale != default_locale)
+        .filter_map(|(locale, min)| {
+            let trs = data.get(locale).unwrap_or(&empty);
+            let translated = keys.iter().filter(|key| trs.contains_key(**key)).count();
+            // Compare in integers, e.g. 9 of 10 keys is 90%
+            if translated * 100 >= keys.len() * min as usize {
+                return None;
+            }
+            Some((locale.to_owned(), translated, keys.len(), min))
+        })
+        .collect()
+}
+
+/// Find the keys of the default locale that each of the `locales` only gets through the fallback chain,
+/// sorted by the locale and the key, as `(locale, key, resolved_locale)`.
+///
+/// The chain is the same as the runtime lookup: the less-specific locales first, e.g. `zh-Hant-TW` to
+/// `zh-Hant` to `zh`, then the `fallback` locales. A key that resolves to a locale of the same language
+/// is not reported, neither the locales in `no_fallback_locales` that never fall back.
+pub fn find_fallback_translations(
+    data: &HashMap<String, HashMap<String, String>>,
+    default_locale: &str,
+    locales: &[String],
+    fallback: &[String],
+    no_fallback_locales: &[String],
+) -> Vec<(String, String, String)> {
+    let Some(default_trs) = data.get(default_locale) else {
+        return vec![];
+    };
+    let language = |locale: &str| {
+        locale
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase()
+    };
+
+    let mut result = vec![];
+    for locale in locales.iter().filter(|locale| *locale != default_locale) {
+        if no_fallback_locales
+            .iter()
+            .any(|strict| strict.eq_ignore_ascii_case(locale))
+        {
+            continue;
+        }
+
+        let mut chain = vec![locale.as_str()];
+        let mut current_locale = locale.as_str();
+        while let Some(parent) = crate::lookup_fallback(current_locale) {
+            chain.push(parent);
+            current_locale = parent;
+        }
+        chain.extend(fallback.iter().map(String::as_str));
+
+        for key in default_trs.keys().filter(|key| *key != "_version") {
+            let resolved = chain
+                .iter()
+                .find(|locale| data.get(**locale).is_some_and(|trs| trs.contains_key(key)));
+            if let Some(resolved) = resolved {
+                if language(resolved) != language(locale) {
+                    result.push((locale.clone(), key.clone(), resolved.to_string()));
+                }
+            }
+        }
+    }
+    result.sort();
+    result
+}
+
+/// Load the attribute of the keys marked by `_meta` in the locale files, the attribute is got by `extract`.
+///
+/// The later files override the earlier by key.
+fn load_meta_keys<T, F: Fn(&str) -> bool>(
+    locales_path: &str,
+    ignore_if: F,
+    separator: &str,
+    extract: impl Fn(&Map) -> Option<T>,
+) -> Result<HashMap<String, T>, I18nError> {
+    let mut keys = HashMap::new();
+
+    for value in read_locale_values(locales_path, ignore_if) {
+        walk_meta("", &value?, separator, &mut |key, meta| {
+            if let Some(attr) = extract(meta) {
+                keys.insert(key.to_string(), attr);
+            }
+        });
+    }
+
+    Ok(keys)
+}
+
+/// Walk the keys marked by `_meta` in the value, `f` is called with each key and its `_meta`,
+/// the nested keys are joined with the `separator`.
+///
+/// The shorthand `_meta: manual` is the same as `_meta: { manual: true }`.
+fn walk_meta(prefix: &str, value: &Value, separator: &str, f: &mut impl FnMut(&str, &Map)) {
+    let Value::Object(o) = value else {
+        return;
+    };
+
+    if !prefix.is_empty() {
+        match o.get(META_KEY) {
+            Some(Value::Object(meta)) => f(prefix, meta),
+            Some(Value::String(name)) => {
+                f(prefix, &Map::from_iter([(name.clone(), Value::Bool(true))]))
+            }
+            _ => {}
+        }
+    }
+
+    for (k, v) in o {
+        if k != META_KEY {
+            walk_meta(&format_keys(&[prefix, k], separator), v, separator, f);
+        }
+    }
+}
+
+/// Parse the boolean of the `_meta`, e.g. `true` or `"true"`.
+fn meta_bool(value: &Value) -> bool {
+    match value {
+        Value::Bool(b) => *b,
+        Value::String(s) => s == "true",
+        _ => false,
+    }
+}
+
+/// Parse the number of the `_meta`, e.g. `12` or `"12"`.
+fn meta_usize(value: &Value) -> Option<usize> {
+    match value {
+        Value::Number(n) => n.as_u64().map(|n| n as usize),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn meta_manual(meta: &Map) -> Option<()> {
+    meta.get("manual").filter(|v| meta_bool(v)).map(|_| ())
+}
+
+fn meta_deprecated(meta: &Map) -> Option<String> {
+    match meta.get("deprecated")? {
+        v if meta_bool(v) => Some(String::new()),
+        Value::String(s) if s != "false" => Some(s.clone()),
+        _ => None,
+    }
+}
+
+fn meta_max_len(meta: &Map) -> Option<usize> {
+    meta_usize(meta.get("max_len")?)
+}
+
+fn meta_max_bytes(meta: &Map) -> Option<BTreeMap<String, usize>> {
+    let budgets = match meta.get("max_bytes")? {
+        Value::Object(channels) => channels
+            .iter()
+            .filter_map(|(channel, bytes)| Some((channel.clone(), meta_usize(bytes)?)))
+            .collect(),
+        bytes => BTreeMap::from([(String::new(), meta_usize(bytes)?)]),
+    };
+    Some(budgets).filter(|budgets| !budgets.is_empty())
+}
+
+fn meta_status(meta: &Map) -> Option<BTreeMap<String, TranslationStatus>> {
+    let Value::Object(statuses) = meta.get("status")? else {
+        return None;
+    };
+    let statuses = statuses
+        .iter()
+        .filter_map(|(locale, status)| Some((locale.clone(), status.as_str()?.parse().ok()?)))
+        .collect::<BTreeMap<_, _>>();
+    Some(statuses).filter(|statuses| !statuses.is_empty())
+}
+
+fn meta_source(meta: &Map) -> Option<String> {
+    meta.get("source")?.as_str().map(String::from)
+}
+
+/// Parse the locale files in the path into the values as written, one file at a time.
+fn read_locale_values<'a, F: Fn(&str) -> bool + 'a>(
+    locales_path: &str,
+    ignore_if: F,
+) -> impl Iterator<Item = Result<Value, I18nError>> + 'a {
+    read_locale_files(locales_path, ignore_if).map(|file| {
+        let (path, content) = file?;
+        split_file_name(&path)
+            .and_then(|(_, ext)| parse_value(&content, ext))
+            .map_err(|message| I18nError::Parse {
+                file: path,
+                message,
+            })
+    })
+}
+
+/// Read the content of the locale files in the path lazily, one file at a time.
+fn read_locale_files<'a, F: Fn(&str) -> bool + 'a>(
+    locales_path: &str,
+    ignore_if: F,
+) -> impl Iterator<Item = Result<(PathBuf, String), I18nError>> + 'a {
+    walk_locale_files(locales_path, ignore_if).map(|entry| {
+        let entry = entry?;
+        let mut content = String::new();
+        if let Err(source) = File::open(&entry)
+            .and_then(|file| std::io::BufReader::new(file).read_to_string(&mut content))
+        {
+            return Err(I18nError::Io {
+                path: entry,
+                source,
+            });
+        }
+        // The BOM would be parsed as a part of the first key
+        let content = strip_bom(&content).to_string();
+
+        Ok((entry, content))
+    })
+}
+
+/// Get the paths of the locale files in the path, the files that are not readable are skipped.
+///
+/// The macros use this to track the locale files, so the changed files are loaded again.
+pub fn locale_file_paths(locales_path: &str) -> Vec<PathBuf> {
+    walk_locale_files(locales_path, |_| false)
+        .filter_map(Result::ok)
+        .collect()
+}
+
+/// Walk the locale files in the path lazily, the files in the overlays directory are skipped.
+fn walk_locale_files<'a, F: Fn(&str) -> bool + 'a>(
+    locales_path: &str,
+    ignore_if: F,
+) -> Box<dyn Iterator<Item = Result<PathBuf, I18nError>> + 'a> {
+    let locales_path = match Path::new(locales_path).normalize() {
+        Ok(p) => p,
+        Err(e) => {
+            if is_debug() {
+                println!("cargo:i18n-error={}", e);
+            }
+            return Box::new(std::iter::empty());
+        }
+    };
+    let locales_path = match locales_path.as_path().to_str() {
+        Some(p) => p.to_owned(),
+        None => {
+            if is_debug() {
+                println!("cargo:i18n-error=could not convert path");
+            }
+            return Box::new(std::iter::empty());
+        }
+    };
+
+    let path_pattern = locale_files_pattern(&locales_path);
+
+    if is_debug() {
+        println!("cargo:i18n-locale={}", &path_pattern);
+    }
+
+    // check dir exists
+    if !Path::new(&locales_path).exists() {
+        if is_debug() {
+            println!("cargo:i18n-error=path not exists: {}", locales_path);
+        }
+        return Box::new(std::iter::empty());
+    }
+
+    let entries = match globwalk::glob(&path_pattern) {
+        Ok(entries) => entries,
+        Err(err) => {
+            return Box::new(std::iter::once(Err(I18nError::Io {
+                path: PathBuf::from(locales_path),
+                source: std::io::Error::new(std::io::ErrorKind::InvalidInput, err),
+            })))
+        }
+    };
+    Box::new(entries.filter_map(move |entry| {
+        let entry = match entry {
+            Ok(entry) => entry.into_path(),
+            Err(err) => {
+                let path = err
+                    .path()
+                    .map_or_else(|| PathBuf::from(&locales_path), Path::to_path_buf);
+                return Some(Err(I18nError::Io {
+                    path,
+                    source: err.into(),
+                }));
+            }
+        };
+        if is_debug() {
+            println!("cargo:i18n-load={}", &entry.display());
+        }
+
+        if ignore_if(&entry.display().to_string()) {
+            return None;
+        }
+        if entry
+            .strip_prefix(&locales_path)
+            .is_ok_and(|path| path.starts_with(OVERLAYS_DIR))
+        {
+            return None;
+        }
+
+        Some(Ok(entry))
+    }))
+}
+
+/// Parse the locale file, the locale of the v1 files is the last part of the file name, e.g. `app.en.yml`.
+fn parse_locale_file(path: &Path, content: &str, separator: &str) -> Result<Translations, String> {
+    let (stem, ext) = split_file_name(path)?;
+    let locale = stem.split('.').next_back().unwrap_or(stem);
+
+    parse_file(content, ext, locale, separator)
+}
+
+/// Split the file name of the locale file into the stem and the extension, e.g. `app.en` and `yml`.
+fn split_file_name(path: &Path) -> Result<(&str, &str), String> {
+    match (
+        path.file_stem().and_then(|s| s.to_str()),
+        path.extension().and_then(|s| s.to_str()),
+    ) {
+        (Some(stem), Some(ext)) => Ok((stem, ext)),
+        _ => Err("Invalid file name, it must be UTF-8 with an extension".into()),
+    }
+}
+
+// Parse Translations from file to support multiple formats
+fn parse_file(
+    content: &str,
+    ext: &str,
+    locale: &str,
+    separator: &str,
+) -> Result<Translations, String> {
+    match parse_value(content, ext) {
+        Ok(v) => match SchemaVersion::from_value(&v)? {
+            SchemaVersion::V2 => {
+                if let Some(trs) = parse_file_v2("", &v, separator) {
+                    return Ok(trs);
+                }
+
+                Err("Invalid locale file format, please check the version field".into())
+            }
+            SchemaVersion::V1 => Ok(parse_file_v1(locale, &v)),
+        },
+        Err(e) => Err(e),
+    }
+}
+
+/// Parse the content of a locale file by the file extension, the keys and values are kept as written.
+pub fn parse_value(content: &str, ext: &str) -> Result<Value, String> {
+    match ext {
+        "yml" | "yaml" => {
+            yaml::from_str(content).map_err(|err| format!("Invalid YAML format, {}", err))
+        }
+        "json" => json::from_str(content).map_err(|err| format!("Invalid JSON format, {}", err)),
+        "toml" => toml::from_str::<serde_json::Value>(content)
+            .map_err(|err| format!("Invalid TOML format, {}", err)),
+        #[cfg(feature = "json5")]
+        "json5" => {
+            json::from_json5_str(content).map_err(|err| format!("Invalid JSON5 format, {}", err))
+        }
+        _ => Err("Invalid file extension".into()),
+    }
+}
+
+/// Locale file format v1
+///
+/// For example:
+/// ```yml
+/// welcome: Welcome
+/// foo: Foo bar
+/// ```
+fn parse_file_v1(locale: &str, data: &serde_json::Value) -> Translations {
+    Translations::from([(locale.to_string(), data.clone())])
+}
+
+/// Locale file format v2
+/// Iter all nested keys, if the value is not a object (Map<locale, string>), then convert into multiple locale translations
+///
+/// If the final value is Map<locale, string>, then convert them and insert into trs
+///
+/// For example (only support 1 level):
+///
+/// ```yml
+/// _version: 2
+/// welcome.first:
+///   en: Welcome
+///   zh-CN: 欢迎
+/// welcome1:
+///   en: Welcome 1
+///   zh-CN: 欢迎 1
+/// ```
+///
+/// into
+///
+/// ```yml
+/// en.welcome.first: Welcome
+/// zh-CN.welcome.first: 欢迎
+/// en.welcome1: Welcome 1
+/// zh-CN.welcome1: 欢迎 1
+/// ```
+///
+/// The value of a locale can also be a map of the plural categories, e.g. `en: { one: 1 item, other: "%{count} items" }`
+/// is loaded as `items.one` and `items.other`, see [`is_plural_map`].
+fn parse_file_v2(
+    key_prefix: &str,
+    data: &serde_json::Value,
+    separator: &str,
+) -> Option<Translations> {
+    let mut trs = Translations::new();
+
+    if let serde_json::Value::Object(messages) = data {
+        for (key, value) in messages {
+            if let serde_json::Value::Object(sub_messages) = value {
+                // If all values are string, then convert them into multiple locale translations
+                for (locale, text) in sub_messages {
+                    if locale == META_KEY {
+                        continue;
+                    }
+                    // Ignore if the locale is not a locale
+                    // e.g:
+                    //  en: Welcome
+                    //  zh-CN: 欢迎
+                    if text.is_string() || text.is_array() || is_plural_map(text) {
+                        let key = format_keys(&[key_prefix, key], separator);
+                        let sub_trs = HashMap::from([(key, text.clone())]);
+                        let sub_value = serde_json::to_value(&sub_trs).unwrap();
+
+                        trs.entry(locale.clone())
+                            .and_modify(|old_value| merge_value(old_value, &sub_value))
+                            .or_insert(sub_value);
+                        continue;
+                    }
+
+                    if text.is_object() {
+                        // Parse the nested keys
+                        // If the value is object (Map<locale, string>), iter them and convert them and insert into trs
+                        let key = format_keys(&[key_prefix, key], separator);
+                        if let Some(sub_trs) = parse_file_v2(&key, value, separator) {
+                            // Merge the sub_trs into trs
+                            for (locale, sub_value) in sub_trs {
+                                trs.entry(locale)
+                                    .and_modify(|old_value| merge_value(old_value, &sub_value))
+                                    .or_insert(sub_value);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if !trs.is_empty() {
+        return Some(trs);
+    }
+
+    None
+}
+
+/// Check if the value is a map of the plural forms, e.g. `{ one: 1 item, other: "%{count} items" }`.
+///
+/// All keys must be the plural categories (`zero`, `one`, `two`, `few`, `many` or `other`) with the text values,
+/// and the `other` form is required.
+pub fn is_plural_map(value: &Value) -> bool {
+    let Value::Object(map) = value else {
+        return false;
+    };
+    map.contains_key(PluralCategory::Other.as_str())
+        && map
+            .iter()
+            .all(|(key, value)| PluralCategory::from_key(key).is_some() && value.is_string())
+}
+
+/// Get `_version` from JSON root
+/// If `_version` is not found, then return 1 as default.
+///
+/// The version is not validated, use [`SchemaVersion::from_value`] to reject the unknown versions.
+pub fn get_version(data: &serde_json::Value) -> usize {
+    if let Some(version) = data.get("_version") {
+        return version
+            .as_u64()
+            .or_else(|| version.as_str().and_then(|v| v.parse().ok()))
+            .unwrap_or(1) as usize;
+    }
+
+    1
+}
+
+/// Join the keys with the separator, if any key is empty, omit it.
+fn format_keys(keys: &[&str], separator: &str) -> String {
+    keys.iter()
+        .filter(|k| !k.is_empty())
+        .map(|k| k.to_string())
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+fn flatten_keys(prefix: &str, trs: &Value, separator: &str) -> HashMap<String, String> {
+    let mut v = HashMap::<String, String>::new();
+    let prefix = prefix.to_string();
+
+    match &trs {
+        serde_json::Value::String(s) => {
+            v.insert(prefix, s.to_string());
+        }
+        serde_json::Value::Object(o) => {
+            for (k, vv) in o {
+                if k == META_KEY {
+                    continue;
+                }
+                let key = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}{}{}", prefix, separator, k)
+                };
+                v.extend(flatten_keys(key.as_str(), vv, separator));
+            }
+        }
+        serde_json::Value::Null => {
+            v.insert(prefix, "".into());
+        }
+        serde_json::Value::Bool(s) => {
+            v.insert(prefix, format!("{}", s));
+        }
+        serde_json::Value::Number(s) => {
+            v.insert(prefix, format!("{}", s));
+        }
+        serde_json::Value::Array(a) => {
+            // Keep the list items as indexed keys, e.g. `tips.0`, `tips.1`
+            for (i, vv) in a.iter().enumerate() {
+                let key = format_keys(&[&prefix, &i.to_string()], separator);
+                v.extend(flatten_keys(key.as_str(), vv, separator));
+            }
+        }
+    }
+
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        canonicalize_locales, count_translation_status, find_empty_translations,
+        find_fallback_translations, find_low_coverage_translations, find_missing_translations,
+        find_needs_review_translations, find_placeholder_mismatches, find_too_long_translations,
+        find_too_many_bytes_translations, merge_value, parse_file, try_load_locales_from_paths,
+        I18nError, TranslationStatus,
+    };
+    use std::collections::{BTreeMap, HashMap};
+
+    /// Collect the attribute of the keys marked by `_meta` in the value, the nested keys are joined with `.`.
+    fn collect_meta_keys<T>(
+        value: &super::Value,
+        extract: impl Fn(&super::Map) -> Option<T>,
+    ) -> HashMap<String, T> {
+        let mut keys = HashMap::new();
+        super::walk_meta("", value, ".", &mut |key, meta| {
+            if let Some(attr) = extract(meta) {
+                keys.insert(key.to_string(), attr);
+            }
+        });
+        keys
+    }
+
+    #[test]
+    fn test_merge_value() {
+        let a = serde_json::from_str::<serde_json::Value>(
+            r#"{"foo": "Foo", "dar": { "a": "1", "b": "2" }}"#,
+        )
+        .unwrap();
+        let b = serde_json::from_str::<serde_json::Value>(
+            r#"{"foo": "Foo1", "bar": "Bar", "dar": { "b": "21" }}"#,
+        )
+        .unwrap();
+
+        let mut c = a;
+        merge_value(&mut c, &b);
+
+        assert_eq!(c["foo"], "Foo1");
+        assert_eq!(c["bar"], "Bar");
+        assert_eq!(c["dar"]["a"], "1");
+        assert_eq!(c["dar"]["b"], "21");
+    }
+
+    #[test]
+    fn test_parse_file_in_yaml() {
+        let content = "foo: Foo\nbar: Bar";
+        let mut trs = parse_file(content, "yml", "en", ".").expect("Should ok");
+        assert_eq!(trs["en"]["foo"], "Foo");
+        assert_eq!(trs["en"]["bar"], "Bar");
+
+        trs = parse_file(content, "yaml", "en", ".").expect("Should ok");
+        assert_eq!(trs["en"]["foo"], "Foo");
+
+        trs = parse_file(content, "yml", "zh-CN", ".").expect("Should ok");
+        assert_eq!(trs["zh-CN"]["foo"], "Foo");
+
+        parse_file(content, "foo", "en", ".").expect_err("Should error");
+    }
+
+    #[test]
+    fn test_parse_file_in_json() {
+        let content = r#"
+        {
+            "foo": "Foo",
+            "bar": "Bar"
+        }
+        "#;
+        let trs = parse_file(content, "json", "en", ".").expect("Should ok");
+        assert_eq!(trs["en"]["foo"], "Foo");
+        assert_eq!(trs["en"]["bar"], "Bar");
+    }
+
+    #[test]
+    #[cfg(feature = "json5")]
+    fn test_parse_file_in_json5() {
+        let content = r#"
+        {
+            // The comments and trailing commas are allowed
+            _version: 2,
+            foo: {
+                en: "Foo",
+                'zh-CN': "Foo 中文",
+            },
+        }
+        "#;
+        let trs = parse_file(content, "json5", "filename", ".").expect("Should ok");
+        assert_eq!(trs["en"]["foo"], "Foo");
+        assert_eq!(trs["zh-CN"]["foo"], "Foo 中文");
+
+        assert!(super::is_locale_extension("json5"));
+        assert!(super::locale_files_pattern("locales").ends_with(",json5}"));
+    }
+
+    #[test]
+    fn test_parse_file_in_toml() {
+        let content = r#"
+        foo = "Foo"
+        bar = "Bar"
+        "#;
+        let trs = parse_file(content, "toml", "en", ".").expect("Should ok");
+        assert_eq!(trs["en"]["foo"], "Foo");
+        assert_eq!(trs["en"]["bar"], "Bar");
+    }
+
+    #[test]
+    fn test_flatten_keys_with_list() {
+        let content = "tips:\n  - Tip 1\n  - Tip 2\n  - nested: Tip 3";
+        let trs = parse_file(content, "yml", "en", ".").expect("Should ok");
+        let trs = super::flatten_keys("", &trs["en"], ".");
+        assert_eq!(trs["tips.0"], "Tip 1");
+        assert_eq!(trs["tips.1"], "Tip 2");
+        assert_eq!(trs["tips.2.nested"], "Tip 3");
+        assert!(!trs.contains_key("tips"));
+    }
+
+    #[test]
+    fn test_flatten_keys_with_separator() {
+        let content = "auth:\n  login:\n    title: Login\ntips:\n  - Tip 1";
+        let trs = parse_file(content, "yml", "en", "/").expect("Should ok");
+        let trs = super::flatten_keys("", &trs["en"], "/");
+        assert_eq!(trs["auth/login/title"], "Login");
+        assert_eq!(trs["tips/0"], "Tip 1");
+        assert!(!trs.contains_key("auth.login.title"));
+    }
+
+    #[test]
+    fn test_parse_file_in_yaml_without_coercion() {
+        let content = "yes: yes\nno: no\non: on\noff: off\n1.0: 1.0\nversion: 1.50";
+        let trs = parse_file(content, "yml", "en", ".").expect("Should ok");
+        let trs = super::flatten_keys("", &trs["en"], ".");
+        assert_eq!(trs["yes"], "yes");
+        assert_eq!(trs["no"], "no");
+        assert_eq!(trs["on"], "on");
+        assert_eq!(trs["off"], "off");
+        assert_eq!(trs["1.0"], "1.0");
+        assert_eq!(trs["version"], "1.50");
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let locales_path = std::env::temp_dir().join("rust-i18n-test-load-errors");
+        let _ = std::fs::remove_dir_all(&locales_path);
+        std::fs::create_dir_all(&locales_path).unwrap();
+        std::fs::write(locales_path.join("en.yml"), "hello: Hello").unwrap();
+        std::fs::write(locales_path.join("fr.yml"), "hello: Bonjour\nfoo: [bar").unwrap();
+
+        let locales_paths = [locales_path.display().to_string()];
+        let errors = try_load_locales_from_paths(&locales_paths, |_| false).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            I18nError::Parse { file, message } => {
+                assert_eq!(file.file_name().unwrap(), "fr.yml");
+                assert!(message.contains("line 2"), "{}", message);
+            }
+            err => panic!("Unexpected error: {:?}", err),
+        }
+        assert!(errors[0].to_string().contains("fr.yml"));
+        let path = locales_path.display().to_string();
+        assert!(matches!(
+            super::load_manual_keys(&path, |_| false),
+            Err(I18nError::Parse { file, .. }) if file.ends_with("fr.yml")
+        ));
+
+        // The unreadable files are reported as the IO errors instead of panic
+        std::fs::write(locales_path.join("fr.yml"), b"hello: \xff\xfe").unwrap();
+        let errors = try_load_locales_from_paths(&locales_paths, |_| false).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            I18nError::Io { path, source } => {
+                assert_eq!(path.file_name().unwrap(), "fr.yml");
+                assert_eq!(source.kind(), std::io::ErrorKind::InvalidData);
+            }
+            err => panic!("Unexpected error: {:?}", err),
+        }
+        assert!(matches!(
+            super::load_status_keys_with_separator(&path, |_| false, "."),
+            Err(I18nError::Io { .. })
+        ));
+
+        std::fs::remove_file(locales_path.join("fr.yml")).unwrap();
+        let data = try_load_locales_from_paths(&locales_paths, |_| false).unwrap();
+        assert_eq!(data["en"]["hello"], "Hello");
+
+        std::fs::remove_dir_all(&locales_path).unwrap();
+    }
+
+    #[test]
+    fn test_find_empty_translations() {
+        let mut data = HashMap::new();
+        data.insert(
+            "en".to_string(),
+            HashMap::from([("hello".to_string(), "".to_string())]),
+        );
+        data.insert(
+            "fr".to_string(),
+            HashMap::from([
+                ("hello".to_string(), "Bonjour".to_string()),
+                ("foo".to_string(), " \n".to_string()),
+                ("bar".to_string(), "".to_string()),
+            ]),
+        );
+
+        assert_eq!(
+            find_empty_translations(&data, "en"),
+            vec![
+                ("fr".to_string(), "bar".to_string()),
+                ("fr".to_string(), "foo".to_string())
+            ]
+        );
+        assert_eq!(find_empty_translations(&data, "fr").len(), 1);
+    }
+
+    #[test]
+    fn test_find_placeholder_mismatches() {
+        let mut data = HashMap::new();
+        data.insert(
+            "en".to_string(),
+            HashMap::from([
+                ("hello".to_string(), "Hello, %{name}!".to_string()),
+                (
+                    "items".to_string(),
+                    "%{count} %{count | item | items}".to_string(),
+                ),
+                ("plain".to_string(), "Plain".to_string()),
+            ]),
+        );
+        data.insert(
+            "fr".to_string(),
+            HashMap::from([
+                ("hello".to_string(), "Bonjour, %{nom} %{title}!".to_string()),
+                ("items".to_string(), "%{count} articles".to_string()),
+                ("plain".to_string(), "%%{name} %{name:x}".to_string()),
+                ("only_fr".to_string(), "%{x}".to_string()),
+            ]),
+        );
+        data.insert(
+            "de".to_string(),
+            HashMap::from([("hello".to_string(), "Hallo, %{name}!".to_string())]),
+        );
+
+        assert_eq!(
+            find_placeholder_mismatches(&data, "en", "%{", "}"),
+            vec![
+                (
+                    "fr".to_string(),
+                    "hello".to_string(),
+                    vec!["name".to_string()],
+                    vec!["nom".to_string(), "title".to_string()]
+                ),
+                (
+                    "fr".to_string(),
+                    "plain".to_string(),
+                    vec![],
+                    vec!["name".to_string()]
+                ),
+            ]
+        );
+        assert!(find_placeholder_mismatches(&data, "ja", "%{", "}").is_empty());
+    }
+
+    #[test]
+    fn test_find_missing_translations() {
+        let mut data = HashMap::new();
+        data.insert(
+            "en".to_string(),
+            HashMap::from([
+                ("hello".to_string(), "Hello".to_string()),
+                ("foo".to_string(), "Foo".to_string()),
+                ("bar".to_string(), "Bar".to_string()),
+            ]),
+        );
+        data.insert(
+            "fr".to_string(),
+            HashMap::from([("hello".to_string(), "Bonjour".to_string())]),
+        );
+        data.insert(
+            "de".to_string(),
+            HashMap::from([
+                ("hello".to_string(), "Hallo".to_string()),
+                ("foo".to_string(), "Foo".to_string()),
+                ("bar".to_string(), "Bar".to_string()),
+            ]),
+        );
+
+        let locales = ["en", "fr", "de", "ja"].map(String::from);
+        assert_eq!(
+            find_missing_translations(&data, "en", &locales),
+            vec![
+                ("fr".to_string(), vec!["bar".to_string(), "foo".to_string()]),
+                (
+                    "ja".to_string(),
+                    vec!["bar".to_string(), "foo".to_string(), "hello".to_string()]
+                ),
+            ]
+        );
+        assert!(find_missing_translations(&data, "en", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_find_low_coverage_translations() {
+        let keys = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
+        let trs = |n: usize| {
+            keys[..n]
+                .iter()
+                .map(|key| (key.to_string(), key.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+        let data = HashMap::from([
+            ("en".to_string(), trs(10)),
+            ("fr".to_string(), trs(9)),
+            ("de".to_string(), trs(5)),
+            ("it".to_string(), trs(10)),
+        ]);
+
+        let min_coverage = BTreeMap::from([("fr".to_string(), 90), ("ja".to_string(), 10)]);
+        assert_eq!(
+            find_low_coverage_translations(&data, "en", &min_coverage),
+            vec![("ja".to_string(), 0, 10, 10)]
+        );
+
+        let min_coverage = BTreeMap::from([("*".to_string(), 80), ("fr".to_string(), 95)]);
+        assert_eq!(
+            find_low_coverage_translations(&data, "en", &min_coverage),
+            vec![("de".to_string(), 5, 10, 80), ("fr".to_string(), 9, 10, 95)]
+        );
+        assert!(find_low_coverage_translations(&data, "en", &BTreeMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_find_fallback_translations() {
+        let mut data = HashMap::new();
+        data.insert(
+            "en".to_string(),
+            HashMap::from([
+                ("hello".to_string(), "Hello".to_string()),
+                ("foo".to_string(), "Foo".to_string()),
+                ("bar".to_string(), "Bar".to_string()),
+            ]),
+        );
+        data.insert(
+            "fr".to_string(),
+            HashMap::from([
+                ("hello".to_string(), "Bonjour".to_string()),
+                ("foo".to_string(), "Foo".to_string()),
+            ]),
+        );
+        data.insert(
+            "fr-CA".to_string(),
+            HashMap::from([("hello".to_string(), "Allo".to_string())]),
+        );
+        data.insert(
+            "de".to_string(),
+            HashMap::from([("hello".to_string(), "Hallo".to_string())]),
+        );
+
+        let locales = ["en", "fr", "fr-CA", "de"].map(String::from);
+        let fallback = ["en".to_string()];
+        assert_eq!(
+            find_fallback_translations(&data, "en", &locales, &fallback, &[]),
+            vec![
+                ("de".to_string(), "bar".to_string(), "en".to_string()),
+                ("de".to_string(), "foo".to_string(), "en".to_string()),
+                ("fr".to_string(), "bar".to_string(), "en".to_string()),
+                ("fr-CA".to_string(), "bar".to_string(), "en".to_string()),
+            ]
+        );
+        assert_eq!(
+            find_fallback_translations(&data, "en", &locales, &fallback, &["de".to_string()]).len(),
+            2
+        );
+        assert!(find_fallback_translations(&data, "en", &locales, &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_plural_map() {
+        let content = r#"
+        _version: 2
+        items:
+            en:
+                one: "%{count} item"
+                other: "%{count} items"
+            zh-CN: "%{count} 件"
+        nested:
+            other:
+                en: Other
+        "#;
+        let trs = parse_file(content, "yml", "filename", ".").expect("Should ok");
+        let en = super::flatten_keys("", &trs["en"], ".");
+        assert_eq!(en["items.one"], "%{count} item");
+        assert_eq!(en["items.other"], "%{count} items");
+        assert_eq!(en["nested.other"], "Other");
+        let zh = super::flatten_keys("", &trs["zh-CN"], ".");
+        assert_eq!(zh["items"], "%{count} 件");
+
+        // The same keys as the sibling keys in v1
+        let trs = parse_file("items:\n  one: 1 item\n  other: items", "yml", "en", ".").unwrap();
+        let en = super::flatten_keys("", &trs["en"], ".");
+        assert_eq!(en["items.one"], "1 item");
+        assert_eq!(en["items.other"], "items");
+
+        assert!(!super::is_plural_map(&serde_json::json!({"one": "1 item"})));
+        assert!(!super::is_plural_map(
+            &serde_json::json!({"other": "Other", "title": "Title"})
+        ));
+        assert!(!super::is_plural_map(
+            &serde_json::json!({"other": {"en": "Other"}})
+        ));
+    }
+
+    #[test]
+    fn test_deprecated_keys() {
+        let content = r#"
+        _version: 2
+        old.key:
+            _meta:
+                deprecated: use new.key
+            en: Hello
+        legacy:
+            _meta: deprecated
+            title:
+                en: Title
+            menu:
+                _meta:
+                    deprecated: use nav.menu
+                item:
+                    en: Item
+        status:
+            _meta:
+                deprecated: false
+                manual: true
+            ok:
+                en: OK
+        "#;
+
+        let trs = parse_file(content, "yml", "filename", ".").expect("Should ok");
+        let en = super::flatten_keys("", &trs["en"], ".");
+        assert_eq!(en["old.key"], "Hello");
+
+        let value = super::yaml::from_str(content).unwrap();
+        let deprecated_keys = collect_meta_keys(&value, super::meta_deprecated);
+        assert_eq!(deprecated_keys.len(), 3);
+        assert_eq!(
+            super::find_deprecated_key(&deprecated_keys, "old.key", "."),
+            Some(("old.key", "use new.key"))
+        );
+        assert_eq!(
+            super::find_deprecated_key(&deprecated_keys, "legacy.title", "."),
+            Some(("legacy", ""))
+        );
+        // The nearest deprecated key wins
+        assert_eq!(
+            super::find_deprecated_key(&deprecated_keys, "legacy.menu.item", "."),
+            Some(("legacy.menu", "use nav.menu"))
+        );
+        assert_eq!(
+            super::find_deprecated_key(&deprecated_keys, "status.ok", "."),
+            None
+        );
+        assert_eq!(
+            super::find_deprecated_key(&deprecated_keys, "legacy_title", "."),
+            None
+        );
+
+        assert_eq!(
+            super::deprecated_key_message("old.key", "old.key", "use new.key"),
+            "the key `old.key` is deprecated: use new.key"
+        );
+        assert_eq!(
+            super::deprecated_key_message("legacy.title", "legacy", ""),
+            "the key `legacy.title` is deprecated by `legacy`"
+        );
+    }
+
+    #[test]
+    fn test_max_len_keys() {
+        let content = r#"
+        _version: 2
+        button.save:
+            _meta:
+                max_len: 8
+            en: Save
+            de: Speichern
+        items:
+            _meta:
+                max_len: 10
+            en:
+                one: 1 item
+                other: "%{count} items"
+        title:
+            _meta: manual
+            en: A long long title
+        "#;
+
+        let trs = parse_file(content, "yml", "filename", ".").expect("Should ok");
+        let mut data = HashMap::new();
+        for (locale, value) in trs.iter() {
+            data.insert(locale.clone(), super::flatten_keys("", value, "."));
+        }
+        assert_eq!(data["de"]["button.save"], "Speichern");
+
+        let value = super::yaml::from_str(content).unwrap();
+        let max_len_keys = collect_meta_keys(&value, super::meta_max_len);
+        assert_eq!(
+            max_len_keys,
+            HashMap::from([("button.save".to_string(), 8), ("items".to_string(), 10)])
+        );
+
+        assert_eq!(
+            find_too_long_translations(&data, &max_len_keys, "."),
+            vec![
+                ("de".to_string(), "button.save".to_string(), 9, 8),
+                ("en".to_string(), "items.other".to_string(), 14, 10),
+            ]
+        );
+        assert!(find_too_long_translations(&data, &HashMap::new(), ".").is_empty());
+    }
+
+    #[test]
+    fn test_status_keys() {
+        let content = r#"
+        _version: 2
+        checkout.title:
+            _meta:
+                status:
+                    fr: approved
+                    de: needs-review
+                    ja: unknown
+            en: Checkout
+            fr: Paiement
+            de: Kasse
+        checkout.items:
+            _meta:
+                status:
+                    de: approved
+            en:
+                one: 1 item
+                other: "%{count} items"
+            de:
+                one: 1 Artikel
+                other: ""
+        "#;
+
+        let trs = parse_file(content, "yml", "filename", ".").expect("Should ok");
+        let mut data = HashMap::new();
+        for (locale, value) in trs.iter() {
+            data.insert(locale.clone(), super::flatten_keys("", value, "."));
+        }
+        data.insert("ja".to_string(), HashMap::new());
+
+        let value = super::yaml::from_str(content).unwrap();
+        let status_keys = collect_meta_keys(&value, super::meta_status);
+        // The unknown status is ignored
+        assert_eq!(
+            status_keys,
+            HashMap::from([
+                (
+                    "checkout.title".to_string(),
+                    BTreeMap::from([
+                        ("de".to_string(), TranslationStatus::NeedsReview),
+                        ("fr".to_string(), TranslationStatus::Approved)
+                    ])
+                ),
+                (
+                    "checkout.items".to_string(),
+                    BTreeMap::from([("de".to_string(), TranslationStatus::Approved)])
+                ),
+            ])
+        );
+
+        let counts = count_translation_status(&data, "en", &status_keys, ".");
+        let count = |locale: &str, status| counts[locale][&status];
+        assert_eq!(count("en", TranslationStatus::Translated), 3);
+        assert_eq!(count("fr", TranslationStatus::Approved), 1);
+        assert_eq!(count("fr", TranslationStatus::Untranslated), 2);
+        // The plural forms take the status of `checkout.items`, the empty one is untranslated
+        assert_eq!(count("de", TranslationStatus::Approved), 1);
+        assert_eq!(count("de", TranslationStatus::NeedsReview), 1);
+        assert_eq!(count("de", TranslationStatus::Untranslated), 1);
+        assert_eq!(count("ja", TranslationStatus::Untranslated), 3);
+        assert_eq!(count("ja", TranslationStatus::Approved), 0);
+
+        assert_eq!(
+            find_needs_review_translations(&status_keys, &["de".to_string()]),
+            vec![("de".to_string(), "checkout.title".to_string())]
+        );
+        assert_eq!(
+            find_needs_review_translations(&status_keys, &["*".to_string()]),
+            vec![("de".to_string(), "checkout.title".to_string())]
+        );
+        assert!(find_needs_review_translations(&status_keys, &["fr".to_string()]).is_empty());
+
+        assert_eq!("needs-review".parse(), Ok(TranslationStatus::NeedsReview));
+        assert!("reviewed".parse::<TranslationStatus>().is_err());
+    }
+
+    #[test]
+    fn test_max_bytes_keys() {
+        let content = r#"
+        _version: 2
+        sms.verify:
+            _meta:
+                max_bytes:
+                    sms: 20
+                    push: 30
+            en: Your code is %{code}
+            ru: Ваш код %{code}
+        push.welcome:
+            _meta:
+                max_bytes: 12
+            en:
+                one: Hello
+                other: Hello, everyone!
+        "#;
+
+        let trs = parse_file(content, "yml", "filename", ".").expect("Should ok");
+        let mut data = HashMap::new();
+        for (locale, value) in trs.iter() {
+            data.insert(locale.clone(), super::flatten_keys("", value, "."));
+        }
+
+        let value = super::yaml::from_str(content).unwrap();
+        let max_bytes_keys = collect_meta_keys(&value, super::meta_max_bytes);
+        assert_eq!(
+            max_bytes_keys,
+            HashMap::from([
+                (
+                    "sms.verify".to_string(),
+                    BTreeMap::from([("push".to_string(), 30), ("sms".to_string(), 20)])
+                ),
+                (
+                    "push.welcome".to_string(),
+                    BTreeMap::from([(String::new(), 12)])
+                ),
+            ])
+        );
+
+        // `Ваш код %{code}` is 15 chars but 21 bytes
+        assert_eq!(
+            find_too_many_bytes_translations(&data, &max_bytes_keys, "."),
+            vec![
+                (
+                    "en".to_string(),
+                    "push.welcome.other".to_string(),
+                    String::new(),
+                    16,
+                    12
+                ),
+                (
+                    "ru".to_string(),
+                    "sms.verify".to_string(),
+                    "sms".to_string(),
+                    21,
+                    20
+                ),
+            ]
+        );
+        assert!(find_too_many_bytes_translations(&data, &HashMap::new(), ".").is_empty());
+    }
+
+    #[test]
+    fn test_source_texts() {
+        let content = r#"
+        _version: 2
+        tr_4Cct6Q289b12SkvF47dXIx:
+            _meta:
+                source: Hello, world!
+            en: Hello, world!
+            zh-CN: 你好，世界！
+        title:
+            _meta:
+                max_len: 10
+            en: Title
+        "#;
+
+        let trs = parse_file(content, "yml", "filename", ".").expect("Should ok");
+        assert!(!trs.contains_key("_meta"));
+
+        let value = super::yaml::from_str(content).unwrap();
+        let source_texts = collect_meta_keys(&value, super::meta_source);
+        assert_eq!(
+            source_texts,
+            HashMap::from([(
+                "tr_4Cct6Q289b12SkvF47dXIx".to_string(),
+                "Hello, world!".to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_manual_keys() {
+        let content = r#"
+        _version: 2
+        greeting.dynamic:
+            _meta: manual
+            en: Hello
+            zh-CN: 你好
+        status:
+            _meta:
+                manual: true
+            ok:
+                en: OK
+        welcome:
+            en: Welcome
+        "#;
+
+        let trs = parse_file(content, "yml", "filename", ".").expect("Should ok");
+        assert!(!trs.contains_key("_meta"));
+        let en = super::flatten_keys("", &trs["en"], ".");
+        assert_eq!(en["greeting.dynamic"], "Hello");
+        assert_eq!(en["status.ok"], "OK");
+        assert!(!en.keys().any(|k| k.contains("_meta")));
+
+        let value = super::yaml::from_str(content).unwrap();
+        let manual_keys = collect_meta_keys(&value, super::meta_manual)
+            .into_keys()
+            .collect();
+        assert!(super::is_manual_key(&manual_keys, "greeting.dynamic"));
+        assert!(super::is_manual_key(&manual_keys, "status.ok"));
+        assert!(!super::is_manual_key(&manual_keys, "status_bar"));
+        assert!(!super::is_manual_key(&manual_keys, "greeting"));
+        assert!(!super::is_manual_key(&manual_keys, "welcome"));
+
+        // Format v1
+        let content = "dynamic:\n  _meta: manual\n  hello: Hello\nfoo: Foo";
+        let trs = parse_file(content, "yml", "en", ".").expect("Should ok");
+        let en = super::flatten_keys("", &trs["en"], ".");
+        assert_eq!(en.len(), 2);
+        let value = super::yaml::from_str(content).unwrap();
+        let manual_keys = collect_meta_keys(&value, super::meta_manual)
+            .into_keys()
+            .collect();
+        assert!(super::is_manual_key(&manual_keys, "dynamic.hello"));
+        assert!(!super::is_manual_key(&manual_keys, "foo"));
+    }
+
+    #[test]
+    fn test_load_locales_from_paths() {
+        let base = std::env::temp_dir().join("rust-i18n-test-load-locales-from-paths");
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(base.join("vendor")).unwrap();
+        std::fs::create_dir_all(base.join("app")).unwrap();
+        std::fs::write(base.join("vendor/en.yml"), "hello: Hello\nok: OK").unwrap();
+        std::fs::write(base.join("app/en.yml"), "hello: Hi").unwrap();
+        std::fs::write(base.join("app/zh.yml"), "hello: 你好").unwrap();
+
+        let paths = [
+            base.join("vendor").display().to_string(),
+            base.join("app").display().to_string(),
+        ];
+        let trs = super::load_locales_from_paths(&paths, |_| false);
+        assert_eq!(trs["en"]["hello"], "Hi");
+        assert_eq!(trs["en"]["ok"], "OK");
+        assert_eq!(trs["zh"]["hello"], "你好");
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_expand_load_path() {
+        let base = std::env::temp_dir().join("rust-i18n-test-expand-load-path");
+        let _ = std::fs::remove_dir_all(&base);
+        for dir in [
+            "locales",
+            "shared-locales",
+            "other",
+            "apps/web/active",
+            "apps/api/active",
+            "apps/api/legacy",
+            "apps/admin/active/active",
+        ] {
+            std::fs::create_dir_all(base.join(dir)).unwrap();
+        }
+        let path = |path: &str| base.join(path).display().to_string();
+
+        assert_eq!(
+            super::expand_load_path(&path("{locales,shared-locales}")),
+            vec![path("locales"), path("shared-locales")]
+        );
+        assert_eq!(
+            super::expand_load_path(&path("apps/**/active")),
+            vec![
+                path("apps/admin/active"),
+                path("apps/api/active"),
+                path("apps/web/active")
+            ]
+        );
+        assert_eq!(
+            super::expand_load_path(&path("apps/*/legacy")),
+            vec![path("apps/api/legacy")]
+        );
+        assert!(super::expand_load_path(&path("missing/*")).is_empty());
+        assert_eq!(
+            super::expand_load_path(&path("missing")),
+            vec![path("missing")]
+        );
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_load_locales_with_bom() {
+        let base = std::env::temp_dir().join("rust-i18n-test-load-locales-with-bom");
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("en.yml"), "\u{feff}hello: Hello\nok: OK").unwrap();
+        std::fs::write(base.join("fr.json"), "\u{feff}{\"hello\": \"Bonjour\"}").unwrap();
+
+        let trs = super::load_locales(&base.display().to_string(), |_| false);
+        assert_eq!(trs["en"]["hello"], "Hello");
+        assert_eq!(trs["en"]["ok"], "OK");
+        assert_eq!(trs["fr"]["hello"], "Bonjour");
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_locale_file_paths() {
+        let base = std::env::temp_dir().join("rust-i18n-test-locale-file-paths");
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(base.join(super::OVERLAYS_DIR).join("staging")).unwrap();
+        std::fs::write(base.join("en.yml"), "hello: Hello").unwrap();
+        std::fs::write(base.join("README.md"), "# Locales").unwrap();
+        std::fs::write(
+            base.join(super::OVERLAYS_DIR)
+                .join("staging")
+                .join("en.yml"),
+            "hello: Hi",
+        )
+        .unwrap();
+
+        // The overlays are tracked by their own paths
+        let paths = super::locale_file_paths(&base.display().to_string());
+        assert_eq!(paths, [base.join("en.yml")]);
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_iter_locale_files() {
+        let base = std::env::temp_dir().join("rust-i18n-test-iter-locale-files");
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("en.yml"), "hello: Hello\nfoo:\n  bar: Bar").unwrap();
+        std::fs::write(
+            base.join("app.yml"),
+            "_version: 2\nbye:\n  en: Bye\n  fr: Au revoir",
+        )
+        .unwrap();
+        std::fs::write(base.join("broken.yml"), "_version: 2\nhello: [").unwrap();
+
+        let mut files = super::iter_locale_files(&base.display().to_string(), |_| false, "/")
+            .collect::<Vec<_>>();
+        files.sort_by_key(|file| match file {
+            Ok(file) => file.path.clone(),
+            Err(err) => err.path().unwrap().to_path_buf(),
+        });
+        assert_eq!(files.len(), 3);
+        let app = files[0].as_ref().unwrap();
+        assert_eq!(app.translations["en"]["bye"], "Bye");
+        assert_eq!(app.translations["fr"]["bye"], "Au revoir");
+        assert!(files[1].is_err());
+        let en = files[2].as_ref().unwrap();
+        assert_eq!(en.translations["en"]["foo/bar"], "Bar");
+        assert_eq!(en.translations.len(), 1);
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_canonicalize_locales() {
+        let trs = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+        let data = HashMap::from([
+            ("en".to_string(), trs(&[("hello", "Hello")])),
+            ("EN-us".to_string(), trs(&[("color", "Color"), ("a", "1")])),
+            ("en-US".to_string(), trs(&[("a", "2")])),
+            ("en_us".to_string(), trs(&[("b", "3"), ("a", "4")])),
+            ("zh_hant".to_string(), trs(&[("hello", "你好")])),
+        ]);
+
+        let data = canonicalize_locales(data);
+        let mut locales = data.keys().cloned().collect::<Vec<_>>();
+        locales.sort();
+        assert_eq!(locales, vec!["en", "en-US", "zh-Hant"]);
+        // The canonical `en-US` wins on key conflict
+        assert_eq!(
+            data["en-US"],
+            trs(&[("color", "Color"), ("a", "2"), ("b", "3")])
+        );
+        assert_eq!(data["zh-Hant"], trs(&[("hello", "你好")]));
+    }
+
+    #[test]
+    fn test_load_duplicate_keys() {
+        let base = std::env::temp_dir().join("rust-i18n-test-load-duplicate-keys");
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(
+            base.join("en.yml"),
+            "hello: Hello\nfoo: Foo\nhello: Hello 2",
+        )
+        .unwrap();
+        std::fs::write(
+            base.join("fr.json"),
+            r#"{"hello": "Bonjour", "nested": {"foo": "Foo", "foo": "Foo 2"}}"#,
+        )
+        .unwrap();
+
+        let mut errors = super::try_load_locales(&base.display().to_string(), |_| false)
+            .unwrap_err()
+            .into_iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>();
+        errors.sort();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("en.yml"), "{}", errors[0]);
+        assert!(errors[0].contains(r#"duplicate entry with key "hello""#));
+        assert!(errors[1].contains("fr.json"), "{}", errors[1]);
+        assert!(errors[1].contains(r#"duplicate entry with key "foo""#));
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_load_locales_with_unknown_version() {
+        let base = std::env::temp_dir().join("rust-i18n-test-load-locales-with-unknown-version");
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("app.yml"), "_version: 3\nhello:\n  en: Hello").unwrap();
+
+        let errors = super::try_load_locales(&base.display().to_string(), |_| false).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            matches!(&errors[0], I18nError::Parse { message, .. } if message.contains("Unknown `_version: 3`"))
+        );
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_get_version() {
+        let json = serde_yml::from_str::<serde_json::Value>("_version: 2").unwrap();
+        assert_eq!(super::get_version(&json), 2);
+
+        let json = serde_yml::from_str::<serde_json::Value>("_version: 1").unwrap();
+        assert_eq!(super::get_version(&json), 1);
+
+        let json = super::yaml::from_str("_version: 2").unwrap();
+        assert_eq!(super::get_version(&json), 2);
+
+        // Default fallback to 1
+        let json = serde_yml::from_str::<serde_json::Value>("foo: Foo").unwrap();
+        assert_eq!(super::get_version(&json), 1);
+    }
+
+    #[test]
+    fn test_parse_file_in_json_with_nested_locale_texts() {
+        let content = r#"{
+            "_version": 2,
+            "welcome": {
+                "en": "Welcome",
+                "zh-CN": "欢迎",
+                "zh-HK": "歡迎"
+            }
+        }"#;
+
+        let trs = parse_file(content, "json", "filename", ".").expect("Should ok");
+        assert_eq!(trs["en"]["welcome"], "Welcome");
+        assert_eq!(trs["zh-CN"]["welcome"], "欢迎");
+        assert_eq!(trs["zh-HK"]["welcome"], "歡迎");
+    }
+
+    #[test]
+    fn test_parse_file_in_yaml_with_nested_locale_texts() {
+        let content = r#"
+        _version: 2
+        welcome:
+            en: Welcome
+            zh-CN: 欢迎
+            jp: ようこそ
+        welcome.sub:
+            en: Welcome 1
+            zh-CN: 欢迎 1
+            jp: ようこそ 1
+        "#;
+
+        let trs = parse_file(content, "yml", "filename", ".").expect("Should ok");
+        assert_eq!(trs["en"]["welcome"], "Welcome");
+        assert_eq!(trs["zh-CN"]["welcome"], "欢迎");
+        assert_eq!(trs["jp"]["welcome"], "ようこそ");
+        assert_eq!(trs["en"]["welcome.sub"], "Welcome 1");
+        assert_eq!(trs["zh-CN"]["welcome.sub"], "欢迎 1");
+        assert_eq!(trs["jp"]["welcome.sub"], "ようこそ 1");
+    }
+}
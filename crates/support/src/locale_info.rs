@@ -0,0 +1,150 @@
+use crate::lookup_fallback;
+
+/// The text direction of a locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Left to right, e.g. `en`
+    Ltr,
+    /// Right to left, e.g. `ar`, it's `dir="rtl"` in HTML
+    Rtl,
+}
+
+impl Direction {
+    /// Get the direction as the HTML `dir` attribute value, `ltr` or `rtl`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+        }
+    }
+}
+
+/// The metadata of a locale, e.g. to render a language switcher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocaleInfo {
+    /// The name in the language itself, e.g. `Deutsch`
+    pub display_name: &'static str,
+    /// The name in English, e.g. `German`
+    pub english_name: &'static str,
+    /// The text direction
+    pub direction: Direction,
+}
+
+use Direction::{Ltr, Rtl};
+
+/// The common locales, sorted by the locale.
+static LOCALES: &[(&str, &str, &str, Direction)] = &[
+    ("am", "አማርኛ", "Amharic", Ltr),
+    ("ar", "العربية", "Arabic", Rtl),
+    ("bg", "Български", "Bulgarian", Ltr),
+    ("bn", "বাংলা", "Bengali", Ltr),
+    ("ca", "Català", "Catalan", Ltr),
+    ("cs", "Čeština", "Czech", Ltr),
+    ("da", "Dansk", "Danish", Ltr),
+    ("de", "Deutsch", "German", Ltr),
+    ("el", "Ελληνικά", "Greek", Ltr),
+    ("en", "English", "English", Ltr),
+    ("en-GB", "English (UK)", "English (UK)", Ltr),
+    ("en-US", "English (US)", "English (US)", Ltr),
+    ("es", "Español", "Spanish", Ltr),
+    ("et", "Eesti", "Estonian", Ltr),
+    ("fa", "فارسی", "Persian", Rtl),
+    ("fi", "Suomi", "Finnish", Ltr),
+    ("fr", "Français", "French", Ltr),
+    ("he", "עברית", "Hebrew", Rtl),
+    ("hi", "हिन्दी", "Hindi", Ltr),
+    ("hr", "Hrvatski", "Croatian", Ltr),
+    ("hu", "Magyar", "Hungarian", Ltr),
+    ("id", "Bahasa Indonesia", "Indonesian", Ltr),
+    ("it", "Italiano", "Italian", Ltr),
+    ("ja", "日本語", "Japanese", Ltr),
+    ("ko", "한국어", "Korean", Ltr),
+    ("lt", "Lietuvių", "Lithuanian", Ltr),
+    ("lv", "Latviešu", "Latvian", Ltr),
+    ("ms", "Bahasa Melayu", "Malay", Ltr),
+    ("nb", "Norsk bokmål", "Norwegian Bokmål", Ltr),
+    ("nl", "Nederlands", "Dutch", Ltr),
+    ("pl", "Polski", "Polish", Ltr),
+    ("pt", "Português", "Portuguese", Ltr),
+    ("pt-BR", "Português (Brasil)", "Portuguese (Brazil)", Ltr),
+    ("ro", "Română", "Romanian", Ltr),
+    ("ru", "Русский", "Russian", Ltr),
+    ("sk", "Slovenčina", "Slovak", Ltr),
+    ("sl", "Slovenščina", "Slovenian", Ltr),
+    ("sr", "Српски", "Serbian", Ltr),
+    ("sv", "Svenska", "Swedish", Ltr),
+    ("sw", "Kiswahili", "Swahili", Ltr),
+    ("ta", "தமிழ்", "Tamil", Ltr),
+    ("th", "ไทย", "Thai", Ltr),
+    ("tr", "Türkçe", "Turkish", Ltr),
+    ("uk", "Українська", "Ukrainian", Ltr),
+    ("ur", "اردو", "Urdu", Rtl),
+    ("vi", "Tiếng Việt", "Vietnamese", Ltr),
+    ("zh", "中文", "Chinese", Ltr),
+    ("zh-CN", "简体中文", "Chinese (Simplified)", Ltr),
+    ("zh-HK", "繁體中文（香港）", "Chinese (Hong Kong)", Ltr),
+    ("zh-Hans", "简体中文", "Chinese (Simplified)", Ltr),
+    ("zh-Hant", "繁體中文", "Chinese (Traditional)", Ltr),
+    ("zh-TW", "繁體中文", "Chinese (Traditional)", Ltr),
+];
+
+/// Get the metadata of the locale, e.g. the display name and the text direction.
+///
+/// The locale is matched case-insensitively and `_` is the same as `-`, the unknown locale falls back
+/// to the less specific locale, e.g. `ar-EG` to `ar`. Returns `None` if no locale is matched.
+///
+/// ```
+/// use rust_i18n_support::{locale_info, Direction};
+///
+/// let info = locale_info("ar-EG").unwrap();
+/// assert_eq!(info.english_name, "Arabic");
+/// assert_eq!(info.direction, Direction::Rtl);
+/// ```
+pub fn locale_info(locale: &str) -> Option<LocaleInfo> {
+    let locale = locale.replace('_', "-");
+    let mut locale = locale.as_str();
+    loop {
+        if let Some(info) = find_locale(locale) {
+            return Some(info);
+        }
+        locale = lookup_fallback(locale)?;
+    }
+}
+
+fn find_locale(locale: &str) -> Option<LocaleInfo> {
+    LOCALES
+        .iter()
+        .find(|(name, ..)| name.eq_ignore_ascii_case(locale))
+        .map(|&(_, display_name, english_name, direction)| LocaleInfo {
+            display_name,
+            english_name,
+            direction,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_info() {
+        let info = locale_info("de").unwrap();
+        assert_eq!(info.display_name, "Deutsch");
+        assert_eq!(info.english_name, "German");
+        assert_eq!(info.direction, Direction::Ltr);
+        assert_eq!(info.direction.as_str(), "ltr");
+
+        for locale in ["ar", "he", "fa", "ur", "ar-EG", "he_IL", "fa-IR-x-private"] {
+            assert_eq!(locale_info(locale).unwrap().direction, Direction::Rtl);
+        }
+
+        assert_eq!(locale_info("zh-CN").unwrap().display_name, "简体中文");
+        assert_eq!(locale_info("zh_tw").unwrap().display_name, "繁體中文");
+        assert_eq!(locale_info("zh-Hant-HK").unwrap().display_name, "繁體中文");
+        assert_eq!(locale_info("zh-SG").unwrap().display_name, "中文");
+        assert_eq!(locale_info("en-AU").unwrap().english_name, "English");
+
+        assert_eq!(locale_info("xx"), None);
+        assert_eq!(locale_info(""), None);
+    }
+}
@@ -0,0 +1,112 @@
+use pulldown_cmark::{html, Options, Parser};
+
+/// Escape the value to be interpolated into a Markdown translation, so it's rendered as plain text.
+///
+/// The HTML special chars are replaced by the entities, and the inline Markdown syntax chars are escaped by `\`.
+/// The line breaks are replaced by spaces, and the block syntax at the start of the value is escaped too,
+/// e.g. `# title` or `1. item`, so the value can't inject any HTML or Markdown structure.
+///
+/// ```
+/// use rust_i18n_support::escape_markdown;
+///
+/// assert_eq!(escape_markdown("<b>Jason</b>"), "&lt;b&gt;Jason&lt;/b&gt;");
+/// assert_eq!(escape_markdown("**bold**"), "\\*\\*bold\\*\\*");
+/// ```
+pub fn escape_markdown(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let trimmed = value.trim_start();
+    // The chars to escape at the start of the value, as the block syntax, e.g. `- item` or `1) item`
+    let digits = trimmed.len()
+        - trimmed
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .len();
+    let block_marker = match trimmed[digits..].chars().next() {
+        Some('.' | ')') if digits > 0 => Some(value.len() - trimmed.len() + digits),
+        Some('-' | '+' | '=') if digits == 0 => Some(value.len() - trimmed.len()),
+        _ => None,
+    };
+
+    for (i, c) in value.char_indices() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            '\r' | '\n' => output.push(' '),
+            '\\' | '`' | '*' | '_' | '[' | ']' | '(' | ')' | '!' | '#' | '|' | '~' | '{' | '}' => {
+                output.push('\\');
+                output.push(c);
+            }
+            _ if block_marker == Some(i) => {
+                output.push('\\');
+                output.push(c);
+            }
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+/// Render the Markdown text to HTML, with the tables and strikethrough extensions.
+///
+/// The raw HTML in the text is kept as is, so the text must be trusted, e.g. the translations,
+/// use [`escape_markdown`] for the untrusted values interpolated into the text.
+///
+/// ```
+/// use rust_i18n_support::render_markdown;
+///
+/// assert_eq!(render_markdown("Hello, **world**!"), "<p>Hello, <strong>world</strong>!</p>\n");
+/// ```
+pub fn render_markdown(text: &str) -> String {
+    let parser = Parser::new_ext(text, Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH);
+    let mut output = String::with_capacity(text.len() * 3 / 2);
+    html::push_html(&mut output, parser);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_markdown() {
+        assert_eq!(escape_markdown("Jason"), "Jason");
+        assert_eq!(escape_markdown("1,234.5"), "1,234.5");
+        assert_eq!(escape_markdown("Tom & Jerry's"), "Tom &amp; Jerry&#39;s");
+        assert_eq!(
+            escape_markdown("[click](javascript:alert(1))"),
+            "\\[click\\]\\(javascript:alert\\(1\\)\\)"
+        );
+        assert_eq!(escape_markdown("# title"), "\\# title");
+        assert_eq!(escape_markdown("- item"), "\\- item");
+        assert_eq!(escape_markdown("  12. item"), "  12\\. item");
+        assert_eq!(escape_markdown("a\n\n# b"), "a  \\# b");
+    }
+
+    #[test]
+    fn test_render_escaped_markdown() {
+        for value in [
+            "<script>alert(1)</script>",
+            "**bold** _em_ `code`",
+            "[click](javascript:alert(1))",
+            "# title",
+            "1. item",
+            "a\n\n<img src=x onerror=alert(1)>",
+        ] {
+            let html = render_markdown(&format!("Hello, **{}**", escape_markdown(value)));
+            let expected = value
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;")
+                .replace("\n", " ");
+            assert_eq!(
+                html,
+                format!("<p>Hello, <strong>{}</strong></p>\n", expected),
+                "{}",
+                value
+            );
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::lookup_fallback;
+
+/// Pick the best of the available locales for an `Accept-Language` header, e.g. `fr-CH, fr;q=0.9, en;q=0.8`.
+///
+/// The languages are tried by the quality values (the order of the header for the same quality), the `q=0` ones are excluded.
+/// For each language:
+///
+/// 1. The available locale that is the same after [`canonicalize_locale`], e.g. `fr_ch` to `fr-CH`.
+/// 2. The less specific locales by [`lookup_fallback`], e.g. `fr-CH` to `fr`.
+/// 3. The first available locale of the same language, e.g. `fr-CH` to `fr-FR`.
+///
+/// The wildcard `*` matches the first available locale.
+///
+/// ```
+/// # use rust_i18n_support::negotiate_locale;
+/// let available = ["en", "fr", "zh-CN"];
+/// assert_eq!(negotiate_locale("fr-CH, fr;q=0.9, en;q=0.8", &available), Some("fr"));
+/// assert_eq!(negotiate_locale("de, zh;q=0.5", &available), Some("zh-CN"));
+/// assert_eq!(negotiate_locale("de", &available), None);
+/// ```
+pub fn negotiate_locale<'a>(
+    accept_language: &str,
+    available_locales: &[&'a str],
+) -> Option<&'a str> {
+    let mut languages = accept_language
+        .split(',')
+        .filter_map(|item| {
+            let mut parts = item.split(';');
+            let tag = parts.next()?.trim();
+            let mut quality = 1.0;
+            for param in parts {
+                let Some(value) = param.trim().strip_prefix("q=") else {
+                    continue;
+                };
+                quality = value.trim().parse::<f32>().ok()?;
+            }
+            (!tag.is_empty() && quality > 0.0).then_some((tag, quality))
+        })
+        .collect::<Vec<_>>();
+    // The sort is stable, the languages of the same quality are kept in the order of the header
+    languages.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    languages
+        .into_iter()
+        .find_map(|(tag, _)| match_locale(tag, available_locales))
+}
+
+/// Find the available locale for the language tag of the header.
+fn match_locale<'a>(tag: &str, available_locales: &[&'a str]) -> Option<&'a str> {
+    if tag == "*" {
+        return available_locales.first().copied();
+    }
+
+    let tag = canonicalize_locale(tag);
+    let find = |tag: &str| {
+        available_locales
+            .iter()
+            .find(|locale| canonicalize_locale(locale) == tag)
+            .copied()
+    };
+
+    let mut current = tag.as_str();
+    loop {
+        if let Some(locale) = find(current) {
+            return Some(locale);
+        }
+        match lookup_fallback(current) {
+            Some(fallback) => current = fallback,
+            None => break,
+        }
+    }
+
+    // The `current` is the language without the subtags now
+    available_locales
+        .iter()
+        .find(|locale| {
+            canonicalize_locale(locale)
+                .split('-')
+                .next()
+                .is_some_and(|language| language == current)
+        })
+        .copied()
+}
+
+/// Canonicalize the casing of a locale, the language is lowercase, the script is titlecase and the region is uppercase,
+/// e.g. `ZH_hant_tw` to `zh-Hant-TW`. The `_` is replaced by `-`.
+///
+/// The subtags after an extension (e.g. `-u-`) and the variants are lowercase.
+///
+/// ```
+/// # use rust_i18n_support::canonicalize_locale;
+/// assert_eq!(canonicalize_locale("EN-us"), "en-US");
+/// assert_eq!(canonicalize_locale("zh_hant_tw"), "zh-Hant-TW");
+/// assert_eq!(canonicalize_locale("es-419"), "es-419");
+/// ```
+pub fn canonicalize_locale(locale: &str) -> String {
+    let mut subtags = locale.trim().split(['-', '_']);
+    let mut result = subtags.next().unwrap_or_default().to_ascii_lowercase();
+    let mut in_extension = false;
+    for subtag in subtags {
+        result.push('-');
+        in_extension |= subtag.len() == 1;
+        let is_alphabetic = subtag.chars().all(|c| c.is_ascii_alphabetic());
+        let is_numeric = subtag.chars().all(|c| c.is_ascii_digit());
+        match subtag.len() {
+            _ if in_extension => result.push_str(&subtag.to_ascii_lowercase()),
+            4 if is_alphabetic => {
+                let (first, rest) = subtag.split_at(1);
+                result.push_str(&first.to_ascii_uppercase());
+                result.push_str(&rest.to_ascii_lowercase());
+            }
+            2 if is_alphabetic => result.push_str(&subtag.to_ascii_uppercase()),
+            3 if is_numeric => result.push_str(subtag),
+            _ => result.push_str(&subtag.to_ascii_lowercase()),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{canonicalize_locale, negotiate_locale};
+
+    #[test]
+    fn test_canonicalize_locale() {
+        assert_eq!(canonicalize_locale("en"), "en");
+        assert_eq!(canonicalize_locale("EN"), "en");
+        assert_eq!(canonicalize_locale("en-us"), "en-US");
+        assert_eq!(canonicalize_locale("EN-US"), "en-US");
+        assert_eq!(canonicalize_locale("en_US"), "en-US");
+        assert_eq!(canonicalize_locale(" zh-hans "), "zh-Hans");
+        assert_eq!(canonicalize_locale("ZH-HANT-TW"), "zh-Hant-TW");
+        assert_eq!(canonicalize_locale("es-419"), "es-419");
+        assert_eq!(canonicalize_locale("sr-LATN-rs"), "sr-Latn-RS");
+        assert_eq!(canonicalize_locale("de-CH-1996"), "de-CH-1996");
+        assert_eq!(canonicalize_locale("ca-ES-VALENCIA"), "ca-ES-valencia");
+        assert_eq!(
+            canonicalize_locale("de-DE-U-CO-PHONEBK"),
+            "de-DE-u-co-phonebk"
+        );
+        assert_eq!(canonicalize_locale("en-XA"), "en-XA");
+        assert_eq!(canonicalize_locale(""), "");
+    }
+
+    #[test]
+    fn test_negotiate_locale() {
+        let available = ["en", "fr", "de-DE", "zh-CN", "zh-TW"];
+
+        // The quality values
+        assert_eq!(
+            negotiate_locale("fr-CH, fr;q=0.9, en;q=0.8", &available),
+            Some("fr")
+        );
+        assert_eq!(negotiate_locale("fr;q=0.5, en", &available), Some("en"));
+        assert_eq!(
+            negotiate_locale("fr;q=0.8, en;q=0.8", &available),
+            Some("fr")
+        );
+        assert_eq!(negotiate_locale("fr;q=0, en;q=0.1", &available), Some("en"));
+        assert_eq!(
+            negotiate_locale("fr;q=abc, en;q=0.1", &available),
+            Some("en")
+        );
+
+        // The region fallbacks, case-insensitively
+        assert_eq!(negotiate_locale("DE-at", &available), Some("de-DE"));
+        assert_eq!(
+            negotiate_locale("de-DE-u-co-phonebk", &available),
+            Some("de-DE")
+        );
+        assert_eq!(negotiate_locale("zh-tw", &available), Some("zh-TW"));
+        assert_eq!(negotiate_locale("zh_TW", &available), Some("zh-TW"));
+        assert_eq!(negotiate_locale("en-US;q=0.9", &available), Some("en"));
+
+        // The wildcards
+        assert_eq!(negotiate_locale("ja, *;q=0.1", &available), Some("en"));
+        assert_eq!(negotiate_locale("*", &available), Some("en"));
+        assert_eq!(negotiate_locale("*", &[]), None);
+
+        assert_eq!(negotiate_locale("ja, ko", &available), None);
+        assert_eq!(negotiate_locale("", &available), None);
+        assert_eq!(negotiate_locale(" , ;q=1", &available), None);
+    }
+}
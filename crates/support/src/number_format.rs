@@ -0,0 +1,129 @@
+use alloc::string::String;
+
+use crate::lookup_fallback;
+
+/// The group and decimal separators of the common locales, sorted by the locale.
+static SEPARATORS: &[(&str, &str, &str)] = &[
+    ("cs", "\u{a0}", ","),
+    ("da", ".", ","),
+    ("de", ".", ","),
+    ("de-CH", "’", "."),
+    ("en", ",", "."),
+    ("es", ".", ","),
+    ("fi", "\u{a0}", ","),
+    ("fr", "\u{202f}", ","),
+    ("id", ".", ","),
+    ("it", ".", ","),
+    ("ja", ",", "."),
+    ("ko", ",", "."),
+    ("nb", "\u{a0}", ","),
+    ("nl", ".", ","),
+    ("pl", "\u{a0}", ","),
+    ("pt", "\u{a0}", ","),
+    ("pt-BR", ".", ","),
+    ("ru", "\u{a0}", ","),
+    ("sv", "\u{a0}", ","),
+    ("tr", ".", ","),
+    ("uk", "\u{a0}", ","),
+    ("vi", ".", ","),
+    ("zh", ",", "."),
+];
+
+/// Format the number with the group and decimal separators of the locale,
+/// e.g. `1234.5` => `1,234.5` in `en`, `1.234,5` in `de`.
+///
+/// The unknown locale falls back to the less specific locale, e.g. `de-AT` to `de`.
+/// Returns `None` if the locale is unknown or the value is not a plain decimal number,
+/// the numbers with leading zeros (e.g. `007`) or an exponent are not formatted.
+///
+/// ```
+/// use rust_i18n_support::format_number;
+///
+/// assert_eq!(format_number("en", "1234").as_deref(), Some("1,234"));
+/// assert_eq!(format_number("de-AT", "-1234567.89").as_deref(), Some("-1.234.567,89"));
+/// assert_eq!(format_number("en", "Jason"), None);
+/// ```
+pub fn format_number(locale: &str, value: &str) -> Option<String> {
+    let (group, decimal) = separators(locale)?;
+
+    let (sign, number) = match value.strip_prefix('-') {
+        Some(number) => ("-", number),
+        None => ("", value),
+    };
+    let (integer, fraction) = match number.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (number, None),
+    };
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !is_digits(integer) || (integer.len() > 1 && integer.starts_with('0')) {
+        return None;
+    }
+    if fraction.is_some_and(|fraction| !is_digits(fraction)) {
+        return None;
+    }
+
+    let mut output = String::with_capacity(value.len() + integer.len() / 3 * group.len());
+    output.push_str(sign);
+    for (i, c) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            output.push_str(group);
+        }
+        output.push(c);
+    }
+    if let Some(fraction) = fraction {
+        output.push_str(decimal);
+        output.push_str(fraction);
+    }
+    Some(output)
+}
+
+fn separators(locale: &str) -> Option<(&'static str, &'static str)> {
+    let mut locale = locale;
+    loop {
+        if let Some(&(_, group, decimal)) = SEPARATORS
+            .iter()
+            .find(|(name, ..)| name.eq_ignore_ascii_case(locale))
+        {
+            return Some((group, decimal));
+        }
+        locale = lookup_fallback(locale)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_number;
+
+    #[test]
+    fn test_format_number() {
+        let cases = [
+            ("en", "1234", Some("1,234")),
+            ("en", "123", Some("123")),
+            ("en", "0", Some("0")),
+            ("en", "1234567.891", Some("1,234,567.891")),
+            ("en-US", "-1234", Some("-1,234")),
+            ("de", "1234", Some("1.234")),
+            ("de", "1234.5", Some("1.234,5")),
+            ("de-CH", "1234.5", Some("1’234.5")),
+            ("fr", "1234", Some("1\u{202f}234")),
+            ("zh-CN", "123456", Some("123,456")),
+            ("en", "007", None),
+            ("en", "1e10", None),
+            ("en", "1.", None),
+            ("en", ".5", None),
+            ("en", "-", None),
+            ("en", "", None),
+            ("en", "Jason", None),
+            ("xx", "1234", None),
+        ];
+        for (locale, value, expected) in cases {
+            assert_eq!(
+                format_number(locale, value).as_deref(),
+                expected,
+                "{} {}",
+                locale,
+                value
+            );
+        }
+    }
+}
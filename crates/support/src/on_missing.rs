@@ -0,0 +1,106 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// The strategy to display a missing translation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "kebab-case"))]
+pub enum OnMissing {
+    /// Display the key (or the message) as is, this is the default.
+    #[default]
+    Key,
+    /// Display the humanized last segment of the key,
+    /// e.g. `settings.notifications.email_frequency` => `Email frequency`.
+    Humanize,
+}
+
+impl OnMissing {
+    /// Get the text to display in place of the missing translation of `key`.
+    pub fn resolve<'r>(&self, key: Cow<'r, str>) -> Cow<'r, str> {
+        match self {
+            Self::Key => key,
+            Self::Humanize => match humanize_key(&key) {
+                Some(text) => Cow::Owned(text),
+                None => key,
+            },
+        }
+    }
+}
+
+impl FromStr for OnMissing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "key" => Ok(Self::Key),
+            "humanize" => Ok(Self::Humanize),
+            _ => Err(format!(
+                "Invalid `on_missing` value `{}`, expected `key` or `humanize`",
+                s
+            )),
+        }
+    }
+}
+
+/// Humanize the last segment of a translation key.
+///
+/// The key is split on `.` and `_`, then the words of the last segment are joined with
+/// spaces and the first letter is uppercased, e.g. `settings.email_frequency` => `Email frequency`.
+///
+/// Returns `None` if the key contains whitespace (it's a message rather than a key),
+/// or there is nothing left to display.
+pub fn humanize_key(key: &str) -> Option<String> {
+    if key.chars().any(char::is_whitespace) {
+        return None;
+    }
+
+    let segment = key.rsplit('.').find(|s| !s.is_empty())?;
+    let text = segment
+        .split('_')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut chars = text.chars();
+    let first = chars.next()?;
+    Some(first.to_uppercase().chain(chars).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_humanize_key() {
+        assert_eq!(
+            humanize_key("settings.notifications.email_frequency").as_deref(),
+            Some("Email frequency")
+        );
+        assert_eq!(humanize_key("hello").as_deref(), Some("Hello"));
+        assert_eq!(humanize_key("foo.__bar__baz").as_deref(), Some("Bar baz"));
+        assert_eq!(humanize_key("foo.bar.").as_deref(), Some("Bar"));
+        assert_eq!(humanize_key("Hello, world!"), None);
+        assert_eq!(humanize_key("..."), None);
+        assert_eq!(humanize_key(""), None);
+    }
+
+    #[test]
+    fn test_on_missing() {
+        assert_eq!("key".parse::<OnMissing>(), Ok(OnMissing::Key));
+        assert_eq!("humanize".parse::<OnMissing>(), Ok(OnMissing::Humanize));
+        assert!("foo".parse::<OnMissing>().is_err());
+
+        let key = "settings.email_frequency";
+        assert_eq!(OnMissing::Key.resolve(key.into()), key);
+        assert_eq!(OnMissing::Humanize.resolve(key.into()), "Email frequency");
+        assert_eq!(
+            OnMissing::Humanize.resolve("Hello, world!".into()),
+            "Hello, world!"
+        );
+    }
+}
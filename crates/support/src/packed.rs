@@ -0,0 +1,205 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Backend, BackendExt};
+
+/// The translations of a locale packed into one string, generated by the `packed` option of `i18n!`.
+///
+/// The `blob` is the keys and values in the order of the keys, e.g. `hellHelloworldWorld` for `hell` and `world`,
+/// each item of the `index` is the offsets of a key and its value, a value ends at the next key.
+pub struct PackedLocale {
+    locale: &'static str,
+    blob: &'static str,
+    index: &'static [(u32, u32)],
+}
+
+impl PackedLocale {
+    /// Create the packed translations of a locale, the `index` must be sorted by the keys.
+    pub const fn new(
+        locale: &'static str,
+        blob: &'static str,
+        index: &'static [(u32, u32)],
+    ) -> Self {
+        Self {
+            locale,
+            blob,
+            index,
+        }
+    }
+
+    /// Get the key and value of the nth item of the index.
+    fn entry(&self, n: usize) -> (&'static str, &'static str) {
+        let (key_start, value_start) = self.index[n];
+        let value_end = self
+            .index
+            .get(n + 1)
+            .map_or(self.blob.len(), |(next, _)| *next as usize);
+        (
+            &self.blob[key_start as usize..value_start as usize],
+            &self.blob[value_start as usize..value_end],
+        )
+    }
+
+    /// Binary search the value of the key.
+    fn get(&self, key: &str) -> Option<&'static str> {
+        let n = self
+            .index
+            .binary_search_by(|(key_start, value_start)| {
+                self.blob[*key_start as usize..*value_start as usize].cmp(key)
+            })
+            .ok()?;
+        Some(self.entry(n).1)
+    }
+}
+
+/// The backend of the packed translations, see the `packed` option of `i18n!`.
+///
+/// The translations are looked up in the static data directly, nothing is copied to the heap.
+pub struct PackedBackend {
+    locales: &'static [PackedLocale],
+}
+
+impl PackedBackend {
+    /// Create a backend of the packed translations of the locales.
+    pub const fn new(locales: &'static [PackedLocale]) -> Self {
+        Self { locales }
+    }
+
+    fn locale(&self, locale: &str) -> Option<&'static PackedLocale> {
+        self.locales.iter().find(|packed| packed.locale == locale)
+    }
+}
+
+impl Backend for PackedBackend {
+    fn available_locales(&self) -> Vec<&str> {
+        let mut locales = self
+            .locales
+            .iter()
+            .map(|packed| packed.locale)
+            .collect::<Vec<_>>();
+        locales.sort();
+        locales
+    }
+
+    fn translate(&self, locale: &str, key: &str) -> Option<&str> {
+        self.locale(locale)?.get(key)
+    }
+
+    fn translate_many(&self, locale: &str, keys: &[&str]) -> Vec<Option<&str>> {
+        // Lookup the locale only once
+        match self.locale(locale) {
+            Some(packed) => keys.iter().map(|key| packed.get(key)).collect(),
+            None => keys.iter().map(|_| None).collect(),
+        }
+    }
+
+    fn translations(&self, locale: &str) -> BTreeMap<&str, &str> {
+        self.locale(locale)
+            .map(|packed| (0..packed.index.len()).map(|n| packed.entry(n)).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl BackendExt for PackedBackend {}
+
+/// Pack the keys and values into one string for [`unpack_translations`], e.g. `5:hello5:Hello` for `hello`,
+/// used by the `max_inline_keys` option of `i18n!` for the keys that are not inlined.
+///
+/// Each key and value is written as its length in bytes, `:` and itself.
+pub fn pack_translations<'a>(translations: impl IntoIterator<Item = (&'a str, &'a str)>) -> String {
+    let mut blob = String::new();
+    for (key, value) in translations {
+        for text in [key, value] {
+            blob.push_str(&alloc::format!("{}:", text.len()));
+            blob.push_str(text);
+        }
+    }
+    blob
+}
+
+/// Iterate the keys and values packed by [`pack_translations`], it stops at the first malformed item.
+///
+/// ```
+/// use rust_i18n_support::{pack_translations, unpack_translations};
+///
+/// let blob = pack_translations([("hello", "Hello"), ("a:b", "")]);
+/// assert_eq!(blob, "5:hello5:Hello3:a:b0:");
+/// assert_eq!(unpack_translations(&blob).collect::<Vec<_>>(), [("hello", "Hello"), ("a:b", "")]);
+/// ```
+pub fn unpack_translations(blob: &str) -> impl Iterator<Item = (&str, &str)> {
+    let mut rest = blob;
+    let mut next_text = move || {
+        let (len, after) = rest.split_once(':')?;
+        let len = len.parse::<usize>().ok()?;
+        let text = after.get(..len)?;
+        rest = &after[len..];
+        Some(text)
+    };
+    core::iter::from_fn(move || Some((next_text()?, next_text()?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pack_translations, unpack_translations, PackedBackend, PackedLocale};
+    use crate::Backend;
+
+    static LOCALES: &[PackedLocale] = &[
+        PackedLocale::new("zh-CN", "hello你好", &[(0, 5)]),
+        PackedLocale::new(
+            "en",
+            "emptyhelloHellohello.worldHello, World",
+            &[(0, 5), (5, 10), (15, 26)],
+        ),
+    ];
+
+    #[test]
+    fn test_packed_backend() {
+        let backend = PackedBackend::new(LOCALES);
+        assert_eq!(backend.available_locales(), vec!["en", "zh-CN"]);
+
+        assert_eq!(backend.translate("en", "hello"), Some("Hello"));
+        assert_eq!(backend.translate("en", "hello.world"), Some("Hello, World"));
+        assert_eq!(backend.translate("en", "empty"), Some(""));
+        assert_eq!(backend.translate("en", "hell"), None);
+        assert_eq!(backend.translate("zh-CN", "hello"), Some("你好"));
+        assert_eq!(backend.translate("fr", "hello"), None);
+        assert!(backend.contains_key("en", "empty"));
+
+        assert_eq!(
+            backend.translate_many("en", &["hello", "foo"]),
+            vec![Some("Hello"), None]
+        );
+        assert_eq!(
+            backend.translations("en").into_iter().collect::<Vec<_>>(),
+            vec![
+                ("empty", ""),
+                ("hello", "Hello"),
+                ("hello.world", "Hello, World")
+            ]
+        );
+        assert_eq!(
+            backend.missing_keys(None)["zh-CN"],
+            vec!["empty", "hello.world"]
+        );
+    }
+
+    #[test]
+    fn test_pack_translations() {
+        let trs = [
+            ("hello", "Hello"),
+            ("hello.world", "你好: 1:2"),
+            ("empty", ""),
+        ];
+        let blob = pack_translations(trs);
+        assert_eq!(unpack_translations(&blob).collect::<Vec<_>>(), trs);
+        assert_eq!(unpack_translations("").count(), 0);
+
+        // The malformed items are skipped with the rest
+        assert_eq!(
+            unpack_translations("5:hello5:Hello9:x").collect::<Vec<_>>(),
+            [("hello", "Hello")]
+        );
+        assert_eq!(unpack_translations("x:hello").count(), 0);
+    }
+}
@@ -0,0 +1,330 @@
+/// The CLDR plural categories, the keys of the plural forms, e.g. `place.one`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// All categories in the CLDR order.
+    pub const ALL: [PluralCategory; 6] = [
+        PluralCategory::Zero,
+        PluralCategory::One,
+        PluralCategory::Two,
+        PluralCategory::Few,
+        PluralCategory::Many,
+        PluralCategory::Other,
+    ];
+
+    /// Get the category of the key, e.g. `one`, `None` if it's not a category.
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str() == key)
+    }
+
+    /// The key of the category, e.g. `one`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PluralCategory::Zero => "zero",
+            PluralCategory::One => "one",
+            PluralCategory::Two => "two",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other",
+        }
+    }
+}
+
+/// Get the CLDR cardinal plural category of the integer in the locale, by its language subtag, e.g. `en-US`.
+///
+/// The rules are from the [CLDR cardinal rules](https://www.unicode.org/cldr/charts/latest/supplemental/language_plural_rules.html)
+/// for the integers, the languages without the plural forms are always [`PluralCategory::Other`],
+/// and the other languages have the `one` form of `1` only like `en`.
+///
+/// ```
+/// use rust_i18n_support::{cardinal_category, PluralCategory};
+///
+/// assert_eq!(cardinal_category("en", 1), PluralCategory::One);
+/// assert_eq!(cardinal_category("en", 0), PluralCategory::Other);
+/// assert_eq!(cardinal_category("fr", 0), PluralCategory::One);
+/// assert_eq!(cardinal_category("ru", 22), PluralCategory::Few);
+/// assert_eq!(cardinal_category("ja", 1), PluralCategory::Other);
+/// ```
+pub fn cardinal_category(locale: &str, n: u64) -> PluralCategory {
+    use PluralCategory::*;
+
+    let language = locale.split(['-', '_']).next().unwrap_or_default();
+    let (n10, n100) = (n % 10, n % 100);
+    match language.to_ascii_lowercase().as_str() {
+        "ja" | "zh" | "ko" | "th" | "vi" | "id" | "ms" | "lo" | "my" | "km" | "yue" => Other,
+        "fr" | "hy" | "kab" | "ff" => match n {
+            0 | 1 => One,
+            _ if n != 0 && n.is_multiple_of(1_000_000) => Many,
+            _ => Other,
+        },
+        "pt" => match n {
+            0 | 1 => One,
+            _ if n.is_multiple_of(1_000_000) => Many,
+            _ => Other,
+        },
+        "es" | "it" | "ca" => match n {
+            1 => One,
+            _ if n != 0 && n.is_multiple_of(1_000_000) => Many,
+            _ => Other,
+        },
+        "hi" | "bn" | "gu" | "kn" | "mr" | "fa" | "zu" | "am" => match n {
+            0 | 1 => One,
+            _ => Other,
+        },
+        "ru" | "uk" | "be" => match (n10, n100) {
+            (1, _) if n100 != 11 => One,
+            (2..=4, _) if !(12..=14).contains(&n100) => Few,
+            _ => Many,
+        },
+        "pl" => match (n10, n100) {
+            _ if n == 1 => One,
+            (2..=4, _) if !(12..=14).contains(&n100) => Few,
+            _ => Many,
+        },
+        "cs" | "sk" => match n {
+            1 => One,
+            2..=4 => Few,
+            _ => Other,
+        },
+        "hr" | "sr" | "bs" => match (n10, n100) {
+            (1, _) if n100 != 11 => One,
+            (2..=4, _) if !(12..=14).contains(&n100) => Few,
+            _ => Other,
+        },
+        "lt" => match (n10, n100) {
+            (1, _) if !(11..=19).contains(&n100) => One,
+            (2..=9, _) if !(11..=19).contains(&n100) => Few,
+            _ => Other,
+        },
+        "lv" => match (n10, n100) {
+            (0, _) => Zero,
+            _ if (11..=19).contains(&n100) => Zero,
+            (1, _) if n100 != 11 => One,
+            _ => Other,
+        },
+        "ro" => match n100 {
+            _ if n == 1 => One,
+            _ if n == 0 || (1..=19).contains(&n100) => Few,
+            _ => Other,
+        },
+        "sl" => match n100 {
+            1 => One,
+            2 => Two,
+            3 | 4 => Few,
+            _ => Other,
+        },
+        "he" | "iw" => match n {
+            1 => One,
+            2 => Two,
+            _ => Other,
+        },
+        "ar" => match (n, n100) {
+            (0, _) => Zero,
+            (1, _) => One,
+            (2, _) => Two,
+            (_, 3..=10) => Few,
+            (_, 11..=99) => Many,
+            _ => Other,
+        },
+        "ga" => match n {
+            1 => One,
+            2 => Two,
+            3..=6 => Few,
+            7..=10 => Many,
+            _ => Other,
+        },
+        "cy" => match n {
+            0 => Zero,
+            1 => One,
+            2 => Two,
+            3 => Few,
+            6 => Many,
+            _ => Other,
+        },
+        // Most languages, e.g. `en`, `de`, `nl`, `sv`, `tr`, only have the `one` form of `1`
+        _ => match n {
+            1 => One,
+            _ => Other,
+        },
+    }
+}
+
+/// Get the CLDR ordinal plural category of the number in the locale, by its language subtag, e.g. `en-US`.
+///
+/// The rules are from the [CLDR ordinal rules](https://www.unicode.org/cldr/charts/latest/supplemental/language_plural_rules.html),
+/// the languages without the ordinal forms and the unknown languages are always [`PluralCategory::Other`].
+///
+/// ```
+/// use rust_i18n_support::{ordinal_category, PluralCategory};
+///
+/// assert_eq!(ordinal_category("en", 1), PluralCategory::One);
+/// assert_eq!(ordinal_category("en", 22), PluralCategory::Two);
+/// assert_eq!(ordinal_category("en", 13), PluralCategory::Other);
+/// assert_eq!(ordinal_category("fr", 1), PluralCategory::One);
+/// assert_eq!(ordinal_category("de", 1), PluralCategory::Other);
+/// ```
+pub fn ordinal_category(locale: &str, n: u64) -> PluralCategory {
+    use PluralCategory::*;
+
+    let language = locale.split(['-', '_']).next().unwrap_or_default();
+    let (n10, n100) = (n % 10, n % 100);
+    match language.to_ascii_lowercase().as_str() {
+        "en" => match (n10, n100) {
+            (1, _) if n100 != 11 => One,
+            (2, _) if n100 != 12 => Two,
+            (3, _) if n100 != 13 => Few,
+            _ => Other,
+        },
+        "fr" | "ga" | "hy" | "lo" | "ms" | "ro" | "tl" | "fil" | "vi" => match n {
+            1 => One,
+            _ => Other,
+        },
+        "sv" => match (n10, n100) {
+            (1 | 2, _) if n100 != 11 && n100 != 12 => One,
+            _ => Other,
+        },
+        "hu" => match n {
+            1 | 5 => One,
+            _ => Other,
+        },
+        "it" => match n {
+            8 | 11 | 80 | 800 => Many,
+            _ => Other,
+        },
+        "ca" => match n {
+            1 | 3 => One,
+            2 => Two,
+            4 => Few,
+            _ => Other,
+        },
+        "hi" | "gu" => match n {
+            1 => One,
+            2 | 3 => Two,
+            4 => Few,
+            6 => Many,
+            _ => Other,
+        },
+        "bn" | "as" => match n {
+            1 | 5 | 7 | 8 | 9 | 10 => One,
+            2 | 3 => Two,
+            4 => Few,
+            6 => Many,
+            _ => Other,
+        },
+        "mr" => match n {
+            1 => One,
+            2 | 3 => Two,
+            4 => Few,
+            _ => Other,
+        },
+        "ne" => match n {
+            1..=4 => One,
+            _ => Other,
+        },
+        "uk" => match (n10, n100) {
+            (3, _) if n100 != 13 => Few,
+            _ => Other,
+        },
+        "be" => match (n10, n100) {
+            (2 | 3, _) if n100 != 12 && n100 != 13 => Few,
+            _ => Other,
+        },
+        "mk" => match (n10, n100) {
+            (1, _) if n100 != 11 => One,
+            (2, _) if n100 != 12 => Two,
+            (7 | 8, _) if n100 != 17 && n100 != 18 => Many,
+            _ => Other,
+        },
+        "sq" => match (n10, n100) {
+            _ if n == 1 => One,
+            (4, _) if n100 != 14 => Many,
+            _ => Other,
+        },
+        "kk" => match n10 {
+            6 | 9 => Many,
+            0 if n != 0 => Many,
+            _ => Other,
+        },
+        "cy" => match n {
+            0 | 7 | 8 | 9 => Zero,
+            1 => One,
+            2 => Two,
+            3 | 4 => Few,
+            5 | 6 => Many,
+            _ => Other,
+        },
+        _ => Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cardinal_category() {
+        let categories = |locale, ns: &[u64]| {
+            ns.iter()
+                .map(|n| cardinal_category(locale, *n).as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        assert_eq!(categories("en-US", &[0, 1, 2, 11]), "other,one,other,other");
+        assert_eq!(categories("de", &[0, 1, 2]), "other,one,other");
+        assert_eq!(
+            categories("fr", &[0, 1, 2, 1_000_000]),
+            "one,one,other,many"
+        );
+        assert_eq!(
+            categories("ru", &[1, 2, 5, 11, 21, 22, 25, 112]),
+            "one,few,many,many,one,few,many,many"
+        );
+        assert_eq!(
+            categories("pl", &[1, 2, 5, 21, 22]),
+            "one,few,many,many,few"
+        );
+        assert_eq!(categories("cs", &[1, 3, 5]), "one,few,other");
+        assert_eq!(
+            categories("ar", &[0, 1, 2, 3, 11, 100]),
+            "zero,one,two,few,many,other"
+        );
+        assert_eq!(categories("zh_CN", &[0, 1, 2]), "other,other,other");
+        assert_eq!(cardinal_category("xx", 1), PluralCategory::One);
+    }
+
+    #[test]
+    fn test_ordinal_category() {
+        let en = |n| ordinal_category("en-US", n).as_str();
+        let suffixes = [1, 2, 3, 4, 11, 12, 13, 21, 22, 23, 101, 111, 112, 0]
+            .map(en)
+            .join(",");
+        assert_eq!(
+            suffixes,
+            "one,two,few,other,other,other,other,one,two,few,one,other,other,other"
+        );
+
+        assert_eq!(PluralCategory::from_key("few"), Some(PluralCategory::Few));
+        assert_eq!(PluralCategory::from_key("items"), None);
+
+        assert_eq!(ordinal_category("FR", 1), PluralCategory::One);
+        assert_eq!(ordinal_category("fr", 2), PluralCategory::Other);
+        assert_eq!(ordinal_category("sv", 2), PluralCategory::One);
+        assert_eq!(ordinal_category("sv", 12), PluralCategory::Other);
+        assert_eq!(ordinal_category("it", 11), PluralCategory::Many);
+        assert_eq!(ordinal_category("cy", 0), PluralCategory::Zero);
+        assert_eq!(ordinal_category("kk", 10), PluralCategory::Many);
+        assert_eq!(ordinal_category("kk", 0), PluralCategory::Other);
+        assert_eq!(ordinal_category("zh-CN", 1), PluralCategory::Other);
+        assert_eq!(ordinal_category("", 1), PluralCategory::Other);
+    }
+}
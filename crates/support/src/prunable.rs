@@ -0,0 +1,214 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use spin::RwLock;
+
+use crate::{Backend, BackendExt};
+
+/// The loader of the static translations of a locale for [`PrunableBackend`], `None` if the locale is unknown.
+pub type LocaleLoader = fn(&str) -> Option<Vec<(&'static str, &'static str)>>;
+
+/// The backend whose locales can be freed at runtime, see the `runtime_prune` option of `i18n!`.
+///
+/// The translations are the static texts of the binary, only the maps of the loaded locales are on the heap,
+/// so the texts returned before are still valid after their locale is pruned.
+/// A pruned locale is loaded again by the `loader` when it's looked up.
+///
+/// ```
+/// use rust_i18n_support::{Backend, PrunableBackend};
+///
+/// fn load(locale: &str) -> Option<Vec<(&'static str, &'static str)>> {
+///     match locale {
+///         "en" => Some(vec![("hello", "Hello")]),
+///         "fr" => Some(vec![("hello", "Bonjour")]),
+///         _ => None,
+///     }
+/// }
+///
+/// let backend = PrunableBackend::new(&["en", "fr"], load);
+/// assert_eq!(backend.prune(&["fr"]), ["en"]);
+/// assert_eq!(backend.loaded_locales(), ["fr"]);
+/// assert_eq!(backend.translate("en", "hello"), Some("Hello"));
+/// assert_eq!(backend.loaded_locales(), ["en", "fr"]);
+/// ```
+pub struct PrunableBackend {
+    locales: &'static [&'static str],
+    loader: LocaleLoader,
+    loaded: RwLock<HashMap<&'static str, HashMap<&'static str, &'static str>>>,
+}
+
+impl PrunableBackend {
+    /// Create a backend of the locales, all of them are loaded by the `loader` at once.
+    pub fn new(locales: &'static [&'static str], loader: LocaleLoader) -> Self {
+        let loaded = locales
+            .iter()
+            .filter_map(|locale| Some((*locale, loader(locale)?.into_iter().collect())))
+            .collect();
+        Self {
+            locales,
+            loader,
+            loaded: RwLock::new(loaded),
+        }
+    }
+
+    /// Free the translations of the locales that are not in `keep`, returns the pruned locales, sorted.
+    ///
+    /// The locales are matched case-insensitively, the same as the lookup of the locales.
+    pub fn prune(&self, keep: &[&str]) -> Vec<&'static str> {
+        let mut loaded = self.loaded.write();
+        let mut pruned = loaded
+            .keys()
+            .filter(|locale| !keep.iter().any(|keep| keep.eq_ignore_ascii_case(locale)))
+            .copied()
+            .collect::<Vec<_>>();
+        pruned.sort();
+        for locale in &pruned {
+            loaded.remove(locale);
+        }
+        pruned
+    }
+
+    /// Get the locales whose translations are in memory, sorted.
+    pub fn loaded_locales(&self) -> Vec<&'static str> {
+        let mut locales = self.loaded.read().keys().copied().collect::<Vec<_>>();
+        locales.sort();
+        locales
+    }
+
+    /// Call `f` with the translations of the locale, it's loaded again if it was pruned.
+    fn with_locale<R>(
+        &self,
+        locale: &str,
+        f: impl FnOnce(&HashMap<&'static str, &'static str>) -> R,
+    ) -> Option<R> {
+        if let Some(trs) = self.loaded.read().get(locale) {
+            return Some(f(trs));
+        }
+        let locale = *self.locales.iter().find(|name| **name == locale)?;
+        let trs = (self.loader)(locale)?.into_iter().collect();
+        let mut loaded = self.loaded.write();
+        Some(f(loaded.entry(locale).or_insert(trs)))
+    }
+}
+
+impl Backend for PrunableBackend {
+    fn available_locales(&self) -> Vec<&str> {
+        let mut locales = self.locales.to_vec();
+        locales.sort();
+        locales
+    }
+
+    fn translate(&self, locale: &str, key: &str) -> Option<&str> {
+        self.with_locale(locale, |trs| trs.get(key).copied())?
+    }
+
+    fn contains_key(&self, locale: &str, key: &str) -> bool {
+        self.with_locale(locale, |trs| trs.contains_key(key))
+            .unwrap_or_default()
+    }
+
+    fn translate_many(&self, locale: &str, keys: &[&str]) -> Vec<Option<&str>> {
+        // Lookup the locale only once
+        self.with_locale(locale, |trs| {
+            keys.iter().map(|key| trs.get(*key).copied()).collect()
+        })
+        .unwrap_or_else(|| keys.iter().map(|_| None).collect())
+    }
+
+    fn translations(&self, locale: &str) -> BTreeMap<&str, &str> {
+        self.with_locale(locale, |trs| {
+            trs.iter()
+                .map(|(k, v)| (*k, *v))
+                .collect::<BTreeMap<&str, &str>>()
+        })
+        .unwrap_or_default()
+    }
+
+    /// Iterate the translations of the loaded locales, the pruned locales are not loaded again.
+    fn entries(&self) -> Box<dyn Iterator<Item = (&str, &str, &str)> + '_> {
+        let entries = self
+            .loaded
+            .read()
+            .iter()
+            .flat_map(|(locale, trs)| trs.iter().map(move |(k, v)| (*locale, *k, *v)))
+            .collect::<Vec<_>>();
+        Box::new(entries.into_iter())
+    }
+}
+
+impl BackendExt for PrunableBackend {}
+
+/// The backend is held in a static by `i18n!`, so it can be pruned while it's extended by the `backend` option.
+impl Backend for &'static PrunableBackend {
+    fn available_locales(&self) -> Vec<&str> {
+        (**self).available_locales()
+    }
+
+    fn translate(&self, locale: &str, key: &str) -> Option<&str> {
+        (**self).translate(locale, key)
+    }
+
+    fn contains_key(&self, locale: &str, key: &str) -> bool {
+        (**self).contains_key(locale, key)
+    }
+
+    fn translate_many(&self, locale: &str, keys: &[&str]) -> Vec<Option<&str>> {
+        (**self).translate_many(locale, keys)
+    }
+
+    fn translations(&self, locale: &str) -> BTreeMap<&str, &str> {
+        (**self).translations(locale)
+    }
+
+    fn entries(&self) -> Box<dyn Iterator<Item = (&str, &str, &str)> + '_> {
+        (**self).entries()
+    }
+}
+
+impl BackendExt for &'static PrunableBackend {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(locale: &str) -> Option<Vec<(&'static str, &'static str)>> {
+        match locale {
+            "en" => Some(vec![("hello", "Hello"), ("foo", "Foo")]),
+            "fr" => Some(vec![("hello", "Bonjour")]),
+            "de" => Some(vec![("hello", "Hallo")]),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_prunable_backend() {
+        let backend = PrunableBackend::new(&["en", "fr", "de"], load);
+        assert_eq!(backend.available_locales(), ["de", "en", "fr"]);
+        assert_eq!(backend.loaded_locales(), ["de", "en", "fr"]);
+
+        let hello = backend.translate("de", "hello").unwrap();
+        assert_eq!(backend.prune(&["FR", "en"]), ["de"]);
+        assert_eq!(backend.loaded_locales(), ["en", "fr"]);
+        // The texts are static, they are still valid after the locale is pruned
+        assert_eq!(hello, "Hallo");
+        assert_eq!(backend.available_locales(), ["de", "en", "fr"]);
+        assert_eq!(backend.entries().count(), 3);
+
+        // The pruned locale is loaded again on lookup
+        assert!(backend.contains_key("de", "hello"));
+        assert_eq!(backend.loaded_locales(), ["de", "en", "fr"]);
+        assert_eq!(backend.prune(&[]), ["de", "en", "fr"]);
+        assert_eq!(
+            backend.translate_many("en", &["hello", "bar"]),
+            [Some("Hello"), None]
+        );
+        assert_eq!(backend.translations("fr").len(), 1);
+        assert_eq!(backend.translate("ja", "hello"), None);
+        assert_eq!(backend.loaded_locales(), ["en", "fr"]);
+    }
+}
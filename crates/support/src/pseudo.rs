@@ -0,0 +1,104 @@
+use alloc::string::String;
+
+/// The pseudo-locale of the `pseudo_locale` option of `i18n!`, by the private use region `XA` of the "Accents" pseudo-locale.
+pub const PSEUDO_LOCALE: &str = "en-XA";
+
+/// The default expansion percentage of the pseudo-localized texts, the translations are often ~30% longer than English.
+pub const DEFAULT_PSEUDO_EXPANSION: usize = 30;
+
+static ACCENTED_LOWERCASE: [char; 26] = [
+    'à', 'ƀ', 'ç', 'ð', 'é', 'ƒ', 'ĝ', 'ĥ', 'î', 'ĵ', 'ķ', 'ļ', 'ɱ', 'ñ', 'ô', 'þ', 'ǫ', 'ŕ', 'ś',
+    'ţ', 'û', 'ṽ', 'ŵ', 'ẋ', 'ý', 'ž',
+];
+
+static ACCENTED_UPPERCASE: [char; 26] = [
+    'Å', 'Ɓ', 'Ç', 'Ð', 'É', 'Ƒ', 'Ĝ', 'Ĥ', 'Î', 'Ĵ', 'Ķ', 'Ļ', 'Ṁ', 'Ñ', 'Ö', 'Þ', 'Ǫ', 'Ŕ', 'Š',
+    'Ţ', 'Û', 'Ṽ', 'Ŵ', 'Ẋ', 'Ý', 'Ž',
+];
+
+fn accent(c: char) -> char {
+    match c {
+        'a'..='z' => ACCENTED_LOWERCASE[c as usize - 'a' as usize],
+        'A'..='Z' => ACCENTED_UPPERCASE[c as usize - 'A' as usize],
+        _ => c,
+    }
+}
+
+/// Pseudo-localize the text for testing the layout, e.g. `Message` -> `[!! Ṁéśśàĝé !]`.
+///
+/// The ASCII letters are accented, and the text is padded with `!` by the `expansion` percentage of its length
+/// and wrapped in `[` and `]`, so the truncated and the hard-coded texts stand out.
+/// The placeholders between `open` and `close` are kept as is, e.g. `%{name}`.
+///
+/// ```
+/// use rust_i18n_support::pseudo_localize;
+///
+/// assert_eq!(pseudo_localize("Message", "%{", "}", 30), "[!! Ṁéśśàĝé !]");
+/// assert_eq!(pseudo_localize("Hi, %{name}", "%{", "}", 0), "[Ĥî, %{name}]");
+/// ```
+pub fn pseudo_localize(text: &str, open: &str, close: &str, expansion: usize) -> String {
+    let mut output = String::with_capacity(text.len() * 2 + 4);
+    let mut len = 0;
+    let mut rest = text;
+    while !rest.is_empty() {
+        if !open.is_empty() && rest.starts_with(open) {
+            if let Some(end) = rest[open.len()..].find(close) {
+                let end = open.len() + end + close.len();
+                output.push_str(&rest[..end]);
+                rest = &rest[end..];
+                continue;
+            }
+        }
+        let c = rest.chars().next().unwrap_or_default();
+        output.push(accent(c));
+        len += 1;
+        rest = &rest[c.len_utf8()..];
+    }
+
+    let padding = (len * expansion).div_ceil(100);
+    let (before, after) = (padding.div_ceil(2), padding / 2);
+    let mut padded = String::with_capacity(output.len() + padding + 4);
+    padded.push('[');
+    if before > 0 {
+        padded.extend(core::iter::repeat_n('!', before));
+        padded.push(' ');
+    }
+    padded.push_str(&output);
+    if after > 0 {
+        padded.push(' ');
+        padded.extend(core::iter::repeat_n('!', after));
+    }
+    padded.push(']');
+    padded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pseudo_localize() {
+        assert_eq!(pseudo_localize("", "%{", "}", 30), "[]");
+        assert_eq!(pseudo_localize("Message", "%{", "}", 0), "[Ṁéśśàĝé]");
+        assert_eq!(pseudo_localize("Message", "%{", "}", 30), "[!! Ṁéśśàĝé !]");
+        assert_eq!(
+            pseudo_localize("Message", "%{", "}", 100),
+            "[!!!! Ṁéśśàĝé !!!]"
+        );
+        assert_eq!(
+            pseudo_localize("Hello, %{name}!", "%{", "}", 0),
+            "[Ĥéļļô, %{name}!]"
+        );
+        assert_eq!(
+            pseudo_localize("%{count | item | items}", "%{", "}", 0),
+            "[%{count | item | items}]"
+        );
+        assert_eq!(
+            pseudo_localize("Hello, {{name}}", "{{", "}}", 0),
+            "[Ĥéļļô, {{name}}]"
+        );
+        // The unclosed placeholder is a text
+        assert_eq!(pseudo_localize("a %{b", "%{", "}", 0), "[à %{ƀ]");
+        assert_eq!(pseudo_localize("你好 ok", "%{", "}", 0), "[你好 ôķ]");
+    }
+}
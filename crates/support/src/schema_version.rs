@@ -0,0 +1,109 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ALLOW_UNKNOWN_VERSION: AtomicBool = AtomicBool::new(false);
+
+/// Load the locale files with an unknown `_version` as the [`SchemaVersion::LATEST`] instead of failing.
+///
+/// This is for the tools to read the files written by a newer version, e.g. `cargo i18n --allow-unknown-version`.
+pub fn set_allow_unknown_version(allow: bool) {
+    ALLOW_UNKNOWN_VERSION.store(allow, Ordering::Relaxed);
+}
+
+/// The `_version` of the locale files, see the "Locale file" section of the README.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SchemaVersion {
+    /// The translations of one locale in each file, the locale is the last part of the file name, e.g. `app.en.yml`
+    #[default]
+    V1,
+    /// The translations of all locales in one file, grouped by key
+    V2,
+}
+
+impl SchemaVersion {
+    /// The latest version, it's written by `cargo i18n` by default.
+    pub const LATEST: SchemaVersion = SchemaVersion::V2;
+
+    /// Get the version of the parsed locale file, it's [`SchemaVersion::V1`] if `_version` is absent.
+    ///
+    /// Returns an error for the unknown versions, unless [`set_allow_unknown_version`] is enabled.
+    pub fn from_value(data: &serde_json::Value) -> Result<Self, String> {
+        Self::from_value_with(data, ALLOW_UNKNOWN_VERSION.load(Ordering::Relaxed))
+    }
+
+    fn from_value_with(data: &serde_json::Value, allow_unknown: bool) -> Result<Self, String> {
+        let Some(version) = data.get("_version") else {
+            return Ok(SchemaVersion::V1);
+        };
+        let number = version
+            .as_u64()
+            .or_else(|| version.as_str().and_then(|v| v.trim().parse().ok()));
+        match number {
+            Some(1) => Ok(SchemaVersion::V1),
+            Some(2) => Ok(SchemaVersion::V2),
+            _ if allow_unknown => Ok(SchemaVersion::LATEST),
+            _ => Err(format!(
+                "Unknown `_version: {}`, expected `1` or `2`, upgrade rust-i18n to load it",
+                version
+                    .as_str()
+                    .map_or_else(|| version.to_string(), str::to_owned)
+            )),
+        }
+    }
+
+    /// The number written as `_version`.
+    pub fn as_u64(&self) -> u64 {
+        match self {
+            SchemaVersion::V1 => 1,
+            SchemaVersion::V2 => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_u64())
+    }
+}
+
+impl std::str::FromStr for SchemaVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1" => Ok(SchemaVersion::V1),
+            "2" => Ok(SchemaVersion::V2),
+            _ => Err(format!(
+                "Unsupported schema version `{}`, expected `1` or `2`",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_version() {
+        let parse = |text: &str| {
+            SchemaVersion::from_value(&serde_yml::from_str::<serde_json::Value>(text).unwrap())
+        };
+        assert_eq!(parse("hello: Hello"), Ok(SchemaVersion::V1));
+        assert_eq!(parse("_version: 1"), Ok(SchemaVersion::V1));
+        assert_eq!(parse("_version: 2"), Ok(SchemaVersion::V2));
+        assert_eq!(parse("_version: '2'"), Ok(SchemaVersion::V2));
+        assert!(parse("_version: 3").unwrap_err().contains("_version: 3"));
+        assert!(parse("_version: two").is_err());
+
+        let value = serde_yml::from_str::<serde_json::Value>("_version: 3").unwrap();
+        assert_eq!(
+            SchemaVersion::from_value_with(&value, true),
+            Ok(SchemaVersion::LATEST)
+        );
+
+        assert_eq!("2".parse(), Ok(SchemaVersion::V2));
+        assert!("3".parse::<SchemaVersion>().is_err());
+        assert_eq!(SchemaVersion::LATEST.to_string(), "2");
+    }
+}
@@ -0,0 +1,226 @@
+use alloc::string::String;
+
+use crate::lookup_fallback;
+
+/// The typographic quotes of a locale, the open and close marks of the double and single quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteMarks {
+    /// The marks replacing `"`, e.g. `“` and `”` in `en`
+    pub double: (char, char),
+    /// The marks replacing `'` around a text, e.g. `‘` and `’` in `en`
+    pub single: (char, char),
+}
+
+const fn marks(double: (char, char), single: (char, char)) -> QuoteMarks {
+    QuoteMarks { double, single }
+}
+
+const ENGLISH: QuoteMarks = marks(('“', '”'), ('‘', '’'));
+const GERMAN: QuoteMarks = marks(('„', '“'), ('‚', '‘'));
+const POLISH: QuoteMarks = marks(('„', '”'), ('‚', '’'));
+const GUILLEMETS: QuoteMarks = marks(('«', '»'), ('‹', '›'));
+const GUILLEMETS_ENGLISH: QuoteMarks = marks(('«', '»'), ('“', '”'));
+const GUILLEMETS_GERMAN: QuoteMarks = marks(('«', '»'), ('„', '“'));
+const SWEDISH: QuoteMarks = marks(('”', '”'), ('’', '’'));
+const CORNER_BRACKETS: QuoteMarks = marks(('「', '」'), ('『', '』'));
+
+/// The quotes of the common locales, the more specific locales are found first by the fallback.
+static QUOTE_MARKS: &[(&str, QuoteMarks)] = &[
+    ("bg", GERMAN),
+    ("cs", GERMAN),
+    ("da", marks(('»', '«'), ('›', '‹'))),
+    ("de", GERMAN),
+    ("de-CH", GUILLEMETS),
+    ("en", ENGLISH),
+    ("es", GUILLEMETS_ENGLISH),
+    ("fi", SWEDISH),
+    ("fr", GUILLEMETS),
+    ("hu", POLISH),
+    ("it", GUILLEMETS_ENGLISH),
+    ("ja", CORNER_BRACKETS),
+    ("ko", ENGLISH),
+    ("nl", ENGLISH),
+    ("no", GUILLEMETS),
+    ("pl", POLISH),
+    ("pt", GUILLEMETS_ENGLISH),
+    ("pt-BR", ENGLISH),
+    ("ro", POLISH),
+    ("ru", GUILLEMETS_GERMAN),
+    ("sv", SWEDISH),
+    ("tr", ENGLISH),
+    ("uk", GUILLEMETS_GERMAN),
+    ("zh", ENGLISH),
+    ("zh-HK", CORNER_BRACKETS),
+    ("zh-Hant", CORNER_BRACKETS),
+    ("zh-TW", CORNER_BRACKETS),
+];
+
+/// Get the typographic quotes of the locale, or `None` if the locale is unknown.
+///
+/// ```
+/// use rust_i18n_support::quote_marks;
+///
+/// assert_eq!(quote_marks("fr-CA").unwrap().double, ('«', '»'));
+/// assert_eq!(quote_marks("de_AT").unwrap().double, ('„', '“'));
+/// assert_eq!(quote_marks("xx"), None);
+/// ```
+pub fn quote_marks(locale: &str) -> Option<QuoteMarks> {
+    let locale = locale.replace('_', "-");
+    let mut locale = locale.as_str();
+    loop {
+        if let Some((_, marks)) = QUOTE_MARKS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(locale))
+        {
+            return Some(*marks);
+        }
+        locale = lookup_fallback(locale)?;
+    }
+}
+
+/// Replace the straight quotes in the text with the typographic quotes of the locale, e.g. `"Hi"` -> `„Hi“` in `de`.
+///
+/// A quote at the start of the text or after a space or an open bracket is an open mark, otherwise a close mark.
+/// The `'` between two letters or digits is an apostrophe `’`, e.g. `don’t`.
+/// The placeholders between `open` and `close`, the escaped placeholders (e.g. `%%{...}`) and the quotes
+/// escaped by `\` are kept as is. The text of an unknown locale is not changed.
+///
+/// ```
+/// use rust_i18n_support::smart_quotes;
+///
+/// assert_eq!(smart_quotes(r#"Say "hi""#, "en", "%{", "}"), "Say “hi”");
+/// assert_eq!(smart_quotes(r#"Dit "%{name}""#, "fr", "%{", "}"), "Dit «%{name}»");
+/// assert_eq!(smart_quotes("It's %{a \"b\"}", "en", "%{", "}"), "It’s %{a \"b\"}");
+/// ```
+pub fn smart_quotes(text: &str, locale: &str, open: &str, close: &str) -> String {
+    let Some(marks) = quote_marks(locale) else {
+        return text.into();
+    };
+    let escape = if open.starts_with('%') { "%" } else { "\\" };
+
+    let mut output = String::with_capacity(text.len() + 8);
+    let mut prev: Option<char> = None;
+    let mut rest = text;
+    while !rest.is_empty() {
+        // The placeholders and the escaped placeholders are kept as is
+        let placeholder_start =
+            if rest.starts_with(escape) && rest[escape.len()..].starts_with(open) {
+                Some(escape.len() + open.len())
+            } else if rest.starts_with(open) {
+                Some(open.len())
+            } else {
+                None
+            };
+        if let Some(start) = placeholder_start.filter(|_| !open.is_empty()) {
+            if let Some(len) = rest[start..].find(close) {
+                let end = start + len + close.len();
+                output.push_str(&rest[..end]);
+                prev = rest[..end].chars().next_back();
+                rest = &rest[end..];
+                continue;
+            }
+        }
+
+        let c = rest.chars().next().unwrap_or_default();
+        rest = &rest[c.len_utf8()..];
+        if let Some(quote) = rest
+            .chars()
+            .next()
+            .filter(|next| c == '\\' && "\"'".contains(*next))
+        {
+            output.push(c);
+            output.push(quote);
+            prev = Some(quote);
+            rest = &rest[1..];
+            continue;
+        }
+        let is_open =
+            prev.is_none_or(|prev| prev.is_whitespace() || "([{<«‹„“‘—–-/".contains(prev));
+        let replaced = match c {
+            '"' if is_open => marks.double.0,
+            '"' => marks.double.1,
+            '\'' if prev.is_some_and(char::is_alphanumeric)
+                && rest.starts_with(char::is_alphanumeric) =>
+            {
+                '’'
+            }
+            '\'' if is_open => marks.single.0,
+            '\'' => marks.single.1,
+            _ => c,
+        };
+        output.push(replaced);
+        prev = Some(c);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_marks() {
+        assert_eq!(quote_marks("en").unwrap().double, ('“', '”'));
+        assert_eq!(quote_marks("de").unwrap().single, ('‚', '‘'));
+        assert_eq!(quote_marks("de-CH").unwrap().double, ('«', '»'));
+        assert_eq!(quote_marks("zh-CN").unwrap().double, ('“', '”'));
+        assert_eq!(quote_marks("zh-Hant-HK").unwrap().double, ('「', '」'));
+        assert_eq!(quote_marks("zh_tw").unwrap().double, ('「', '」'));
+        assert_eq!(quote_marks(""), None);
+    }
+
+    #[test]
+    fn test_smart_quotes() {
+        assert_eq!(smart_quotes("", "en", "%{", "}"), "");
+        assert_eq!(
+            smart_quotes(r#""Hello", she said"#, "en", "%{", "}"),
+            "“Hello”, she said"
+        );
+        assert_eq!(
+            smart_quotes(r#"Er sagte "Hallo""#, "de", "%{", "}"),
+            "Er sagte „Hallo“"
+        );
+        assert_eq!(
+            smart_quotes(r#"Il a dit "bonjour""#, "fr", "%{", "}"),
+            "Il a dit «bonjour»"
+        );
+        assert_eq!(
+            smart_quotes(r#"("quoted")"#, "ja", "%{", "}"),
+            "(「quoted」)"
+        );
+        assert_eq!(
+            smart_quotes("The 'best' isn't", "en", "%{", "}"),
+            "The ‘best’ isn’t"
+        );
+        assert_eq!(
+            smart_quotes("Das 'Beste' gibt's", "de", "%{", "}"),
+            "Das ‚Beste‘ gibt’s"
+        );
+        assert_eq!(
+            smart_quotes("the students' books", "en", "%{", "}"),
+            "the students’ books"
+        );
+
+        // The placeholders and the escaped sections are kept as is
+        assert_eq!(
+            smart_quotes(r#""%{name}" %{a "b"}"#, "en", "%{", "}"),
+            r#"“%{name}” %{a "b"}"#
+        );
+        assert_eq!(
+            smart_quotes(r#"%%{a "b"} "c""#, "en", "%{", "}"),
+            r#"%%{a "b"} “c”"#
+        );
+        assert_eq!(
+            smart_quotes(r#"\{{a "b"}} {{"c"}} "d""#, "en", "{{", "}}"),
+            r#"\{{a "b"}} {{"c"}} “d”"#
+        );
+        assert_eq!(
+            smart_quotes(r#"a \"b\" "c""#, "en", "%{", "}"),
+            r#"a \"b\" “c”"#
+        );
+        // The unclosed placeholder is a text
+        assert_eq!(smart_quotes(r#"%{"a""#, "en", "%{", "}"), "%{“a”");
+
+        assert_eq!(smart_quotes(r#""a""#, "xx", "%{", "}"), r#""a""#);
+    }
+}
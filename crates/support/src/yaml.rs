@@ -0,0 +1,153 @@
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt;
+
+/// Parse YAML into a JSON value, the keys and the scalar values are kept as written.
+///
+/// A YAML parser resolves plain scalars like `1.50`, `0x1F` or `True` into numbers and booleans,
+/// which would corrupt the translations (e.g. `1.50` becomes `1.5`).
+/// So the content is parsed twice, the first pass to get the structure of the document,
+/// and the second pass to read every scalar as the string it is written.
+pub(crate) fn from_str(content: &str) -> Result<Value, serde_yml::Error> {
+    let shape = serde_yml::from_str::<serde_yml::Value>(content)?;
+    if shape.is_null() {
+        return Ok(Value::Null);
+    }
+
+    Shaped(&shape).deserialize(serde_yml::Deserializer::from_str(content))
+}
+
+/// Deserialize a node of the YAML document as the given shape.
+struct Shaped<'a>(&'a serde_yml::Value);
+
+impl<'de, 'a> DeserializeSeed<'de> for Shaped<'a> {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        match self.0 {
+            serde_yml::Value::Mapping(mapping) => {
+                deserializer.deserialize_map(MappingVisitor(mapping))
+            }
+            serde_yml::Value::Sequence(sequence) => {
+                deserializer.deserialize_seq(SequenceVisitor(sequence))
+            }
+            serde_yml::Value::Null => {
+                IgnoredAny::deserialize(deserializer)?;
+                Ok(Value::Null)
+            }
+            serde_yml::Value::Tagged(_) => Value::deserialize(deserializer),
+            _ => deserializer.deserialize_str(StringVisitor),
+        }
+    }
+}
+
+struct MappingVisitor<'a>(&'a serde_yml::Mapping);
+
+impl<'de, 'a> Visitor<'de> for MappingVisitor<'a> {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a mapping")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut shapes = self.0.values();
+        let mut result = serde_json::Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let shape = shapes
+                .next()
+                .ok_or_else(|| de::Error::custom("mismatched mapping"))?;
+            let value = map.next_value_seed(Shaped(shape))?;
+            result.insert(key, value);
+        }
+        Ok(Value::Object(result))
+    }
+}
+
+struct SequenceVisitor<'a>(&'a serde_yml::Sequence);
+
+impl<'de, 'a> Visitor<'de> for SequenceVisitor<'a> {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut result = Vec::with_capacity(self.0.len());
+        for shape in self.0 {
+            match seq.next_element_seed(Shaped(shape))? {
+                Some(value) => result.push(value),
+                None => return Err(de::Error::custom("mismatched sequence")),
+            }
+        }
+        Ok(Value::Array(result))
+    }
+}
+
+struct StringVisitor;
+
+impl<'de> Visitor<'de> for StringVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a scalar")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::String(v.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_str;
+
+    #[test]
+    fn test_from_str_keeps_scalars_as_written() {
+        let content = r#"
+        yes: yes
+        no: no
+        on: on
+        off: off
+        1.0: 1.0
+        1.50: 1.50
+        true: True
+        hex: 0x1F
+        quoted: "1.0"
+        empty:
+        nested:
+          1: [1.0, off]
+        "#;
+
+        let value = from_str(content).unwrap();
+        assert_eq!(value["yes"], "yes");
+        assert_eq!(value["no"], "no");
+        assert_eq!(value["on"], "on");
+        assert_eq!(value["off"], "off");
+        assert_eq!(value["1.0"], "1.0");
+        assert_eq!(value["1.50"], "1.50");
+        assert_eq!(value["true"], "True");
+        assert_eq!(value["hex"], "0x1F");
+        assert_eq!(value["quoted"], "1.0");
+        assert!(value["empty"].is_null());
+        assert_eq!(value["nested"]["1"][0], "1.0");
+        assert_eq!(value["nested"]["1"][1], "off");
+
+        assert!(from_str("").unwrap().is_null());
+        assert!(from_str("foo: [").is_err());
+    }
+}
@@ -0,0 +1,13 @@
+#![no_std]
+
+extern crate alloc;
+
+#[macro_use]
+extern crate rust_i18n;
+
+i18n!("locales", fallback = "en");
+
+/// Greet in the given locale, without the standard library.
+pub fn hello<'a>(locale: &str, name: &str) -> alloc::borrow::Cow<'a, str> {
+    t!("hello", locale = locale, name = name)
+}
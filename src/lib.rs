@@ -1,37 +1,525 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use std::ops::Deref;
+extern crate alloc;
 
-use once_cell::sync::Lazy;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use __private::Lazy;
 
 #[doc(hidden)]
+#[cfg(feature = "std")]
 pub use once_cell;
 #[doc(hidden)]
-pub use rust_i18n_macro::{_minify_key, _tr, i18n};
+pub use rust_i18n_macro::{_minify_key, _tr, i18n, with_key_prefix};
+pub use rust_i18n_macro::{Interpolate, Localize};
+#[cfg(feature = "std")]
+pub use rust_i18n_support::CachingBackend;
 pub use rust_i18n_support::{
-    AtomicStr, Backend, BackendExt, CowStr, MinifyKey, SimpleBackend, DEFAULT_MINIFY_KEY,
-    DEFAULT_MINIFY_KEY_LEN, DEFAULT_MINIFY_KEY_PREFIX, DEFAULT_MINIFY_KEY_THRESH,
+    bidi_isolate, cardinal_category, escape_html, format_number, is_rtl, locale_info,
+    lookup_fallback, namespace_default_locale, negotiate_locale, ordinal_category, pseudo_localize,
+    AtomicStr, Backend, BackendExt, CowStr, Direction, Interpolate, LocaleInfo, MinifyKey,
+    OnMissing, PackedBackend, PackedLocale, PluralCategory, PrunableBackend, SimpleBackend,
+    DEFAULT_MINIFY_KEY, DEFAULT_MINIFY_KEY_LEN, DEFAULT_MINIFY_KEY_PREFIX,
+    DEFAULT_MINIFY_KEY_THRESH, DEFAULT_PSEUDO_EXPANSION, PSEUDO_LOCALE,
 };
+#[cfg(feature = "markdown")]
+pub use rust_i18n_support::{escape_markdown, render_markdown};
+#[cfg(feature = "datetime")]
+pub use rust_i18n_support::{format_date, format_relative_time, Date, DateStyle};
+
+/// The items used by the code generated from [`i18n!`], they are available in both `std` and `no_std`.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::{
+        borrow::Cow, boxed::Box, collections::BTreeMap, format, string::String, vec::Vec,
+    };
+    #[cfg(feature = "std")]
+    pub use once_cell::sync::Lazy;
+    #[cfg(feature = "serde")]
+    pub use serde;
+    #[cfg(not(feature = "std"))]
+    pub use spin::Lazy;
+
+    pub use rust_i18n_support::unpack_translations;
+
+    /// Watch the locales paths to reload the translations in debug builds.
+    #[cfg(all(feature = "std", debug_assertions))]
+    pub fn hot_reload(
+        backend: crate::SimpleBackend,
+        locales_paths: &[&str],
+        key_separator: &str,
+        namespace: &str,
+    ) -> CompiledBackend {
+        rust_i18n_support::HotReloadBackend::new_with_namespace(
+            backend,
+            locales_paths,
+            key_separator,
+            namespace,
+        )
+    }
+
+    /// The type of the backend returned by [`hot_reload`], `i18n!` holds it as is without `backend`.
+    #[cfg(all(feature = "std", debug_assertions))]
+    pub type CompiledBackend = rust_i18n_support::HotReloadBackend;
+
+    /// The type of the backend returned by [`hot_reload`], `i18n!` holds it as is without `backend`.
+    #[cfg(not(all(feature = "std", debug_assertions)))]
+    pub type CompiledBackend = crate::SimpleBackend;
+
+    /// Get the backend set by [`set_backend_for_tests`](crate::set_backend_for_tests).
+    #[cfg(feature = "test-backend")]
+    #[inline]
+    pub fn test_backend() -> Option<&'static dyn crate::Backend> {
+        *crate::TEST_BACKEND.read()
+    }
+
+    /// The backend can't be replaced without the `test-backend` feature.
+    #[cfg(not(feature = "test-backend"))]
+    #[inline(always)]
+    pub fn test_backend() -> Option<&'static dyn crate::Backend> {
+        None
+    }
+
+    /// Get the backend set by [`set_base_backend`](crate::set_base_backend).
+    #[inline]
+    pub fn base_backend() -> Option<&'static dyn crate::Backend> {
+        *crate::BASE_BACKEND.read()
+    }
+
+    /// Whether [`t!`](crate::t) shows the keys, see [`set_debug_show_keys`](crate::set_debug_show_keys).
+    #[cfg(feature = "debug-keys")]
+    #[inline]
+    pub fn debug_show_keys() -> bool {
+        crate::DEBUG_SHOW_KEYS.load(core::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// The keys are never shown without the `debug-keys` feature.
+    #[cfg(not(feature = "debug-keys"))]
+    #[inline(always)]
+    pub fn debug_show_keys() -> bool {
+        false
+    }
+
+    /// Record a missing translation for [`collect_missing`](crate::collect_missing) of the current thread.
+    #[cfg(feature = "std")]
+    pub fn report_missing(locale: &str, key: &str) {
+        crate::MISSING_TRANSLATIONS.with(|missing| {
+            if let Some(missing) = missing.borrow_mut().as_mut() {
+                missing.push((locale.into(), key.into()));
+            }
+        });
+    }
+
+    /// The missing translations can't be collected without `std`.
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    pub fn report_missing(_locale: &str, _key: &str) {}
+
+    /// The translations are never reloaded in release builds.
+    #[cfg(not(all(feature = "std", debug_assertions)))]
+    pub fn hot_reload(
+        backend: crate::SimpleBackend,
+        _locales_paths: &[&str],
+        _key_separator: &str,
+        _namespace: &str,
+    ) -> CompiledBackend {
+        backend
+    }
+
+    /// Warn once for each deprecated key used in debug builds, the keys nested under a deprecated key are also deprecated.
+    #[cfg(all(feature = "std", debug_assertions))]
+    pub fn warn_deprecated_key(deprecated_keys: &[(&str, &str)], key: &str, key_separator: &str) {
+        static WARNED: spin::RwLock<alloc::collections::BTreeSet<String>> =
+            spin::RwLock::new(alloc::collections::BTreeSet::new());
+
+        // The nearest deprecated key wins, the same as `find_deprecated_key`
+        let Some((deprecated, message)) = rust_i18n_support::key_ancestors(key, key_separator)
+            .find_map(|key| {
+                deprecated_keys
+                    .iter()
+                    .find(|(deprecated, _)| *deprecated == key)
+            })
+        else {
+            return;
+        };
+        if WARNED.read().contains(key) || !WARNED.write().insert(key.into()) {
+            return;
+        }
+        std::eprintln!(
+            "Warning: {}",
+            rust_i18n_support::deprecated_key_message(key, deprecated, message)
+        );
+    }
+
+    /// The deprecated keys are not checked in release builds.
+    #[cfg(not(all(feature = "std", debug_assertions)))]
+    #[inline(always)]
+    pub fn warn_deprecated_key(
+        _deprecated_keys: &[(&str, &str)],
+        _key: &str,
+        _key_separator: &str,
+    ) {
+    }
+}
 
 static CURRENT_LOCALE: Lazy<AtomicStr> = Lazy::new(|| AtomicStr::from("en"));
 
+#[cfg(feature = "test-backend")]
+static TEST_BACKEND: spin::RwLock<Option<&'static dyn Backend>> = spin::RwLock::new(None);
+
+/// Replace the backend of [`i18n!`] with `backend`, e.g. to inject the translations deterministically in tests.
+///
+/// **Test only**: this is enabled by the `test-backend` feature, which should be enabled in
+/// `[dev-dependencies]` only. The backend is replaced for all crates using [`i18n!`] in the process,
+/// and it's leaked to be shared by all threads. Use [`reset_backend_for_tests`] to restore the compiled backend.
+///
+/// ```toml
+/// [dev-dependencies]
+/// rust-i18n = { version = "3", features = ["test-backend"] }
+/// ```
+///
+/// The `static_only` translations are resolved at compile time, they can't be replaced.
+#[cfg(feature = "test-backend")]
+pub fn set_backend_for_tests(backend: alloc::boxed::Box<dyn Backend>) {
+    *TEST_BACKEND.write() = Some(alloc::boxed::Box::leak(backend));
+}
+
+/// Restore the compiled backend of [`i18n!`] replaced by [`set_backend_for_tests`].
+#[cfg(feature = "test-backend")]
+pub fn reset_backend_for_tests() {
+    *TEST_BACKEND.write() = None;
+}
+
+#[cfg(feature = "debug-keys")]
+static DEBUG_SHOW_KEYS: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+
+/// Show the keys instead of the translations in [`t!`] of all crates while enabled, e.g. `auth.login.title`,
+/// to find the key of a text in a running UI.
+///
+/// **Debug only**: this is enabled by the `debug-keys` feature, without it [`t!`] has no overhead for the check.
+///
+/// ```toml
+/// [features]
+/// debug-keys = ["rust-i18n/debug-keys"]
+/// ```
+///
+/// The keys are shown regardless of the locale, the minified keys are shown as is with `minify_key`.
+/// The `static_only` translations are resolved at compile time, they can't be replaced.
+#[cfg(feature = "debug-keys")]
+pub fn set_debug_show_keys(enabled: bool) {
+    DEBUG_SHOW_KEYS.store(enabled, core::sync::atomic::Ordering::Relaxed);
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static MISSING_TRANSLATIONS: core::cell::RefCell<Option<Vec<(String, String)>>> =
+        const { core::cell::RefCell::new(None) };
+}
+
+/// Run `f` and collect the missing translations of [`t!`] in it, as `(locale, key)` in the order they are missed,
+/// e.g. to assert that a rendering path of an integration test is fully translated.
+///
+/// Only the misses of the current thread are collected, the keys are the lookup keys, e.g. the minified keys
+/// with `minify_key`. The misses of a nested `collect_missing` are also collected by the outer one.
+///
+/// ```no_run
+/// # fn render_checkout() {}
+/// let missing = rust_i18n::collect_missing(|| {
+///     render_checkout();
+/// });
+/// assert!(missing.is_empty(), "missing translations: {:?}", missing);
+/// ```
+#[cfg(feature = "std")]
+pub fn collect_missing<F: FnOnce()>(f: F) -> Vec<(String, String)> {
+    // Restore the outer collector even if `f` panics
+    struct Guard(Option<Vec<(String, String)>>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            let outer = self.0.take();
+            MISSING_TRANSLATIONS.with(|missing| {
+                let mut missing = missing.borrow_mut();
+                let inner = missing.take().unwrap_or_default();
+                *missing = outer.map(|mut outer| {
+                    outer.extend(inner);
+                    outer
+                });
+            });
+        }
+    }
+
+    let outer = MISSING_TRANSLATIONS.with(|missing| missing.borrow_mut().replace(Vec::new()));
+    let _guard = Guard(outer);
+    f();
+    MISSING_TRANSLATIONS.with(|missing| missing.borrow().clone().unwrap_or_default())
+}
+
+static BASE_BACKEND: spin::RwLock<Option<&'static dyn Backend>> = spin::RwLock::new(None);
+
+/// Set the last-approved translations as the base, e.g. in a preview build for the review,
+/// then [`changed_since_base!`] tells the keys whose translations are changed since the base.
+///
+/// The keys of the base are not namespaced, the same as in the locale files. The backend is leaked to be shared by all threads,
+/// compose the snapshots of several directories with [`BackendExt::extend`] first.
+///
+/// ```no_run
+/// # use std::collections::HashMap;
+/// use rust_i18n::SimpleBackend;
+///
+/// // e.g. the translations loaded from the `base/` snapshot
+/// let mut base = SimpleBackend::new();
+/// base.add_translations("en", &HashMap::from([("hello", "Hello")]));
+/// rust_i18n::set_base_backend(Box::new(base));
+/// ```
+pub fn set_base_backend(backend: alloc::boxed::Box<dyn Backend>) {
+    *BASE_BACKEND.write() = Some(alloc::boxed::Box::leak(backend));
+}
+
+/// The locale aliases of all crates, see [`add_locale_aliases`].
+static LOCALE_ALIASES: spin::RwLock<Vec<(&'static str, &'static str)>> =
+    spin::RwLock::new(Vec::new());
+
+/// Add the locale aliases, this is called by the code generated from [`i18n!`] with its `aliases`.
+///
+/// The aliases of all crates are merged, the first crate that adds an alias wins on conflict,
+/// so a crate without `aliases` doesn't clear the aliases of the others.
+#[doc(hidden)]
+pub fn add_locale_aliases(aliases: &'static [(&'static str, &'static str)]) {
+    if aliases.is_empty() {
+        return;
+    }
+    let mut merged = LOCALE_ALIASES.write();
+    for (alias, preferred) in aliases {
+        if !merged
+            .iter()
+            .any(|(merged, _)| merged.eq_ignore_ascii_case(alias))
+        {
+            merged.push((alias, preferred));
+        }
+    }
+}
+
+/// Map the locale alias to the preferred locale, the alias is matched case-insensitively.
+///
+/// ```
+/// # use rust_i18n::resolve_alias;
+/// let aliases = [("zh", "zh-CN"), ("no", "nb")];
+/// assert_eq!(resolve_alias(&aliases, "ZH"), "zh-CN");
+/// assert_eq!(resolve_alias(&aliases, "zh-TW"), "zh-TW");
+/// ```
+pub fn resolve_alias<'a>(aliases: &[(&str, &'a str)], locale: &'a str) -> &'a str {
+    aliases
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(locale))
+        .map_or(locale, |(_, preferred)| preferred)
+}
+
+/// Binary search the table sorted by the key, this is used by the `_rust_i18n_t_static` generated by [`i18n!`].
+///
+/// ```
+/// # use rust_i18n::lookup_static_table;
+/// const TABLE: &[(&str, &str)] = &[("hello", "Hello"), ("world", "World")];
+/// const HELLO: Option<&str> = lookup_static_table(TABLE, "hello");
+/// assert_eq!(HELLO, Some("Hello"));
+/// assert_eq!(lookup_static_table(TABLE, "missing"), None);
+/// ```
+pub const fn lookup_static_table<V: Copy>(table: &[(&str, V)], key: &str) -> Option<V> {
+    let (mut low, mut high) = (0, table.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match compare_str(table[mid].0, key) {
+            core::cmp::Ordering::Less => low = mid + 1,
+            core::cmp::Ordering::Greater => high = mid,
+            core::cmp::Ordering::Equal => return Some(table[mid].1),
+        }
+    }
+    None
+}
+
+/// Compare the strings by bytes in `const fn`, it's the same order as `str::cmp`.
+const fn compare_str(a: &str, b: &str) -> core::cmp::Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut i = 0;
+    while i < a.len() && i < b.len() {
+        if a[i] != b[i] {
+            return if a[i] < b[i] {
+                core::cmp::Ordering::Less
+            } else {
+                core::cmp::Ordering::Greater
+            };
+        }
+        i += 1;
+    }
+    if a.len() < b.len() {
+        core::cmp::Ordering::Less
+    } else if a.len() > b.len() {
+        core::cmp::Ordering::Greater
+    } else {
+        core::cmp::Ordering::Equal
+    }
+}
+
 /// Set current locale
+///
+/// The locale alias configured by `aliases` of [`i18n!`] is replaced by the preferred locale, e.g. `zh` => `zh-CN`.
 pub fn set_locale(locale: &str) {
-    CURRENT_LOCALE.replace(locale);
+    CURRENT_LOCALE.replace(resolve_alias(&LOCALE_ALIASES.read(), locale));
 }
 
-/// Get current locale
+/// Get the current locale, the locale that [`t!`] uses if the `locale` argument is absent.
+///
+/// This is the single source of truth of the current locale, both [`t!`] and the `LocalizedKey` serialization read it here,
+/// so log it to see exactly which locale a `t!` call will use. The locale set by [`set_locale`] is already resolved from its alias,
+/// e.g. `zh` => `zh-CN`.
+///
+/// It's cheap, the returned guard refers to the stored string without allocation, keep it only for the duration of a call,
+/// as the replaced string of [`set_locale`] is kept alive until the guard is dropped.
+///
+/// ```
+/// rust_i18n::set_locale("fr");
+/// assert_eq!(&*rust_i18n::locale(), "fr");
+/// println!("t! uses {}", &*rust_i18n::locale());
+/// # rust_i18n::set_locale("en");
+/// ```
 pub fn locale() -> impl Deref<Target = str> {
     CURRENT_LOCALE.as_str()
 }
 
+/// The error returned by [`set_locale_checked!`] if the locale is not available.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownLocale(String);
+
+impl UnknownLocale {
+    /// Create a new `UnknownLocale` with the given locale.
+    pub fn new(locale: &str) -> Self {
+        Self(locale.into())
+    }
+
+    /// Get the unknown locale.
+    pub fn locale(&self) -> &str {
+        &self.0
+    }
+}
+
+impl core::fmt::Display for UnknownLocale {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "unknown locale `{}`", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownLocale {}
+
+/// The locale that a translation returned by [`t_resolved!`] is resolved from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedFrom {
+    /// Found in the requested locale (after the locale aliases are applied).
+    Exact(String),
+    /// Found in a fallback locale, e.g. `zh` for `zh-CN`, or the `fallback` of [`i18n!`].
+    Fallback(String),
+    /// Not found in any locale, the text is the missing key output, see `on_missing` of [`i18n!`].
+    Missing,
+}
+
+impl ResolvedFrom {
+    /// Get the locale that the translation is found in, `None` if it's missing.
+    pub fn locale(&self) -> Option<&str> {
+        match self {
+            Self::Exact(locale) | Self::Fallback(locale) => Some(locale),
+            Self::Missing => None,
+        }
+    }
+}
+
+static DEFAULT_LOCALE: spin::RwLock<&'static str> = spin::RwLock::new("en");
+
+/// Set the default locale, this is called by the code generated from [`i18n!`] with its `default_locale`.
+#[doc(hidden)]
+pub fn set_default_locale(locale: &'static str) {
+    *DEFAULT_LOCALE.write() = locale;
+    set_locale(locale);
+}
+
+/// Get the default locale configured by `default_locale` of [`i18n!`] or `[package.metadata.i18n]`, default: `"en"`.
+///
+/// The translations (and the default locale) are initialized on the first lookup, e.g. [`t!`].
+pub fn default_locale() -> &'static str {
+    *DEFAULT_LOCALE.read()
+}
+
+/// Reset the current locale to the [`default_locale`].
+///
+/// ```
+/// rust_i18n::set_locale("fr");
+/// rust_i18n::reset_locale();
+/// assert_eq!(&*rust_i18n::locale(), rust_i18n::default_locale());
+/// ```
+pub fn reset_locale() {
+    set_locale(default_locale());
+}
+
+static RANDOM_SOURCE: spin::RwLock<fn() -> usize> = spin::RwLock::new(default_random);
+
+/// Set the source of random numbers used by `t!("key", random = true)`.
+///
+/// This is useful for getting a deterministic result in tests.
+///
+/// ```
+/// rust_i18n::set_random_source(|| 1);
+/// assert_eq!(rust_i18n::random(), 1);
+/// ```
+pub fn set_random_source(source: fn() -> usize) {
+    *RANDOM_SOURCE.write() = source;
+}
+
+/// Get a random number from the source set by [`set_random_source`].
+#[doc(hidden)]
+pub fn random() -> usize {
+    (RANDOM_SOURCE.read())()
+}
+
+#[cfg(feature = "std")]
+fn default_random() -> usize {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    // Each `RandomState` is seeded differently, so this is good enough for picking a list item.
+    RandomState::new().build_hasher().finish() as usize
+}
+
+#[cfg(not(feature = "std"))]
+fn default_random() -> usize {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    // There is no entropy source without `std`, a linear congruential generator is used instead.
+    static STATE: AtomicUsize = AtomicUsize::new(0x2545_f491);
+    let next = STATE
+        .load(Ordering::Relaxed)
+        .wrapping_mul(1_103_515_245)
+        .wrapping_add(12_345);
+    STATE.store(next, Ordering::Relaxed);
+    next >> 16
+}
+
+/// Convert a replaced value of [`replace_patterns`], e.g. format the numbers for the locale.
+pub type FormatValue<'f> = &'f dyn Fn(&str) -> Cow<'_, str>;
+
 /// Replace patterns and return a new string.
 ///
+/// The input is scanned once, the patterns in the replaced values are kept as is instead of being expanded again.
+///
 /// # Arguments
 ///
 /// * `input` - The input string, containing patterns like `%{name}`.
+/// * `open`, `close` - The delimiters of the patterns, e.g. `"%{"` and `"}"`.
 /// * `patterns` - The patterns to replace.
 /// * `values` - The values to replace.
+/// * `format` - Convert each replaced value, e.g. format the numbers for the locale, `None` to keep the values as is.
+///   The inline plurals are still selected by the raw values.
 ///
 /// # Example
 ///
@@ -40,55 +528,219 @@ pub fn locale() -> impl Deref<Target = str> {
 /// let input = "Hello, %{name}!";
 /// let patterns = &["name"];
 /// let values = &["world".to_string()];
-/// let output = replace_patterns(input, patterns, values);
+/// let output = replace_patterns(input, "%{", "}", patterns, values, None);
+/// assert_eq!(output, "Hello, world!");
+/// let output = replace_patterns("Hello, {{name}}!", "{{", "}}", patterns, values, None);
 /// assert_eq!(output, "Hello, world!");
 /// ```
-pub fn replace_patterns(input: &str, patterns: &[&str], values: &[String]) -> String {
-    let input_bytes = input.as_bytes();
-    let mut pattern_pos = smallvec::SmallVec::<[usize; 64]>::new();
-    let mut stage = 0;
-    for (i, &b) in input_bytes.iter().enumerate() {
-        match (stage, b) {
-            (1, b'{') => {
-                stage = 2;
-                pattern_pos.push(i);
-            }
-            (2, b'}') => {
-                stage = 0;
-                pattern_pos.push(i);
-            }
-            (_, b'%') => {
-                stage = 1;
+///
+/// The `%{name | singular | plural}` pattern selects the singular form if the value of `name` is `1`,
+/// otherwise the plural form:
+///
+/// ```
+/// # use rust_i18n::replace_patterns;
+/// let input = "You have %{count} %{count | item | items}";
+/// let output = replace_patterns(input, "%{", "}", &["count"], &["1".to_string()], None);
+/// assert_eq!(output, "You have 1 item");
+/// let output = replace_patterns(input, "%{", "}", &["count"], &["5".to_string()], None);
+/// assert_eq!(output, "You have 5 items");
+/// ```
+///
+/// The `%{name:default}` pattern is replaced by the default if there is no value of `name`:
+///
+/// ```
+/// # use rust_i18n::replace_patterns;
+/// let input = "Hello, %{name:guest}! It's 12:00.";
+/// assert_eq!(replace_patterns(input, "%{", "}", &[], &[], None), "Hello, guest! It's 12:00.");
+/// assert_eq!(
+///     replace_patterns(input, "%{", "}", &["name"], &["Jason".to_string()], None),
+///     "Hello, Jason! It's 12:00."
+/// );
+/// ```
+///
+/// The replaced values are converted by `format`:
+///
+/// ```
+/// # use rust_i18n::{bidi_isolate, replace_patterns};
+/// let input = "مرحبا %{name}";
+/// let output = replace_patterns(input, "%{", "}", &["name"], &["Jason".to_string()], Some(&|v| {
+///     bidi_isolate(v).into()
+/// }));
+/// assert_eq!(output, "مرحبا \u{2068}Jason\u{2069}");
+/// ```
+pub fn replace_patterns(
+    input: &str,
+    open: &str,
+    close: &str,
+    patterns: &[&str],
+    values: &[String],
+    format: Option<FormatValue<'_>>,
+) -> String {
+    let escape = interpolation_escape(open);
+    let lookup = |name: &str| {
+        patterns
+            .iter()
+            .zip(values.iter())
+            .find(|(&pattern, _)| pattern == name)
+            .map(|(_, value)| value.as_str())
+    };
+    let mut output = String::with_capacity(input.len() + 128);
+    let mut rest = input;
+    while let Some(start) = rest.find(open) {
+        let after_open = &rest[start + open.len()..];
+        // The escaped open delimiter is a literal, e.g. `%%{` => `%{`
+        if rest[..start].ends_with(escape) {
+            output.push_str(&rest[..start - escape.len()]);
+            output.push_str(open);
+            rest = after_open;
+            continue;
+        }
+        let Some(len) = after_open.find(close) else {
+            break;
+        };
+        let key = &after_open[..len];
+        output.push_str(&rest[..start]);
+        let default = rust_i18n_support::placeholder_default(key);
+        let value = lookup(key).or_else(|| default.and_then(|(name, _)| lookup(name)));
+        match (value, format) {
+            (Some(value), Some(format)) => output.push_str(&format(value)),
+            (Some(value), None) => output.push_str(value),
+            (None, _) => {
+                match select_plural(key, patterns, values).or(default.map(|(_, default)| default)) {
+                    Some(value) => output.push_str(value),
+                    // The unknown pattern is kept as is, e.g. `%{unknown}`
+                    None => output.push_str(&rest[start..start + open.len() + len + close.len()]),
+                }
             }
-            _ => {}
         }
+        rest = &after_open[len + close.len()..];
     }
-    let mut output: Vec<u8> = Vec::with_capacity(input_bytes.len() + 128);
-    let mut prev_end = 0;
-    let pattern_values = patterns.iter().zip(values.iter());
-    for pos in pattern_pos.chunks_exact(2) {
-        let start = pos[0];
-        let end = pos[1];
-        let key = &input_bytes[start + 1..end];
-        if prev_end < start {
-            let prev_chunk = &input_bytes[prev_end..start - 1];
-            output.extend_from_slice(prev_chunk);
+    output.push_str(rest);
+    output
+}
+
+/// Replace patterns and format the numeric values for the locale, e.g. `1234` => `1.234` in `de`.
+///
+/// This is the same as [`replace_patterns`] with [`format_number`] as the `format`,
+/// the inline plurals are still selected by the raw values.
+///
+/// # Example
+///
+/// ```
+/// # use rust_i18n::replace_patterns_localized;
+/// let input = "%{count} %{count | item | items}";
+/// let output = replace_patterns_localized("de", input, "%{", "}", &["count"], &["1234".to_string()]);
+/// assert_eq!(output, "1.234 items");
+/// ```
+pub fn replace_patterns_localized(
+    locale: &str,
+    input: &str,
+    open: &str,
+    close: &str,
+    patterns: &[&str],
+    values: &[String],
+) -> String {
+    replace_patterns(
+        input,
+        open,
+        close,
+        patterns,
+        values,
+        Some(&|v| format_number(locale, v).map_or_else(|| v.into(), Cow::Owned)),
+    )
+}
+
+/// Unescape the escaped open delimiters in the text without variables, e.g. `%%{` => `%{`.
+///
+/// # Example
+///
+/// ```
+/// # use rust_i18n::unescape_patterns;
+/// assert_eq!(unescape_patterns("Use %%{name} in %{lang}".into(), "%{"), "Use %{name} in %{lang}");
+/// assert_eq!(unescape_patterns(r"Use \{{name}}".into(), "{{"), "Use {{name}}");
+/// ```
+pub fn unescape_patterns<'a>(input: Cow<'a, str>, open: &str) -> Cow<'a, str> {
+    let escape = interpolation_escape(open);
+    if !input.contains(&[escape, open].concat()) {
+        return input;
+    }
+    input.replace(&[escape, open].concat(), open).into()
+}
+
+/// Serialize the translations into a flat JSON object, only the keys starting with `prefix` are included.
+///
+/// # Example
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// # use rust_i18n::translations_to_json;
+/// let trs = BTreeMap::from([("auth.title", "Sign \"in\""), ("home.title", "Home")]);
+/// assert_eq!(translations_to_json(trs, "auth."), r#"{"auth.title":"Sign \"in\""}"#);
+/// ```
+pub fn translations_to_json<'a>(
+    translations: impl IntoIterator<Item = (&'a str, &'a str)>,
+    prefix: &str,
+) -> String {
+    let mut output = String::from("{");
+    for (key, value) in translations {
+        if !key.starts_with(prefix) {
+            continue;
         }
-        if let Some((_, v)) = pattern_values
-            .clone()
-            .find(|(&pattern, _)| pattern.as_bytes() == key)
-        {
-            output.extend_from_slice(v.as_bytes());
-        } else {
-            output.extend_from_slice(&input_bytes[start - 1..end + 1]);
+        if output.len() > 1 {
+            output.push(',');
+        }
+        push_json_str(&mut output, key);
+        output.push(':');
+        push_json_str(&mut output, value);
+    }
+    output.push('}');
+    output
+}
+
+/// Push the string as a JSON string literal.
+fn push_json_str(output: &mut String, s: &str) {
+    output.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                output.push_str(&alloc::format!("\\u{:04x}", c as u32));
+            }
+            c => output.push(c),
         }
-        prev_end = end + 1;
     }
-    if prev_end < input_bytes.len() {
-        let remaining = &input_bytes[prev_end..];
-        output.extend_from_slice(remaining);
+    output.push('"');
+}
+
+/// Get the escape prefix of the open delimiter, `%` for `%{` (i.e. `%%{`), otherwise `\\`, e.g. `\\{{`.
+fn interpolation_escape(open: &str) -> &'static str {
+    if open.starts_with('%') {
+        "%"
+    } else {
+        "\\"
+    }
+}
+
+/// Select the singular or plural form of an inline plural pattern like `%{count | item | items}`.
+///
+/// Returns the singular form if the value of `count` is `1`, otherwise the plural form,
+/// returns `None` if the pattern is not an inline plural or the value is not numeric.
+fn select_plural<'a>(key: &'a str, patterns: &[&str], values: &[String]) -> Option<&'a str> {
+    let mut parts = key.split('|').map(str::trim);
+    let (name, singular, plural) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
     }
-    unsafe { String::from_utf8_unchecked(output) }
+    let (_, value) = patterns
+        .iter()
+        .zip(values.iter())
+        .find(|(&pattern, _)| pattern == name)?;
+    let value = value.trim().parse::<f64>().ok()?;
+    Some(if value == 1.0 { singular } else { plural })
 }
 
 /// Get I18n text
@@ -104,6 +756,15 @@ pub fn replace_patterns(input: &str, patterns: &[&str], values: &[String]) -> St
 ///   - Dynamic messages are also supported, such as `t!(format!("Hello, {}!", name))`.
 ///     However, if `minify_key` is enabled, the entire message will be hashed and used as a key for every lookup, which may consume more CPU cycles.
 /// * `locale` - The locale to use. If not specified, the current locale will be used.
+/// * `index` - Select an item by index if the translation is a list, the index wraps around the length of the list.
+/// * `random` - Select a random item if the translation is a list and the value is `true`, see [`set_random_source`].
+/// * `ordinal` - Select the ordinal plural form of the number by the CLDR rules of the locale, e.g. `place.one` for `1st`,
+///   falling back to `place.other`, see [`ordinal_category`]. The number is also available as the `%{ordinal}` variable.
+/// * `count` - Select the cardinal plural form of the number by the CLDR rules of the locale, e.g. `files.one` for `1`,
+///   falling back to `files.other`, see [`cardinal_category`]. Without the plural forms the key itself is used,
+///   so `count` can be only displayed as the `%{count}` variable.
+/// * `default` - The text to use if the translation is missing in the locale and all its fallback locales, instead of the text of `on_missing`,
+///   the variables are replaced in it too. It's also the untranslated text written by `cargo i18n`.
 /// * `args` - The arguments to be replaced in the translated text.
 ///    - These should be passed in the format `key = value` or `key => value`.
 ///    - Alternatively, you can specify the value format using the `key = value : {:format_specifier}` syntax.
@@ -137,6 +798,34 @@ pub fn replace_patterns(input: &str, patterns: &[&str], values: &[String]) -> St
 /// // With locale and variables
 /// t!("messages.hello", locale = "de", name = "Jason");
 /// // messages.hello: "Hallo, %{name}" => "Hallo, Jason"
+///
+/// // With a default for the variable that is not given by `%{name:default}`
+/// t!("greeting");
+/// // greeting: "Hello, %{name:guest}" => "Hello, guest"
+///
+/// // With a variable that is a key, it's translated in the same locale by `%{@name}`,
+/// // the `%{...}` in the translated value are never expanded again
+/// t!("status_line", status = "status.active");
+/// // status_line: "Status: %{@status}", status.active: "Active" => "Status: Active"
+///
+/// // Select an item from a list
+/// t!("tips", index = 1);
+/// // tips: ["Tip 1", "Tip 2"] => "Tip 2"
+/// t!("tips", random = true);
+/// // tips: ["Tip 1", "Tip 2"] => "Tip 1" or "Tip 2"
+///
+/// // Select the ordinal plural form
+/// t!("place", ordinal = 2);
+/// // place: { one: "%{ordinal}st place", two: "%{ordinal}nd place", few: "%{ordinal}rd place", other: "%{ordinal}th place" }
+/// // => "2nd place"
+///
+/// // Select the variant of an experiment, falls back to the key if the variant is missing
+/// t!("cta.title", variant = "variant_b");
+/// // cta.title#variant_b: "Join now" => "Join now"
+///
+/// // With an inline default for the missing key
+/// t!("messages.new", default = "Hello, %{name}!", name = "Jason");
+/// // => "Hello, Jason!"
 /// # }
 /// ```
 #[macro_export]
@@ -147,6 +836,142 @@ macro_rules! t {
     }
 }
 
+/// Get I18n text of a key in the namespace, the namespace and the key are joined by the `key-separator`
+///
+/// This is the same as [`t!`] with the joined key and supports the same arguments, e.g. `t_ns!(auth, "login.title")`
+/// is `t!("auth.login.title")`. The namespace is an identifier or a string literal, e.g. `"billing.invoice"`.
+///
+/// The key must be a string literal, it's checked at compile time if any key of the namespace is loaded by [`i18n!`],
+/// an unknown key in the namespace is a compile error, e.g. a typo of `login.titel`.
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+///
+/// # macro_rules! t_ns { ($($all:tt)*) => {} }
+/// # fn main() {
+/// t_ns!(auth, "login.title");
+/// // auth.login.title: "Sign in" => "Sign in"
+///
+/// t_ns!("billing.invoice", "total", amount = 42);
+/// // billing.invoice.total: "Total: %{amount}" => "Total: 42"
+/// # }
+/// ```
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! t_ns {
+    ($namespace:tt, $($all:tt)*) => {
+        crate::_rust_i18n_t!($($all)*, _namespace = $namespace)
+    }
+}
+
+/// Get I18n text as an owned `String`
+///
+/// This is the same as [`t!`] and supports the same arguments, but returns `String` instead of `Cow<str>`,
+/// it's useful where the ownership is required.
+///
+/// # Example
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+///
+/// # macro_rules! t_string { ($($all:tt)*) => { String::new() } }
+/// struct Notification {
+///     title: String,
+///     body: String,
+/// }
+///
+/// # fn main() {
+/// let notification = Notification {
+///     title: t_string!("notification.title"),
+///     body: t_string!("messages.hello", locale = "de", name = "Jason"),
+/// };
+/// let items: Vec<String> = vec![t_string!("items", count = 3)];
+/// # }
+/// ```
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! t_string {
+    ($($all:tt)*) => {
+        $crate::__private::String::from(crate::_rust_i18n_t!($($all)*))
+    }
+}
+
+/// Get I18n text rendered from Markdown to HTML, requires the `markdown` feature
+///
+/// The arguments are the same as [`t!`], but only the `name = value` variables are supported,
+/// and the `locale` must be the first if present. Each value is escaped by [`escape_markdown`] first,
+/// then it's interpolated into the translation, and the result is rendered by [`render_markdown`],
+/// so the values can't inject any HTML or Markdown, while the translation itself is trusted.
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+///
+/// # macro_rules! t_markdown { ($($all:tt)*) => { String::new() } }
+/// # fn main() {
+/// // messages.welcome: "Welcome, **%{name}**! Read the [guide](/guide)."
+/// let html = t_markdown!("messages.welcome", name = "<b>Jason</b>");
+/// // => "<p>Welcome, <strong>&lt;b&gt;Jason&lt;/b&gt;</strong>! Read the <a href=\"/guide\">guide</a>.</p>\n"
+/// let html = t_markdown!("messages.welcome", locale = "de", name = "Jason");
+/// # }
+/// ```
+#[cfg(feature = "markdown")]
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! t_markdown {
+    ($key:expr, locale = $locale:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::render_markdown(&crate::_rust_i18n_t!(
+            $key,
+            locale = $locale
+            $(, $name = $crate::escape_markdown(&$crate::__private::format!("{}", $value)))*
+        ))
+    };
+    ($key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::render_markdown(&crate::_rust_i18n_t!(
+            $key
+            $(, $name = $crate::escape_markdown(&$crate::__private::format!("{}", $value)))*
+        ))
+    };
+}
+
+/// Get I18n text to be inserted into HTML, with the variables escaped by [`escape_html`]
+///
+/// The arguments are the same as [`t!`], but only the `name = value` variables are supported,
+/// and the `locale` must be the first if present.
+///
+/// The translation is trusted, it's returned as is, including the HTML markup in it, e.g. `<b>%{name}</b>`.
+/// Only the values are untrusted, each of them is escaped before it's interpolated, so a value like
+/// `<script>` is rendered as plain text. A translation that is influenced by the users, e.g. from a
+/// database without review, must not be rendered by this macro.
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+///
+/// # macro_rules! t_html { ($($all:tt)*) => { String::new() } }
+/// # fn main() {
+/// // messages.welcome: "Welcome, <b>%{name}</b>!"
+/// let html = t_html!("messages.welcome", name = "<i>Jason</i>");
+/// // => "Welcome, <b>&lt;i&gt;Jason&lt;/i&gt;</b>!"
+/// let html = t_html!("messages.welcome", locale = "de", name = "Jason");
+/// # }
+/// ```
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! t_html {
+    ($key:expr, locale = $locale:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::__private::String::from(crate::_rust_i18n_t!(
+            $key,
+            locale = $locale
+            $(, $name = $crate::escape_html(&$crate::__private::format!("{}", $value)))*
+        ))
+    };
+    ($key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::__private::String::from(crate::_rust_i18n_t!(
+            $key
+            $(, $name = $crate::escape_html(&$crate::__private::format!("{}", $value)))*
+        ))
+    };
+}
+
 /// A macro that generates a translation key and corresponding value pair from a given input value.
 ///
 /// It's useful when you want to use a long string as a key, but you don't want to type it twice.
@@ -179,15 +1004,158 @@ macro_rules! tkv {
     };
 }
 
-/// Get available locales
+/// Get I18n texts of many keys at once, in the same order as the keys
+///
+/// This is faster than calling [`t!`] for each key, the keys are looked up in bulk by
+/// [`Backend::translate_many`], and only the missing keys are looked up by the fallback locales.
+/// Returns `None` for the missing keys, and the variables are not replaced.
+///
+/// ```
+/// # rust_i18n::i18n!("tests/locales", fallback = "en");
+/// # fn main() {
+/// let texts = rust_i18n::t_many!("zh-CN", &["hello", "custom.greeting", "missing.key"]);
+/// assert_eq!(texts[0].as_deref(), Some("Bar - 你好世界！"));
+/// // The missing keys are looked up by the fallback locales
+/// assert_eq!(texts[1].as_deref(), Some("Hello, %{name}!!!"));
+/// assert_eq!(texts[2], None);
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! t_many {
+    ($locale:expr, $keys:expr) => {
+        crate::_rust_i18n_try_translate_many($locale, $keys)
+    };
+}
+
+/// Get I18n text by the exact locale and key from the static table, requires `static_table = true` of [`i18n!`]
+///
+/// This is a binary search of the tables sorted at compile time, without allocation, hashing and dynamic dispatch,
+/// for the latency-sensitive code. It's a `const fn`, so it can be used in the constants too.
+/// Returns `None` for the missing keys, the variables are not replaced, and the backend,
+/// the fallback locales and the locale aliases are bypassed.
 ///
 /// ```no_run
 /// #[macro_use] extern crate rust_i18n;
-/// # pub fn _rust_i18n_available_locales() -> Vec<&'static str> { todo!() }
+/// # pub const fn _rust_i18n_t_static(locale: &str, key: &str) -> Option<&'static str> { None }
+/// # fn main() {
+/// let text = rust_i18n::t_static!("en", "hello");
+/// // => Some("Hello world")
+/// const TITLE: Option<&str> = rust_i18n::t_static!("en", "title");
+/// # }
+/// ```
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! t_static {
+    ($locale:expr, $key:expr) => {
+        crate::_rust_i18n_t_static($locale, $key)
+    };
+}
+
+/// Export all translations of the locale as a flat JSON object, e.g. to hydrate the i18n library of a frontend
+///
+/// The translations are loaded from the backend by [`Backend::translations`], pass a key prefix
+/// as the second argument to only export a subset of the keys.
+///
+/// ```
+/// # rust_i18n::i18n!("tests/locales", fallback = "en");
 /// # fn main() {
-/// rust_i18n::available_locales!();
+/// let json = rust_i18n::export_locale_json!("en");
+/// assert!(json.starts_with('{') && json.contains(r#""hello":"Bar - Hello, World!""#));
+/// let json = rust_i18n::export_locale_json!("en", "custom.greeting");
+/// assert_eq!(json, r#"{"custom.greeting":"Hello, %{name}!!!"}"#);
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! export_locale_json {
+    ($locale:expr) => {
+        crate::_rust_i18n_export_locale_json($locale, "")
+    };
+    ($locale:expr, $prefix:expr) => {
+        crate::_rust_i18n_export_locale_json($locale, $prefix)
+    };
+}
+
+/// Get the translation of the key in the locale, with the locale that it is resolved from
+///
+/// This is a diagnostic API, e.g. to show a "not translated" badge in the UI, the text is the same
+/// as `t!` without the interpolation. Returns a `(Cow<str>, ResolvedFrom)` tuple.
+///
+/// ```
+/// # rust_i18n::i18n!("tests/locales", fallback = "en");
+/// # fn main() {
+/// use rust_i18n::ResolvedFrom;
+///
+/// let (text, from) = rust_i18n::t_resolved!("zh-CN", "custom.greeting");
+/// assert_eq!(text, "Hello, %{name}!!!");
+/// assert_eq!(from, ResolvedFrom::Fallback("en".into()));
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! t_resolved {
+    ($locale:expr, $key:expr) => {
+        crate::_rust_i18n_t_resolved($locale, $key)
+    };
+}
+
+/// Get the missing keys of each locale at runtime, e.g. for a health check endpoint
+///
+/// The keys are compared with the union of the keys of all locales, or with the given locale.
+/// Returns a `BTreeMap<String, Vec<String>>` of the locale and its missing keys, sorted by the locale and key.
+///
+/// ```
+/// # rust_i18n::i18n!("tests/locales", fallback = "en");
+/// # fn main() {
+/// let missing = rust_i18n::missing_keys!("en");
+/// assert!(missing["en"].is_empty());
+/// assert!(missing["zh-CN"].contains(&"custom.greeting".to_string()));
+/// let missing = rust_i18n::missing_keys!();
+/// assert!(missing["en"].contains(&"fallback_to_cn".to_string()));
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! missing_keys {
+    () => {
+        crate::_rust_i18n_missing_keys(None)
+    };
+    ($locale:expr) => {
+        crate::_rust_i18n_missing_keys(Some($locale))
+    };
+}
+
+/// Iterate all `(locale, key, value)` entries of the translations at runtime, e.g. for an admin API or a diagnostics page
+///
+/// The entries are borrowed from the backend without copying, the order is unspecified,
+/// collect and sort them if a stable order is needed. The keys are without the `namespace` of [`i18n!`].
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+/// # pub fn _rust_i18n_all_entries() -> impl Iterator<Item = (&'static str, &'static str, &'static str)> { std::iter::empty() }
+/// # fn main() {
+/// for (locale, key, value) in rust_i18n::all_entries!() {
+///     println!("{}.{} = {}", locale, key, value);
+/// }
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! all_entries {
+    () => {
+        crate::_rust_i18n_all_entries()
+    };
+}
+
+/// Get available locales
+///
+/// ```
+/// # rust_i18n::i18n!("tests/locales", fallback = "en");
+/// # fn main() {
+/// let locales = rust_i18n::available_locales!();
+/// assert!(locales.contains(&"en") && locales.contains(&"zh-CN"));
 /// # }
-/// // => ["en", "zh-CN"]
 /// ```
 #[macro_export(local_inner_macros)]
 #[allow(clippy::crate_in_macro_def)]
@@ -197,9 +1165,221 @@ macro_rules! available_locales {
     };
 }
 
+/// Get the locales that have any translation of the keys starting with the prefix, sorted and deduplicated
+///
+/// e.g. to offer the languages of a product area, when the areas are translated into different languages.
+/// All keys are scanned by [`Backend::available_locales_for_prefix`], it's O(keys) unless the backend has an index.
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+/// # pub fn _rust_i18n_available_locales_for_prefix(prefix: &str) -> Vec<&'static str> { todo!() }
+/// # fn main() {
+/// rust_i18n::available_locales_for_prefix!("checkout.");
+/// // => ["de", "en"]
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! available_locales_for_prefix {
+    ($prefix:expr) => {
+        crate::_rust_i18n_available_locales_for_prefix($prefix)
+    };
+}
+
+/// Initialize the translations eagerly, e.g. at the startup of a server
+///
+/// The backend is loaded on the first [`t!`] by default, which delays the first request.
+/// It's safe to call more than once, the later calls do nothing.
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+/// # pub fn _rust_i18n_warm_up() { todo!() }
+/// # fn main() {
+/// rust_i18n::warm_up!();
+/// // Serve the traffic
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! warm_up {
+    () => {
+        crate::_rust_i18n_warm_up()
+    };
+}
+
+/// Get the locales that are looked up in order for the locale, e.g. to debug why a translation is resolved from a locale
+///
+/// The locale itself is the first after resolving its alias, then the less specific locales, then the `fallback` locales of [`i18n!`].
+/// With a key, the default locale of its namespace (`namespace_default_locales`) is also included before the `fallback` locales.
+/// The strict locales of `no_fallback_locales` have no other locales, and the pseudo-locale has the locales of the default locale.
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+/// # pub fn _rust_i18n_fallback_chain(locale: &str, key: Option<&str>) -> Vec<String> { todo!() }
+/// # fn main() {
+/// // i18n!("locales", fallback = ["en", "fr"]);
+/// rust_i18n::fallback_chain!("zh-Hant-CN-x-private");
+/// // => ["zh-Hant-CN-x-private", "zh-Hant-CN", "zh-Hant", "zh", "en", "fr"]
+///
+/// // i18n!("locales", fallback = "en", namespace_default_locales = ["vendor.payments=fr"]);
+/// rust_i18n::fallback_chain!("de-AT", "vendor.payments.title");
+/// // => ["de-AT", "de", "fr", "en"]
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! fallback_chain {
+    ($locale:expr) => {
+        crate::_rust_i18n_fallback_chain($locale, None)
+    };
+    ($locale:expr, $key:expr) => {
+        crate::_rust_i18n_fallback_chain($locale, Some($key))
+    };
+}
+
+/// Free the translations of the locales that are not looked up for the locale, default: the current locale, returns the pruned locales
+///
+/// It's for the `runtime_prune` option of [`i18n!`], e.g. to only keep one language in the memory of a per-market deployment.
+/// The locales of its fallback chain (see [`fallback_chain!`]) and the default locales of the key namespaces are kept.
+/// A pruned locale is loaded again from the static texts of the binary when it's looked up, so switching to it still works,
+/// call it again after switching to free the others. It's a no-op that returns nothing without `runtime_prune`.
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+/// # pub fn _rust_i18n_prune_locales(locale: &str) -> Vec<&'static str> { todo!() }
+/// # fn main() {
+/// // i18n!("locales", fallback = "en", runtime_prune = true);
+/// rust_i18n::set_locale("fr-CA");
+/// rust_i18n::prune_locales!();
+/// // => ["de", "ja", "zh-CN"], only "fr" and "en" are kept
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! prune_locales {
+    () => {
+        crate::_rust_i18n_prune_locales(&$crate::locale())
+    };
+    ($locale:expr) => {
+        crate::_rust_i18n_prune_locales($locale)
+    };
+}
+
+/// Check if the key is translated in exactly the locale, without the fallback locales, default: the current locale.
+///
+/// It's cheaper than [`t!`], no `Cow` is allocated and the fallback locales are not looked up.
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+/// # pub fn _rust_i18n_has_translation(locale: &str, key: &str) -> bool { todo!() }
+/// # fn main() {
+/// if rust_i18n::has_translation!("checkout.new_flow") {
+///     // Show the new flow once its texts are translated in the current locale
+/// }
+/// rust_i18n::has_translation!("zh-CN", "hello");
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! has_translation {
+    ($key:expr) => {
+        crate::_rust_i18n_has_translation(&$crate::locale(), $key)
+    };
+    ($locale:expr, $key:expr) => {
+        crate::_rust_i18n_has_translation($locale, $key)
+    };
+}
+
+/// Check if the translation of the key is changed since the base set by [`set_base_backend`],
+/// e.g. to highlight the changed texts in a preview build for the review.
+///
+/// The translations are resolved through the fallback chain of the locale in both, so a key that is only in the current
+/// translations is changed, and it's never changed without the base.
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+/// # pub fn _rust_i18n_changed_since_base(locale: &str, key: &str) -> bool { todo!() }
+/// # fn main() {
+/// if rust_i18n::changed_since_base!("checkout.title") {
+///     // Highlight the text in the current locale
+/// }
+/// rust_i18n::changed_since_base!("zh-CN", "hello");
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! changed_since_base {
+    ($key:expr) => {
+        crate::_rust_i18n_changed_since_base(&$crate::locale(), $key)
+    };
+    ($locale:expr, $key:expr) => {
+        crate::_rust_i18n_changed_since_base($locale, $key)
+    };
+}
+
+/// Pick the best available locale for an `Accept-Language` header, e.g. `fr-CH, fr;q=0.9, en;q=0.8`
+///
+/// The locale aliases are also matched, the default locale is picked for the wildcard `*`.
+/// Returns `None` if no locale matches, see [`negotiate_locale()`] for the matching rules.
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+/// # pub fn _rust_i18n_negotiate_locale(accept_language: &str) -> Option<&'static str> { todo!() }
+/// # fn main() {
+/// let locale = rust_i18n::negotiate_locale!("fr-CH, fr;q=0.9, en;q=0.8").unwrap_or("en");
+/// // => "fr"
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! negotiate_locale {
+    ($accept_language:expr) => {
+        crate::_rust_i18n_negotiate_locale($accept_language)
+    };
+}
+
+/// Check if the locale is available in the translations, the aliases of `i18n!` are resolved first
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+/// # pub fn _rust_i18n_is_locale_available(locale: &str) -> bool { todo!() }
+/// # fn main() {
+/// rust_i18n::is_locale_available!("zh-CN");
+/// # }
+/// // => true
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! is_locale_available {
+    ($locale:expr) => {
+        crate::_rust_i18n_is_locale_available($locale)
+    };
+}
+
+/// Set current locale only if it's available in the translations, otherwise returns [`UnknownLocale`]
+///
+/// ```no_run
+/// #[macro_use] extern crate rust_i18n;
+/// # pub fn _rust_i18n_set_locale_checked(locale: &str) -> Result<(), rust_i18n::UnknownLocale> { todo!() }
+/// # fn main() {
+/// rust_i18n::set_locale_checked!("zh-CN").unwrap();
+/// assert!(rust_i18n::set_locale_checked!("xx").is_err());
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! set_locale_checked {
+    ($locale:expr) => {
+        crate::_rust_i18n_set_locale_checked($locale)
+    };
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{locale, CURRENT_LOCALE};
+    use crate::{
+        default_locale, locale, replace_patterns, reset_locale, set_locale, translations_to_json,
+        CURRENT_LOCALE,
+    };
 
     fn assert_locale_type(s: &str, val: &str) {
         assert_eq!(s, val);
@@ -210,4 +1390,173 @@ mod tests {
         assert_locale_type(&locale(), &CURRENT_LOCALE.as_str());
         assert_eq!(&*locale(), "en");
     }
+
+    #[test]
+    fn test_replace_patterns_keeps_other_braces() {
+        let patterns = ["amount", "min"];
+        let values = ["5".to_string(), "1".to_string()];
+        // Only `%{...}` is substituted, the `format!`-style fragments are kept byte-for-byte
+        assert_eq!(
+            replace_patterns(
+                "Total: %{amount} (min %{min}) {:>10} {} {{x}} {amount}",
+                "%{",
+                "}",
+                &patterns,
+                &values,
+                None
+            ),
+            "Total: 5 (min 1) {:>10} {} {{x}} {amount}"
+        );
+        assert_eq!(
+            replace_patterns("50% off {amount}", "%{", "}", &patterns, &values, None),
+            "50% off {amount}"
+        );
+        assert_eq!(
+            replace_patterns(
+                "100% {:>10}, %{amount}%",
+                "%{",
+                "}",
+                &patterns,
+                &values,
+                None
+            ),
+            "100% {:>10}, 5%"
+        );
+        // The text after `:` is the default of the pattern, not a format spec
+        assert_eq!(
+            replace_patterns("%{:>10} %{amount:>4}", "%{", "}", &patterns, &values, None),
+            "%{:>10} 5"
+        );
+        assert_eq!(
+            replace_patterns(
+                "Total: %{amount} {:>10} 50% {min}",
+                "%{",
+                "}",
+                &patterns,
+                &values,
+                None
+            ),
+            "Total: 5 {:>10} 50% {min}"
+        );
+    }
+
+    #[test]
+    fn test_replace_patterns_with_escape() {
+        let patterns = ["name", "not_a_var"];
+        let values = ["Jason".to_string(), "x".to_string()];
+        assert_eq!(
+            replace_patterns(
+                "Use %%{name} for %{name}, 100%%{not_a_var}",
+                "%{",
+                "}",
+                &patterns,
+                &values,
+                None
+            ),
+            "Use %{name} for Jason, 100%{not_a_var}"
+        );
+        assert_eq!(
+            replace_patterns(
+                "%%{name}%{name}%%{name",
+                "%{",
+                "}",
+                &patterns,
+                &values,
+                None
+            ),
+            "%{name}Jason%{name"
+        );
+        assert_eq!(
+            replace_patterns("%{unknown} %%{", "%{", "}", &patterns, &values, None),
+            "%{unknown} %{"
+        );
+        assert_eq!(
+            replace_patterns(
+                "Use %%{name} for %{name}",
+                "%{",
+                "}",
+                &patterns,
+                &values,
+                None
+            ),
+            "Use %{name} for Jason"
+        );
+        assert_eq!(
+            replace_patterns(
+                r"Use \{{name}} for {{name}}",
+                "{{",
+                "}}",
+                &patterns,
+                &values,
+                None
+            ),
+            "Use {{name}} for Jason"
+        );
+    }
+
+    #[test]
+    fn test_replace_patterns_format() {
+        let patterns = ["n"];
+        let values = ["1234".to_string()];
+        let format: crate::FormatValue = &|v| alloc::format!("<{v}>").into();
+        // Only the values are converted, not the plural forms or the defaults
+        assert_eq!(
+            replace_patterns(
+                "{{n}} {{n | item | items}} {{x:0}} {{n:0}} 50%",
+                "{{",
+                "}}",
+                &patterns,
+                &values,
+                Some(format)
+            ),
+            "<1234> items 0 <1234> 50%"
+        );
+        assert_eq!(
+            replace_patterns("50% %{n}%", "%{", "}", &patterns, &values, Some(format)),
+            "50% <1234>%"
+        );
+    }
+
+    #[test]
+    fn test_replace_patterns_single_pass() {
+        // The replaced values are never expanded again, so a self-referential value can't loop or grow
+        let patterns = ["name", "other"];
+        let values = ["%{name}%{name}".to_string(), "%{name}".to_string()];
+        assert_eq!(
+            replace_patterns("Hi %{name}, %{other}", "%{", "}", &patterns, &values, None),
+            "Hi %{name}%{name}, %{name}"
+        );
+        assert_eq!(
+            replace_patterns("Hi {{name}}", "{{", "}}", &patterns, &values, None),
+            "Hi %{name}%{name}"
+        );
+        let values = ["{{name}}".to_string(), "x".to_string()];
+        assert_eq!(
+            replace_patterns("{{name}} {{name}}", "{{", "}}", &patterns, &values, None),
+            "{{name}} {{name}}"
+        );
+    }
+
+    #[test]
+    fn test_translations_to_json() {
+        let trs = [
+            ("a", "Tab\tand \"quote\"\n"),
+            ("b", "\\ \u{1}"),
+            ("c", "你好"),
+        ];
+        assert_eq!(
+            translations_to_json(trs, ""),
+            r#"{"a":"Tab\tand \"quote\"\n","b":"\\ \u0001","c":"你好"}"#
+        );
+        assert_eq!(translations_to_json(trs, "c"), r#"{"c":"你好"}"#);
+        assert_eq!(translations_to_json(trs, "d"), "{}");
+    }
+
+    #[test]
+    fn test_reset_locale() {
+        assert_eq!(default_locale(), "en");
+        set_locale("fr");
+        reset_locale();
+        assert_eq!(&*locale(), "en");
+    }
 }
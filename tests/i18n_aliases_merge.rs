@@ -0,0 +1,21 @@
+mod with_aliases {
+    rust_i18n::i18n!("./tests/locales", aliases = ["zh=zh-CN"]);
+}
+
+mod without_aliases {
+    rust_i18n::i18n!("./tests/locales");
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_aliases_of_many_backends() {
+        // The backend without aliases is initialized last, it doesn't clear the aliases of the other
+        crate::with_aliases::_rust_i18n_warm_up();
+        crate::without_aliases::_rust_i18n_warm_up();
+
+        rust_i18n::set_locale("zh");
+        assert_eq!(&*rust_i18n::locale(), "zh-CN");
+        rust_i18n::set_locale("en");
+    }
+}
@@ -0,0 +1,36 @@
+rust_i18n::i18n!("./tests/locales-plural");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::{changed_since_base, SimpleBackend};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_changed_since_base() {
+        // Nothing is changed without the base
+        assert!(!changed_since_base!("en", "files.one"));
+
+        let mut base = SimpleBackend::new();
+        base.add_translations(
+            "en",
+            &HashMap::from([
+                ("files.one", "One file"),
+                ("files.other", "%{count} files"),
+                ("uploaded", "Uploaded: %{count}"),
+            ]),
+        );
+        rust_i18n::set_base_backend(Box::new(base));
+
+        assert!(changed_since_base!("en", "files.one"));
+        assert!(!changed_since_base!("en", "files.other"));
+        assert!(!changed_since_base!("en", "uploaded"));
+        // Both are resolved through the fallback chain
+        assert!(!changed_since_base!("en-US", "uploaded"));
+        // The new translations are changed
+        assert!(changed_since_base!("ru", "uploaded"));
+        assert!(!changed_since_base!("en", "unknown"));
+
+        rust_i18n::set_locale("en");
+        assert!(changed_since_base!("files.one"));
+    }
+}
@@ -0,0 +1,21 @@
+rust_i18n::i18n!("./tests/locales", bidi_isolate = true);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_t_with_bidi_isolate() {
+        assert_eq!(
+            t!("messages.hello", locale = "en", name = "Jason"),
+            "Hello, \u{2068}Jason\u{2069}!"
+        );
+        // The inline plurals are selected by the raw values
+        assert_eq!(
+            t!("messages.inline_plural", locale = "en", count = 1),
+            "You have \u{2068}1\u{2069} item."
+        );
+        // No value, no isolates
+        assert_eq!(t!("hello", locale = "en"), "Bar - Hello, World!");
+    }
+}
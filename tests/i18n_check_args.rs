@@ -0,0 +1,29 @@
+rust_i18n::i18n!("./tests/locales", check_args = true);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_check_args() {
+        assert_eq!(
+            t!("messages.hello", locale = "en", name = "Jason"),
+            "Hello, Jason!"
+        );
+        assert_eq!(
+            t!("messages.inline_plural", locale = "en", count = 2),
+            "You have 2 items."
+        );
+        assert_eq!(t!("hello", locale = "en"), "Bar - Hello, World!");
+        // The placeholders with a default are optional
+        assert_eq!(
+            t!("messages.default_arg", locale = "en", time = "9:30"),
+            "Hello, guest! See you at 9:30: %{name:x} is kept."
+        );
+
+        // The dynamic keys and the missing keys are not checked
+        let key = "messages.hello";
+        assert_eq!(t!(key, locale = "en"), "Hello, %{name}!");
+        assert_eq!(t!("unknown", locale = "en", name = "Jason"), "unknown");
+    }
+}
@@ -0,0 +1,16 @@
+rust_i18n::i18n!("./tests/locales", check_max_len = true);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_check_max_len() {
+        // The `_meta` of a key is not loaded as a translation
+        assert_eq!(t!("t_29xGXAUPAkgvVzCf9ES3q8", locale = "de"), "Apfel");
+        assert_eq!(
+            t!("t_29xGXAUPAkgvVzCf9ES3q8._meta", locale = "de"),
+            "t_29xGXAUPAkgvVzCf9ES3q8._meta"
+        );
+    }
+}
@@ -0,0 +1,20 @@
+// `en` is split into 8 chunks of 128 keys, `fr` is under the chunk size
+rust_i18n::i18n!("./tests/locales-large", fallback = "en", chunk_size = 128);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::{t, Backend};
+
+    #[test]
+    fn test_chunk_size() {
+        for i in 0..1000 {
+            let key = format!("key_{}", i);
+            assert_eq!(t!(&key, locale = "en"), format!("Value {}", i));
+        }
+        assert_eq!(t!("key_2", locale = "fr"), "Valeur 2");
+        assert_eq!(t!("key_999", locale = "fr"), "Value 999");
+        assert_eq!(t!("key_1000", locale = "en"), "key_1000");
+
+        assert_eq!(crate::_RUST_I18N_BACKEND.translations("en").len(), 1000);
+    }
+}
@@ -0,0 +1,68 @@
+rust_i18n::i18n!("./tests/locales-required");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    fn render() -> String {
+        format!(
+            "{} {} {}",
+            t!("hello", locale = "de"),
+            t!("welcome", locale = "de"),
+            t!("missing.title", locale = "fr", name = "Jason"),
+        )
+    }
+
+    #[test]
+    fn test_collect_missing() {
+        let missing = rust_i18n::collect_missing(|| {
+            assert_eq!(render(), "Hallo welcome missing.title");
+        });
+        assert_eq!(
+            missing,
+            vec![
+                ("de".to_string(), "welcome".to_string()),
+                ("fr".to_string(), "missing.title".to_string())
+            ]
+        );
+
+        // Only the misses in the closure are collected
+        render();
+        assert!(rust_i18n::collect_missing(|| {
+            t!("hello", locale = "fr");
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn test_collect_missing_nested() {
+        let mut inner = vec![];
+        let outer = rust_i18n::collect_missing(|| {
+            t!("goodbye", locale = "de");
+            inner = rust_i18n::collect_missing(|| {
+                t!("welcome", locale = "de", default = "Willkommen");
+            });
+        });
+        assert_eq!(inner, vec![("de".to_string(), "welcome".to_string())]);
+        assert_eq!(
+            outer,
+            vec![
+                ("de".to_string(), "goodbye".to_string()),
+                ("de".to_string(), "welcome".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_missing_panic() {
+        let result = std::panic::catch_unwind(|| {
+            rust_i18n::collect_missing(|| {
+                t!("welcome", locale = "de");
+                panic!("render failed");
+            })
+        });
+        assert!(result.is_err());
+        // The collector is removed after the panic
+        assert!(rust_i18n::collect_missing(|| {}).is_empty());
+    }
+}
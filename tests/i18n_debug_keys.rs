@@ -0,0 +1,24 @@
+rust_i18n::i18n!("./tests/locales", fallback = "en");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_debug_show_keys() {
+        assert_eq!(t!("hello", locale = "en"), "Bar - Hello, World!");
+
+        rust_i18n::set_debug_show_keys(true);
+        assert_eq!(t!("hello", locale = "en"), "hello");
+        assert_eq!(t!("hello", locale = "zh-CN"), "hello");
+        assert_eq!(t!("messages.hello", name = "Jason"), "messages.hello");
+        assert_eq!(t!("missing.key", locale = "en"), "missing.key");
+        assert_eq!(
+            rust_i18n::t_many!("en", &["hello", "missing.key"]),
+            [Some("hello".into()), Some("missing.key".into())]
+        );
+
+        rust_i18n::set_debug_show_keys(false);
+        assert_eq!(t!("hello", locale = "en"), "Bar - Hello, World!");
+    }
+}
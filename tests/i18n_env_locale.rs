@@ -0,0 +1,17 @@
+// The variables are unset, so the config values are used
+rust_i18n::i18n!(
+    "./tests/locales",
+    default_locale = env("RUST_I18N_TEST_UNSET_DEFAULT_LOCALE"),
+    fallback = "zh-CN"
+);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_env_locale() {
+        assert_eq!(t!("hello"), "Bar - Hello, World!");
+        assert_eq!(rust_i18n::default_locale(), "en");
+    }
+}
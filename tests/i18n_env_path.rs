@@ -0,0 +1,16 @@
+// The environment variables of the path are replaced at compile time, e.g. `${OUT_DIR}` of `build.rs`
+rust_i18n::i18n!(
+    "${CARGO_MANIFEST_DIR}/tests/locales-variant",
+    fallback = "en"
+);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_env_path() {
+        assert_eq!(t!("cta.title", locale = "en"), "Sign up");
+        assert_eq!(t!("tips.1", locale = "en"), "Tip 2");
+    }
+}
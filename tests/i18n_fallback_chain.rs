@@ -0,0 +1,39 @@
+rust_i18n::i18n!(
+    "./tests/locales",
+    fallback = ["en", "zh"],
+    aliases = ["tw=zh-Hant-TW"],
+    no_fallback_locales = ["de"]
+);
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_fallback_chain() {
+        assert_eq!(rust_i18n::fallback_chain!("en"), ["en", "zh"]);
+        assert_eq!(
+            rust_i18n::fallback_chain!("fr-CA"),
+            ["fr-CA", "fr", "en", "zh"]
+        );
+        assert_eq!(
+            rust_i18n::fallback_chain!("zh-Hant-CN-x-private"),
+            ["zh-Hant-CN-x-private", "zh-Hant-CN", "zh-Hant", "zh", "en"]
+        );
+        assert_eq!(
+            rust_i18n::fallback_chain!("en-US-u-ca-gregory"),
+            ["en-US-u-ca-gregory", "en-US-u-ca", "en-US", "en", "zh"]
+        );
+
+        // The alias is resolved first
+        assert_eq!(
+            rust_i18n::fallback_chain!("TW"),
+            ["zh-Hant-TW", "zh-Hant", "zh", "en"]
+        );
+
+        // The strict locales never fall back
+        assert_eq!(rust_i18n::fallback_chain!("de"), ["de"]);
+        assert_eq!(
+            rust_i18n::fallback_chain!("de-AT", "hello"),
+            ["de-AT", "de", "en", "zh"]
+        );
+    }
+}
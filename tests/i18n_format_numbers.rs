@@ -0,0 +1,32 @@
+rust_i18n::i18n!("./tests/locales", format_numbers = true);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_t_with_format_numbers() {
+        assert_eq!(
+            t!("messages.inline_plural", locale = "en", count = 1234),
+            "You have 1,234 items."
+        );
+        assert_eq!(
+            t!("messages.inline_plural", locale = "en", count = 1),
+            "You have 1 item."
+        );
+        assert_eq!(
+            t!(
+                "Total: %{total}, %{name}",
+                locale = "de",
+                total = 1234.5,
+                name = "Jason"
+            ),
+            "Total: 1.234,5, Jason"
+        );
+        // The numbers with leading zeros are not formatted
+        assert_eq!(
+            t!("Serial: %{sn}", locale = "en", sn = 1234 : {:08}),
+            "Serial: 00001234"
+        );
+    }
+}
@@ -0,0 +1,14 @@
+rust_i18n::i18n!("./tests/locales-{wildcard,required}");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_glob_path() {
+        // Merged from `locales-required` and `locales-wildcard`
+        assert_eq!(t!("hello", locale = "fr"), "Bonjour");
+        assert_eq!(t!("error.not_found", locale = "fr"), "Introuvable.");
+        assert_eq!(t!("goodbye", locale = "en"), "Goodbye");
+    }
+}
@@ -0,0 +1,36 @@
+rust_i18n::i18n!("./tests/locales");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t_html;
+
+    #[test]
+    fn test_t_html() {
+        assert_eq!(t_html!("hello", locale = "en"), "Bar - Hello, World!");
+        // The translation is trusted, its markup is kept
+        assert_eq!(
+            t_html!("Welcome, <b>%{name}</b>!", name = "Jason"),
+            "Welcome, <b>Jason</b>!"
+        );
+    }
+
+    #[test]
+    fn test_t_html_escapes_values() {
+        assert_eq!(
+            t_html!(
+                "Hello, %{name}!",
+                locale = "en",
+                name = "<script>alert(1)</script>"
+            ),
+            "Hello, &lt;script&gt;alert(1)&lt;/script&gt;!"
+        );
+        assert_eq!(
+            t_html!(
+                "<a title=\"%{title}\">%{count}</a>",
+                title = "\" onclick=\"x",
+                count = 3
+            ),
+            "<a title=\"&quot; onclick=&quot;x\">3</a>"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+rust_i18n::i18n!("./tests/locales");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::{t, Interpolate};
+
+    #[derive(Interpolate)]
+    struct Message {
+        name: String,
+        msg: &'static str,
+    }
+
+    #[derive(Interpolate)]
+    struct Account<'a> {
+        #[interpolate(rename = "name")]
+        first_name: &'a str,
+        surname: &'a str,
+        id: u64,
+        email: &'a str,
+        city: &'a str,
+        zip: u32,
+        website: &'a str,
+        #[interpolate(skip)]
+        #[allow(dead_code)]
+        password: &'a str,
+    }
+
+    #[test]
+    fn test_interpolate_fields() {
+        let message = Message {
+            name: "Jason".into(),
+            msg: "Hi",
+        };
+        assert_eq!(
+            message.interpolate_fields(),
+            vec![("name", "Jason".to_string()), ("msg", "Hi".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let message = Message {
+            name: "Jason".into(),
+            msg: "Hi",
+        };
+        assert_eq!(
+            t!("a.very.nested.message", locale = "en", ..&message),
+            "Hello, Jason. Your message is: Hi"
+        );
+        // The arguments take precedence over the fields
+        assert_eq!(
+            t!(
+                "a.very.nested.message",
+                locale = "en",
+                name = "Lily",
+                ..message
+            ),
+            "Hello, Lily. Your message is: Hi"
+        );
+
+        let account = Account {
+            first_name: "Jason",
+            surname: "Lee",
+            id: 123,
+            email: "jason@example.com",
+            city: "Shanghai",
+            zip: 200000,
+            website: "https://example.com",
+            password: "secret",
+        };
+        assert_eq!(
+            t!("a.very.nested.response", locale = "en", ..account),
+            "Hello Jason Lee, your account id is 123, email address is jason@example.com. You live in Shanghai 200000. Your website is https://example.com."
+        );
+    }
+}
@@ -0,0 +1,48 @@
+rust_i18n::i18n!("./tests/locales", interpolation = "{{ }}");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_t_with_custom_interpolation() {
+        assert_eq!(
+            t!("messages.braces", locale = "en", name = "Jason", count = 1),
+            "Hello, Jason! You have 1 message."
+        );
+        assert_eq!(
+            t!("messages.braces", locale = "en", name = "Jason", count = 3 : {:02}),
+            "Hello, Jason! You have 03 messages."
+        );
+        // The default delimiters are not replaced
+        assert_eq!(
+            t!("messages.hello", locale = "en", name = "world"),
+            "Hello, %{name}!"
+        );
+        assert_eq!(t!("Hi, {{name}}", name = "world"), "Hi, world");
+    }
+
+    #[test]
+    fn test_t_with_escaped_custom_interpolation() {
+        assert_eq!(
+            t!("messages.braces_escaped", locale = "en", name = "Jason"),
+            "Use {{name}} to show Jason."
+        );
+        assert_eq!(
+            t!("messages.braces_escaped", locale = "en"),
+            "Use {{name}} to show {{name}}."
+        );
+    }
+
+    #[test]
+    fn test_t_with_default_custom_interpolation() {
+        assert_eq!(
+            t!("messages.braces_default", locale = "en"),
+            "Hello, guest!"
+        );
+        assert_eq!(
+            t!("messages.braces_default", locale = "en", name = "Jason"),
+            "Hello, Jason!"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+rust_i18n::i18n!("./tests/locales-key-placeholders", fallback = "en");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_key_placeholders() {
+        assert_eq!(
+            t!(
+                "status_line",
+                locale = "en",
+                status = "status.active",
+                date = "May 1"
+            ),
+            "Status: Active (since May 1)"
+        );
+        assert_eq!(
+            t!(
+                "status_line",
+                locale = "fr",
+                status = "status.active",
+                date = "1 mai"
+            ),
+            "Statut : Actif (depuis 1 mai)"
+        );
+        // The key is translated with the fallback locales and `on_missing`
+        assert_eq!(
+            t!(
+                "status_line",
+                locale = "fr",
+                status = "status.blocked",
+                date = "1 mai"
+            ),
+            "Statut : Blocked (depuis 1 mai)"
+        );
+        assert_eq!(
+            t!(
+                "status_line",
+                locale = "en",
+                status = "status.unknown",
+                date = "May 1"
+            ),
+            "Status: status.unknown (since May 1)"
+        );
+        // The translated value is never expanded again, even if the key refers to itself
+        assert_eq!(
+            t!(
+                "status_line",
+                locale = "en",
+                status = "status.self",
+                date = "May 1"
+            ),
+            "Status: Self: %{@status} %{date} (since May 1)"
+        );
+        assert_eq!(
+            t!(
+                "status.self",
+                locale = "en",
+                status = "status.self",
+                date = "May 1"
+            ),
+            "Self: Self: %{@status} %{date} May 1"
+        );
+        // The plain placeholders are not translated
+        assert_eq!(
+            t!("Status: %{status}", status = "status.active"),
+            "Status: status.active"
+        );
+    }
+}
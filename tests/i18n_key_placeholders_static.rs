@@ -0,0 +1,19 @@
+rust_i18n::i18n!("./tests/locales-key-placeholders", static_only = "fr");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_key_placeholders_static_only() {
+        // Initialize the translations
+        crate::_rust_i18n_available_locales();
+
+        // The key placeholders are translated at runtime, the others are inlined
+        assert_eq!(
+            t!("status_line", status = "status.active", date = "1 mai"),
+            "Statut : Actif (depuis 1 mai)"
+        );
+        assert_eq!(t!("status_line"), "Statut : %{@status} (depuis %{date})");
+    }
+}
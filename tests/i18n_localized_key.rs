@@ -0,0 +1,23 @@
+rust_i18n::i18n!("./tests/locales");
+
+#[cfg(test)]
+mod tests {
+    use super::LocalizedKey;
+
+    #[test]
+    fn test_localized_key() {
+        let key = LocalizedKey::from("hello");
+
+        rust_i18n::set_locale("en");
+        assert_eq!(
+            serde_json::to_string(&key).unwrap(),
+            r#""Bar - Hello, World!""#
+        );
+
+        rust_i18n::set_locale("zh-CN");
+        assert_eq!(
+            serde_json::to_string(&vec![key, LocalizedKey::from("missing")]).unwrap(),
+            r#"["Bar - 你好世界！","zh-CN.missing"]"#
+        );
+    }
+}
@@ -0,0 +1,37 @@
+rust_i18n::i18n!("./tests/locales");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t_markdown;
+
+    #[test]
+    fn test_t_markdown() {
+        assert_eq!(
+            t_markdown!("hello", locale = "en"),
+            "<p>Bar - Hello, World!</p>\n"
+        );
+        assert_eq!(
+            t_markdown!(
+                "Welcome, **%{name}**! Read the [guide](/guide).",
+                name = "Jason"
+            ),
+            "<p>Welcome, <strong>Jason</strong>! Read the <a href=\"/guide\">guide</a>.</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_t_markdown_escapes_values() {
+        assert_eq!(
+            t_markdown!(
+                "Hello, %{name}!",
+                locale = "en",
+                name = "<script>alert(1)</script>"
+            ),
+            "<p>Hello, &lt;script&gt;alert(1)&lt;/script&gt;!</p>\n"
+        );
+        assert_eq!(
+            t_markdown!("%{name} wrote", name = "# [x](javascript:alert(1))"),
+            "<p># [x](javascript:alert(1)) wrote</p>\n"
+        );
+    }
+}
@@ -0,0 +1,19 @@
+// The build fails if `fr` has less than all keys of `en`, or `de` less than a third of them
+rust_i18n::i18n!(
+    "./tests/locales-required",
+    fallback = "en",
+    min_coverage = ["fr=100", "de=33"]
+);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_min_coverage() {
+        assert_eq!(t!("welcome", locale = "fr"), "Bienvenue");
+        // `de` covers 1 of 3 keys
+        assert_eq!(t!("hello", locale = "de"), "Hallo");
+        assert_eq!(t!("welcome", locale = "de"), "Welcome");
+    }
+}
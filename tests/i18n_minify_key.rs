@@ -49,6 +49,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_try_translate_minified() {
+        let (key, msg) = tkv!("Bar - Hello, World!");
+        assert_eq!(
+            crate::_rust_i18n_try_translate_minified("zh-CN", key).as_deref(),
+            Some("Bar - 你好世界！")
+        );
+        // The key is not hashed again, so the message itself is not found
+        assert_eq!(crate::_rust_i18n_try_translate_minified("zh-CN", msg), None);
+    }
+
     #[test]
     fn test_tkv() {
         let (key, msg) = tkv!("");
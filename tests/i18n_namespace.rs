@@ -0,0 +1,30 @@
+rust_i18n::i18n!("./tests/locales-wildcard", namespace = "app");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_namespace() {
+        assert_eq!(t!("error.not_found", locale = "fr"), "Introuvable.");
+        assert_eq!(t!("error.unknown", locale = "en"), "Something went wrong.");
+        assert_eq!(t!("error.db.timeout", locale = "en"), "Database error.");
+
+        // The keys are stored with the namespace
+        let backend = crate::_rust_i18n_backend();
+        assert_eq!(
+            backend.translate("en", "app.error.not_found"),
+            Some("Not found.")
+        );
+        assert_eq!(backend.translate("en", "error.not_found"), None);
+
+        assert_eq!(
+            rust_i18n::t_many!("en", &["error.not_found", "app.error.not_found"]),
+            vec![Some("Not found.".into()), None]
+        );
+        assert_eq!(
+            rust_i18n::export_locale_json!("en", "error.auth"),
+            r#"{"error.auth.denied":"Access denied."}"#
+        );
+    }
+}
@@ -0,0 +1,44 @@
+// The `vendor` module is written in German, and its `payments` in French
+rust_i18n::i18n!(
+    "./tests/locales-namespaces",
+    fallback = "en",
+    namespace_default_locales = ["vendor=de", "vendor.payments=fr"],
+    no_fallback_locales = ["ja"]
+);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_namespace_default_locales() {
+        // The longest matching namespace wins
+        assert_eq!(t!("vendor.payments.title", locale = "en"), "Paiement");
+        assert_eq!(t!("vendor.payments.title", locale = "en-US"), "Paiement");
+        assert_eq!(t!("vendor.shipping.title", locale = "en"), "Versand");
+        assert_eq!(t!("vendor.shipping.title", locale = "fr"), "Versand");
+
+        // The translations of the locale and its less specific locales come first
+        assert_eq!(t!("vendor.payments.title", locale = "de"), "Zahlung");
+        assert_eq!(t!("vendor.payments.title", locale = "de-AT"), "Zahlung");
+
+        // The other keys fall back to the `fallback` locales
+        assert_eq!(t!("greeting", locale = "it"), "Hello");
+
+        // The strict locales never fall back
+        assert_eq!(
+            t!("vendor.shipping.title", locale = "ja"),
+            "vendor.shipping.title"
+        );
+
+        assert_eq!(
+            rust_i18n::fallback_chain!("de-AT", "vendor.payments.title"),
+            ["de-AT", "de", "fr", "en"]
+        );
+        assert_eq!(
+            rust_i18n::fallback_chain!("en-US", "vendor.payments.title"),
+            ["en-US", "en", "fr"]
+        );
+        assert_eq!(rust_i18n::fallback_chain!("de-AT"), ["de-AT", "de", "en"]);
+    }
+}
@@ -0,0 +1,23 @@
+rust_i18n::i18n!("./tests/locales-ordinal");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_t_with_ordinal() {
+        let places = [1, 2, 3, 4, 11, 12, 13, 21, 22, 101, 111]
+            .map(|n| t!("place", locale = "en", ordinal = n))
+            .join(", ");
+        assert_eq!(
+            places,
+            "1st place, 2nd place, 3rd place, 4th place, 11th place, 12th place, 13th place, \
+             21st place, 22nd place, 101st place, 111th place"
+        );
+
+        assert_eq!(t!("place", locale = "fr", ordinal = 1), "1re place");
+        assert_eq!(t!("place", locale = "fr", ordinal = 2), "2e place");
+        // The `other` form is missing
+        assert_eq!(t!("place", locale = "de", ordinal = 1), "place");
+    }
+}
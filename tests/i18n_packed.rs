@@ -0,0 +1,38 @@
+rust_i18n::i18n!("./tests/locales", fallback = "en", packed = true);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+    use rust_i18n_support::load_locales;
+
+    #[test]
+    fn test_packed() {
+        assert_eq!(t!("hello", locale = "en"), "Bar - Hello, World!");
+        assert_eq!(
+            t!("messages.hello", locale = "zh-CN", name = "Jason"),
+            "你好，Jason！"
+        );
+        // The fallback works as usual
+        assert_eq!(
+            t!("missing.default", locale = "zh-CN"),
+            "This is missing key fallbacked to en."
+        );
+    }
+
+    #[test]
+    fn test_packed_translations() {
+        // The same translations as the files
+        let data = load_locales("./tests/locales", |_| false);
+        let backend = crate::_rust_i18n_backend();
+        let mut locales = data.keys().map(String::as_str).collect::<Vec<_>>();
+        locales.sort();
+        assert_eq!(backend.available_locales(), locales);
+        for (locale, trs) in &data {
+            let packed = backend.translations(locale);
+            assert_eq!(packed.len(), trs.len(), "{}", locale);
+            for (key, value) in trs {
+                assert_eq!(packed.get(key.as_str()), Some(&value.as_str()));
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+rust_i18n::i18n!("./tests/locales-plural");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_t_with_count() {
+        assert_eq!(t!("files", locale = "en", count = 0), "0 files");
+        assert_eq!(t!("files", locale = "en", count = 1), "1 file");
+        assert_eq!(t!("files", locale = "en", count = 2), "2 files");
+
+        let files = [1, 2, 5, 11, 21, 22]
+            .map(|n| t!("files", locale = "ru", count = n))
+            .join(", ");
+        assert_eq!(
+            files,
+            "1 файл, 2 файла, 5 файлов, 11 файлов, 21 файл, 22 файла"
+        );
+
+        // The fractions and the non-numeric counts use the `other` form and the key as is
+        assert_eq!(t!("files", locale = "en", count = 1.5), "1.5 files");
+        assert_eq!(t!("files", locale = "en", count = "many"), "files");
+    }
+
+    #[test]
+    fn test_t_with_count_without_plural_forms() {
+        assert_eq!(t!("uploaded", locale = "en", count = 1), "Uploaded: 1");
+        assert_eq!(t!("uploaded", locale = "en", count = 3), "Uploaded: 3");
+        assert_eq!(t!("uploaded", locale = "ru", count = 5), "Загружено: 5");
+    }
+}
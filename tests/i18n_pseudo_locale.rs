@@ -0,0 +1,30 @@
+rust_i18n::i18n!("./tests/locales", fallback = "en", pseudo_locale = true);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_pseudo_locale() {
+        assert_eq!(
+            t!("messages.hello", locale = "en-XA", name = "Jason"),
+            "[!! Ĥéļļô, Jason! !]"
+        );
+        assert_eq!(
+            t!("messages.inline_plural", locale = "en-XA", count = 2),
+            "[!! Ýôû ĥàṽé 2 items. !!]"
+        );
+        assert_eq!(t!("hello", locale = "en"), "Bar - Hello, World!");
+        assert_eq!(t!("missing.key", locale = "en-XA"), "missing.key");
+
+        assert_eq!(rust_i18n::fallback_chain!("en-XA"), ["en"]);
+    }
+
+    #[test]
+    fn test_t_many_pseudo_locale() {
+        assert_eq!(
+            rust_i18n::t_many!("en-XA", &["hello", "missing.key"]),
+            [Some(t!("hello", locale = "en-XA")), None]
+        );
+    }
+}
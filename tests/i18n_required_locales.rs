@@ -0,0 +1,19 @@
+// The build fails if `fr` misses any key of `en`
+rust_i18n::i18n!(
+    "./tests/locales-required",
+    fallback = "en",
+    required_locales = ["fr"]
+);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_required_locales() {
+        assert_eq!(t!("welcome", locale = "fr"), "Bienvenue");
+        // The other locales can be partial
+        assert_eq!(t!("hello", locale = "de"), "Hallo");
+        assert_eq!(t!("welcome", locale = "de"), "Welcome");
+    }
+}
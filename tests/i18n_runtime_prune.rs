@@ -0,0 +1,34 @@
+rust_i18n::i18n!(
+    "./tests/locales-large",
+    fallback = "en",
+    max_inline_keys = 100,
+    runtime_prune = true
+);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_runtime_prune() {
+        let backend = crate::_rust_i18n_compiled_backend();
+        assert_eq!(backend.loaded_locales(), ["en", "fr"]);
+        let text = t!("key_2", locale = "fr");
+
+        // The fallback locale is kept
+        assert_eq!(rust_i18n::prune_locales!("fr-CA"), Vec::<&str>::new());
+        assert_eq!(rust_i18n::prune_locales!("en"), ["fr"]);
+        assert_eq!(backend.loaded_locales(), ["en"]);
+        assert_eq!(text, "Valeur 2");
+        assert_eq!(rust_i18n::available_locales!(), ["en", "fr"]);
+        assert_eq!(t!("key_999", locale = "en"), "Value 999");
+
+        // The pruned locale is loaded again on lookup
+        assert_eq!(t!("key_2", locale = "fr"), "Valeur 2");
+        assert_eq!(t!("key_999", locale = "fr"), "Value 999");
+        assert_eq!(backend.loaded_locales(), ["en", "fr"]);
+
+        rust_i18n::set_locale("en");
+        assert_eq!(rust_i18n::prune_locales!(), ["fr"]);
+    }
+}
@@ -0,0 +1,24 @@
+rust_i18n::i18n!("./tests/locales-smart-quotes", smart_quotes = true);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_smart_quotes() {
+        assert_eq!(t!("quoted", locale = "en"), "Click “Save” to keep it");
+        assert_eq!(t!("quoted", locale = "fr"), "Cliquez sur «Enregistrer»");
+        assert_eq!(t!("quoted", locale = "de"), "Klicken Sie auf „Speichern“");
+        assert_eq!(t!("quoted", locale = "ja"), "「保存」をクリック");
+        assert_eq!(
+            t!("apostrophe", locale = "en", name = "Jason"),
+            "Don’t forget Jason’s ‘draft’"
+        );
+
+        // The placeholders and the escaped placeholders are kept as is
+        assert_eq!(
+            t!("placeholder", locale = "en", name = "\"Jason\""),
+            "Hello, “\"Jason\"” %{not \"a\" var}"
+        );
+    }
+}
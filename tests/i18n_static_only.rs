@@ -0,0 +1,61 @@
+rust_i18n::i18n!("./tests/locales", static_only = "zh-CN");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_available_locales() {
+        assert_eq!(rust_i18n::available_locales!(), &["zh-CN"]);
+    }
+
+    #[test]
+    fn test_t() {
+        assert_eq!(t!("hello"), "Bar - 你好世界！");
+        assert_eq!(t!("messages.hello", name = "world"), "你好，world！");
+        assert_eq!(t!("messages.hello", name => "world"), "你好，world！");
+        assert_eq!(
+            t!("messages.other", count = 1 + 2 : {:03}),
+            "你收到了 003 条新消息。"
+        );
+        assert_eq!(t!("messages.other"), "你收到了 %{count} 条新消息。");
+        assert_eq!(t!("missing.key"), "missing.key");
+    }
+
+    #[test]
+    fn test_t_with_escaped_interpolation() {
+        assert_eq!(
+            t!("messages.escaped", name = "Jason"),
+            "使用 %{name} 显示 Jason，%{not_a_var} 保持不变。"
+        );
+        assert_eq!(
+            t!("messages.escaped"),
+            "使用 %{name} 显示 %{name}，%{not_a_var} 保持不变。"
+        );
+    }
+
+    #[test]
+    fn test_t_with_default_interpolation() {
+        assert_eq!(t!("messages.default_arg"), "你好，客人！");
+        assert_eq!(t!("messages.default_arg", name = "Jason"), "你好，Jason！");
+    }
+
+    #[test]
+    fn test_reset_locale() {
+        // Initialize the translations
+        crate::_rust_i18n_available_locales();
+
+        assert_eq!(rust_i18n::default_locale(), "zh-CN");
+        rust_i18n::set_locale("en");
+        rust_i18n::reset_locale();
+        assert_eq!(&*rust_i18n::locale(), "zh-CN");
+    }
+
+    #[test]
+    fn test_t_with_runtime_lookup() {
+        let key = "hello";
+        assert_eq!(t!(key), "Bar - 你好世界！");
+        assert_eq!(t!("hello", locale = "zh-CN"), "Bar - 你好世界！");
+        assert_eq!(t!("hello", locale = "en"), "hello");
+    }
+}
@@ -0,0 +1,29 @@
+rust_i18n::i18n!("./tests/locales", static_table = true);
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    const HELLO: Option<&str> = rust_i18n::t_static!("en", "hello");
+
+    #[test]
+    fn test_t_static() {
+        assert_eq!(HELLO, Some("Bar - Hello, World!"));
+        assert_eq!(
+            rust_i18n::t_static!("zh-CN", "messages.hello"),
+            Some("你好，%{name}！")
+        );
+        // The same translations as the backend
+        for (locale, key) in [("en", "a.very.nested.message"), ("zh-CN", "hello")] {
+            assert_eq!(
+                rust_i18n::t_static!(locale, key),
+                Some(t!(key, locale = locale).as_ref())
+            );
+        }
+
+        // No fallback, no aliases
+        assert_eq!(rust_i18n::t_static!("en", "missing"), None);
+        assert_eq!(rust_i18n::t_static!("zh-TW", "hello"), None);
+        assert_eq!(rust_i18n::t_static!("unknown", "hello"), None);
+    }
+}
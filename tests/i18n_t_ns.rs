@@ -0,0 +1,32 @@
+rust_i18n::i18n!("./tests/locales");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t_ns;
+
+    #[test]
+    fn test_t_ns() {
+        assert_eq!(
+            t_ns!(messages, "hello", locale = "en", name = "Jason"),
+            "Hello, Jason!"
+        );
+        assert_eq!(
+            t_ns!(
+                "a.very.nested",
+                "message",
+                locale = "en",
+                name = "Jason",
+                msg = "Hi"
+            ),
+            "Hello, Jason. Your message is: Hi"
+        );
+        // The plural forms of the key
+        assert_eq!(
+            t_ns!(messages, "inline_plural", locale = "en", count = 2),
+            "You have 2 items."
+        );
+
+        // The namespace without any key loaded at compile time is not checked
+        assert_eq!(t_ns!(runtime, "title", locale = "en"), "runtime.title");
+    }
+}
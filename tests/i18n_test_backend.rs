@@ -0,0 +1,21 @@
+rust_i18n::i18n!("./tests/locales");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::{t, SimpleBackend};
+
+    #[test]
+    fn test_set_backend_for_tests() {
+        assert_eq!(t!("hello", locale = "en"), "Bar - Hello, World!");
+
+        let mut backend = SimpleBackend::new();
+        backend.add_translations("en", &[("hello", "Mocked")].into_iter().collect());
+        rust_i18n::set_backend_for_tests(Box::new(backend));
+        assert_eq!(t!("hello", locale = "en"), "Mocked");
+        assert_eq!(t!("messages.zero", locale = "en"), "messages.zero");
+        assert_eq!(rust_i18n::available_locales!(), vec!["en"]);
+
+        rust_i18n::reset_backend_for_tests();
+        assert_eq!(t!("hello", locale = "en"), "Bar - Hello, World!");
+    }
+}
@@ -0,0 +1,29 @@
+rust_i18n::i18n!("./tests/locales", transform = trailing_colon);
+
+/// Append a colon to the labels of `en`, the other translations are kept as is
+fn trailing_colon<'a>(locale: &str, key: &str, value: &'a str) -> std::borrow::Cow<'a, str> {
+    if locale == "en" && key.starts_with("messages.") && !value.ends_with(':') {
+        Cow::Owned(format!("{}:", value))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_transform() {
+        // The placeholders are kept for the interpolation
+        assert_eq!(
+            t!("messages.hello", locale = "en", name = "Jason"),
+            "Hello, Jason!:"
+        );
+        assert_eq!(
+            t!("messages.hello", locale = "zh-CN", name = "Jason"),
+            "你好，Jason！"
+        );
+        assert_eq!(t!("hello", locale = "en"), "Bar - Hello, World!");
+    }
+}
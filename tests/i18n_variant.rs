@@ -0,0 +1,52 @@
+rust_i18n::i18n!("./tests/locales-variant", fallback = "en");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_variant() {
+        assert_eq!(t!("cta.title", locale = "en"), "Sign up");
+        assert_eq!(
+            t!(
+                "cta.title",
+                locale = "en",
+                variant = "variant_b",
+                name = "Jason"
+            ),
+            "Join now, Jason"
+        );
+
+        // The missing variant falls back to the key
+        assert_eq!(
+            t!("cta.title", locale = "en", variant = "variant_c"),
+            "Sign up"
+        );
+        assert_eq!(t!("cta.title", locale = "en", variant = ""), "Sign up");
+
+        // The variant is looked up in the fallback locales like the key
+        assert_eq!(
+            t!(
+                "cta.title",
+                locale = "fr",
+                variant = "variant_b",
+                name = "Jason"
+            ),
+            "Join now, Jason"
+        );
+        assert_eq!(
+            t!("cta.title", locale = "fr", variant = "variant_c"),
+            "Inscription"
+        );
+
+        let variant = String::from("variant_b");
+        assert_eq!(
+            t!("tips", locale = "en", variant = variant, index = 1),
+            "Hint 2"
+        );
+        assert_eq!(
+            t!("tips", locale = "en", variant = "variant_c", index = 1),
+            "Tip 2"
+        );
+    }
+}
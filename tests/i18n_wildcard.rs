@@ -0,0 +1,66 @@
+rust_i18n::i18n!("./tests/locales-wildcard", fallback = "en");
+
+#[cfg(test)]
+mod tests {
+    use rust_i18n::t;
+
+    #[test]
+    fn test_wildcard_keys() {
+        // The exact key
+        assert_eq!(t!("error.not_found", locale = "fr"), "Introuvable.");
+        // The wildcard key of the locale, before the fallback locale
+        assert_eq!(
+            t!("error.timeout", locale = "fr"),
+            "Une erreur est survenue."
+        );
+        assert_eq!(
+            t!("error.auth.denied", locale = "fr"),
+            "Une erreur est survenue."
+        );
+        // The nearest wildcard key
+        assert_eq!(t!("error.db.timeout", locale = "en"), "Database error.");
+        assert_eq!(
+            t!("error.db.timeout", locale = "fr"),
+            "Une erreur est survenue."
+        );
+        // The wildcard key of the fallback locale
+        assert_eq!(t!("error.timeout", locale = "de"), "Something went wrong.");
+        assert_eq!(
+            crate::_rust_i18n_try_translate("en", "warning.timeout"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_t_many_wildcard_keys() {
+        // The same as `t!` of each key
+        let keys = [
+            "error.not_found",
+            "error.db.timeout",
+            "error.timeout",
+            "warning.timeout",
+        ];
+        let expected = keys
+            .iter()
+            .map(|key| crate::_rust_i18n_try_translate("fr", key))
+            .collect::<Vec<_>>();
+        assert_eq!(rust_i18n::t_many!("fr", &keys), expected);
+        assert_eq!(
+            rust_i18n::t_many!("de", &keys),
+            [
+                Some("Not found.".into()),
+                Some("Database error.".into()),
+                Some("Something went wrong.".into()),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wildcard_keys_with_list() {
+        assert_eq!(
+            t!("error.hints", locale = "en", index = 3),
+            "Contact the support."
+        );
+    }
+}
@@ -88,6 +88,218 @@ mod tests {
         rust_i18n::i18n!();
     }
 
+    mod test6 {
+        rust_i18n::i18n!("./tests/locales", on_missing = "humanize");
+
+        #[test]
+        fn test_on_missing_humanize() {
+            assert_eq!(
+                crate::tests::test6::_rust_i18n_translate(
+                    "en",
+                    "settings.notifications.email_frequency"
+                ),
+                "Email frequency"
+            );
+            assert_eq!(
+                crate::tests::test6::_rust_i18n_translate("en", "hello"),
+                "Bar - Hello, World!"
+            );
+            assert_eq!(
+                crate::tests::test6::_rust_i18n_translate("en", "Hello, world!"),
+                "Hello, world!"
+            );
+        }
+    }
+
+    mod test8 {
+        rust_i18n::i18n!("./tests/locales", include = ["zh-CN"], fallback = "en");
+
+        #[test]
+        fn test_include() {
+            let mut locales = crate::tests::test8::_rust_i18n_available_locales();
+            locales.sort();
+            assert_eq!(locales, vec!["en", "zh-CN"]);
+            assert_eq!(
+                crate::tests::test8::_rust_i18n_translate("zh-CN", "hello"),
+                "Bar - 你好世界！"
+            );
+            // The excluded locale falls through to the fallback
+            assert_eq!(
+                crate::tests::test8::_rust_i18n_translate("zh", "messages.zero"),
+                "You have no messages."
+            );
+        }
+    }
+
+    mod test9 {
+        rust_i18n::i18n!("./tests/locales", forbid_empty = true);
+
+        #[test]
+        fn test_forbid_empty() {
+            assert_eq!(
+                crate::tests::test9::_rust_i18n_translate("zh-CN", "hello"),
+                "Bar - 你好世界！"
+            );
+        }
+    }
+
+    mod test10 {
+        rust_i18n::i18n!("./tests/locales", generate_keys = true);
+
+        #[test]
+        fn test_generate_keys() {
+            assert_eq!(keys::HELLO, "hello");
+            assert_eq!(keys::MESSAGES_HELLO, "messages.hello");
+            assert_eq!(keys::LOREM_IPSUM, "lorem-ipsum");
+            assert_eq!(keys::TIPS_0, "tips.0");
+            assert_eq!(
+                crate::tests::test10::_rust_i18n_translate("en", keys::HELLO),
+                "Bar - Hello, World!"
+            );
+        }
+    }
+
+    mod test11 {
+        rust_i18n::i18n!("./tests/locales", overlay = "staging");
+
+        #[test]
+        fn test_overlay() {
+            assert_eq!(
+                crate::tests::test11::_rust_i18n_translate("en", "hello"),
+                "STAGING - Hello, World!"
+            );
+            // The keys not in the overlay are kept
+            assert_eq!(
+                crate::tests::test11::_rust_i18n_translate("en", "messages.hello"),
+                "Hello, %{name}!"
+            );
+            assert_eq!(
+                crate::tests::test11::_rust_i18n_translate("zh-CN", "hello"),
+                "Bar - 你好世界！"
+            );
+        }
+    }
+
+    mod test12 {
+        rust_i18n::i18n!("./tests/locales", aliases = ["zh=zh-CN", "no=nb"]);
+
+        #[test]
+        fn test_aliases() {
+            // The alias is replaced before lookup, `zh.yml` has no `hello`
+            assert_eq!(
+                crate::tests::test12::_rust_i18n_translate("zh", "hello"),
+                "Bar - 你好世界！"
+            );
+            assert_eq!(
+                crate::tests::test12::_rust_i18n_translate("ZH", "hello"),
+                "Bar - 你好世界！"
+            );
+            // Then the fallback is applied to the preferred locale, i.e. `zh-CN` -> `zh`
+            assert_eq!(
+                crate::tests::test12::_rust_i18n_translate("zh", "messages.zero"),
+                "你没有消息。"
+            );
+            assert_eq!(crate::tests::test12::_rust_i18n_resolve_alias("no"), "nb");
+            assert_eq!(
+                crate::tests::test12::_rust_i18n_resolve_alias("zh-TW"),
+                "zh-TW"
+            );
+        }
+
+        #[test]
+        fn test_is_locale_available_with_aliases() {
+            assert!(crate::tests::test12::_rust_i18n_is_locale_available("zh"));
+            assert!(crate::tests::test12::_rust_i18n_is_locale_available("ZH"));
+            assert!(crate::tests::test12::_rust_i18n_is_locale_available(
+                "zh-CN"
+            ));
+            assert!(!crate::tests::test12::_rust_i18n_is_locale_available("xx"));
+        }
+
+        #[test]
+        fn test_negotiate_locale_with_aliases() {
+            assert_eq!(
+                crate::tests::test12::_rust_i18n_negotiate_locale("ZH, en;q=0.5"),
+                Some("zh-CN")
+            );
+        }
+    }
+
+    mod test13 {
+        rust_i18n::i18n!("./tests/locales", key_separator = "/");
+
+        #[test]
+        fn test_key_separator() {
+            assert_eq!(
+                crate::tests::test13::_rust_i18n_translate("en", "messages/zero"),
+                "You have no messages."
+            );
+            assert_eq!(
+                crate::tests::test13::_rust_i18n_try_translate("en", "messages.zero"),
+                None
+            );
+            assert_eq!(
+                crate::tests::test13::_rust_i18n_select_key("en", "tips", Some(4)),
+                "tips/1"
+            );
+        }
+    }
+
+    mod test14 {
+        rust_i18n::i18n!(
+            "./tests/locales",
+            fallback = "en",
+            no_fallback_locales = ["zh-CN"]
+        );
+
+        #[test]
+        fn test_no_fallback_locales() {
+            assert_eq!(
+                crate::tests::test14::_rust_i18n_try_translate("zh-CN", "missing.default"),
+                None
+            );
+            assert_eq!(
+                crate::tests::test14::_rust_i18n_try_translate("zh-cn", "missing.default"),
+                None
+            );
+            assert_eq!(
+                crate::tests::test14::_rust_i18n_translate("zh-CN", "hello"),
+                "Bar - 你好世界！"
+            );
+            // The other locales still fall back
+            assert_eq!(
+                crate::tests::test14::_rust_i18n_translate("fr", "missing.default"),
+                "This is missing key fallbacked to en."
+            );
+        }
+    }
+
+    mod test7 {
+        rust_i18n::i18n!(["./tests/locales", "./tests/locales-override"]);
+
+        #[test]
+        fn test_multiple_load_paths() {
+            // The later path overrides the earlier
+            assert_eq!(
+                crate::tests::test7::_rust_i18n_translate("en", "hello"),
+                "Override - Hello, World!"
+            );
+            assert_eq!(
+                crate::tests::test7::_rust_i18n_translate("en", "messages.hello"),
+                "Hi, %{name}!"
+            );
+            // The keys only in the earlier path are kept
+            assert_eq!(
+                crate::tests::test7::_rust_i18n_translate("en", "custom.greeting"),
+                "Hello, %{name}!!!"
+            );
+            assert_eq!(
+                crate::tests::test7::_rust_i18n_translate("zh-CN", "hello"),
+                "Bar - 你好世界！"
+            );
+        }
+    }
+
     #[test]
     fn check_test_environment() {
         assert_eq!(
@@ -120,6 +332,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_available_locales_for_prefix() {
+        assert_eq!(
+            rust_i18n::available_locales_for_prefix!("missing."),
+            &["en", "zh"]
+        );
+        assert_eq!(
+            rust_i18n::available_locales_for_prefix!("messages.hello"),
+            &["en", "zh-CN"]
+        );
+        assert!(rust_i18n::available_locales_for_prefix!("not-found.").is_empty());
+    }
+
     #[test]
     fn test_t() {
         rust_i18n::set_locale("en");
@@ -203,6 +428,126 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_t_with_list() {
+        rust_i18n::set_locale("en");
+
+        assert_eq!(t!("tips", index = 0), "Tip 1");
+        assert_eq!(t!("tips", index = 2), "Tip 3");
+        // Out-of-range index wraps around
+        assert_eq!(t!("tips", index = 4), "Tip 2");
+        let i = 5;
+        assert_eq!(t!("tips", index = i), "Tip 3");
+        assert_eq!(t!("tips.1"), "Tip 2");
+        assert_eq!(t!("tips", locale = "zh-CN", index = 1), "Tip 2");
+
+        rust_i18n::set_random_source(|| 7);
+        assert_eq!(t!("tips", random = true), "Tip 2");
+        assert_eq!(t!("tips", random = false, index = 0), "Tip 1");
+
+        // Not a list
+        assert_eq!(t!("hello", index = 1), "Bar - Hello, World!");
+    }
+
+    #[test]
+    fn test_t_with_default() {
+        rust_i18n::set_locale("en");
+
+        assert_eq!(t!("hello", default = "Default"), "Bar - Hello, World!");
+        assert_eq!(t!("missing.key", default = "Default"), "Default");
+        // The fallback locales are tried before the default
+        assert_eq!(
+            t!("missing.default", locale = "zh-CN", default = "Default"),
+            "This is missing key fallbacked to en."
+        );
+        // The default is unescaped and its patterns with a default are replaced, the same as a translation
+        assert_eq!(t!("missing", default = "100%%{x}"), "100%{x}");
+        assert_eq!(
+            t!("missing", default = "Hello, %{name:guest}!"),
+            "Hello, guest!"
+        );
+        let default = String::from("Owned default");
+        assert_eq!(t!("missing.key", default = default), "Owned default");
+        // The variables are replaced in the default too
+        assert_eq!(
+            t!("missing.key", default = "Hello, %{name}!", name = "Jason"),
+            "Hello, Jason!"
+        );
+        assert_eq!(
+            t!(
+                "messages.hello",
+                locale = "zh-CN",
+                default = "Hello, %{name}!",
+                name = "Jason"
+            ),
+            "你好，Jason！"
+        );
+    }
+
+    #[test]
+    fn test_with_key_prefix() {
+        rust_i18n::set_locale("en");
+
+        rust_i18n::with_key_prefix!("a.very", {
+            assert_eq!(
+                t!("nested.message", name = "Jason"),
+                "Hello, Jason. Your message is: %{msg}"
+            );
+            rust_i18n::with_key_prefix!("nested", {
+                assert_eq!(
+                    t!("message", name = "Jason", msg = "Bla bla"),
+                    "Hello, Jason. Your message is: Bla bla"
+                );
+            });
+            // The keys that are not literals are not prefixed
+            let key = "hello";
+            assert_eq!(t!(key), "Bar - Hello, World!");
+        });
+
+        let text = rust_i18n::with_key_prefix!("messages", {
+            t!("hello", locale = "zh-CN", name = "world")
+        });
+        assert_eq!(text, "你好，world！");
+    }
+
+    #[test]
+    fn test_t_with_inline_plural() {
+        rust_i18n::set_locale("en");
+
+        assert_eq!(t!("messages.inline_plural", count = 1), "You have 1 item.");
+        assert_eq!(t!("messages.inline_plural", count = 0), "You have 0 items.");
+        assert_eq!(
+            t!("messages.inline_plural", count = 1.5),
+            "You have 1.5 items."
+        );
+        assert_eq!(
+            t!("messages.inline_plural", count = 1 : {:02}),
+            "You have 01 item."
+        );
+        // Non-numeric value leaves the pattern untouched
+        assert_eq!(
+            t!("messages.inline_plural", count = "one"),
+            "You have one %{count | item | items}."
+        );
+        assert_eq!(
+            t!("You have %{n} %{n | file | files}", n = 1),
+            "You have 1 file"
+        );
+    }
+
+    #[test]
+    fn test_t_string() {
+        rust_i18n::set_locale("en");
+
+        let hello: String = rust_i18n::t_string!("hello");
+        assert_eq!(hello, "Bar - Hello, World!");
+        let message: String =
+            rust_i18n::t_string!("messages.hello", locale = "zh-CN", name = "Jason");
+        assert_eq!(message, "你好，Jason！");
+        let items: String = rust_i18n::t_string!("messages.inline_plural", count = 2);
+        assert_eq!(items, "You have 2 items.");
+    }
+
     #[test]
     fn test_with_merge_file() {
         rust_i18n::set_locale("en");
@@ -236,6 +581,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_t_with_any_expr_args() {
+        struct User {
+            name: String,
+        }
+        fn compute() -> String {
+            "Jason".into()
+        }
+
+        let x = String::from("Bla bla");
+        let user = User {
+            name: " Jason Lee ".into(),
+        };
+        let names = ["Jason"];
+
+        // Blocks, references, method chains and the trailing comma
+        assert_eq!(
+            t!(
+                "a.very.nested.message",
+                locale = "en",
+                name = { compute() },
+                msg = &x,
+            ),
+            "Hello, Jason. Your message is: Bla bla"
+        );
+        assert_eq!(
+            t!(
+                "a.very.nested.message",
+                locale = "en",
+                name = user.name.trim().to_uppercase(),
+                msg = names[0],
+            ),
+            "Hello, JASON LEE. Your message is: Jason"
+        );
+        assert_eq!(
+            t!(
+                "a.very.nested.message",
+                locale = "en",
+                name = if x.is_empty() { "a" } else { "b" },
+                msg = match names.len() {
+                    1 => "one",
+                    _ => "other",
+                },
+            ),
+            "Hello, b. Your message is: one"
+        );
+        assert_eq!(
+            t!("messages.other", locale = "en", count = x.len() : {:03},),
+            "You have 007 messages."
+        );
+        assert_eq!(t!("hello", locale = "en",), "Bar - Hello, World!");
+    }
+
     #[test]
     fn test_fallback_missing_locale() {
         assert_eq!(
@@ -304,6 +702,235 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_has_translation() {
+        assert!(rust_i18n::has_translation!("en", "hello"));
+        assert!(rust_i18n::has_translation!("zh-CN", "messages.hello"));
+        assert!(!rust_i18n::has_translation!("en", "missing"));
+        // The fallback locales are not looked up
+        assert_eq!(
+            rust_i18n::t!("missing.default", locale = "zh-CN"),
+            "This is missing key fallbacked to en."
+        );
+        assert!(!rust_i18n::has_translation!("zh-CN", "missing.default"));
+    }
+
+    #[test]
+    fn test_negotiate_locale() {
+        assert_eq!(
+            rust_i18n::negotiate_locale!("fr-CH, fr;q=0.9, en;q=0.8"),
+            Some("fr")
+        );
+        assert_eq!(
+            rust_i18n::negotiate_locale!("es;q=0.9, pt-BR;q=0.8, en;q=0.1"),
+            Some("pt")
+        );
+        assert_eq!(rust_i18n::negotiate_locale!("es, *;q=0.5"), Some("en"));
+        assert_eq!(rust_i18n::negotiate_locale!("es, it"), None);
+    }
+
+    #[test]
+    fn test_warm_up() {
+        rust_i18n::warm_up!();
+        rust_i18n::warm_up!();
+        assert!(rust_i18n::has_translation!("en", "hello"));
+    }
+
+    #[test]
+    fn test_is_locale_available() {
+        assert!(rust_i18n::is_locale_available!("en"));
+        assert!(rust_i18n::is_locale_available!("zh-CN"));
+        assert!(!rust_i18n::is_locale_available!("xx"));
+
+        rust_i18n::set_locale("en");
+        assert_eq!(rust_i18n::set_locale_checked!("zh-CN"), Ok(()));
+        assert_eq!(&*rust_i18n::locale(), "zh-CN");
+        let err = rust_i18n::set_locale_checked!("xx").unwrap_err();
+        assert_eq!(err.locale(), "xx");
+        assert_eq!(err.to_string(), "unknown locale `xx`");
+        assert_eq!(&*rust_i18n::locale(), "zh-CN");
+        rust_i18n::set_locale("en");
+    }
+
+    #[test]
+    fn test_t_with_rtl_locale() {
+        // The values are always isolated for the RTL locales
+        assert_eq!(
+            t!("Hello, %{name}!", locale = "ar", name = "Jason"),
+            "Hello, \u{2068}Jason\u{2069}!"
+        );
+        assert_eq!(
+            t!("Hello, %{name}!", locale = "en", name = "Jason"),
+            "Hello, Jason!"
+        );
+    }
+
+    #[test]
+    fn test_t_with_escaped_interpolation() {
+        assert_eq!(
+            t!("messages.escaped", locale = "en", name = "Jason"),
+            "Use %{name} to show Jason, %{not_a_var} is kept."
+        );
+        assert_eq!(
+            t!("messages.escaped", locale = "en"),
+            "Use %{name} to show %{name}, %{not_a_var} is kept."
+        );
+        assert_eq!(
+            t!("messages.escaped", locale = "ar", name = "Jason"),
+            "Use %{name} to show \u{2068}Jason\u{2069}, %{not_a_var} is kept."
+        );
+    }
+
+    #[test]
+    fn test_t_with_default_interpolation() {
+        assert_eq!(
+            t!("messages.default_arg", locale = "en"),
+            "Hello, guest! See you at 12:00: %{name:x} is kept."
+        );
+        assert_eq!(
+            t!("messages.default_arg", locale = "en", name = "Jason"),
+            "Hello, Jason! See you at 12:00: %{name:x} is kept."
+        );
+        assert_eq!(
+            t!("messages.default_arg", locale = "en", time = "9:30"),
+            "Hello, guest! See you at 9:30: %{name:x} is kept."
+        );
+        assert_eq!(
+            t!("messages.default_arg", locale = "ar", name = "Jason"),
+            "Hello, \u{2068}Jason\u{2069}! See you at 12:00: %{name:x} is kept."
+        );
+        // The `:` outside the placeholders is a literal
+        assert_eq!(
+            t!(
+                "At 12:00: %{name:guest}, %{greeting:hi}",
+                locale = "en",
+                greeting = "hey"
+            ),
+            "At 12:00: guest, hey"
+        );
+    }
+
+    #[derive(rust_i18n::Localize)]
+    #[localize(prefix = "messages")]
+    enum Messages {
+        Zero,
+        Other(#[allow(dead_code)] usize),
+        #[localize(key = "hello")]
+        Hello,
+        InlinePlural {},
+    }
+
+    #[test]
+    fn test_derive_localize() {
+        assert_eq!(Messages::Zero.localize_key(), "messages.zero");
+        assert_eq!(Messages::Other(2).localize_key(), "messages.other");
+        assert_eq!(Messages::Hello.localize_key(), "hello");
+        assert_eq!(
+            Messages::InlinePlural {}.localize_key(),
+            "messages.inline_plural"
+        );
+
+        assert_eq!(Messages::Zero.localized("en"), "You have no messages.");
+        assert_eq!(
+            Messages::Other(2).localized("zh-CN"),
+            "你收到了 %{count} 条新消息。"
+        );
+        assert_eq!(Messages::Hello.localized("en"), "Bar - Hello, World!");
+    }
+
+    #[test]
+    fn test_export_locale_json() {
+        let json = rust_i18n::export_locale_json!("zh-CN", "messages.");
+        let value: serde_yml::Value = serde_yml::from_str(&json).unwrap();
+        assert_eq!(value["messages.hello"], "你好，%{name}！");
+        assert!(value
+            .as_mapping()
+            .unwrap()
+            .keys()
+            .all(|k| k.as_str().unwrap().starts_with("messages.")));
+
+        let json = rust_i18n::export_locale_json!("zh-CN");
+        let value: serde_yml::Value = serde_yml::from_str(&json).unwrap();
+        assert_eq!(value["hello"], "Bar - 你好世界！");
+
+        assert_eq!(rust_i18n::export_locale_json!("xx"), "{}");
+    }
+
+    #[test]
+    fn test_missing_keys() {
+        let missing = rust_i18n::missing_keys!();
+        assert!(missing.contains_key("en"));
+        assert!(missing["zh-CN"].contains(&"messages.inline_plural".to_string()));
+        assert!(!missing["zh-CN"].contains(&"hello".to_string()));
+
+        let missing = rust_i18n::missing_keys!("en");
+        assert!(missing["en"].is_empty());
+        assert!(missing["zh-CN"].contains(&"messages.inline_plural".to_string()));
+    }
+
+    #[test]
+    fn test_all_entries() {
+        let entries = rust_i18n::all_entries!().collect::<Vec<_>>();
+        assert!(entries.contains(&("en", "hello", "Bar - Hello, World!")));
+        assert!(entries.contains(&("zh-CN", "hello", "Bar - 你好世界！")));
+        assert_eq!(
+            entries
+                .iter()
+                .filter(|(locale, ..)| *locale == "en")
+                .count(),
+            crate::_rust_i18n_backend().translations("en").len()
+        );
+    }
+
+    #[test]
+    fn test_t_many() {
+        let texts = rust_i18n::t_many!(
+            "zh-CN",
+            &[
+                "hello",
+                "missing.lookup-fallback",
+                "messages.hello",
+                "not.exists"
+            ]
+        );
+        assert_eq!(
+            texts,
+            vec![
+                Some("Bar - 你好世界！".into()),
+                Some("在 zh-XXX 中缺失的的翻译。".into()),
+                Some("你好，%{name}！".into()),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_t_resolved() {
+        use rust_i18n::ResolvedFrom;
+
+        assert_eq!(
+            rust_i18n::t_resolved!("zh-CN", "hello"),
+            (
+                "Bar - 你好世界！".into(),
+                ResolvedFrom::Exact("zh-CN".into())
+            )
+        );
+        assert_eq!(
+            rust_i18n::t_resolved!("zh-CN", "missing.lookup-fallback"),
+            (
+                "在 zh-XXX 中缺失的的翻译。".into(),
+                ResolvedFrom::Fallback("zh".into())
+            )
+        );
+        let (text, from) = rust_i18n::t_resolved!("zh-CN", "missing.default");
+        assert_eq!(text, "This is missing key fallbacked to en.");
+        assert_eq!(from.locale(), Some("en"));
+        assert_eq!(
+            rust_i18n::t_resolved!("zh-CN", "not.exists"),
+            ("zh-CN.not.exists".into(), ResolvedFrom::Missing)
+        );
+    }
+
     #[test]
     fn test_lookup_fallback() {
         assert_eq!(